@@ -423,12 +423,16 @@ fn build_benchmark_comp(
         "bg",
         wavyte::Asset::Path(wavyte::PathAsset {
             svg_path_d: bg_path,
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     )?
     .asset(
         "img",
         wavyte::Asset::Image(wavyte::ImageAsset {
             source: assets.image_rel.clone(),
+            sampler: None,
         }),
     )?
     .asset(
@@ -445,18 +449,30 @@ fn build_benchmark_comp(
             size_px: 64.0,
             max_width_px: Some(params.width as f32 - 80.0),
             color_rgba8: [255, 255, 255, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )?
     .asset(
         "tri_a",
         wavyte::Asset::Path(wavyte::PathAsset {
             svg_path_d: "M60,0 L120,120 L0,120 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     )?
     .asset(
         "tri_b",
         wavyte::Asset::Path(wavyte::PathAsset {
             svg_path_d: "M0,0 L120,0 L60,120 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     )?
     .track(build_track(
@@ -565,6 +581,10 @@ fn run_once(
     let backend_create_t0 = Instant::now();
     let settings = wavyte::RenderSettings {
         clear_rgba: Some([18, 20, 28, 255]),
+        text_snap: wavyte::TextSnap::None,
+        scale: 1.0,
+        profile_ops: false,
+        missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
     };
     let kind = match args.backend {
         Backend::Cpu => wavyte::BackendKind::Cpu,
@@ -584,12 +604,8 @@ fn run_once(
         };
 
         let spawn_t0 = Instant::now();
-        let cfg = wavyte::default_mp4_config(
-            out_path,
-            comp.canvas.width,
-            comp.canvas.height,
-            comp.fps.num,
-        );
+        let cfg =
+            wavyte::default_mp4_config(out_path, comp.canvas.width, comp.canvas.height, comp.fps);
         let enc = wavyte::FfmpegEncoder::new(cfg, [18, 20, 28, 255])?;
         let ffmpeg_spawn = spawn_t0.elapsed();
         Some((enc, ffmpeg_spawn))
@@ -607,6 +623,7 @@ fn run_once(
             chunk_size: args.chunk_size,
             threads: args.threads,
             static_frame_elision: args.static_frame_elision,
+            elision_cache_frames: 0,
         };
 
         let range = wavyte::FrameRange::new(wavyte::FrameIndex(0), comp.duration)?;
@@ -21,6 +21,8 @@ enum Command {
     Frame(FrameArgs),
     /// Render an MP4 video (requires `ffmpeg` on PATH).
     Render(RenderArgs),
+    /// Print composition diagnostics (assets, sizes, warnings, memory estimate).
+    Probe(ProbeArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -55,7 +57,7 @@ struct RenderArgs {
     /// Input composition JSON.
     #[arg(long = "in")]
     in_path: PathBuf,
-
+    /// Output video path (`.mp4`, or `.webm` to encode VP9).
     /// Output MP4 path.
     #[arg(long)]
     out: PathBuf,
@@ -73,6 +75,13 @@ struct RenderArgs {
     dump_svg_fonts: bool,
 }
 
+#[derive(Parser, Debug)]
+struct ProbeArgs {
+    /// Input composition JSON.
+    #[arg(long = "in")]
+    in_path: PathBuf,
+}
+
 #[derive(Clone, Copy, Debug, ValueEnum)]
 enum BackendChoice {
     Cpu,
@@ -83,6 +92,7 @@ fn main() -> anyhow::Result<()> {
     match cli.cmd {
         Command::Frame(args) => cmd_frame(args),
         Command::Render(args) => cmd_render(args),
+        Command::Probe(args) => cmd_probe(args),
     }
 }
 
@@ -111,6 +121,10 @@ fn cmd_frame(args: FrameArgs) -> anyhow::Result<()> {
 
     let settings = wavyte::RenderSettings {
         clear_rgba: Some([18, 20, 28, 255]),
+        text_snap: wavyte::TextSnap::None,
+        scale: 1.0,
+        profile_ops: false,
+        missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
     };
 
     let mut backend = make_backend(args.backend, &settings)?;
@@ -154,6 +168,10 @@ fn cmd_render(args: RenderArgs) -> anyhow::Result<()> {
 
     let settings = wavyte::RenderSettings {
         clear_rgba: Some([18, 20, 28, 255]),
+        text_snap: wavyte::TextSnap::None,
+        scale: 1.0,
+        profile_ops: false,
+        missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
     };
     let mut backend = make_backend(args.backend, &settings)?;
 
@@ -169,6 +187,19 @@ fn cmd_render(args: RenderArgs) -> anyhow::Result<()> {
         bg_rgba: settings.clear_rgba.unwrap_or([0, 0, 0, 255]),
         overwrite: true,
         threading: wavyte::RenderThreading::default(),
+        timecode: None,
+        codec: if args
+            .out
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("webm"))
+        {
+            wavyte::VideoCodec::Vp9
+        } else {
+            wavyte::VideoCodec::H264
+        },
+        preserve_alpha: false,
+        first_frame_pts: wavyte::FrameIndex(0),
+        metadata_sidecar: None,
     };
 
     wavyte::render_to_mp4(&comp, &args.out, opts, backend.as_mut(), &assets)?;
@@ -177,6 +208,102 @@ fn cmd_render(args: RenderArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn cmd_probe(args: ProbeArgs) -> anyhow::Result<()> {
+    let comp = read_comp_json(&args.in_path)?;
+
+    println!("canvas:   {}x{}", comp.canvas.width, comp.canvas.height);
+    println!("fps:      {}/{}", comp.fps.num, comp.fps.den);
+    println!(
+        "duration: {} frames ({:.3}s)",
+        comp.duration.0,
+        comp.fps.frames_to_secs(comp.duration.0)
+    );
+
+    let warnings = probe_warnings(&comp);
+    // Asset sizes need prepared assets, which requires a valid composition.
+    let validation = comp.validate();
+    let assets = match &validation {
+        Ok(()) => {
+            let root = args.in_path.parent().unwrap_or_else(|| Path::new("."));
+            Some(wavyte::PreparedAssetStore::prepare(&comp, root)?)
+        }
+        Err(_) => None,
+    };
+
+    println!("assets:   {}", comp.assets.len());
+    let mut asset_bytes = 0u64;
+    for key in comp.assets.keys() {
+        let Some(assets) = &assets else {
+            println!("  {key}");
+            continue;
+        };
+        let (w, h) = wavyte::intrinsic_size_for_asset_key(key, assets)?;
+        let bytes = prepared_asset_bytes(assets.get(assets.id_for_key(key)?)?);
+        asset_bytes += bytes;
+        println!("  {key}: {w:.0}x{h:.0}, {bytes} bytes");
+    }
+
+    let frame_bytes = u64::from(comp.canvas.width) * u64::from(comp.canvas.height) * 4;
+    if assets.is_some() {
+        println!(
+            "estimated memory: {} bytes (assets {asset_bytes} + frame {frame_bytes})",
+            asset_bytes + frame_bytes
+        );
+    }
+
+    println!("warnings: {}", warnings.len());
+    for w in &warnings {
+        println!("  warning: {w}");
+    }
+
+    validation?;
+    Ok(())
+}
+
+/// Non-fatal issues worth surfacing; some (like out-of-range clips) also fail validation.
+fn probe_warnings(comp: &wavyte::Composition) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut used = std::collections::BTreeSet::<&str>::new();
+    for track in &comp.tracks {
+        for clip in &track.clips {
+            used.insert(clip.asset.as_str());
+            if !comp.assets.contains_key(&clip.asset) {
+                out.push(format!(
+                    "clip '{}' references missing asset '{}'",
+                    clip.id, clip.asset
+                ));
+            }
+            if clip.range.end.0 > comp.duration.0 {
+                out.push(format!(
+                    "clip '{}' ends at frame {} past the composition duration ({})",
+                    clip.id, clip.range.end.0, comp.duration.0
+                ));
+            }
+        }
+    }
+    for key in comp.assets.keys() {
+        if !used.contains(key.as_str()) {
+            out.push(format!("asset '{key}' is not used by any clip"));
+        }
+    }
+    out
+}
+
+/// Decoded bytes held by a prepared asset (one decoded frame for video).
+fn prepared_asset_bytes(asset: &wavyte::PreparedAsset) -> u64 {
+    let rgba = |w: u32, h: u32| u64::from(w) * u64::from(h) * 4;
+    let pcm = |a: &wavyte::PreparedAudio| a.interleaved_f32.len() as u64 * 4;
+    match asset {
+        wavyte::PreparedAsset::Image(i) => rgba(i.width, i.height),
+        wavyte::PreparedAsset::Video(v) => {
+            rgba(v.info.width, v.info.height) + v.audio.as_ref().map_or(0, pcm)
+        }
+        wavyte::PreparedAsset::Audio(a) => pcm(a),
+        wavyte::PreparedAsset::RadialGradient(g) => rgba(g.width, g.height),
+        _ => 0,
+    }
+}
+
 fn dump_font_diagnostics(
     comp: &wavyte::Composition,
     assets: &wavyte::PreparedAssetStore,
@@ -14,54 +14,60 @@ fn cli_frame_writes_png() {
     let out_path = dir.join("out.png");
     let _ = std::fs::remove_file(&out_path);
 
-    let mut assets = std::collections::BTreeMap::new();
-    assets.insert(
-        "p0".to_string(),
+    let comp = smoke_comp();
+    let f = std::fs::File::create(&comp_path).unwrap();
+    serde_json::to_writer_pretty(f, &comp).unwrap();
+
+    let comp_arg = comp_path.to_string_lossy().to_string();
+    let out_arg = out_path.to_string_lossy().to_string();
+    let output = run_cli(&[
+        "frame",
+        "--in",
+        comp_arg.as_str(),
+        "--frame",
+        "0",
+        "--out",
+        out_arg.as_str(),
+    ]);
+
+    assert!(output.status.success());
+    assert!(out_path.exists());
+}
+
+#[test]
+fn cli_probe_reports_assets_and_warnings() {
+    let dir = PathBuf::from("target").join("cli_smoke_probe");
+    std::fs::create_dir_all(&dir).unwrap();
+    let comp_path = dir.join("comp.json");
+
+    let mut comp = smoke_comp();
+    comp.assets.insert(
+        "unused".to_string(),
         Asset::Path(PathAsset {
-            svg_path_d: "M0,0 L40,0 L40,40 L0,40 Z".to_string(),
+            svg_path_d: "M0,0 L8,0 L8,8 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     );
-
-    let comp = Composition {
-        fps: Fps::new(30, 1).unwrap(),
-        canvas: Canvas {
-            width: 64,
-            height: 64,
-        },
-        duration: FrameIndex(2),
-        assets,
-        tracks: vec![Track {
-            name: "main".to_string(),
-            z_base: 0,
-            layout_mode: wavyte::LayoutMode::Absolute,
-            layout_gap_px: 0.0,
-            layout_padding: wavyte::Edges::default(),
-            layout_align_x: wavyte::LayoutAlignX::Start,
-            layout_align_y: wavyte::LayoutAlignY::Start,
-            layout_grid_columns: 2,
-            clips: vec![Clip {
-                id: "c0".to_string(),
-                asset: "p0".to_string(),
-                range: FrameRange::new(FrameIndex(0), FrameIndex(2)).unwrap(),
-                props: ClipProps {
-                    transform: Anim::constant(Transform2D::default()),
-                    opacity: Anim::constant(1.0),
-                    blend: BlendMode::Normal,
-                },
-                z_offset: 0,
-                effects: vec![],
-                transition_in: None,
-                transition_out: None,
-            }],
-        }],
-        seed: 1,
-    };
-
     let f = std::fs::File::create(&comp_path).unwrap();
     serde_json::to_writer_pretty(f, &comp).unwrap();
 
     let comp_arg = comp_path.to_string_lossy().to_string();
-    let out_arg = out_path.to_string_lossy().to_string();
+    let output = run_cli(&["probe", "--in", comp_arg.as_str()]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("canvas:   64x64"), "{stdout}");
+    assert!(stdout.contains("assets:   2"), "{stdout}");
+    assert!(stdout.contains("  p0: 40x40"), "{stdout}");
+    assert!(
+        stdout.contains("warning: asset 'unused' is not used by any clip"),
+        "{stdout}"
+    );
+}
+
+/// Run the `wavyte` binary (or `cargo run` it as a workspace fallback) and capture its output.
+fn run_cli(args: &[&str]) -> std::process::Output {
     let profile_dir = if cfg!(debug_assertions) {
         "debug"
     } else {
@@ -79,12 +85,8 @@ fn cli_frame_writes_png() {
             if p.is_file() { Some(p) } else { None }
         });
 
-    let status = if let Some(exe) = direct_bin {
-        std::process::Command::new(exe)
-            .args(["frame", "--in", comp_arg.as_str(), "--frame", "0", "--out"])
-            .arg(out_arg.as_str())
-            .status()
-            .unwrap()
+    if let Some(exe) = direct_bin {
+        std::process::Command::new(exe).args(args).output().unwrap()
     } else {
         // Workspace fallback: invoke Cargo to run the dedicated CLI crate.
         let cargo = std::env::var_os("CARGO")
@@ -99,18 +101,71 @@ fn cli_frame_writes_png() {
                 "wavyte",
                 "--release",
                 "--",
-                "frame",
-                "--in",
-                comp_arg.as_str(),
-                "--frame",
-                "0",
-                "--out",
-                out_arg.as_str(),
             ])
-            .status()
+            .args(args)
+            .output()
             .unwrap()
-    };
+    }
+}
 
-    assert!(status.success());
-    assert!(out_path.exists());
+fn smoke_comp() -> Composition {
+    let mut assets = std::collections::BTreeMap::new();
+    assets.insert(
+        "p0".to_string(),
+        Asset::Path(PathAsset {
+            svg_path_d: "M0,0 L40,0 L40,40 L0,40 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
+        }),
+    );
+
+    Composition {
+        fps: Fps::new(30, 1).unwrap(),
+        canvas: Canvas {
+            width: 64,
+            height: 64,
+        },
+        duration: FrameIndex(2),
+        assets,
+        tracks: vec![Track {
+            name: "main".to_string(),
+            z_base: 0,
+            layout_mode: wavyte::LayoutMode::Absolute,
+            layout_gap_px: 0.0,
+            layout_padding: wavyte::Edges::default(),
+            layout_align_x: wavyte::LayoutAlignX::Start,
+            layout_align_y: wavyte::LayoutAlignY::Start,
+            layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
+            clips: vec![Clip {
+                id: "c0".to_string(),
+                asset: "p0".to_string(),
+                range: FrameRange::new(FrameIndex(0), FrameIndex(2)).unwrap(),
+                props: ClipProps {
+                    transform: Anim::constant(Transform2D::default()),
+                    opacity: Anim::constant(1.0),
+                    blend: BlendMode::Normal,
+                    opacity_at_composite: false,
+                    wiggle: None,
+                    box_anchor: None,
+                    aspect_ratio: None,
+                    fit: wavyte::ContentFit::None,
+                },
+                z_offset: 0,
+                effects: vec![],
+                transition_in: None,
+                transition_out: None,
+                seed: None,
+                loop_content: false,
+                time_remap: None,
+                reverse: false,
+            }],
+        }],
+        seed: 1,
+        background: None,
+        title: None,
+        description: None,
+    }
 }
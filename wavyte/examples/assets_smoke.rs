@@ -26,6 +26,7 @@ fn main() -> anyhow::Result<()> {
         "image".to_string(),
         Asset::Image(wavyte::ImageAsset {
             source: "assets/test_image_1.jpg".to_string(),
+            sampler: None,
         }),
     );
     assets.insert(
@@ -42,6 +43,12 @@ fn main() -> anyhow::Result<()> {
             size_px: 48.0,
             max_width_px: Some(512.0),
             color_rgba8: [255, 255, 255, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     );
 
@@ -55,6 +62,9 @@ fn main() -> anyhow::Result<()> {
         assets,
         tracks: vec![],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     };
     let store = PreparedAssetStore::prepare(&comp, ".")?;
 
@@ -81,6 +91,15 @@ fn main() -> anyhow::Result<()> {
             PreparedAsset::Path(_) => {
                 println!("{name}: path asset");
             }
+            PreparedAsset::RadialGradient(g) => {
+                println!("{name}: radial gradient {}x{}", g.width, g.height);
+            }
+            PreparedAsset::SolidRect(r) => {
+                println!("{name}: solid rect {}x{}", r.width, r.height);
+            }
+            PreparedAsset::Composition(c) => {
+                println!("{name}: nested composition {}x{}", c.width, c.height);
+            }
             PreparedAsset::Video(v) => {
                 println!(
                     "{name}: video {}x{} @ {:.3}fps",
@@ -89,6 +108,14 @@ fn main() -> anyhow::Result<()> {
                     v.info.source_fps()
                 );
             }
+            PreparedAsset::ImageSequence(q) => {
+                println!(
+                    "{name}: image sequence {}x{}, {} frames",
+                    q.width,
+                    q.height,
+                    q.frames.len()
+                );
+            }
             PreparedAsset::Audio(a) => {
                 println!(
                     "{name}: audio {}ch @ {}Hz ({} samples)",
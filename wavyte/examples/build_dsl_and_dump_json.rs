@@ -30,6 +30,12 @@ fn main() -> anyhow::Result<()> {
             size_px: 48.0,
             max_width_px: None,
             color_rgba8: [255, 255, 255, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )?
     .track(track)
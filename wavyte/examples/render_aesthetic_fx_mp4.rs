@@ -130,6 +130,7 @@ fn build_comp() -> anyhow::Result<Composition> {
         "img",
         Asset::Image(ImageAsset {
             source: "assets/test_image_1.jpg".to_string(),
+            sampler: None,
         }),
     )?
     .asset(
@@ -146,6 +147,12 @@ fn build_comp() -> anyhow::Result<Composition> {
             size_px: 56.0,
             max_width_px: Some(1050.0),
             color_rgba8: [245, 245, 255, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )?
     .track(bg_track)
@@ -174,6 +181,7 @@ fn try_main() -> anyhow::Result<()> {
             chunk_size: 48,
             threads: Some(4),
             static_frame_elision: false,
+            elision_cache_frames: 0,
         },
     )?;
     eprintln!("wrote {}", out.display());
@@ -96,6 +96,7 @@ fn build_comp() -> anyhow::Result<Composition> {
         "img",
         Asset::Image(ImageAsset {
             source: "assets/test_image_1.jpg".to_string(),
+            sampler: None,
         }),
     )?
     .asset(
@@ -112,30 +113,48 @@ fn build_comp() -> anyhow::Result<Composition> {
             size_px: 48.0,
             max_width_px: Some(520.0),
             color_rgba8: [240, 238, 232, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )?
     .asset(
         "badge",
         Asset::Path(PathAsset {
             svg_path_d: "M0,30 C0,13.4 13.4,0 30,0 L220,0 C236.6,0 250,13.4 250,30 C250,46.6 236.6,60 220,60 L30,60 C13.4,60 0,46.6 0,30 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     )?
     .asset(
         "chip_a",
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L180,0 L180,42 L0,42 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     )?
     .asset(
         "chip_b",
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L180,0 L180,42 L0,42 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     )?
     .asset(
         "chip_c",
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L180,0 L180,42 L0,42 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     )?
     .asset(
@@ -146,6 +165,12 @@ fn build_comp() -> anyhow::Result<Composition> {
             size_px: 34.0,
             max_width_px: Some(840.0),
             color_rgba8: [252, 214, 101, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )?
     .track(grid_track)
@@ -174,6 +199,7 @@ fn try_main() -> anyhow::Result<()> {
             chunk_size: 48,
             threads: Some(4),
             static_frame_elision: false,
+            elision_cache_frames: 0,
         },
     )?;
     eprintln!("wrote {}", out.display());
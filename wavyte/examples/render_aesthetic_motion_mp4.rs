@@ -186,6 +186,7 @@ fn build_comp() -> anyhow::Result<Composition> {
         "img",
         Asset::Image(ImageAsset {
             source: "assets/test_image_1.jpg".to_string(),
+            sampler: None,
         }),
     )?
     .asset(
@@ -198,6 +199,9 @@ fn build_comp() -> anyhow::Result<Composition> {
         "ring",
         Asset::Path(PathAsset {
             svg_path_d: "M60,0 A60,60 0 1 1 59.9,0 M60,22 A38,38 0 1 0 60.1,22 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     )?
     .asset(
@@ -208,6 +212,12 @@ fn build_comp() -> anyhow::Result<Composition> {
             size_px: 66.0,
             max_width_px: Some(1100.0),
             color_rgba8: [244, 244, 236, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )?
     .track(hero)
@@ -235,6 +245,7 @@ fn try_main() -> anyhow::Result<()> {
             chunk_size: 48,
             threads: Some(4),
             static_frame_elision: false,
+            elision_cache_frames: 0,
         },
     )?;
     eprintln!("wrote {}", out.display());
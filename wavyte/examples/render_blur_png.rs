@@ -22,6 +22,9 @@ fn build_comp() -> Composition {
         "rect".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L120,0 L120,120 L0,120 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     );
 
@@ -46,6 +49,8 @@ fn build_comp() -> Composition {
             layout_align_x: wavyte::LayoutAlignX::Start,
             layout_align_y: wavyte::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![Clip {
                 id: "blurred_rect".to_string(),
                 asset: "rect".to_string(),
@@ -58,6 +63,11 @@ fn build_comp() -> Composition {
                     }),
                     opacity: Anim::constant(1.0),
                     blend: BlendMode::Normal,
+                    opacity_at_composite: false,
+                    wiggle: None,
+                    box_anchor: None,
+                    aspect_ratio: None,
+                    fit: wavyte::ContentFit::None,
                 },
                 z_offset: 0,
                 effects: vec![EffectInstance {
@@ -66,9 +76,16 @@ fn build_comp() -> Composition {
                 }],
                 transition_in: None,
                 transition_out: None,
+                seed: None,
+                loop_content: false,
+                time_remap: None,
+                reverse: false,
             }],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     }
 }
 
@@ -85,6 +102,10 @@ fn try_main() -> anyhow::Result<()> {
 
     let settings = RenderSettings {
         clear_rgba: Some([18, 20, 28, 255]),
+        text_snap: wavyte::TextSnap::None,
+        scale: 1.0,
+        profile_ops: false,
+        missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
     };
     let mut backend = create_backend(parse_backend()?, &settings)?;
     let assets = wavyte::PreparedAssetStore::prepare(&comp, ".")?;
@@ -124,7 +124,11 @@ fn build_comp() -> anyhow::Result<Composition> {
             volume: 1.0,
             fade_in_sec: 0.0,
             fade_out_sec: 0.0,
+            fade_curve: wavyte::AudioFadeCurve::Linear,
             muted: false,
+            frame_blend: false,
+            sampler: None,
+            loop_mode: wavyte::VideoLoopMode::None,
         }),
     )?
     .asset(
@@ -137,13 +141,18 @@ fn build_comp() -> anyhow::Result<Composition> {
             volume: 0.45,
             fade_in_sec: 0.3,
             fade_out_sec: 0.45,
+            fade_curve: wavyte::AudioFadeCurve::Linear,
             muted: false,
+            pan: 0.0,
+            width: 1.0,
+            duck: None,
         }),
     )?
     .asset(
         "still",
         Asset::Image(ImageAsset {
             source: "assets/test_image_1.jpg".to_string(),
+            sampler: None,
         }),
     )?
     .asset(
@@ -160,6 +169,12 @@ fn build_comp() -> anyhow::Result<Composition> {
             size_px: 52.0,
             max_width_px: Some(1200.0),
             color_rgba8: [245, 245, 242, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )?
     .asset(
@@ -170,18 +185,30 @@ fn build_comp() -> anyhow::Result<Composition> {
             size_px: 28.0,
             max_width_px: Some(1200.0),
             color_rgba8: [240, 220, 145, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )?
     .asset(
         "badge",
         Asset::Path(PathAsset {
             svg_path_d: "M0,30 C0,13.4 13.4,0 30,0 L310,0 C326.6,0 340,13.4 340,30 C340,46.6 326.6,60 310,60 L30,60 C13.4,60 0,46.6 0,30 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     )?
     .asset(
         "bar",
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L1120,0 L1120,8 L0,8 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     )?
     .asset(
@@ -192,6 +219,12 @@ fn build_comp() -> anyhow::Result<Composition> {
             size_px: 30.0,
             max_width_px: Some(420.0),
             color_rgba8: [250, 197, 92, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )?
     .track(media_grid)
@@ -233,6 +266,7 @@ fn try_main() -> anyhow::Result<()> {
             chunk_size: 48,
             threads: Some(4),
             static_frame_elision: false,
+            elision_cache_frames: 0,
         },
     )?;
 
@@ -39,12 +39,21 @@ fn build_comp() -> Composition {
         "p0".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L120,0 L120,120 L0,120 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     );
 
     if let Some(jpg) = first_asset_path_with_ext("jpg").or_else(|| first_asset_path_with_ext("png"))
     {
-        assets.insert("img0".to_string(), Asset::Image(ImageAsset { source: jpg }));
+        assets.insert(
+            "img0".to_string(),
+            Asset::Image(ImageAsset {
+                source: jpg,
+                sampler: None,
+            }),
+        );
     }
 
     if let Some(svg) = first_asset_path_with_ext("svg") {
@@ -62,6 +71,12 @@ fn build_comp() -> Composition {
                 size_px: 42.0,
                 max_width_px: Some(480.0),
                 color_rgba8: [255, 255, 255, 255],
+                align: wavyte::TextAlign::Left,
+                valign: wavyte::TextVAlign::Top,
+                spans: vec![],
+                line_height: None,
+                letter_spacing_px: None,
+                fallback_fonts: vec![],
             }),
         );
     }
@@ -83,11 +98,20 @@ fn build_comp() -> Composition {
             }),
             opacity: Anim::constant(0.9),
             blend: BlendMode::Normal,
+            opacity_at_composite: false,
+            wiggle: None,
+            box_anchor: None,
+            aspect_ratio: None,
+            fit: wavyte::ContentFit::None,
         },
         z_offset: 0,
         effects: vec![],
         transition_in: None,
         transition_out: None,
+        seed: None,
+        loop_content: false,
+        time_remap: None,
+        reverse: false,
     });
 
     if assets.contains_key("img0") {
@@ -103,11 +127,20 @@ fn build_comp() -> Composition {
                 }),
                 opacity: Anim::constant(1.0),
                 blend: BlendMode::Normal,
+                opacity_at_composite: false,
+                wiggle: None,
+                box_anchor: None,
+                aspect_ratio: None,
+                fit: wavyte::ContentFit::None,
             },
             z_offset: 10,
             effects: vec![],
             transition_in: None,
             transition_out: None,
+            seed: None,
+            loop_content: false,
+            time_remap: None,
+            reverse: false,
         });
     }
 
@@ -124,11 +157,20 @@ fn build_comp() -> Composition {
                 }),
                 opacity: Anim::constant(1.0),
                 blend: BlendMode::Normal,
+                opacity_at_composite: false,
+                wiggle: None,
+                box_anchor: None,
+                aspect_ratio: None,
+                fit: wavyte::ContentFit::None,
             },
             z_offset: 20,
             effects: vec![],
             transition_in: None,
             transition_out: None,
+            seed: None,
+            loop_content: false,
+            time_remap: None,
+            reverse: false,
         });
     }
 
@@ -144,11 +186,20 @@ fn build_comp() -> Composition {
                 }),
                 opacity: Anim::constant(1.0),
                 blend: BlendMode::Normal,
+                opacity_at_composite: false,
+                wiggle: None,
+                box_anchor: None,
+                aspect_ratio: None,
+                fit: wavyte::ContentFit::None,
             },
             z_offset: 30,
             effects: vec![],
             transition_in: None,
             transition_out: None,
+            seed: None,
+            loop_content: false,
+            time_remap: None,
+            reverse: false,
         });
     }
 
@@ -169,9 +220,14 @@ fn build_comp() -> Composition {
             layout_align_x: wavyte::LayoutAlignX::Start,
             layout_align_y: wavyte::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips,
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     }
 }
 
@@ -196,6 +252,10 @@ fn try_main() -> anyhow::Result<()> {
 
     let settings = RenderSettings {
         clear_rgba: Some([18, 20, 28, 255]),
+        text_snap: wavyte::TextSnap::None,
+        scale: 1.0,
+        profile_ops: false,
+        missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
     };
 
     let kind = match parse_backend() {
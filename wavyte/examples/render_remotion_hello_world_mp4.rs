@@ -241,6 +241,12 @@ fn build_comp() -> anyhow::Result<Composition> {
             size_px: 96.0,
             max_width_px: Some(1000.0),
             color_rgba8: [244, 246, 255, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )?
     .asset(
@@ -251,12 +257,21 @@ fn build_comp() -> anyhow::Result<Composition> {
             size_px: 42.0,
             max_width_px: Some(900.0),
             color_rgba8: [205, 220, 255, 255],
+            align: wavyte::TextAlign::Left,
+            valign: wavyte::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )?
     .asset(
         "orb",
         Asset::Path(PathAsset {
             svg_path_d: "M60,0 A60,60 0 1 1 59.9,0".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     )?
     .track(glow_track)
@@ -291,6 +306,7 @@ fn try_main() -> anyhow::Result<()> {
             chunk_size: 48,
             threads: Some(threads),
             static_frame_elision: false,
+            elision_cache_frames: 0,
         },
     )?;
     eprintln!("threads={threads}");
@@ -72,6 +72,8 @@ fn build_comp() -> Composition {
             layout_align_x: wavyte::LayoutAlignX::Start,
             layout_align_y: wavyte::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![Clip {
                 id: "logo".to_string(),
                 asset: "logo".to_string(),
@@ -85,14 +87,26 @@ fn build_comp() -> Composition {
                     }),
                     opacity: fade,
                     blend: BlendMode::Normal,
+                    opacity_at_composite: false,
+                    wiggle: None,
+                    box_anchor: None,
+                    aspect_ratio: None,
+                    fit: wavyte::ContentFit::None,
                 },
                 z_offset: 0,
                 effects: vec![],
                 transition_in: None,
                 transition_out: None,
+                seed: None,
+                loop_content: false,
+                time_remap: None,
+                reverse: false,
             }],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     }
 }
 
@@ -109,6 +123,10 @@ fn try_main() -> anyhow::Result<()> {
 
     let settings = RenderSettings {
         clear_rgba: Some([18, 20, 28, 255]),
+        text_snap: wavyte::TextSnap::None,
+        scale: 1.0,
+        profile_ops: false,
+        missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
     };
     let mut backend = create_backend(parse_backend()?, &settings)?;
     let assets = wavyte::PreparedAssetStore::prepare(&comp, ".")?;
@@ -125,6 +143,11 @@ fn try_main() -> anyhow::Result<()> {
             bg_rgba: settings.clear_rgba.unwrap_or([0, 0, 0, 255]),
             overwrite: true,
             threading: wavyte::RenderThreading::default(),
+            timecode: None,
+            codec: wavyte::VideoCodec::H264,
+            preserve_alpha: false,
+            first_frame_pts: wavyte::FrameIndex(0),
+            metadata_sidecar: None,
         },
         backend.as_mut(),
         &assets,
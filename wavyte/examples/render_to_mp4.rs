@@ -21,12 +21,18 @@ fn build_comp() -> Composition {
         "rect".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L120,0 L120,120 L0,120 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     );
     assets.insert(
         "tri".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M60,0 L120,120 L0,120 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     );
 
@@ -60,6 +66,8 @@ fn build_comp() -> Composition {
             layout_align_x: wavyte::LayoutAlignX::Start,
             layout_align_y: wavyte::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![
                 Clip {
                     id: "a_rect".to_string(),
@@ -73,11 +81,20 @@ fn build_comp() -> Composition {
                         }),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: wavyte::ContentFit::None,
                     },
                     z_offset: 0,
                     effects: vec![],
                     transition_in: None,
                     transition_out: Some(tr.clone()),
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 },
                 Clip {
                     id: "b_tri".to_string(),
@@ -91,15 +108,27 @@ fn build_comp() -> Composition {
                         }),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: wavyte::ContentFit::None,
                     },
                     z_offset: 1,
                     effects: vec![],
                     transition_in: Some(tr),
                     transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 },
             ],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     }
 }
 
@@ -121,6 +150,10 @@ fn try_main() -> anyhow::Result<()> {
 
     let settings = RenderSettings {
         clear_rgba: Some([18, 20, 28, 255]),
+        text_snap: wavyte::TextSnap::None,
+        scale: 1.0,
+        profile_ops: false,
+        missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
     };
     let mut backend = create_backend(parse_backend()?, &settings)?;
     let assets = wavyte::PreparedAssetStore::prepare(&comp, ".")?;
@@ -145,6 +178,11 @@ fn try_main() -> anyhow::Result<()> {
             bg_rgba: settings.clear_rgba.unwrap_or([0, 0, 0, 255]),
             overwrite: true,
             threading: wavyte::RenderThreading::default(),
+            timecode: None,
+            codec: wavyte::VideoCodec::H264,
+            preserve_alpha: false,
+            first_frame_pts: wavyte::FrameIndex(0),
+            metadata_sidecar: None,
         },
         backend.as_mut(),
         &assets,
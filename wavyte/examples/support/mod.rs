@@ -48,6 +48,10 @@ pub fn render_comp_to_assets_mp4(
 
     let settings = RenderSettings {
         clear_rgba: Some(clear_rgba),
+        text_snap: wavyte::TextSnap::None,
+        scale: 1.0,
+        profile_ops: false,
+        missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
     };
     let mut backend = create_backend(BackendKind::Cpu, &settings)?;
     let assets = wavyte::PreparedAssetStore::prepare(comp, ".")?;
@@ -60,6 +64,11 @@ pub fn render_comp_to_assets_mp4(
             bg_rgba: clear_rgba,
             overwrite: true,
             threading,
+            timecode: None,
+            codec: wavyte::VideoCodec::H264,
+            preserve_alpha: false,
+            first_frame_pts: wavyte::FrameIndex(0),
+            metadata_sidecar: None,
         },
         backend.as_mut(),
         &assets,
@@ -1,6 +1,6 @@
 use crate::{
     animation::ease::Ease,
-    animation::proc::{ProcValue, Procedural},
+    animation::proc::{ProcValue, Procedural, ProceduralKind},
     foundation::core::{FrameIndex, Transform2D, Vec2},
     foundation::error::{WavyteError, WavyteResult},
 };
@@ -23,7 +23,9 @@ pub struct SampleCtx {
 
 /// Interpolation contract for animation value types.
 pub trait Lerp: Sized {
-    /// Interpolate from `a` to `b` with normalized factor `t` in `[0, 1]`.
+    /// Interpolate from `a` to `b` with normalized factor `t`, usually in `[0, 1]`.
+    ///
+    /// Spring interpolation can pass `t` slightly outside that range (overshoot).
     fn lerp(a: &Self, b: &Self, t: f64) -> Self;
 }
 
@@ -88,6 +90,31 @@ impl<T> Anim<T>
 where
     T: Lerp + Clone + ProcValue,
 {
+    /// Length in frames of this animation's finite timeline, if it has one.
+    ///
+    /// Keyframe lanes end on their last key (a lane whose only key is at frame 0 has none);
+    /// procedurals and loops are unbounded. Time operators map their inner length.
+    pub fn natural_duration(&self) -> Option<u64> {
+        match self {
+            Self::Keyframes(k) => k.keys.last().map(|k| k.frame.0).filter(|&f| f > 0),
+            Self::Procedural(_) => None,
+            Self::Expr(Expr::Delay { inner, by }) => inner.natural_duration().map(|d| d + by),
+            Self::Expr(Expr::Speed { inner, factor }) => inner
+                .natural_duration()
+                .map(|d| (d as f64 / factor).ceil() as u64),
+            Self::Expr(Expr::Reverse { duration, .. }) => Some(*duration),
+            Self::Expr(Expr::Loop { .. }) => None,
+            Self::Expr(Expr::Mix { a, b, t }) => [
+                a.natural_duration(),
+                b.natural_duration(),
+                t.natural_duration(),
+            ]
+            .into_iter()
+            .flatten()
+            .max(),
+        }
+    }
+
     /// Build a constant animation that always returns `value`.
     pub fn constant(value: T) -> Self {
         Self::Keyframes(Keyframes {
@@ -114,12 +141,33 @@ where
     pub fn validate(&self) -> WavyteResult<()> {
         match self {
             Self::Keyframes(kf) => kf.validate(),
-            Self::Procedural(_proc) => Ok(()),
+            Self::Procedural(proc) => proc.validate(),
             Self::Expr(expr) => expr.validate(),
         }
     }
 }
 
+impl Anim<Transform2D> {
+    /// Pan + zoom over a still ("Ken Burns"): the focus point moves from `from_center` to
+    /// `to_center` (clip-local coordinates) while zooming from `from_scale` to `to_scale` over
+    /// `duration_frames`, keeping the focus pinned at the screen position of `from_center`.
+    pub fn ken_burns(
+        from_scale: f64,
+        to_scale: f64,
+        from_center: Vec2,
+        to_center: Vec2,
+        duration_frames: u64,
+    ) -> Self {
+        Self::Procedural(Procedural::new(ProceduralKind::KenBurns {
+            from_scale,
+            to_scale,
+            from_center,
+            to_center,
+            duration_frames,
+        }))
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 /// Keyframed animation with optional default value.
 pub struct Keyframes<T> {
@@ -147,6 +195,27 @@ where
                 "Keyframes keys must be sorted by frame",
             ));
         }
+        if let InterpMode::Spring {
+            stiffness,
+            damping,
+            mass,
+        } = self.mode
+            && !(stiffness.is_finite()
+                && stiffness > 0.0
+                && damping.is_finite()
+                && damping >= 0.0
+                && mass.is_finite()
+                && mass > 0.0)
+        {
+            return Err(WavyteError::animation(
+                "Spring interpolation needs finite stiffness > 0, damping >= 0, and mass > 0",
+            ));
+        }
+        if let InterpMode::Steps { count: 0, .. } = self.mode {
+            return Err(WavyteError::animation(
+                "Steps interpolation needs count > 0",
+            ));
+        }
         Ok(())
     }
 
@@ -177,10 +246,27 @@ where
         }
 
         let t = ((f - a.frame.0) as f64) / (denom as f64);
-        let te = a.ease.apply(t);
         match self.mode {
             InterpMode::Hold => Ok(a.value.clone()),
-            InterpMode::Linear => Ok(T::lerp(&a.value, &b.value, te)),
+            InterpMode::Linear => Ok(T::lerp(&a.value, &b.value, a.ease.apply(t))),
+            InterpMode::Spring {
+                stiffness,
+                damping,
+                mass,
+            } => {
+                let secs = ctx.fps.frames_to_secs(f - a.frame.0);
+                let p = spring_progress(
+                    f64::from(stiffness),
+                    f64::from(damping),
+                    f64::from(mass),
+                    secs,
+                );
+                Ok(T::lerp(&a.value, &b.value, p))
+            }
+            InterpMode::Steps { count, jump_start } => {
+                let p = InterpMode::step_progress(count, jump_start, a.ease.apply(t));
+                Ok(T::lerp(&a.value, &b.value, p))
+            }
         }
     }
 }
@@ -197,12 +283,140 @@ pub struct Keyframe<T> {
 }
 
 #[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "InterpModeRepr")]
 /// Interpolation strategy between keyframes.
+///
+/// Deserializes from the tagged form it serializes to or from the string form accepted by
+/// [`str::parse`], such as `"hold"` or `"steps(12, start)"`.
 pub enum InterpMode {
     /// Hold the previous key value until the next keyframe.
     Hold,
     /// Interpolate between keyframes using [`Ease`].
     Linear,
+    /// Damped spring released at rest from each key toward the next (ignores [`Ease`]).
+    ///
+    /// Progress follows the analytic mass-spring-damper step response in seconds since the
+    /// previous key, so it can overshoot (underdamped) and may not settle before the next key.
+    Spring {
+        /// Spring constant `k` (> 0).
+        stiffness: f32,
+        /// Damping coefficient `c` (>= 0).
+        damping: f32,
+        /// Mass `m` (> 0).
+        mass: f32,
+    },
+    /// Quantize eased progress into `count` discrete levels (CSS `steps()`).
+    ///
+    /// With `jump_start`, the first jump happens at the previous key (levels `1/count..=1`);
+    /// otherwise after the first interval (levels `0..count-1` over `count`).
+    Steps {
+        /// Number of steps between adjacent keys (> 0).
+        count: u32,
+        /// Jump at the start of each interval instead of its end.
+        jump_start: bool,
+    },
+}
+
+/// Accepted JSON shapes for [`InterpMode`].
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum InterpModeRepr {
+    Css(String),
+    Tagged(#[serde(with = "InterpModeTagged")] InterpMode),
+}
+
+/// Derived tagged representation of [`InterpMode`], kept in sync by the compiler.
+#[derive(serde::Deserialize)]
+#[serde(remote = "InterpMode")]
+enum InterpModeTagged {
+    Hold,
+    Linear,
+    Spring {
+        stiffness: f32,
+        damping: f32,
+        mass: f32,
+    },
+    Steps {
+        count: u32,
+        jump_start: bool,
+    },
+}
+
+impl TryFrom<InterpModeRepr> for InterpMode {
+    type Error = WavyteError;
+
+    fn try_from(repr: InterpModeRepr) -> Result<Self, Self::Error> {
+        match repr {
+            InterpModeRepr::Css(s) => s.parse(),
+            InterpModeRepr::Tagged(mode) => Ok(mode),
+        }
+    }
+}
+
+impl InterpMode {
+    fn step_progress(count: u32, jump_start: bool, p: f64) -> f64 {
+        let n = f64::from(count);
+        let level = (p * n).floor() + if jump_start { 1.0 } else { 0.0 };
+        (level / n).clamp(0.0, 1.0)
+    }
+}
+
+impl std::str::FromStr for InterpMode {
+    type Err = WavyteError;
+
+    /// Parse `hold`, `linear`, or `steps(N)` / `steps(N, start)` / `steps(N, end)`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let norm = s.trim().to_ascii_lowercase();
+        match norm.as_str() {
+            "hold" => return Ok(Self::Hold),
+            "linear" => return Ok(Self::Linear),
+            _ => {}
+        }
+        let args = norm
+            .strip_prefix("steps(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(|| WavyteError::animation(format!("unknown interpolation mode '{s}'")))?;
+        let (count, position) = match args.split_once(',') {
+            Some((count, position)) => (count.trim(), position.trim()),
+            None => (args.trim(), "end"),
+        };
+        let count = count
+            .parse::<u32>()
+            .ok()
+            .filter(|&c| c > 0)
+            .ok_or_else(|| {
+                WavyteError::animation(format!("steps() count must be an integer > 0 in '{s}'"))
+            })?;
+        let jump_start = match position {
+            "start" | "jump-start" => true,
+            "end" | "jump-end" => false,
+            other => {
+                return Err(WavyteError::animation(format!(
+                    "unknown steps() position '{other}' (expected 'start' or 'end')"
+                )));
+            }
+        };
+        Ok(Self::Steps { count, jump_start })
+    }
+}
+
+/// Closed-form step response `x(t)` of `m x'' + c x' + k (x - 1) = 0` with `x(0) = x'(0) = 0`.
+fn spring_progress(stiffness: f64, damping: f64, mass: f64, t: f64) -> f64 {
+    let omega = (stiffness / mass).sqrt();
+    let zeta = damping / (2.0 * (stiffness * mass).sqrt());
+    let residual = if zeta < 1.0 {
+        let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+        (-zeta * omega * t).exp()
+            * ((omega_d * t).cos() + (zeta * omega / omega_d) * (omega_d * t).sin())
+    } else if zeta == 1.0 {
+        (-omega * t).exp() * (1.0 + omega * t)
+    } else {
+        let root = (zeta * zeta - 1.0).sqrt();
+        let r1 = -omega * (zeta - root);
+        let r2 = -omega * (zeta + root);
+        (r2 * (r1 * t).exp() - r1 * (r2 * t).exp()) / (r2 - r1)
+    };
+    1.0 - residual
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -1,5 +1,13 @@
+use std::cell::Cell;
+use std::sync::{OnceLock, RwLock};
+
+use crate::foundation::error::{WavyteError, WavyteResult};
+
 /// Easing functions used to map normalized animation progress.
-#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+///
+/// Serialized as its name (for example `"InOutCubic"`); names registered with [`register_ease`]
+/// are accepted as well.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Ease {
     /// Linear interpolation.
     Linear,
@@ -15,9 +23,131 @@ pub enum Ease {
     OutCubic,
     /// Cubic ease-in/out.
     InOutCubic,
+    /// User curve registered with [`register_ease`].
+    Custom(CustomEase),
+}
+
+/// Handle to an easing function registered with [`register_ease`].
+///
+/// Each registration gets its own handle, so an [`Ease`] parsed earlier keeps the curve that was
+/// registered at the time even if the name is registered again later.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CustomEase(u32);
+
+struct CustomEaseEntry {
+    name: String,
+    f: fn(f64) -> f64,
+}
+
+fn custom_eases() -> &'static RwLock<Vec<CustomEaseEntry>> {
+    static REGISTRY: OnceLock<RwLock<Vec<CustomEaseEntry>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Register a named easing curve for use in keyframe and transition `ease` fields.
+///
+/// Names resolve when a composition is parsed: register curves before parsing compositions that
+/// reference them. `f` receives progress clamped to `[0, 1]` and may overshoot. Registering an
+/// existing custom name makes later parses use the new function while compositions already
+/// parsed keep the old one; built-in names are rejected.
+pub fn register_ease(name: &str, f: fn(f64) -> f64) -> WavyteResult<CustomEase> {
+    if name.trim().is_empty() {
+        return Err(WavyteError::validation("ease name must be non-empty"));
+    }
+    if Ease::BUILTIN.iter().any(|(n, _)| *n == name) {
+        return Err(WavyteError::validation(format!(
+            "ease '{name}' is built in and cannot be registered"
+        )));
+    }
+    let mut registry = custom_eases()
+        .write()
+        .map_err(|_| WavyteError::evaluation("ease registry poisoned"))?;
+    registry.push(CustomEaseEntry {
+        name: name.to_string(),
+        f,
+    });
+    Ok(CustomEase((registry.len() - 1) as u32))
+}
+
+impl CustomEase {
+    /// Registered name of this curve.
+    pub fn name(self) -> String {
+        custom_eases()
+            .read()
+            .ok()
+            .and_then(|r| r.get(self.0 as usize).map(|e| e.name.clone()))
+            .unwrap_or_default()
+    }
+
+    fn apply(self, t: f64) -> f64 {
+        let f = custom_eases()
+            .read()
+            .ok()
+            .and_then(|r| r.get(self.0 as usize).map(|e| e.f));
+        f.map_or(t, |f| f(t))
+    }
+
+    /// Name plus a fingerprint of the curve's shape, stable across processes and registrations
+    /// of the same function.
+    fn identity(self) -> String {
+        let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+        for i in 0..=64 {
+            for b in self.apply(f64::from(i) / 64.0).to_bits().to_le_bytes() {
+                h = (h ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        }
+        format!("{}@{h:016x}", self.name())
+    }
+}
+
+thread_local! {
+    static SERIALIZE_CURVE_IDENTITY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` with custom eases serializing as their curve identity instead of their bare name.
+///
+/// Used by [`crate::Composition::content_hash`] so that two curves registered under one name
+/// hash differently.
+pub(crate) fn with_curve_identity<R>(f: impl FnOnce() -> R) -> R {
+    let prev = SERIALIZE_CURVE_IDENTITY.with(|c| c.replace(true));
+    let out = f();
+    SERIALIZE_CURVE_IDENTITY.with(|c| c.set(prev));
+    out
 }
 
 impl Ease {
+    const BUILTIN: [(&'static str, Ease); 7] = [
+        ("Linear", Ease::Linear),
+        ("InQuad", Ease::InQuad),
+        ("OutQuad", Ease::OutQuad),
+        ("InOutQuad", Ease::InOutQuad),
+        ("InCubic", Ease::InCubic),
+        ("OutCubic", Ease::OutCubic),
+        ("InOutCubic", Ease::InOutCubic),
+    ];
+
+    /// Look up a built-in or registered easing by name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some((_, ease)) = Self::BUILTIN.iter().find(|(n, _)| *n == name) {
+            return Some(*ease);
+        }
+        let registry = custom_eases().read().ok()?;
+        let idx = registry.iter().rposition(|e| e.name == name)?;
+        Some(Self::Custom(CustomEase(idx as u32)))
+    }
+
+    /// Name this easing serializes as.
+    pub fn name(self) -> String {
+        match self {
+            Self::Custom(c) => c.name(),
+            builtin => Self::BUILTIN
+                .iter()
+                .find(|(_, e)| *e == builtin)
+                .map(|(n, _)| (*n).to_string())
+                .unwrap_or_default(),
+        }
+    }
+
     /// Apply this easing function to normalized progress `t` in `[0, 1]`.
     pub fn apply(self, t: f64) -> f64 {
         let t = t.clamp(0.0, 1.0);
@@ -41,10 +171,33 @@ impl Ease {
                     1.0 - ((-2.0 * t + 2.0).powi(3) / 2.0)
                 }
             }
+            Self::Custom(c) => c.apply(t),
+        }
+    }
+}
+
+impl serde::Serialize for Ease {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Custom(c) if SERIALIZE_CURVE_IDENTITY.with(Cell::get) => {
+                serializer.serialize_str(&c.identity())
+            }
+            _ => serializer.serialize_str(&self.name()),
         }
     }
 }
 
+impl<'de> serde::Deserialize<'de> for Ease {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ease::from_name(&name).ok_or_else(|| {
+            serde::de::Error::custom(format!(
+                "unknown ease '{name}' (built-in or registered with register_ease)"
+            ))
+        })
+    }
+}
+
 #[cfg(test)]
 #[path = "../../tests/unit/animation/ease.rs"]
 mod tests;
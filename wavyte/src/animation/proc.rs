@@ -33,11 +33,62 @@ where
     }
 }
 
+impl<T> Procedural<T> {
+    pub fn validate(&self) -> WavyteResult<()> {
+        if let ProceduralKind::KenBurns {
+            from_scale,
+            to_scale,
+            from_center,
+            to_center,
+            duration_frames,
+        } = &self.kind
+        {
+            if *duration_frames == 0 {
+                return Err(WavyteError::validation(
+                    "KenBurns duration_frames must be > 0",
+                ));
+            }
+            if !(from_scale.is_finite()
+                && *from_scale > 0.0
+                && to_scale.is_finite()
+                && *to_scale > 0.0)
+            {
+                return Err(WavyteError::validation(
+                    "KenBurns scales must be finite and > 0",
+                ));
+            }
+            if ![from_center.x, from_center.y, to_center.x, to_center.y]
+                .iter()
+                .all(|v| v.is_finite())
+            {
+                return Err(WavyteError::validation("KenBurns centers must be finite"));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "kind", content = "params")]
 pub enum ProceduralKind {
     Scalar(ProcScalar),
-    Vec2 { x: ProcScalar, y: ProcScalar },
+    Vec2 {
+        x: ProcScalar,
+        y: ProcScalar,
+    },
+    /// Rotate-free pan + zoom over a still ("Ken Burns"), sampled as a `Transform2D`.
+    ///
+    /// Over `duration_frames` the focus point (clip-local coordinates) moves linearly from
+    /// `from_center` to `to_center` while the zoom around it goes from `from_scale` to `to_scale`;
+    /// the focus point stays pinned at the screen position of `from_center`. Holds the `to` pose
+    /// from frame `duration_frames - 1` on.
+    KenBurns {
+        from_scale: f64,
+        to_scale: f64,
+        from_center: Vec2,
+        to_center: Vec2,
+        duration_frames: u64,
+    },
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -53,6 +104,26 @@ pub enum ProcScalar {
         freq_hz: f64,
         offset: f64,
     },
+    /// Sine-aligned triangle wave: rises through `offset` at phase 0, peaks a quarter cycle later.
+    Triangle {
+        amp: f64,
+        freq_hz: f64,
+        phase: f64,
+        offset: f64,
+    },
+    /// Rising ramp from `offset - amp` that wraps back at every period boundary.
+    Sawtooth {
+        amp: f64,
+        freq_hz: f64,
+        phase: f64,
+        offset: f64,
+    },
+    /// Fresh value in `[offset, offset + amp)` every frame, hashed from the clip seed and
+    /// clip-local frame, so re-rendering a frame reproduces it.
+    Random {
+        amp: f64,
+        offset: f64,
+    },
     Envelope {
         attack: u64,
         decay: u64,
@@ -97,6 +168,23 @@ fn noise01(seed: u64, x: u64) -> f64 {
     rng.next_f64_01()
 }
 
+/// Piecewise-linear value noise in `[-1, 1]`, with one random knot per unit of `x`.
+fn value_noise(seed: u64, x: f64) -> f64 {
+    let i0 = x.floor();
+    let t = x - i0;
+    let i0u = i0.max(0.0) as u64;
+    let i1u = i0u + 1;
+
+    let a = noise01(seed, i0u) * 2.0 - 1.0;
+    let b = noise01(seed, i1u) * 2.0 - 1.0;
+    a + (b - a) * t
+}
+
+/// Position within the current cycle in `[0, 1)`; `phase` is in radians like [`ProcScalar::Sine`].
+fn cycle_position(freq_hz: f64, phase: f64, secs: f64) -> f64 {
+    (freq_hz * secs + phase / std::f64::consts::TAU).rem_euclid(1.0)
+}
+
 fn sample_scalar(s: &ProcScalar, fps: Fps, frame: u64, seed: u64) -> f64 {
     let secs = fps.frames_to_secs(frame);
     match *s {
@@ -106,22 +194,34 @@ fn sample_scalar(s: &ProcScalar, fps: Fps, frame: u64, seed: u64) -> f64 {
             phase,
             offset,
         } => offset + amp * (std::f64::consts::TAU * freq_hz * secs + phase).sin(),
-        ProcScalar::Noise1D {
+        ProcScalar::Triangle {
             amp,
             freq_hz,
+            phase,
             offset,
         } => {
-            let x = secs * freq_hz;
-            let i0 = x.floor();
-            let t = x - i0;
-            let i0u = i0.max(0.0) as u64;
-            let i1u = i0u + 1;
-
-            let a = noise01(seed, i0u) * 2.0 - 1.0;
-            let b = noise01(seed, i1u) * 2.0 - 1.0;
-            let v = a + (b - a) * t;
+            let u = cycle_position(freq_hz, phase, secs);
+            let v = if u < 0.25 {
+                4.0 * u
+            } else if u < 0.75 {
+                2.0 - 4.0 * u
+            } else {
+                4.0 * u - 4.0
+            };
             offset + amp * v
         }
+        ProcScalar::Sawtooth {
+            amp,
+            freq_hz,
+            phase,
+            offset,
+        } => offset + amp * (2.0 * cycle_position(freq_hz, phase, secs) - 1.0),
+        ProcScalar::Noise1D {
+            amp,
+            freq_hz,
+            offset,
+        } => offset + amp * value_noise(seed, secs * freq_hz),
+        ProcScalar::Random { amp, offset } => offset + amp * noise01(seed, frame),
         ProcScalar::Envelope {
             attack,
             decay,
@@ -156,6 +256,76 @@ fn sample_scalar(s: &ProcScalar, fps: Fps, frame: u64, seed: u64) -> f64 {
     }
 }
 
+/// Seeded per-frame jitter layered on top of a clip's animated transform, like After
+/// Effects' `wiggle(freq, amp)`.
+///
+/// Each channel (x, y, rotation, scale) follows its own noise curve with knots `1 / freq_hz`
+/// seconds apart, so offsets stay within `±amp` and are a pure function of the clip-local
+/// frame, the clip seed, and [`Wiggle::seed`].
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Wiggle {
+    /// Wiggles per second.
+    pub freq_hz: f64,
+    /// Maximum translation offset in pixels, applied independently on x and y.
+    pub amp: f64,
+    /// Maximum rotation offset in radians.
+    #[serde(default)]
+    pub rotation_amp_rad: f64,
+    /// Maximum uniform scale offset (added to both scale components).
+    #[serde(default)]
+    pub scale_amp: f64,
+    /// Extra seed so otherwise identical wiggles on one clip can differ.
+    #[serde(default)]
+    pub seed: u64,
+}
+
+impl Wiggle {
+    /// Translation-only wiggle.
+    pub fn new(freq_hz: f64, amp: f64, seed: u64) -> Self {
+        Self {
+            freq_hz,
+            amp,
+            rotation_amp_rad: 0.0,
+            scale_amp: 0.0,
+            seed,
+        }
+    }
+
+    /// Validate that frequency and amplitudes are finite and non-negative.
+    pub fn validate(&self) -> WavyteResult<()> {
+        if ![
+            self.freq_hz,
+            self.amp,
+            self.rotation_amp_rad,
+            self.scale_amp,
+        ]
+        .iter()
+        .all(|v| v.is_finite() && *v >= 0.0)
+        {
+            return Err(WavyteError::validation(
+                "wiggle freq_hz and amplitudes must be finite and >= 0",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Offset `base` by this wiggle's noise at `ctx`.
+    pub fn apply(&self, base: Transform2D, ctx: SampleCtx) -> Transform2D {
+        let x = ctx.fps.frames_to_secs(ctx.clip_local.0) * self.freq_hz;
+        let seed = ctx.seed ^ self.seed.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let channel = |i: u64| value_noise(Rng64::new(seed ^ i).next_u64(), x);
+
+        let mut out = base;
+        out.translate.x += self.amp * channel(0);
+        out.translate.y += self.amp * channel(1);
+        out.rotation_rad += self.rotation_amp_rad * channel(2);
+        let ds = self.scale_amp * channel(3);
+        out.scale.x += ds;
+        out.scale.y += ds;
+        out
+    }
+}
+
 impl ProcValue for f64 {
     fn from_procedural(kind: &ProceduralKind, ctx: SampleCtx) -> WavyteResult<Self> {
         match kind {
@@ -163,6 +333,9 @@ impl ProcValue for f64 {
             ProceduralKind::Vec2 { .. } => Err(WavyteError::animation(
                 "procedural kind Vec2 cannot be sampled as f64",
             )),
+            ProceduralKind::KenBurns { .. } => Err(WavyteError::animation(
+                "procedural kind KenBurns cannot be sampled as f64",
+            )),
         }
     }
 }
@@ -179,6 +352,9 @@ impl ProcValue for Vec2 {
             ProceduralKind::Scalar(_) => Err(WavyteError::animation(
                 "procedural kind Scalar cannot be sampled as Vec2",
             )),
+            ProceduralKind::KenBurns { .. } => Err(WavyteError::animation(
+                "procedural kind KenBurns cannot be sampled as Vec2",
+            )),
             ProceduralKind::Vec2 { x, y } => Ok(Vec2::new(
                 sample_scalar(x, ctx.fps, ctx.clip_local.0, ctx.seed),
                 sample_scalar(y, ctx.fps, ctx.clip_local.0, ctx.seed),
@@ -188,10 +364,30 @@ impl ProcValue for Vec2 {
 }
 
 impl ProcValue for Transform2D {
-    fn from_procedural(_kind: &ProceduralKind, _ctx: SampleCtx) -> WavyteResult<Self> {
-        Err(WavyteError::animation(
-            "procedural sampling for Transform2D is not supported in v0.2",
-        ))
+    fn from_procedural(kind: &ProceduralKind, ctx: SampleCtx) -> WavyteResult<Self> {
+        match *kind {
+            ProceduralKind::KenBurns {
+                from_scale,
+                to_scale,
+                from_center,
+                to_center,
+                duration_frames,
+            } => {
+                let last = duration_frames.saturating_sub(1).max(1);
+                let t = (ctx.clip_local.0.min(last) as f64) / (last as f64);
+                let scale = from_scale + (to_scale - from_scale) * t;
+                let center = from_center + (to_center - from_center) * t;
+                Ok(Self {
+                    translate: from_center - center,
+                    rotation_rad: 0.0,
+                    scale: Vec2::new(scale, scale),
+                    anchor: center,
+                })
+            }
+            ProceduralKind::Scalar(_) | ProceduralKind::Vec2 { .. } => Err(WavyteError::animation(
+                "procedural kind cannot be sampled as Transform2D (only KenBurns)",
+            )),
+        }
     }
 }
 
@@ -20,6 +20,7 @@ pub fn decode_image(bytes: &[u8]) -> WavyteResult<PreparedImage> {
         width,
         height,
         rgba8_premul: Arc::new(rgba8_premul),
+        sampling: crate::ImageSampling::default(),
     })
 }
 
@@ -1,7 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use crate::{
-    composition::model::{AudioAsset, VideoAsset},
+    composition::model::{AudioAsset, ImageSequenceAsset, VideoAsset, VideoLoopMode},
     foundation::error::{WavyteError, WavyteResult},
 };
 
@@ -47,18 +47,105 @@ impl VideoSourceInfo {
             f64::from(self.fps_num) / f64::from(self.fps_den)
         }
     }
+
+    /// Index of the source frame nearest to `source_time_sec`.
+    ///
+    /// Falls back to millisecond steps when the stream reports no usable frame rate.
+    pub fn frame_index_at(&self, source_time_sec: f64) -> u64 {
+        (source_time_sec.max(0.0) * self.frame_rate_or_ms()).round() as u64
+    }
+
+    /// Presentation time in seconds of source frame `index` (inverse of
+    /// [`VideoSourceInfo::frame_index_at`]).
+    pub fn frame_time_sec(&self, index: u64) -> f64 {
+        index as f64 / self.frame_rate_or_ms()
+    }
+
+    fn frame_rate_or_ms(&self) -> f64 {
+        let fps = self.source_fps();
+        if fps.is_finite() && fps > 0.0 {
+            fps
+        } else {
+            1000.0
+        }
+    }
 }
 
-/// Map clip-local timeline frame to source video time in seconds.
-pub fn video_source_time_sec(asset: &VideoAsset, clip_local_frames: u64, fps: crate::Fps) -> f64 {
-    let timeline_t = (clip_local_frames as f64) * (f64::from(fps.den) / f64::from(fps.num));
+/// Map a (possibly fractional) clip-local timeline frame to source video time in seconds.
+pub fn video_source_time_sec(asset: &VideoAsset, clip_local_frames: f64, fps: crate::Fps) -> f64 {
+    let timeline_t = clip_local_frames * (f64::from(fps.den) / f64::from(fps.num));
     let mut src_t = asset.trim_start_sec + timeline_t * asset.playback_rate;
-    if let Some(end) = asset.trim_end_sec {
+    // Looping videos are wrapped at draw time, once the source duration is known.
+    if asset.loop_mode == VideoLoopMode::None
+        && let Some(end) = asset.trim_end_sec
+    {
         src_t = src_t.min(end.max(asset.trim_start_sec));
     }
     src_t.max(0.0)
 }
 
+/// Wrap `source_time_sec` into the trimmed source window `[trim_start_sec, trim_end_sec)` per
+/// `mode`.
+///
+/// The window ends at `trim_end_sec` or, when unset or past it, the probed source duration. The
+/// result never lands past the last source frame in the window.
+pub fn loop_video_source_time(
+    mode: VideoLoopMode,
+    trim_start_sec: f64,
+    trim_end_sec: Option<f64>,
+    info: &VideoSourceInfo,
+    source_time_sec: f64,
+) -> f64 {
+    let mut end = trim_end_sec.unwrap_or(info.duration_sec);
+    if info.duration_sec > 0.0 {
+        end = end.min(info.duration_sec);
+    }
+    let len = end - trim_start_sec;
+    if mode == VideoLoopMode::None || !len.is_finite() || len <= 0.0 {
+        return source_time_sec;
+    }
+    let rel = (source_time_sec - trim_start_sec).max(0.0);
+    let rel = match mode {
+        VideoLoopMode::None | VideoLoopMode::Loop => rel.rem_euclid(len),
+        VideoLoopMode::PingPong => {
+            let p = rel.rem_euclid(2.0 * len);
+            if p < len { p } else { 2.0 * len - p }
+        }
+    };
+    let last_frame = (len - info.frame_time_sec(1)).max(0.0);
+    trim_start_sec + rel.min(last_frame)
+}
+
+/// Map clip-local timeline frame to the 0-based frame of an image sequence, before looping.
+///
+/// Uses exact rational arithmetic, so each sequence frame covers the same span of timeline
+/// frames regardless of how the two frame rates relate.
+pub fn image_sequence_frame(
+    asset: &ImageSequenceAsset,
+    clip_local_frames: u64,
+    fps: crate::Fps,
+) -> u64 {
+    let num = u128::from(clip_local_frames) * u128::from(asset.fps.num) * u128::from(fps.den);
+    let den = u128::from(asset.fps.den) * u128::from(fps.num);
+    (num / den) as u64
+}
+
+/// Wrap 0-based frame `index` into a sequence of `frame_count` frames per `mode`.
+pub fn loop_frame_index(mode: VideoLoopMode, index: u64, frame_count: u64) -> u64 {
+    let Some(last) = frame_count.checked_sub(1) else {
+        return 0;
+    };
+    match mode {
+        VideoLoopMode::None => index.min(last),
+        VideoLoopMode::Loop => index % frame_count,
+        VideoLoopMode::PingPong if last == 0 => 0,
+        VideoLoopMode::PingPong => {
+            let p = index % (2 * last);
+            if p <= last { p } else { 2 * last - p }
+        }
+    }
+}
+
 /// Map clip-local timeline frame to source audio time in seconds.
 pub fn audio_source_time_sec(asset: &AudioAsset, clip_local_frames: u64, fps: crate::Fps) -> f64 {
     let timeline_t = (clip_local_frames as f64) * (f64::from(fps.den) / f64::from(fps.num));
@@ -100,9 +187,9 @@ pub fn probe_video(source_path: &Path) -> WavyteResult<VideoSourceInfo> {
         ])
         .arg(source_path)
         .output()
-        .map_err(|e| WavyteError::evaluation(format!("failed to run ffprobe: {e}")))?;
+        .map_err(|e| WavyteError::io(format!("failed to run ffprobe: {e}")))?;
     if !out.status.success() {
-        return Err(WavyteError::evaluation(format!(
+        return Err(WavyteError::decode(format!(
             "ffprobe failed for '{}': {}",
             source_path.display(),
             String::from_utf8_lossy(&out.stderr).trim()
@@ -110,21 +197,21 @@ pub fn probe_video(source_path: &Path) -> WavyteResult<VideoSourceInfo> {
     }
 
     let parsed: ProbeOut = serde_json::from_slice(&out.stdout)
-        .map_err(|e| WavyteError::evaluation(format!("ffprobe json parse failed: {e}")))?;
+        .map_err(|e| WavyteError::decode(format!("ffprobe json parse failed: {e}")))?;
     let video_stream = parsed
         .streams
         .iter()
         .find(|s| s.codec_type.as_deref() == Some("video"))
-        .ok_or_else(|| WavyteError::evaluation("no video stream found"))?;
+        .ok_or_else(|| WavyteError::decode("no video stream found"))?;
     let width = video_stream
         .width
-        .ok_or_else(|| WavyteError::evaluation("missing video width from ffprobe"))?;
+        .ok_or_else(|| WavyteError::decode("missing video width from ffprobe"))?;
     let height = video_stream
         .height
-        .ok_or_else(|| WavyteError::evaluation("missing video height from ffprobe"))?;
+        .ok_or_else(|| WavyteError::decode("missing video height from ffprobe"))?;
 
     let (fps_num, fps_den) = parse_ff_ratio(video_stream.r_frame_rate.as_deref().unwrap_or("0/1"))
-        .ok_or_else(|| WavyteError::evaluation("invalid video r_frame_rate"))?;
+        .ok_or_else(|| WavyteError::decode("invalid video r_frame_rate"))?;
     let duration_sec = parsed
         .format
         .as_ref()
@@ -152,7 +239,7 @@ pub fn probe_video(source_path: &Path) -> WavyteResult<VideoSourceInfo> {
 ///
 /// Returns an error when `media-ffmpeg` feature is disabled.
 pub fn probe_video(_source_path: &Path) -> WavyteResult<VideoSourceInfo> {
-    Err(WavyteError::evaluation(
+    Err(WavyteError::unsupported(
         "video/audio assets require the 'media-ffmpeg' feature",
     ))
 }
@@ -165,7 +252,7 @@ pub fn decode_video_frame_rgba8(
 ) -> WavyteResult<Vec<u8>> {
     let mut frames = decode_video_frames_rgba8(source, source_time_sec, 1)?;
     frames.pop().ok_or_else(|| {
-        WavyteError::evaluation(format!(
+        WavyteError::decode(format!(
             "ffmpeg returned no video frames for '{}'",
             source.source_path.display()
         ))
@@ -197,12 +284,10 @@ pub(crate) fn decode_video_frames_rgba8(
             "pipe:1",
         ])
         .output()
-        .map_err(|e| {
-            WavyteError::evaluation(format!("failed to run ffmpeg for video decode: {e}"))
-        })?;
+        .map_err(|e| WavyteError::io(format!("failed to run ffmpeg for video decode: {e}")))?;
 
     if !out.status.success() {
-        return Err(WavyteError::evaluation(format!(
+        return Err(WavyteError::decode(format!(
             "ffmpeg video decode batch failed for '{}': {}",
             source.source_path.display(),
             String::from_utf8_lossy(&out.stderr).trim()
@@ -211,12 +296,12 @@ pub(crate) fn decode_video_frames_rgba8(
 
     let expected_len = source.width as usize * source.height as usize * 4;
     if expected_len == 0 {
-        return Err(WavyteError::evaluation(
+        return Err(WavyteError::decode(
             "decoded video frame size is zero (invalid source dimensions)",
         ));
     }
     if out.stdout.len() < expected_len || !out.stdout.len().is_multiple_of(expected_len) {
-        return Err(WavyteError::evaluation(format!(
+        return Err(WavyteError::decode(format!(
             "decoded video batch has invalid size: got {} bytes, expected multiples of {expected_len}",
             out.stdout.len()
         )));
@@ -239,7 +324,7 @@ pub fn decode_video_frame_rgba8(
     _source: &VideoSourceInfo,
     _source_time_sec: f64,
 ) -> WavyteResult<Vec<u8>> {
-    Err(WavyteError::evaluation(
+    Err(WavyteError::unsupported(
         "video/audio assets require the 'media-ffmpeg' feature",
     ))
 }
@@ -253,7 +338,7 @@ pub(crate) fn decode_video_frames_rgba8(
     _start_time_sec: f64,
     _frame_count: u32,
 ) -> WavyteResult<Vec<Vec<u8>>> {
-    Err(WavyteError::evaluation(
+    Err(WavyteError::unsupported(
         "video/audio assets require the 'media-ffmpeg' feature",
     ))
 }
@@ -277,9 +362,7 @@ pub fn decode_audio_f32_stereo(path: &Path, sample_rate: u32) -> WavyteResult<Au
             "pipe:1",
         ])
         .output()
-        .map_err(|e| {
-            WavyteError::evaluation(format!("failed to run ffmpeg for audio decode: {e}"))
-        })?;
+        .map_err(|e| WavyteError::io(format!("failed to run ffmpeg for audio decode: {e}")))?;
 
     if !out.status.success() {
         let msg = String::from_utf8_lossy(&out.stderr);
@@ -295,7 +378,7 @@ pub fn decode_audio_f32_stereo(path: &Path, sample_rate: u32) -> WavyteResult<Au
                 interleaved_f32: Vec::new(),
             });
         }
-        return Err(WavyteError::evaluation(format!(
+        return Err(WavyteError::decode(format!(
             "ffmpeg audio decode failed for '{}': {}",
             path.display(),
             msg.trim()
@@ -303,7 +386,7 @@ pub fn decode_audio_f32_stereo(path: &Path, sample_rate: u32) -> WavyteResult<Au
     }
 
     if !out.stdout.len().is_multiple_of(4) {
-        return Err(WavyteError::evaluation(
+        return Err(WavyteError::decode(
             "decoded audio byte length is not aligned to f32 samples",
         ));
     }
@@ -319,12 +402,103 @@ pub fn decode_audio_f32_stereo(path: &Path, sample_rate: u32) -> WavyteResult<Au
     })
 }
 
+/// Decode an in-memory RIFF/WAVE file to stereo interleaved `f32` PCM at `sample_rate`.
+///
+/// Handles integer PCM (8/16/24/32-bit) and 32-bit float, mono or stereo, without ffmpeg. Mono
+/// is duplicated to both channels and other rates are resampled linearly.
+pub fn decode_wav_f32_stereo(bytes: &[u8], sample_rate: u32) -> WavyteResult<AudioPcm> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(WavyteError::decode("audio is not a RIFF/WAVE file"));
+    }
+    let u16_at = |b: &[u8], at: usize| u16::from_le_bytes([b[at], b[at + 1]]);
+    let mut fmt = None;
+    let mut data = None;
+    let mut rest = &bytes[12..];
+    while rest.len() >= 8 {
+        let len = u32::from_le_bytes([rest[4], rest[5], rest[6], rest[7]]) as usize;
+        let body = rest
+            .get(8..8 + len)
+            .ok_or_else(|| WavyteError::decode("truncated WAV chunk"))?;
+        match &rest[0..4] {
+            b"fmt " if body.len() >= 16 => fmt = Some(body),
+            b"data" => data = Some(body),
+            _ => {}
+        }
+        rest = rest.get(8 + len + len % 2..).unwrap_or_default();
+    }
+    let (Some(fmt), Some(data)) = (fmt, data) else {
+        return Err(WavyteError::decode("WAV file lacks a fmt or data chunk"));
+    };
+
+    let mut format = u16_at(fmt, 0);
+    if format == 0xFFFE && fmt.len() >= 26 {
+        // WAVE_FORMAT_EXTENSIBLE: the subformat GUID starts with the plain format tag.
+        format = u16_at(fmt, 24);
+    }
+    let channels = usize::from(u16_at(fmt, 2));
+    let source_rate = u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]);
+    let bits = u16_at(fmt, 14);
+    let sample: fn(&[u8]) -> f32 = match (format, bits) {
+        (1, 8) => |b| (f32::from(b[0]) - 128.0) / 128.0,
+        (1, 16) => |b| f32::from(i16::from_le_bytes([b[0], b[1]])) / 32_768.0,
+        (1, 24) => |b| (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8_388_608.0,
+        (1, 32) => |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0,
+        (3, 32) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        _ => {
+            return Err(WavyteError::unsupported(format!(
+                "WAV format {format} with {bits}-bit samples is not supported"
+            )));
+        }
+    };
+    if !(1..=2).contains(&channels) || source_rate == 0 {
+        return Err(WavyteError::unsupported(format!(
+            "WAV files must be mono or stereo with a positive rate (got {channels} channels at \
+             {source_rate} Hz)"
+        )));
+    }
+
+    let width = usize::from(bits / 8);
+    let stereo = data
+        .chunks_exact(width * channels)
+        .map(|frame| {
+            let left = sample(&frame[..width]);
+            let right = if channels == 2 {
+                sample(&frame[width..])
+            } else {
+                left
+            };
+            [left, right]
+        })
+        .collect::<Vec<_>>();
+
+    let interleaved_f32 = if source_rate == sample_rate {
+        stereo.concat()
+    } else {
+        let out_frames = stereo.len() as u64 * u64::from(sample_rate) / u64::from(source_rate);
+        let step = f64::from(source_rate) / f64::from(sample_rate);
+        (0..out_frames)
+            .flat_map(|i| {
+                let pos = i as f64 * step;
+                let i0 = (pos.floor() as usize).min(stereo.len() - 1);
+                let i1 = (i0 + 1).min(stereo.len() - 1);
+                let t = (pos - i0 as f64) as f32;
+                [0, 1].map(|c| stereo[i0][c] + (stereo[i1][c] - stereo[i0][c]) * t)
+            })
+            .collect()
+    };
+    Ok(AudioPcm {
+        sample_rate,
+        channels: 2,
+        interleaved_f32,
+    })
+}
+
 #[cfg(not(feature = "media-ffmpeg"))]
 /// Decode audio from media source to stereo interleaved `f32` PCM.
 ///
 /// Returns an error when `media-ffmpeg` feature is disabled.
 pub fn decode_audio_f32_stereo(_path: &Path, _sample_rate: u32) -> WavyteResult<AudioPcm> {
-    Err(WavyteError::evaluation(
+    Err(WavyteError::unsupported(
         "video/audio assets require the 'media-ffmpeg' feature",
     ))
 }
@@ -1,4 +1,7 @@
 pub mod decode;
+pub mod gradient;
 pub mod media;
+pub mod source;
 pub mod store;
 pub mod svg_raster;
+pub mod zip_source;
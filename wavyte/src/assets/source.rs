@@ -0,0 +1,110 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::{
+    assets::store::normalize_rel_path,
+    foundation::error::{WavyteError, WavyteResult},
+};
+
+/// Source of raw asset bytes used by [`crate::PreparedAssetStore::prepare_with_source`].
+///
+/// Implement this to serve assets from virtual filesystems, archives, or embedded resources.
+/// Paths passed to [`AssetSource::read_bytes`] are already normalized with
+/// [`crate::normalize_rel_path`]; this includes images referenced from inside SVG assets.
+pub trait AssetSource: Send + Sync {
+    /// Read all bytes of the asset at normalized relative path `norm_path`.
+    fn read_bytes(&self, norm_path: &str) -> WavyteResult<Vec<u8>>;
+
+    /// Return `true` when an asset exists at normalized relative path `norm_path`.
+    ///
+    /// Used to find the end of image sequences, so that a failed read of a frame that does
+    /// exist is reported instead of cutting the sequence short. The default attempts
+    /// [`AssetSource::read_bytes`]; override it when reads can fail for other reasons.
+    fn exists(&self, norm_path: &str) -> bool {
+        self.read_bytes(norm_path).is_ok()
+    }
+
+    /// Filesystem root backing this source, if any.
+    ///
+    /// Without a root, SVG resources and font directories are not searched, video assets (which
+    /// are decoded by external `ffmpeg` processes) are rejected, and audio must be WAV.
+    fn root(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// [`AssetSource`] reading files relative to a filesystem root.
+#[derive(Clone, Debug)]
+pub struct FsAssetSource {
+    root: PathBuf,
+}
+
+impl FsAssetSource {
+    /// Create a source resolving relative paths against `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl AssetSource for FsAssetSource {
+    fn read_bytes(&self, norm_path: &str) -> WavyteResult<Vec<u8>> {
+        let path = self.root.join(Path::new(norm_path));
+        std::fs::read(&path)
+            .with_context(|| format!("read asset bytes from '{}'", path.display()))
+            .map_err(WavyteError::from)
+    }
+
+    fn exists(&self, norm_path: &str) -> bool {
+        self.root.join(Path::new(norm_path)).is_file()
+    }
+
+    fn root(&self) -> Option<&Path> {
+        Some(&self.root)
+    }
+}
+
+/// [`AssetSource`] serving bytes from memory, for targets without a filesystem (e.g. `wasm32`).
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryAssetMap {
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+impl InMemoryAssetMap {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert `bytes` under relative path `path` (normalized like composition asset sources).
+    pub fn insert(&mut self, path: &str, bytes: impl Into<Vec<u8>>) -> WavyteResult<()> {
+        self.files.insert(normalize_rel_path(path)?, bytes.into());
+        Ok(())
+    }
+
+    /// Number of stored files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Return `true` when no files are stored.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+impl AssetSource for InMemoryAssetMap {
+    fn read_bytes(&self, norm_path: &str) -> WavyteResult<Vec<u8>> {
+        self.files
+            .get(norm_path)
+            .cloned()
+            .ok_or_else(|| WavyteError::io(format!("in-memory asset '{norm_path}' not found")))
+    }
+
+    fn exists(&self, norm_path: &str) -> bool {
+        self.files.contains_key(norm_path)
+    }
+}
@@ -9,6 +9,7 @@ use anyhow::Context;
 use crate::{
     assets::decode as assets_decode,
     assets::media,
+    assets::source::{AssetSource, FsAssetSource},
     composition::model,
     foundation::core::BezPath,
     foundation::error::{WavyteError, WavyteResult},
@@ -24,6 +25,8 @@ pub struct PreparedImage {
     pub height: u32,
     /// Pixel bytes in row-major premultiplied RGBA8.
     pub rgba8_premul: Arc<Vec<u8>>,
+    /// Sampling filter used when drawing the image.
+    pub sampling: model::ImageSampling,
 }
 
 #[derive(Clone, Debug)]
@@ -55,6 +58,40 @@ pub struct PreparedText {
     pub font_bytes: Arc<Vec<u8>>,
     /// Primary detected family name from font data.
     pub font_family: String,
+    /// Codepoints of the text the font has no glyph for (see [`MissingGlyphPolicy`]).
+    ///
+    /// [`MissingGlyphPolicy`]: crate::MissingGlyphPolicy
+    pub missing_codepoints: Vec<char>,
+    /// Vertical offset of the layout from the clip origin, from [`model::TextAsset::valign`].
+    pub origin_offset_y: f32,
+    /// Bytes of [`model::TextAsset::fallback_fonts`], in order.
+    pub fallback_font_bytes: Vec<Arc<Vec<u8>>>,
+    /// Parley blob ids of the primary font followed by each fallback, to map glyph runs back to
+    /// their font (see [`PreparedText::font_slot`]).
+    pub font_blob_ids: Vec<u64>,
+}
+
+impl PreparedText {
+    /// Index of the font a glyph run was shaped with: `0` for the primary font, `i + 1` for
+    /// `fallback_font_bytes[i]`. Unknown fonts map to the primary.
+    pub fn font_slot(&self, font: &parley::FontData) -> usize {
+        let id = font.data.id();
+        self.font_blob_ids
+            .iter()
+            .position(|&b| b == id)
+            .unwrap_or(0)
+    }
+
+    /// Bytes of the font in `slot` (see [`PreparedText::font_slot`]).
+    pub fn font_bytes_for_slot(&self, slot: usize) -> &Arc<Vec<u8>> {
+        match slot {
+            0 => &self.font_bytes,
+            i => self
+                .fallback_font_bytes
+                .get(i - 1)
+                .unwrap_or(&self.font_bytes),
+        }
+    }
 }
 
 impl std::fmt::Debug for PreparedText {
@@ -63,6 +100,9 @@ impl std::fmt::Debug for PreparedText {
             .field("layout_ptr", &Arc::as_ptr(&self.layout))
             .field("font_bytes_len", &self.font_bytes.len())
             .field("font_family", &self.font_family)
+            .field("missing_codepoints", &self.missing_codepoints)
+            .field("origin_offset_y", &self.origin_offset_y)
+            .field("fallback_fonts", &self.fallback_font_bytes.len())
             .finish()
     }
 }
@@ -72,6 +112,61 @@ impl std::fmt::Debug for PreparedText {
 pub struct PreparedPath {
     /// Parsed Bezier path.
     pub path: BezPath,
+    /// Fill color (straight-alpha RGBA8), or `None` for stroke-only paths.
+    pub fill: Option<[u8; 4]>,
+    /// Fill rule for self-overlapping geometry.
+    pub fill_rule: model::FillRule,
+    /// Optional outline drawn over the fill.
+    pub stroke: Option<model::PathStroke>,
+}
+
+impl PreparedPath {
+    /// Local-space bounds of the painted pixels, including the stroke out to the default miter
+    /// limit.
+    pub fn paint_bounds(&self) -> kurbo::Rect {
+        let bounds = kurbo::Shape::bounding_box(&self.path);
+        match &self.stroke {
+            Some(s) => bounds.inflate(s.width_px * 2.0, s.width_px * 2.0),
+            None => bounds,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Prepared radial gradient parameters with resolved pixel size.
+pub struct PreparedRadialGradient {
+    /// Raster width in pixels.
+    pub width: u32,
+    /// Raster height in pixels.
+    pub height: u32,
+    /// Center in normalized `0..1` coordinates.
+    pub center: [f64; 2],
+    /// Radius in normalized units.
+    pub radius: f64,
+    /// Color stops sorted by offset (at least two).
+    pub stops: Vec<model::GradientStop>,
+}
+
+#[derive(Clone, Debug)]
+/// Prepared constant-color (optionally rounded) rectangle with resolved size.
+pub struct PreparedSolidRect {
+    /// Width in pixels.
+    pub width: f64,
+    /// Height in pixels.
+    pub height: f64,
+    /// Fill color (straight-alpha RGBA8).
+    pub color: [u8; 4],
+    /// Corner radius in pixels, at most half the smaller side (`0` for sharp corners).
+    pub corner_radius: f64,
+}
+
+#[derive(Clone, Debug)]
+/// Prepared nested composition; used for its intrinsic (canvas) size.
+pub struct PreparedComposition {
+    /// Child canvas width in pixels.
+    pub width: u32,
+    /// Child canvas height in pixels.
+    pub height: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -85,6 +180,31 @@ pub struct PreparedAudio {
     pub interleaved_f32: Arc<Vec<f32>>,
 }
 
+#[derive(Clone, Debug)]
+/// Prepared image sequence: encoded frames, decoded on demand by the renderer.
+pub struct PreparedImageSequence {
+    /// Encoded bytes of each frame, in playback order.
+    pub frames: Vec<Arc<Vec<u8>>>,
+    /// Width in pixels of the first frame.
+    pub width: u32,
+    /// Height in pixels of the first frame.
+    pub height: u32,
+    /// Playback rate of the sequence.
+    pub fps: crate::Fps,
+    /// Behavior after the last frame.
+    pub loop_mode: model::VideoLoopMode,
+    /// Sampling filter used when drawing frames.
+    pub sampling: model::ImageSampling,
+}
+
+impl PreparedImageSequence {
+    /// Index into [`Self::frames`] shown at sequence time `source_time_s`, after looping.
+    pub fn frame_at(&self, source_time_s: f64) -> usize {
+        let index = (source_time_s.max(0.0) * self.fps.as_f64()).round() as u64;
+        media::loop_frame_index(self.loop_mode, index, self.frames.len() as u64) as usize
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Prepared video asset metadata and optional decoded audio track.
 pub struct PreparedVideo {
@@ -92,6 +212,16 @@ pub struct PreparedVideo {
     pub info: Arc<media::VideoSourceInfo>,
     /// Predecoded audio stream if present.
     pub audio: Option<PreparedAudio>,
+    /// Blend bracketing source frames when sampling between them.
+    pub frame_blend: bool,
+    /// Sampling filter used when drawing frames.
+    pub sampling: model::ImageSampling,
+    /// Loop behavior past the trimmed end (see [`media::loop_video_source_time`]).
+    pub loop_mode: model::VideoLoopMode,
+    /// Trimmed source start in seconds, used when looping.
+    pub trim_start_sec: f64,
+    /// Trimmed source end in seconds, used when looping.
+    pub trim_end_sec: Option<f64>,
 }
 
 #[derive(Clone, Debug)]
@@ -107,8 +237,16 @@ pub enum PreparedAsset {
     Path(PreparedPath),
     /// Prepared video metadata/audio.
     Video(PreparedVideo),
+    /// Prepared image sequence frames.
+    ImageSequence(PreparedImageSequence),
     /// Prepared audio PCM.
     Audio(PreparedAudio),
+    /// Prepared radial gradient.
+    RadialGradient(PreparedRadialGradient),
+    /// Prepared solid rectangle.
+    SolidRect(PreparedSolidRect),
+    /// Prepared nested composition (its assets are stored under prefixed keys).
+    Composition(PreparedComposition),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -157,56 +295,146 @@ impl PreparedAssetStore {
     ///
     /// This front-loads IO/decoding so render stages can remain deterministic and IO-free.
     pub fn prepare(comp: &model::Composition, root: impl Into<PathBuf>) -> WavyteResult<Self> {
-        let root = root.into();
+        Self::prepare_with_source(comp, &FsAssetSource::new(root))
+    }
+
+    /// Prepare all assets referenced by `comp`, reading bytes through `source`.
+    ///
+    /// Sources without a filesystem root (such as [`crate::InMemoryAssetMap`]) support image,
+    /// SVG, and text assets, and audio assets stored as WAV; video assets are rejected.
+    pub fn prepare_with_source(
+        comp: &model::Composition,
+        source: &dyn AssetSource,
+    ) -> WavyteResult<Self> {
+        let root = source.root().map(Path::to_path_buf);
         let mut out = Self {
-            root,
+            root: root.clone().unwrap_or_default(),
             ids_by_key: HashMap::new(),
             assets_by_id: HashMap::new(),
         };
 
         let mut text_engine = TextLayoutEngine::new();
+        out.prepare_assets(comp, source, root.as_deref(), "", &mut text_engine)?;
+
+        Ok(out)
+    }
+
+    /// Prepare `comp`'s assets under `prefix`, recursing into nested compositions.
+    fn prepare_assets(
+        &mut self,
+        comp: &model::Composition,
+        source: &dyn AssetSource,
+        root: Option<&Path>,
+        prefix: &str,
+        text_engine: &mut TextLayoutEngine,
+    ) -> WavyteResult<()> {
         for (asset_key, asset) in &comp.assets {
-            let (kind, key) = out.key_for(asset)?;
+            let (kind, key) = Self::key_for(asset, comp.canvas)?;
             let id = Self::hash_id_for_key(kind, &key);
 
             let prepared = match asset {
-                model::Asset::Image(_) => {
-                    let bytes = out.read_bytes(&key.norm_path)?;
-                    PreparedAsset::Image(assets_decode::decode_image(&bytes)?)
+                model::Asset::Image(a) => {
+                    let bytes = source.read_bytes(&key.norm_path)?;
+                    let mut image = assets_decode::decode_image(&bytes)?;
+                    image.sampling = a.sampler.unwrap_or_default();
+                    PreparedAsset::Image(image)
+                }
+                model::Asset::ImageSequence(a) => {
+                    let mut frames = Vec::new();
+                    for index in a.start_index.. {
+                        let path = normalize_rel_path(&a.frame_path(index)?)?;
+                        // The sequence ends at the first missing frame; other failures surface.
+                        match source.read_bytes(&path) {
+                            Ok(bytes) => frames.push(Arc::new(bytes)),
+                            Err(_) if !frames.is_empty() && !source.exists(&path) => break,
+                            Err(e) => return Err(e),
+                        }
+                    }
+                    let first = assets_decode::decode_image(&frames[0])?;
+                    PreparedAsset::ImageSequence(PreparedImageSequence {
+                        frames,
+                        width: first.width,
+                        height: first.height,
+                        fps: a.fps,
+                        loop_mode: a.loop_mode,
+                        sampling: a.sampler.unwrap_or_default(),
+                    })
                 }
                 model::Asset::Svg(_) => {
-                    let bytes = out.read_bytes(&key.norm_path)?;
-                    PreparedAsset::Svg(parse_svg_with_options(&out.root, &key.norm_path, &bytes)?)
+                    let bytes = source.read_bytes(&key.norm_path)?;
+                    PreparedAsset::Svg(parse_svg_with_options(source, &key.norm_path, &bytes)?)
                 }
                 model::Asset::Text(a) => {
-                    let font_bytes = out.read_bytes(&key.norm_path)?;
+                    let font_bytes = source.read_bytes(&key.norm_path)?;
+                    let fallback_font_bytes = a
+                        .fallback_fonts
+                        .iter()
+                        .map(|p| Ok(Arc::new(source.read_bytes(&normalize_rel_path(p)?)?)))
+                        .collect::<WavyteResult<Vec<_>>>()?;
+                    text_engine.set_fallback_fonts(
+                        &fallback_font_bytes
+                            .iter()
+                            .map(|b| b.as_slice())
+                            .collect::<Vec<_>>(),
+                    )?;
                     let brush = TextBrushRgba8 {
                         r: a.color_rgba8[0],
                         g: a.color_rgba8[1],
                         b: a.color_rgba8[2],
                         a: a.color_rgba8[3],
                     };
-                    let layout = text_engine.layout_plain(
-                        &a.text,
-                        font_bytes.as_slice(),
-                        a.size_px,
-                        brush,
-                        a.max_width_px,
-                    )?;
+                    let paragraph = TextParagraph {
+                        max_width_px: a.max_width_px,
+                        align: a.align,
+                        line_height: a.line_height,
+                        letter_spacing_px: a.letter_spacing_px,
+                    };
+                    let layout = if a.spans.is_empty() {
+                        text_engine.layout_aligned(
+                            &a.text,
+                            font_bytes.as_slice(),
+                            a.size_px,
+                            brush,
+                            paragraph,
+                        )?
+                    } else {
+                        text_engine.layout_spans(
+                            &a.spans,
+                            font_bytes.as_slice(),
+                            a.size_px,
+                            brush,
+                            paragraph,
+                        )?
+                    };
+                    let origin_offset_y = match a.valign {
+                        model::TextVAlign::Top => 0.0,
+                        model::TextVAlign::Middle => -layout.height() * 0.5,
+                        model::TextVAlign::Bottom => -layout.height(),
+                    };
+                    let font_blob_ids = text_engine.last_font_blob_ids();
                     let family = text_engine
                         .last_family_name()
                         .unwrap_or_else(|| "unknown".to_string());
+                    let missing_codepoints =
+                        text_engine.missing_codepoints(&a.content(), font_bytes.as_slice())?;
                     PreparedAsset::Text(PreparedText {
                         layout: Arc::new(layout),
                         font_bytes: Arc::new(font_bytes),
                         font_family: family,
+                        missing_codepoints,
+                        origin_offset_y,
+                        fallback_font_bytes,
+                        font_blob_ids,
                     })
                 }
                 model::Asset::Path(a) => PreparedAsset::Path(PreparedPath {
                     path: parse_svg_path(&a.svg_path_d)?,
+                    fill: a.resolved_fill(),
+                    fill_rule: a.fill_rule,
+                    stroke: a.stroke.clone(),
                 }),
                 model::Asset::Video(a) => {
-                    let source_path = out.root.join(Path::new(&key.norm_path));
+                    let source_path = media_source_path(root, &key.norm_path)?;
                     let info = media::probe_video(&source_path)?;
                     let audio = if info.has_audio {
                         let pcm =
@@ -223,31 +451,88 @@ impl PreparedAssetStore {
                     } else {
                         None
                     };
-                    let _ = a;
                     PreparedAsset::Video(PreparedVideo {
                         info: Arc::new(info),
                         audio,
+                        frame_blend: a.frame_blend,
+                        sampling: a.sampler.unwrap_or_default(),
+                        loop_mode: a.loop_mode,
+                        trim_start_sec: a.trim_start_sec,
+                        trim_end_sec: a.trim_end_sec,
                     })
                 }
                 model::Asset::Audio(_) => {
-                    let source_path = out.root.join(Path::new(&key.norm_path));
-                    let pcm = media::decode_audio_f32_stereo(&source_path, media::MIX_SAMPLE_RATE)?;
+                    let pcm = match root {
+                        Some(root) => media::decode_audio_f32_stereo(
+                            &root.join(Path::new(&key.norm_path)),
+                            media::MIX_SAMPLE_RATE,
+                        )?,
+                        // Without a filesystem there is no ffmpeg input; decode WAV in-process.
+                        None => media::decode_wav_f32_stereo(
+                            &source.read_bytes(&key.norm_path)?,
+                            media::MIX_SAMPLE_RATE,
+                        )?,
+                    };
                     PreparedAsset::Audio(PreparedAudio {
                         sample_rate: pcm.sample_rate,
                         channels: pcm.channels,
                         interleaved_f32: Arc::new(pcm.interleaved_f32),
                     })
                 }
+                model::Asset::RadialGradient(a) => {
+                    let (w, h) = intrinsic_size_or_canvas(a.width, a.height, comp.canvas);
+                    PreparedAsset::RadialGradient(PreparedRadialGradient {
+                        width: (w.ceil() as u32).max(1),
+                        height: (h.ceil() as u32).max(1),
+                        center: a.center,
+                        radius: a.radius,
+                        stops: a.resolved_stops(),
+                    })
+                }
+                model::Asset::SolidRect(a) => {
+                    let (width, height) = intrinsic_size_or_canvas(a.width, a.height, comp.canvas);
+                    PreparedAsset::SolidRect(PreparedSolidRect {
+                        width,
+                        height,
+                        color: a.color,
+                        corner_radius: 0.0,
+                    })
+                }
+                model::Asset::RoundedRect(a) => {
+                    let (width, height) = intrinsic_size_or_canvas(a.width, a.height, comp.canvas);
+                    PreparedAsset::SolidRect(PreparedSolidRect {
+                        width,
+                        height,
+                        color: a.color,
+                        corner_radius: a.radius_px.min(width.min(height) * 0.5),
+                    })
+                }
+                model::Asset::Composition(a) => {
+                    let child = &a.composition;
+                    self.prepare_assets(
+                        child,
+                        source,
+                        root,
+                        &format!("{prefix}{asset_key}/"),
+                        text_engine,
+                    )?;
+                    PreparedAsset::Composition(PreparedComposition {
+                        width: child.canvas.width,
+                        height: child.canvas.height,
+                    })
+                }
             };
 
-            out.ids_by_key.insert(asset_key.clone(), id);
-            out.assets_by_id.insert(id, prepared);
+            self.ids_by_key.insert(format!("{prefix}{asset_key}"), id);
+            self.assets_by_id.insert(id, prepared);
         }
 
-        Ok(out)
+        Ok(())
     }
 
     /// Return root directory used when resolving relative asset paths.
+    ///
+    /// Empty when the store was prepared from a source without a filesystem root.
     pub fn root(&self) -> &Path {
         &self.root
     }
@@ -260,6 +545,15 @@ impl PreparedAssetStore {
             .ok_or_else(|| WavyteError::evaluation(format!("unknown asset key '{key}'")))
     }
 
+    /// Composition asset key for `id` (the smallest one when several keys share an asset).
+    pub(crate) fn key_for_id(&self, id: AssetId) -> Option<&str> {
+        self.ids_by_key
+            .iter()
+            .filter(|(_, v)| **v == id)
+            .map(|(k, _)| k.as_str())
+            .min()
+    }
+
     /// Lookup prepared asset data by [`AssetId`].
     pub fn get(&self, id: AssetId) -> WavyteResult<&PreparedAsset> {
         self.assets_by_id
@@ -267,10 +561,23 @@ impl PreparedAssetStore {
             .ok_or_else(|| WavyteError::evaluation(format!("unknown AssetId {}", id.as_u64())))
     }
 
-    fn key_for(&self, asset: &model::Asset) -> WavyteResult<(u8, AssetKey)> {
+    fn key_for(
+        asset: &model::Asset,
+        canvas: crate::foundation::core::Canvas,
+    ) -> WavyteResult<(u8, AssetKey)> {
         match asset {
-            model::Asset::Image(a) => {
-                Ok((b'I', AssetKey::new(normalize_rel_path(&a.source)?, vec![])))
+            model::Asset::Image(a) => Ok((
+                b'I',
+                AssetKey::new(normalize_rel_path(&a.source)?, sampler_params(a.sampler)),
+            )),
+            model::Asset::ImageSequence(a) => {
+                let mut params = sampler_params(a.sampler);
+                params.push(("fps".to_string(), format!("{}/{}", a.fps.num, a.fps.den)));
+                params.push(("start_index".to_string(), a.start_index.to_string()));
+                if a.loop_mode != model::VideoLoopMode::None {
+                    params.push(("loop_mode".to_string(), format!("{:?}", a.loop_mode)));
+                }
+                Ok((b'N', AssetKey::new(normalize_rel_path(&a.pattern)?, params)))
             }
             model::Asset::Svg(a) => {
                 Ok((b'S', AssetKey::new(normalize_rel_path(&a.source)?, vec![])))
@@ -297,21 +604,144 @@ impl PreparedAssetStore {
                         format!("0x{:08x}", w.to_bits()),
                     ));
                 }
+                if a.align != model::TextAlign::Left {
+                    params.push(("align".to_string(), format!("{:?}", a.align)));
+                }
+                if a.valign != model::TextVAlign::Top {
+                    params.push(("valign".to_string(), format!("{:?}", a.valign)));
+                }
+                for (i, p) in a.fallback_fonts.iter().enumerate() {
+                    params.push((format!("fallback_font_{i}"), normalize_rel_path(p)?));
+                }
+                if let Some(v) = a.line_height {
+                    params.push((
+                        "line_height_bits".to_string(),
+                        format!("0x{:08x}", v.to_bits()),
+                    ));
+                }
+                if let Some(v) = a.letter_spacing_px {
+                    params.push((
+                        "letter_spacing_px_bits".to_string(),
+                        format!("0x{:08x}", v.to_bits()),
+                    ));
+                }
+                if !a.spans.is_empty() {
+                    params.push((
+                        "spans".to_string(),
+                        serde_json::to_string(&a.spans)
+                            .map_err(|e| WavyteError::serde(format!("text spans: {e}")))?,
+                    ));
+                }
                 Ok((b'T', AssetKey::new(norm_path, params)))
             }
-            model::Asset::Path(a) => Ok((
-                b'P',
-                AssetKey::new(
-                    "inline:path".to_string(),
-                    vec![("svg_path_d".to_string(), a.svg_path_d.clone())],
-                ),
-            )),
+            model::Asset::Path(a) => {
+                let mut params = vec![("svg_path_d".to_string(), a.svg_path_d.clone())];
+                if let Some(fill) = a.fill {
+                    params.push(("fill".to_string(), format!("{fill:?}")));
+                }
+                if a.fill_rule != model::FillRule::NonZero {
+                    params.push(("fill_rule".to_string(), format!("{:?}", a.fill_rule)));
+                }
+                if let Some(stroke) = &a.stroke {
+                    params.push((
+                        "stroke".to_string(),
+                        format!("{:?}:0x{:016x}", stroke.color, stroke.width_px.to_bits()),
+                    ));
+                }
+                Ok((b'P', AssetKey::new("inline:path".to_string(), params)))
+            }
             model::Asset::Video(a) => {
-                Ok((b'V', AssetKey::new(normalize_rel_path(&a.source)?, vec![])))
+                let mut params = sampler_params(a.sampler);
+                if a.frame_blend {
+                    params.push(("frame_blend".to_string(), "1".to_string()));
+                }
+                if a.loop_mode != model::VideoLoopMode::None {
+                    params.push(("loop_mode".to_string(), format!("{:?}", a.loop_mode)));
+                    params.push((
+                        "trim_start".to_string(),
+                        a.trim_start_sec.to_bits().to_string(),
+                    ));
+                    if let Some(end) = a.trim_end_sec {
+                        params.push(("trim_end".to_string(), end.to_bits().to_string()));
+                    }
+                }
+                Ok((b'V', AssetKey::new(normalize_rel_path(&a.source)?, params)))
             }
             model::Asset::Audio(a) => {
                 Ok((b'A', AssetKey::new(normalize_rel_path(&a.source)?, vec![])))
             }
+            model::Asset::RadialGradient(a) => Ok((
+                b'R',
+                AssetKey::new(
+                    "inline:radial_gradient".to_string(),
+                    vec![
+                        (
+                            "center_bits".to_string(),
+                            format!(
+                                "0x{:016x},0x{:016x}",
+                                a.center[0].to_bits(),
+                                a.center[1].to_bits()
+                            ),
+                        ),
+                        (
+                            "radius_bits".to_string(),
+                            format!("0x{:016x}", a.radius.to_bits()),
+                        ),
+                        (
+                            "stops".to_string(),
+                            a.resolved_stops()
+                                .iter()
+                                .map(|s| format!("0x{:016x}:{:?}", s.offset.to_bits(), s.color))
+                                .collect::<Vec<_>>()
+                                .join(";"),
+                        ),
+                        (
+                            "size".to_string(),
+                            resolved_size_param(a.width, a.height, canvas),
+                        ),
+                    ],
+                ),
+            )),
+            model::Asset::SolidRect(a) => Ok((
+                b'Q',
+                AssetKey::new(
+                    "inline:solid_rect".to_string(),
+                    vec![
+                        ("color".to_string(), format!("{:?}", a.color)),
+                        (
+                            "size".to_string(),
+                            resolved_size_param(a.width, a.height, canvas),
+                        ),
+                    ],
+                ),
+            )),
+            model::Asset::RoundedRect(a) => Ok((
+                b'O',
+                AssetKey::new(
+                    "inline:rounded_rect".to_string(),
+                    vec![
+                        ("color".to_string(), format!("{:?}", a.color)),
+                        (
+                            "size".to_string(),
+                            resolved_size_param(a.width, a.height, canvas),
+                        ),
+                        ("radius".to_string(), format!("{:?}", a.radius_px)),
+                    ],
+                ),
+            )),
+            model::Asset::Composition(a) => Ok((
+                b'C',
+                AssetKey::new(
+                    "inline:composition".to_string(),
+                    vec![(
+                        "size".to_string(),
+                        format!(
+                            "{}x{}",
+                            a.composition.canvas.width, a.composition.canvas.height
+                        ),
+                    )],
+                ),
+            )),
         }
     }
 
@@ -328,18 +758,56 @@ impl PreparedAssetStore {
         }
         AssetId(hasher.finish())
     }
+}
 
-    fn read_bytes(&self, norm_path: &str) -> WavyteResult<Vec<u8>> {
-        let path = self.root.join(Path::new(norm_path));
-        std::fs::read(&path)
-            .with_context(|| format!("read asset bytes from '{}'", path.display()))
-            .map_err(WavyteError::from)
+/// Key params for a non-default sampler, so assets differing only in sampling get distinct ids.
+fn sampler_params(sampler: Option<model::ImageSampling>) -> Vec<(String, String)> {
+    match sampler.unwrap_or_default() {
+        model::ImageSampling::Bilinear => vec![],
+        model::ImageSampling::Nearest => vec![("sampler".to_string(), "nearest".to_string())],
     }
 }
 
-fn parse_svg_with_options(root: &Path, norm_path: &str, bytes: &[u8]) -> WavyteResult<PreparedSvg> {
-    let abs = root.join(Path::new(norm_path));
-    let resources_dir = abs.parent().map(|p| p.to_path_buf());
+fn media_source_path(root: Option<&Path>, norm_path: &str) -> WavyteResult<PathBuf> {
+    root.map(|root| root.join(Path::new(norm_path)))
+        .ok_or_else(|| {
+            WavyteError::unsupported(format!(
+                "video/audio asset '{norm_path}' requires a filesystem asset source"
+            ))
+        })
+}
+
+/// Key param for an optional intrinsic size, resolved against `canvas` so canvas-sized assets of
+/// nested compositions with different canvases get distinct ids.
+fn resolved_size_param(
+    width: Option<f64>,
+    height: Option<f64>,
+    canvas: crate::foundation::core::Canvas,
+) -> String {
+    let (w, h) = intrinsic_size_or_canvas(width, height, canvas);
+    format!("{w:?}x{h:?}")
+}
+
+fn intrinsic_size_or_canvas(
+    width: Option<f64>,
+    height: Option<f64>,
+    canvas: crate::foundation::core::Canvas,
+) -> (f64, f64) {
+    (
+        width.unwrap_or(f64::from(canvas.width)),
+        height.unwrap_or(f64::from(canvas.height)),
+    )
+}
+
+fn parse_svg_with_options(
+    source: &dyn AssetSource,
+    norm_path: &str,
+    bytes: &[u8],
+) -> WavyteResult<PreparedSvg> {
+    let root = source.root();
+    let resources_dir = root
+        .map(|root| root.join(Path::new(norm_path)))
+        .and_then(|abs| abs.parent().map(|p| p.to_path_buf()));
 
     let fontdb = build_svg_fontdb(root, resources_dir.as_deref());
     let font_resolver = make_svg_font_resolver();
@@ -347,6 +815,7 @@ fn parse_svg_with_options(root: &Path, norm_path: &str, bytes: &[u8]) -> WavyteR
         resources_dir,
         fontdb,
         font_resolver,
+        image_href_resolver: make_svg_image_href_resolver(source, norm_path),
         ..Default::default()
     };
 
@@ -356,15 +825,59 @@ fn parse_svg_with_options(root: &Path, norm_path: &str, bytes: &[u8]) -> WavyteR
     })
 }
 
+/// Resolve `<image href="...">` through `source` (relative to the SVG's own directory), falling
+/// back to usvg's filesystem lookup for hrefs the source cannot serve.
+fn make_svg_image_href_resolver<'a>(
+    source: &'a dyn AssetSource,
+    svg_norm_path: &'a str,
+) -> usvg::ImageHrefResolver<'a> {
+    let fs_fallback = source
+        .root()
+        .map(|_| usvg::ImageHrefResolver::default_string_resolver());
+    let sniff = usvg::ImageHrefResolver::default_data_resolver();
+    usvg::ImageHrefResolver {
+        resolve_data: usvg::ImageHrefResolver::default_data_resolver(),
+        resolve_string: Box::new(move |href, opts| {
+            let from_source = resolve_svg_href(svg_norm_path, href)
+                .and_then(|rel| source.read_bytes(&rel).ok())
+                .and_then(|bytes| sniff("text/plain", Arc::new(bytes), opts));
+            from_source.or_else(|| fs_fallback.as_ref().and_then(|f| f(href, opts)))
+        }),
+    }
+}
+
+/// Join a relative `href` onto the directory of `svg_norm_path`, returning a normalized relative
+/// path, or `None` for URLs, absolute paths, and paths escaping the source root.
+fn resolve_svg_href(svg_norm_path: &str, href: &str) -> Option<String> {
+    let href = href.replace('\\', "/");
+    if href.contains(':') || href.starts_with('/') {
+        return None;
+    }
+    let mut parts = svg_norm_path.split('/').collect::<Vec<_>>();
+    parts.pop();
+    for part in href.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            _ => parts.push(part),
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
 fn build_svg_fontdb(
-    root: &Path,
+    root: Option<&Path>,
     resources_dir: Option<&Path>,
 ) -> std::sync::Arc<usvg::fontdb::Database> {
     let mut db = usvg::fontdb::Database::new();
     db.load_system_fonts();
 
-    load_fonts_from_dir(&mut db, &root.join("fonts"));
-    load_fonts_from_dir(&mut db, &root.join("assets"));
+    if let Some(root) = root {
+        load_fonts_from_dir(&mut db, &root.join("fonts"));
+        load_fonts_from_dir(&mut db, &root.join("assets"));
+    }
 
     if let Some(dir) = resources_dir {
         load_fonts_from_dir(&mut db, dir);
@@ -494,11 +1007,28 @@ fn parse_svg_path(d: &str) -> WavyteResult<BezPath> {
     BezPath::from_svg(d).map_err(|e| WavyteError::validation(format!("invalid svg_path_d: {e}")))
 }
 
+/// Paragraph-level layout options for [`TextLayoutEngine`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextParagraph {
+    /// Wrap width in pixels; lines also align within it.
+    pub max_width_px: Option<f32>,
+    /// Horizontal line alignment.
+    pub align: model::TextAlign,
+    /// Line height as a multiple of the font size; `None` uses the font's own metrics.
+    pub line_height: Option<f32>,
+    /// Extra space between characters in pixels.
+    pub letter_spacing_px: Option<f32>,
+}
+
 /// Stateful helper for building Parley text layouts from raw font bytes.
 pub struct TextLayoutEngine {
     font_ctx: parley::FontContext,
     layout_ctx: parley::LayoutContext<TextBrushRgba8>,
     last_family_name: Option<String>,
+    last_blob_id: Option<u64>,
+    registered_fonts: HashMap<u64, (String, u64)>,
+    fallback_families: Vec<String>,
+    fallback_blob_ids: Vec<u64>,
 }
 
 impl Default for TextLayoutEngine {
@@ -514,6 +1044,10 @@ impl TextLayoutEngine {
             font_ctx: parley::FontContext::default(),
             layout_ctx: parley::LayoutContext::new(),
             last_family_name: None,
+            last_blob_id: None,
+            registered_fonts: HashMap::new(),
+            fallback_families: Vec::new(),
+            fallback_blob_ids: Vec::new(),
         }
     }
 
@@ -522,6 +1056,29 @@ impl TextLayoutEngine {
         self.last_family_name.clone()
     }
 
+    /// Blob ids of the last primary font followed by the current fallback fonts.
+    pub fn last_font_blob_ids(&self) -> Vec<u64> {
+        self.last_blob_id
+            .into_iter()
+            .chain(self.fallback_blob_ids.iter().copied())
+            .collect()
+    }
+
+    /// Register fonts that later layouts fall back to, in order, for characters the primary
+    /// font lacks. Replaces any previous fallbacks; pass an empty slice to clear them.
+    pub fn set_fallback_fonts(&mut self, fonts: &[&[u8]]) -> WavyteResult<()> {
+        let mut families = Vec::with_capacity(fonts.len());
+        let mut blob_ids = Vec::with_capacity(fonts.len());
+        for bytes in fonts {
+            let (family, blob_id) = self.register_font(bytes)?;
+            families.push(family);
+            blob_ids.push(blob_id);
+        }
+        self.fallback_families = families;
+        self.fallback_blob_ids = blob_ids;
+        Ok(())
+    }
+
     /// Shape and lay out plain text using provided font bytes and styling.
     pub fn layout_plain(
         &mut self,
@@ -530,6 +1087,52 @@ impl TextLayoutEngine {
         size_px: f32,
         brush: TextBrushRgba8,
         max_width_px: Option<f32>,
+    ) -> WavyteResult<parley::Layout<TextBrushRgba8>> {
+        self.layout_aligned(
+            text,
+            font_bytes,
+            size_px,
+            brush,
+            TextParagraph {
+                max_width_px,
+                ..TextParagraph::default()
+            },
+        )
+    }
+
+    /// Like [`TextLayoutEngine::layout_plain`], with paragraph options (alignment, spacing).
+    pub fn layout_aligned(
+        &mut self,
+        text: &str,
+        font_bytes: &[u8],
+        size_px: f32,
+        brush: TextBrushRgba8,
+        paragraph: TextParagraph,
+    ) -> WavyteResult<parley::Layout<TextBrushRgba8>> {
+        self.layout_styled(text, &[], font_bytes, size_px, brush, paragraph)
+    }
+
+    /// Lay out styled `spans` back to back; unset span fields fall back to `size_px`/`brush`.
+    pub fn layout_spans(
+        &mut self,
+        spans: &[model::TextSpan],
+        font_bytes: &[u8],
+        size_px: f32,
+        brush: TextBrushRgba8,
+        paragraph: TextParagraph,
+    ) -> WavyteResult<parley::Layout<TextBrushRgba8>> {
+        let text = spans.iter().map(|s| s.text.as_str()).collect::<String>();
+        self.layout_styled(&text, spans, font_bytes, size_px, brush, paragraph)
+    }
+
+    fn layout_styled(
+        &mut self,
+        text: &str,
+        spans: &[model::TextSpan],
+        font_bytes: &[u8],
+        size_px: f32,
+        brush: TextBrushRgba8,
+        paragraph: TextParagraph,
     ) -> WavyteResult<parley::Layout<TextBrushRgba8>> {
         if !size_px.is_finite() || size_px <= 0.0 {
             return Err(WavyteError::validation(
@@ -537,33 +1140,169 @@ impl TextLayoutEngine {
             ));
         }
 
-        let families = self
-            .font_ctx
-            .collection
-            .register_fonts(parley::fontique::Blob::from(font_bytes.to_vec()), None);
-        let family_id = families.first().map(|(id, _)| *id).ok_or_else(|| {
-            WavyteError::validation("no font families registered from font bytes")
-        })?;
+        let family_name = self.register_family(font_bytes)?;
+        let mut layout = self.build_layout(text, spans, family_name, size_px, brush, paragraph);
+        if paragraph.align != model::TextAlign::Left {
+            // Parley leaves the last line of each paragraph unjustified.
+            let alignment = match paragraph.align {
+                model::TextAlign::Left => parley::Alignment::Start,
+                model::TextAlign::Center => parley::Alignment::Center,
+                model::TextAlign::Right => parley::Alignment::End,
+                model::TextAlign::Justify => parley::Alignment::Justify,
+            };
+            layout.align(
+                paragraph.max_width_px,
+                alignment,
+                parley::AlignmentOptions::default(),
+            );
+        }
+        Ok(layout)
+    }
+
+    /// Return the distinct codepoints of `text` that neither `font_bytes` nor the current fallback
+    /// fonts have a glyph for, in order.
+    ///
+    /// Whitespace and control characters are ignored. Each remaining codepoint is shaped on its
+    /// own, so this is meant for prepare-time diagnostics rather than per-frame use.
+    pub fn missing_codepoints(&mut self, text: &str, font_bytes: &[u8]) -> WavyteResult<Vec<char>> {
+        let mut chars = text
+            .chars()
+            .filter(|c| !c.is_whitespace() && !c.is_control())
+            .collect::<Vec<_>>();
+        chars.sort_unstable();
+        chars.dedup();
+        if chars.is_empty() {
+            return Ok(chars);
+        }
+
+        let family_name = self.register_family(font_bytes)?;
+        let mut missing = Vec::new();
+        for c in chars {
+            let layout = self.build_layout(
+                c.encode_utf8(&mut [0u8; 4]),
+                &[],
+                family_name.clone(),
+                16.0,
+                TextBrushRgba8::default(),
+                TextParagraph::default(),
+            );
+            // Glyph id 0 is `.notdef`, which renders as tofu.
+            let notdef = layout.lines().any(|line| {
+                line.items().any(|item| match item {
+                    parley::layout::PositionedLayoutItem::GlyphRun(run) => {
+                        run.glyphs().any(|g| g.id == 0)
+                    }
+                    _ => false,
+                })
+            });
+            if notdef {
+                missing.push(c);
+            }
+        }
+        Ok(missing)
+    }
+
+    fn register_family(&mut self, font_bytes: &[u8]) -> WavyteResult<String> {
+        let (family_name, blob_id) = self.register_font(font_bytes)?;
+        self.last_family_name = Some(family_name.clone());
+        self.last_blob_id = Some(blob_id);
+        Ok(family_name)
+    }
+
+    fn register_font(&mut self, font_bytes: &[u8]) -> WavyteResult<(String, u64)> {
+        // Register each distinct font once so parley resolves a family to a single blob.
+        let mut hasher = Fnv1a64::new_default();
+        hasher.write_bytes(font_bytes);
+        let content_hash = hasher.finish();
+        if let Some(registered) = self.registered_fonts.get(&content_hash) {
+            return Ok(registered.clone());
+        }
+
+        let blob = parley::fontique::Blob::from(font_bytes.to_vec());
+        let blob_id = blob.id();
+        let families = self.font_ctx.collection.register_fonts(blob, None);
+        let family_id = families
+            .first()
+            .map(|(id, _)| *id)
+            .ok_or_else(|| WavyteError::decode("no font families registered from font bytes"))?;
 
         let family_name = self
             .font_ctx
             .collection
             .family_name(family_id)
-            .ok_or_else(|| WavyteError::validation("registered font family has no name"))?
+            .ok_or_else(|| WavyteError::decode("registered font family has no name"))?
             .to_string();
-        self.last_family_name = Some(family_name.clone());
+        self.registered_fonts
+            .insert(content_hash, (family_name.clone(), blob_id));
+        Ok((family_name, blob_id))
+    }
 
+    fn build_layout(
+        &mut self,
+        text: &str,
+        spans: &[model::TextSpan],
+        family_name: String,
+        size_px: f32,
+        brush: TextBrushRgba8,
+        paragraph: TextParagraph,
+    ) -> parley::Layout<TextBrushRgba8> {
         let mut builder = self
             .layout_ctx
             .ranged_builder(&mut self.font_ctx, text, 1.0, true);
+        let stack = if self.fallback_families.is_empty() {
+            family_name
+        } else {
+            std::iter::once(&family_name)
+                .chain(&self.fallback_families)
+                .map(|f| format!("\"{f}\""))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
         builder.push_default(parley::style::StyleProperty::FontStack(
-            parley::style::FontStack::Source(std::borrow::Cow::Owned(family_name)),
+            parley::style::FontStack::Source(std::borrow::Cow::Owned(stack)),
         ));
         builder.push_default(parley::style::StyleProperty::FontSize(size_px));
         builder.push_default(parley::style::StyleProperty::Brush(brush));
+        if let Some(m) = paragraph.line_height {
+            builder.push_default(parley::style::StyleProperty::LineHeight(
+                parley::style::LineHeight::FontSizeRelative(m),
+            ));
+        }
+        if let Some(px) = paragraph.letter_spacing_px {
+            builder.push_default(parley::style::StyleProperty::LetterSpacing(px));
+        }
+
+        let mut start = 0usize;
+        for span in spans {
+            let range = start..start + span.text.len();
+            start = range.end;
+            if let Some([r, g, b, a]) = span.color_rgba8 {
+                builder.push(
+                    parley::style::StyleProperty::Brush(TextBrushRgba8 { r, g, b, a }),
+                    range.clone(),
+                );
+            }
+            if let Some(size) = span.size_px {
+                builder.push(parley::style::StyleProperty::FontSize(size), range.clone());
+            }
+            if let Some(weight) = span.weight {
+                builder.push(
+                    parley::style::StyleProperty::FontWeight(parley::style::FontWeight::new(
+                        weight,
+                    )),
+                    range.clone(),
+                );
+            }
+            if span.italic {
+                builder.push(
+                    parley::style::StyleProperty::FontStyle(parley::style::FontStyle::Italic),
+                    range,
+                );
+            }
+        }
 
         let mut layout: parley::Layout<TextBrushRgba8> = builder.build(text);
-        if let Some(w) = max_width_px {
+        if let Some(w) = paragraph.max_width_px {
             layout.break_all_lines(Some(w));
             layout.align(
                 Some(w),
@@ -573,8 +1312,7 @@ impl TextLayoutEngine {
         } else {
             layout.break_all_lines(None);
         }
-
-        Ok(layout)
+        layout
     }
 }
 
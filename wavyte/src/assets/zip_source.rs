@@ -0,0 +1,265 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context;
+
+use crate::{
+    assets::source::AssetSource,
+    assets::store::normalize_rel_path,
+    composition::model::{Asset, Composition},
+    foundation::error::{WavyteError, WavyteResult},
+};
+
+const EOCD_SIG: u32 = 0x0605_4b50;
+const CENTRAL_SIG: u32 = 0x0201_4b50;
+const LOCAL_SIG: u32 = 0x0403_4b50;
+const EOCD_MIN_LEN: usize = 22;
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATE: u16 = 8;
+
+#[derive(Clone, Copy, Debug)]
+struct ZipEntry {
+    method: u16,
+    crc32: u32,
+    compressed_size: u32,
+    uncompressed_size: u32,
+    local_header_offset: u32,
+}
+
+/// [`AssetSource`] reading assets out of a zip archive (a `.wavyte` bundle).
+///
+/// Entry names are matched against normalized composition-relative paths. Stored and deflate
+/// entries are supported (no zip64 or encryption); decompressed entries are cached.
+pub struct ZipAssetSource {
+    archive: Vec<u8>,
+    entries: BTreeMap<String, ZipEntry>,
+    cache: Mutex<HashMap<String, Arc<Vec<u8>>>>,
+}
+
+impl std::fmt::Debug for ZipAssetSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZipAssetSource")
+            .field("archive_bytes", &self.archive.len())
+            .field("entries", &self.entries.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl ZipAssetSource {
+    /// Name of the composition entry read by [`ZipAssetSource::load_composition`].
+    pub const COMPOSITION_ENTRY: &'static str = "composition.json";
+
+    /// Open a bundle from a file on disk.
+    pub fn open(path: impl AsRef<Path>) -> WavyteResult<Self> {
+        let path = path.as_ref();
+        let archive = std::fs::read(path)
+            .with_context(|| format!("read asset bundle '{}'", path.display()))?;
+        Self::from_bytes(archive)
+    }
+
+    /// Index a bundle already held in memory.
+    pub fn from_bytes(archive: Vec<u8>) -> WavyteResult<Self> {
+        let entries = read_central_directory(&archive)?;
+        Ok(Self {
+            archive,
+            entries,
+            cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Return `true` when the bundle has an entry for normalized path `norm_path`.
+    pub fn contains(&self, norm_path: &str) -> bool {
+        self.entries.contains_key(norm_path)
+    }
+
+    /// Normalized names of all file entries, sorted.
+    pub fn entry_names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Check that every file referenced by `comp`'s assets is present in the bundle.
+    ///
+    /// Reports all missing paths at once, before any asset is decoded.
+    pub fn validate_composition(&self, comp: &Composition) -> WavyteResult<()> {
+        let mut missing = Vec::new();
+        self.collect_missing(comp, &mut missing)?;
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            missing.sort();
+            missing.dedup();
+            Err(WavyteError::validation(format!(
+                "asset bundle is missing referenced assets: {}",
+                missing.join(", ")
+            )))
+        }
+    }
+
+    /// Push the referenced paths of `comp`, and of compositions nested in it, that the bundle
+    /// lacks.
+    fn collect_missing(&self, comp: &Composition, missing: &mut Vec<String>) -> WavyteResult<()> {
+        for asset in comp.assets.values() {
+            for source in asset.source_paths() {
+                let norm = normalize_rel_path(&source)?;
+                if !self.contains(&norm) {
+                    missing.push(norm);
+                }
+            }
+            if let Asset::Composition(a) = asset {
+                self.collect_missing(&a.composition, missing)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse and validate the bundled [`ZipAssetSource::COMPOSITION_ENTRY`], then check that the
+    /// bundle contains all assets it references.
+    pub fn load_composition(&self) -> WavyteResult<Composition> {
+        let bytes = self.read_bytes(Self::COMPOSITION_ENTRY)?;
+        let comp: Composition = serde_json::from_slice(&bytes)?;
+        comp.validate()?;
+        self.validate_composition(&comp)?;
+        Ok(comp)
+    }
+
+    fn extract(&self, norm_path: &str, entry: ZipEntry) -> WavyteResult<Vec<u8>> {
+        let data = entry_data(&self.archive, entry)
+            .map_err(|e| WavyteError::decode(format!("bundle entry '{norm_path}': {e}")))?;
+        let out = match entry.method {
+            METHOD_STORED => data.to_vec(),
+            METHOD_DEFLATE => miniz_oxide::inflate::decompress_to_vec_with_limit(
+                data,
+                entry.uncompressed_size as usize,
+            )
+            .map_err(|e| {
+                WavyteError::decode(format!("bundle entry '{norm_path}': inflate failed: {e:?}"))
+            })?,
+            m => {
+                return Err(WavyteError::unsupported(format!(
+                    "bundle entry '{norm_path}' uses unsupported compression method {m}"
+                )));
+            }
+        };
+        if out.len() != entry.uncompressed_size as usize || crc32fast::hash(&out) != entry.crc32 {
+            return Err(WavyteError::decode(format!(
+                "bundle entry '{norm_path}' failed size/crc32 check"
+            )));
+        }
+        Ok(out)
+    }
+}
+
+impl AssetSource for ZipAssetSource {
+    fn read_bytes(&self, norm_path: &str) -> WavyteResult<Vec<u8>> {
+        if let Some(bytes) = self
+            .cache
+            .lock()
+            .ok()
+            .and_then(|c| c.get(norm_path).cloned())
+        {
+            return Ok(bytes.as_ref().clone());
+        }
+        let entry = *self
+            .entries
+            .get(norm_path)
+            .ok_or_else(|| WavyteError::io(format!("asset '{norm_path}' not found in bundle")))?;
+        let bytes = Arc::new(self.extract(norm_path, entry)?);
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.insert(norm_path.to_string(), bytes.clone());
+        }
+        Ok(bytes.as_ref().clone())
+    }
+
+    fn exists(&self, norm_path: &str) -> bool {
+        self.contains(norm_path)
+    }
+}
+
+fn u16_at(buf: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(buf.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_at(buf: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(buf.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn read_central_directory(archive: &[u8]) -> WavyteResult<BTreeMap<String, ZipEntry>> {
+    let bad = |msg: &str| WavyteError::decode(format!("invalid asset bundle: {msg}"));
+
+    // The end-of-central-directory record sits in the last 22 + 65535 (max comment) bytes.
+    let search_from = archive
+        .len()
+        .saturating_sub(EOCD_MIN_LEN + usize::from(u16::MAX));
+    let eocd = (search_from..=archive.len().saturating_sub(EOCD_MIN_LEN))
+        .rev()
+        .find(|&at| u32_at(archive, at) == Some(EOCD_SIG))
+        .ok_or_else(|| bad("end of central directory not found"))?;
+
+    let count = u16_at(archive, eocd + 10).ok_or_else(|| bad("truncated directory"))?;
+    let cd_offset = u32_at(archive, eocd + 16).ok_or_else(|| bad("truncated directory"))?;
+    if count == u16::MAX || cd_offset == u32::MAX {
+        return Err(WavyteError::unsupported(
+            "zip64 asset bundles are not supported",
+        ));
+    }
+
+    let mut entries = BTreeMap::new();
+    let mut at = cd_offset as usize;
+    for _ in 0..count {
+        if u32_at(archive, at) != Some(CENTRAL_SIG) {
+            return Err(bad("corrupt central directory entry"));
+        }
+        let field = |off: usize| u32_at(archive, at + off).ok_or_else(|| bad("truncated entry"));
+        let short = |off: usize| u16_at(archive, at + off).ok_or_else(|| bad("truncated entry"));
+        let flags = short(8)?;
+        let method = short(10)?;
+        let crc32 = field(16)?;
+        let compressed_size = field(20)?;
+        let uncompressed_size = field(24)?;
+        let name_len = usize::from(short(28)?);
+        let extra_len = usize::from(short(30)?);
+        let comment_len = usize::from(short(32)?);
+        let local_header_offset = field(42)?;
+        let name = archive
+            .get(at + 46..at + 46 + name_len)
+            .ok_or_else(|| bad("truncated entry name"))?;
+        at += 46 + name_len + extra_len + comment_len;
+
+        let name = String::from_utf8_lossy(name);
+        if name.ends_with('/') {
+            continue;
+        }
+        if flags & 1 != 0 {
+            return Err(WavyteError::unsupported(format!(
+                "encrypted bundle entry '{name}' is not supported"
+            )));
+        }
+        entries.insert(
+            normalize_rel_path(&name)?,
+            ZipEntry {
+                method,
+                crc32,
+                compressed_size,
+                uncompressed_size,
+                local_header_offset,
+            },
+        );
+    }
+    Ok(entries)
+}
+
+fn entry_data(archive: &[u8], entry: ZipEntry) -> Result<&[u8], &'static str> {
+    let at = entry.local_header_offset as usize;
+    if u32_at(archive, at) != Some(LOCAL_SIG) {
+        return Err("bad local header");
+    }
+    let name_len = usize::from(u16_at(archive, at + 26).ok_or("truncated local header")?);
+    let extra_len = usize::from(u16_at(archive, at + 28).ok_or("truncated local header")?);
+    let start = at + 30 + name_len + extra_len;
+    archive
+        .get(start..start + entry.compressed_size as usize)
+        .ok_or("truncated entry data")
+}
@@ -3,7 +3,9 @@ use std::{path::Path, sync::Arc};
 use crate::{
     assets::media,
     assets::store::{PreparedAsset, PreparedAssetStore},
-    composition::model::{Asset, AudioAsset, Clip, Composition, VideoAsset},
+    composition::model::{
+        Asset, AudioAsset, AudioDuck, AudioFadeCurve, Clip, Composition, VideoAsset,
+    },
     foundation::core::{Fps, FrameIndex, FrameRange},
     foundation::error::{WavyteError, WavyteResult},
 };
@@ -27,12 +29,22 @@ pub struct AudioSegment {
     pub fade_in_sec: f64,
     /// Fade-out duration in seconds.
     pub fade_out_sec: f64,
+    /// Shape of the fade ramps.
+    pub fade_curve: AudioFadeCurve,
     /// Source sample rate in Hz.
     pub source_sample_rate: u32,
     /// Source channel count.
     pub source_channels: u16,
     /// Source interleaved PCM data.
     pub source_interleaved_f32: Arc<Vec<f32>>,
+    /// Constant-power stereo position in `[-1, 1]`.
+    pub pan: f32,
+    /// Mid/side width multiplier (`0` is mono, `1` unchanged).
+    pub width: f32,
+    /// Composition asset key this segment plays (used to find duck triggers).
+    pub asset_key: String,
+    /// Sidechain ducking applied to this segment.
+    pub duck: Option<AudioDuck>,
 }
 
 #[derive(Clone, Debug)]
@@ -48,6 +60,44 @@ pub struct AudioManifest {
     pub segments: Vec<AudioSegment>,
 }
 
+#[derive(Clone, Debug)]
+/// Mixed timeline audio returned by [`mix_audio`].
+pub struct AudioBuffer {
+    /// Sample rate in Hz (`MIX_SAMPLE_RATE`).
+    pub sample_rate: u32,
+    /// Channel count (stereo).
+    pub channels: u16,
+    /// Samples per channel.
+    pub samples: u64,
+    /// Interleaved PCM in `[-1, 1]`.
+    pub interleaved_f32: Vec<f32>,
+}
+
+/// Mix the audio of a timeline range into memory.
+///
+/// Produces the same PCM that [`render_to_mp4`](crate::render_to_mp4) feeds to ffmpeg, with
+/// per-asset volume, mute, trim, playback rate and fades applied, e.g. to drive waveform
+/// visualizations.
+pub fn mix_audio(
+    comp: &Composition,
+    assets: &PreparedAssetStore,
+    range: FrameRange,
+) -> WavyteResult<AudioBuffer> {
+    comp.validate()?;
+    if range.end.0 > comp.duration.0 {
+        return Err(WavyteError::validation(
+            "audio mix range must be within composition duration",
+        ));
+    }
+    let manifest = build_audio_manifest(comp, assets, range)?;
+    Ok(AudioBuffer {
+        sample_rate: manifest.sample_rate,
+        channels: manifest.channels,
+        samples: manifest.total_samples,
+        interleaved_f32: mix_manifest(&manifest),
+    })
+}
+
 /// Build audio mixing manifest for the given timeline range.
 pub fn build_audio_manifest(
     comp: &Composition,
@@ -113,62 +163,30 @@ pub fn mix_manifest(manifest: &AudioManifest) -> Vec<f32> {
     let frames = manifest.total_samples as usize;
     let mut out = vec![0.0f32; frames * usize::from(manifest.channels)];
 
-    for seg in &manifest.segments {
-        let seg_len_samples = seg
-            .timeline_end_sample
-            .saturating_sub(seg.timeline_start_sample);
-        if seg_len_samples == 0 {
-            continue;
+    // One envelope per distinct follower, shared by every segment ducked by it.
+    let mut envelopes = Vec::<(&AudioDuck, Vec<f32>)>::new();
+    for duck in manifest.segments.iter().filter_map(|s| s.duck.as_ref()) {
+        if !envelopes.iter().any(|(d, _)| same_follower(d, duck)) {
+            envelopes.push((duck, duck_envelope(manifest, duck)));
         }
-        let src = seg.source_interleaved_f32.as_ref();
-        let src_frames = src.len() / usize::from(seg.source_channels);
-        if src_frames == 0 {
-            continue;
-        }
-
-        for dst_sample in seg.timeline_start_sample..seg.timeline_end_sample {
-            let rel_sample = dst_sample - seg.timeline_start_sample;
-            let rel_sec = (rel_sample as f64) / f64::from(manifest.sample_rate);
-            let src_sec = seg.source_start_sec + rel_sec * seg.playback_rate;
-            if let Some(end_sec) = seg.source_end_sec
-                && src_sec >= end_sec
-            {
-                break;
-            }
-            let src_pos = src_sec * f64::from(seg.source_sample_rate);
-            if !src_pos.is_finite() || src_pos < 0.0 {
-                break;
-            }
-            let src_frame0 = src_pos.floor() as usize;
-            if src_frame0 >= src_frames {
-                break;
-            }
-            let src_frame1 = (src_frame0 + 1).min(src_frames.saturating_sub(1));
-            let frac = (src_pos - src_frame0 as f64) as f32;
+    }
 
-            let src_gain = fade_gain(seg, rel_sec, seg_len_samples, manifest.sample_rate);
-            let gain = src_gain * seg.volume;
-            let dst_idx = dst_sample as usize * usize::from(manifest.channels);
-            let (l, r) = if seg.source_channels == 1 {
-                let v0 = src[src_frame0];
-                let v1 = src[src_frame1];
-                let v = v0 + ((v1 - v0) * frac);
-                (v, v)
-            } else {
-                let i0 = src_frame0 * usize::from(seg.source_channels);
-                let i1 = src_frame1 * usize::from(seg.source_channels);
-                let l0 = src[i0];
-                let l1 = src[i1];
-                let r0 = src[i0 + 1];
-                let r1 = src[i1 + 1];
-                (l0 + ((l1 - l0) * frac), r0 + ((r1 - r0) * frac))
+    for seg in &manifest.segments {
+        let duck = seg.duck.as_ref().and_then(|d| {
+            let (_, env) = envelopes.iter().find(|(e, _)| same_follower(e, d))?;
+            Some((d.amount as f32, env))
+        });
+        for_each_segment_sample(seg, manifest.sample_rate, |dst_sample, l, r| {
+            let gain = match duck {
+                Some((amount, env)) => 1.0 - amount * env[dst_sample as usize],
+                None => 1.0,
             };
-
+            let dst_idx = dst_sample as usize * usize::from(manifest.channels);
             out[dst_idx] += l * gain;
             if manifest.channels > 1 {
                 out[dst_idx + 1] += r * gain;
             }
-        }
+        });
     }
 
     for s in &mut out {
@@ -177,6 +195,123 @@ pub fn mix_manifest(manifest: &AudioManifest) -> Vec<f32> {
     out
 }
 
+/// Per-sample ducking depth in `[0, 1]` driven by the (pre-duck) level of `duck.trigger_asset`.
+///
+/// The trigger's peak level, normalized by [`AudioDuck::threshold`], is smoothed by a one-pole
+/// follower with separate attack/release time constants, so the result depends only on the
+/// manifest.
+fn duck_envelope(manifest: &AudioManifest, duck: &AudioDuck) -> Vec<f32> {
+    let mut level = vec![0.0f32; manifest.total_samples as usize * 2];
+    for seg in manifest
+        .segments
+        .iter()
+        .filter(|s| s.asset_key == duck.trigger_asset)
+    {
+        for_each_segment_sample(seg, manifest.sample_rate, |dst_sample, l, r| {
+            level[dst_sample as usize * 2] += l;
+            level[dst_sample as usize * 2 + 1] += r;
+        });
+    }
+
+    let coef = |ms: f64| {
+        let samples = ms / 1000.0 * f64::from(manifest.sample_rate);
+        if samples > 0.0 {
+            (-1.0 / samples).exp() as f32
+        } else {
+            0.0
+        }
+    };
+    let (attack, release) = (coef(duck.attack_ms), coef(duck.release_ms));
+    let full_level = duck.threshold as f32;
+    let mut env = 0.0f32;
+    level
+        .chunks_exact(2)
+        .map(|lr| {
+            let target = (lr[0].abs().max(lr[1].abs()) / full_level).min(1.0);
+            let c = if target > env { attack } else { release };
+            env = target + c * (env - target);
+            env
+        })
+        .collect()
+}
+
+/// Whether two duck settings produce the same envelope (`amount` only scales its use).
+fn same_follower(a: &AudioDuck, b: &AudioDuck) -> bool {
+    a.trigger_asset == b.trigger_asset
+        && a.attack_ms == b.attack_ms
+        && a.release_ms == b.release_ms
+        && a.threshold == b.threshold
+}
+
+/// Call `f(dst_sample, left, right)` with the gained (volume and fades) stereo contribution of
+/// `seg` at each timeline sample it covers.
+fn for_each_segment_sample(seg: &AudioSegment, sample_rate: u32, mut f: impl FnMut(u64, f32, f32)) {
+    let seg_len_samples = seg
+        .timeline_end_sample
+        .saturating_sub(seg.timeline_start_sample);
+    if seg_len_samples == 0 {
+        return;
+    }
+    let src = seg.source_interleaved_f32.as_ref();
+    let src_frames = src.len() / usize::from(seg.source_channels);
+    if src_frames == 0 {
+        return;
+    }
+    let spatial = (seg.pan != 0.0 || seg.width != 1.0).then(|| {
+        // Constant-power law normalized to unity gain at center.
+        let theta = (seg.pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+        let norm = std::f32::consts::SQRT_2;
+        (theta.cos() * norm, theta.sin() * norm, seg.width.max(0.0))
+    });
+
+    for dst_sample in seg.timeline_start_sample..seg.timeline_end_sample {
+        let rel_sample = dst_sample - seg.timeline_start_sample;
+        let rel_sec = (rel_sample as f64) / f64::from(sample_rate);
+        let src_sec = seg.source_start_sec + rel_sec * seg.playback_rate;
+        if let Some(end_sec) = seg.source_end_sec
+            && src_sec >= end_sec
+        {
+            break;
+        }
+        let src_pos = src_sec * f64::from(seg.source_sample_rate);
+        if !src_pos.is_finite() || src_pos < 0.0 {
+            break;
+        }
+        let src_frame0 = src_pos.floor() as usize;
+        if src_frame0 >= src_frames {
+            break;
+        }
+        let src_frame1 = (src_frame0 + 1).min(src_frames.saturating_sub(1));
+        let frac = (src_pos - src_frame0 as f64) as f32;
+
+        let src_gain = fade_gain(seg, rel_sec, seg_len_samples, sample_rate);
+        let gain = src_gain * seg.volume;
+        let (l, r) = if seg.source_channels == 1 {
+            let v0 = src[src_frame0];
+            let v1 = src[src_frame1];
+            let v = v0 + ((v1 - v0) * frac);
+            (v, v)
+        } else {
+            let i0 = src_frame0 * usize::from(seg.source_channels);
+            let i1 = src_frame1 * usize::from(seg.source_channels);
+            let l0 = src[i0];
+            let l1 = src[i1];
+            let r0 = src[i0 + 1];
+            let r1 = src[i1 + 1];
+            (l0 + ((l1 - l0) * frac), r0 + ((r1 - r0) * frac))
+        };
+        let (l, r) = match spatial {
+            Some((pan_l, pan_r, width)) => {
+                let mid = (l + r) * 0.5;
+                let side = (l - r) * 0.5 * width;
+                ((mid + side) * pan_l, (mid - side) * pan_r)
+            }
+            None => (l, r),
+        };
+        f(dst_sample, l * gain, r * gain);
+    }
+}
+
 /// Write interleaved `f32` PCM samples to raw little-endian file.
 pub fn write_mix_to_f32le_file(samples_interleaved: &[f32], out_path: &Path) -> WavyteResult<()> {
     if let Some(parent) = out_path.parent() {
@@ -201,16 +336,20 @@ pub fn write_mix_to_f32le_file(samples_interleaved: &[f32], out_path: &Path) ->
 }
 
 fn fade_gain(seg: &AudioSegment, rel_sec: f64, seg_len_samples: u64, sample_rate: u32) -> f32 {
+    let shape = |t: f32| match seg.fade_curve {
+        AudioFadeCurve::Linear => t,
+        AudioFadeCurve::EqualPower => (t * std::f32::consts::FRAC_PI_2).sin(),
+    };
     let mut gain = 1.0f32;
     if seg.fade_in_sec > 0.0 {
         let t = (rel_sec / seg.fade_in_sec).clamp(0.0, 1.0) as f32;
-        gain *= t;
+        gain *= shape(t);
     }
     if seg.fade_out_sec > 0.0 {
         let seg_len_sec = (seg_len_samples as f64) / f64::from(sample_rate);
         let rem = (seg_len_sec - rel_sec).max(0.0);
         let t = (rem / seg.fade_out_sec).clamp(0.0, 1.0) as f32;
-        gain *= t;
+        gain *= shape(t);
     }
     gain
 }
@@ -246,9 +385,13 @@ fn push_audio_segment(
         audio_asset.volume as f32,
         audio_asset.fade_in_sec,
         audio_asset.fade_out_sec,
+        audio_asset.fade_curve,
         pcm.sample_rate,
         pcm.channels,
         pcm.interleaved_f32.clone(),
+        &clip.asset,
+        [audio_asset.pan as f32, audio_asset.width as f32],
+        audio_asset.duck.clone(),
     );
     Ok(())
 }
@@ -281,15 +424,23 @@ fn push_video_audio_segment(
         range_start,
         clip.range.start,
         fps,
-        media::video_source_time_sec(video_asset, intersection.start.0 - clip.range.start.0, fps),
+        media::video_source_time_sec(
+            video_asset,
+            (intersection.start.0 - clip.range.start.0) as f64,
+            fps,
+        ),
         video_asset.trim_end_sec,
         video_asset.playback_rate,
         video_asset.volume as f32,
         video_asset.fade_in_sec,
         video_asset.fade_out_sec,
+        video_asset.fade_curve,
         audio.sample_rate,
         audio.channels,
         audio.interleaved_f32.clone(),
+        &clip.asset,
+        [0.0, 1.0],
+        None,
     );
     Ok(())
 }
@@ -307,9 +458,13 @@ fn push_segment_common(
     volume: f32,
     fade_in_sec: f64,
     fade_out_sec: f64,
+    fade_curve: AudioFadeCurve,
     source_sample_rate: u32,
     source_channels: u16,
     source_interleaved_f32: Arc<Vec<f32>>,
+    asset_key: &str,
+    [pan, width]: [f32; 2],
+    duck: Option<AudioDuck>,
 ) {
     let timeline_start_sample = frame_to_sample(
         intersection.start.0 - range_start.0,
@@ -332,9 +487,14 @@ fn push_segment_common(
         volume,
         fade_in_sec,
         fade_out_sec,
+        fade_curve,
         source_sample_rate,
         source_channels,
         source_interleaved_f32,
+        pan,
+        width,
+        asset_key: asset_key.to_string(),
+        duck,
     });
 }
 
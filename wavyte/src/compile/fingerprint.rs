@@ -1,5 +1,7 @@
 use crate::{
-    composition::model::BlendMode, eval::evaluator::EvaluatedGraph, foundation::math::Fnv1a64,
+    composition::model::BlendMode,
+    eval::evaluator::{EvaluatedGraph, ResolvedTransition},
+    foundation::math::Fnv1a64,
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -27,13 +29,24 @@ pub fn fingerprint_eval(eval: &EvaluatedGraph) -> FrameFingerprint {
             write_u64_pair(&mut a, &mut b, c.to_bits());
         }
         write_u64_pair(&mut a, &mut b, node.opacity.to_bits());
-        write_u8_pair(
-            &mut a,
-            &mut b,
-            match node.blend {
-                BlendMode::Normal => 0,
-            },
-        );
+        write_blend_pair(&mut a, &mut b, node.blend);
+        write_u8_pair(&mut a, &mut b, u8::from(node.opacity_at_composite));
+        write_u64_pair(&mut a, &mut b, node.isolation.len() as u64);
+        for g in &node.isolation {
+            write_u64_pair(&mut a, &mut b, g.id as u64);
+            write_rect_pair(&mut a, &mut b, g.clip);
+            match &g.composite {
+                Some(c) => {
+                    write_u8_pair(&mut a, &mut b, 1);
+                    write_u64_pair(&mut a, &mut b, c.opacity.to_bits());
+                    write_blend_pair(&mut a, &mut b, c.blend);
+                    write_transition_pair(&mut a, &mut b, c.transition_in.as_ref());
+                    write_transition_pair(&mut a, &mut b, c.transition_out.as_ref());
+                }
+                None => write_u8_pair(&mut a, &mut b, 0),
+            }
+        }
+        write_rect_pair(&mut a, &mut b, node.content_clip);
         match node.source_time_s {
             Some(t) => {
                 write_u8_pair(&mut a, &mut b, 1);
@@ -48,24 +61,8 @@ pub fn fingerprint_eval(eval: &EvaluatedGraph) -> FrameFingerprint {
             write_json_value_pair(&mut a, &mut b, &fx.params);
         }
 
-        match &node.transition_in {
-            Some(tr) => {
-                write_u8_pair(&mut a, &mut b, 1);
-                write_str_pair(&mut a, &mut b, &tr.kind);
-                write_u64_pair(&mut a, &mut b, tr.progress.to_bits());
-                write_json_value_pair(&mut a, &mut b, &tr.params);
-            }
-            None => write_u8_pair(&mut a, &mut b, 0),
-        }
-        match &node.transition_out {
-            Some(tr) => {
-                write_u8_pair(&mut a, &mut b, 1);
-                write_str_pair(&mut a, &mut b, &tr.kind);
-                write_u64_pair(&mut a, &mut b, tr.progress.to_bits());
-                write_json_value_pair(&mut a, &mut b, &tr.params);
-            }
-            None => write_u8_pair(&mut a, &mut b, 0),
-        }
+        write_transition_pair(&mut a, &mut b, node.transition_in.as_ref());
+        write_transition_pair(&mut a, &mut b, node.transition_out.as_ref());
     }
 
     FrameFingerprint {
@@ -74,6 +71,45 @@ pub fn fingerprint_eval(eval: &EvaluatedGraph) -> FrameFingerprint {
     }
 }
 
+fn write_blend_pair(a: &mut Fnv1a64, b: &mut Fnv1a64, blend: BlendMode) {
+    write_u8_pair(
+        a,
+        b,
+        match blend {
+            BlendMode::Normal => 0,
+            BlendMode::Multiply => 1,
+            BlendMode::Hue => 2,
+            BlendMode::Saturation => 3,
+            BlendMode::Color => 4,
+            BlendMode::Luminosity => 5,
+        },
+    );
+}
+
+fn write_rect_pair(a: &mut Fnv1a64, b: &mut Fnv1a64, rect: Option<kurbo::Rect>) {
+    match rect {
+        Some(r) => {
+            write_u8_pair(a, b, 1);
+            for c in [r.x0, r.y0, r.x1, r.y1] {
+                write_u64_pair(a, b, c.to_bits());
+            }
+        }
+        None => write_u8_pair(a, b, 0),
+    }
+}
+
+fn write_transition_pair(a: &mut Fnv1a64, b: &mut Fnv1a64, tr: Option<&ResolvedTransition>) {
+    match tr {
+        Some(tr) => {
+            write_u8_pair(a, b, 1);
+            write_str_pair(a, b, &tr.kind);
+            write_u64_pair(a, b, tr.progress.to_bits());
+            write_json_value_pair(a, b, &tr.params);
+        }
+        None => write_u8_pair(a, b, 0),
+    }
+}
+
 fn write_json_value_pair(a: &mut Fnv1a64, b: &mut Fnv1a64, v: &serde_json::Value) {
     match v {
         serde_json::Value::Null => write_u8_pair(a, b, 0),
@@ -2,19 +2,19 @@ use std::collections::HashMap;
 
 use crate::{
     assets::store::{AssetId, PreparedAsset, PreparedAssetStore},
-    composition::model::{BlendMode, Composition},
+    composition::model::{BlendMode, Composition, FillRule},
     effects::fx::{PassFx, normalize_effects, parse_effect},
     effects::transitions::{TransitionKind, WipeDir, parse_transition_kind_params},
     eval::evaluator::EvaluatedGraph,
-    foundation::core::{Affine, BezPath, Canvas, Rgba8Premul},
-    foundation::error::WavyteResult,
+    foundation::core::{Affine, BezPath, Canvas, PixelRect, Rect, Rgba8Premul},
+    foundation::error::{WavyteError, WavyteResult},
     foundation::math::Fnv1a64,
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct JsonFingerprint {
-    hi: u64,
-    lo: u64,
+pub(crate) struct JsonFingerprint {
+    pub(crate) hi: u64,
+    pub(crate) lo: u64,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -68,6 +68,275 @@ pub struct RenderPlan {
     pub passes: Vec<Pass>,
     /// Surface to read back as final frame.
     pub final_surface: SurfaceId,
+    /// Composition background (straight-alpha RGBA8) the final surface is cleared to.
+    ///
+    /// Backend-level clear settings take precedence when set.
+    pub clear_rgba: Option<[u8; 4]>,
+}
+
+impl RenderPlan {
+    /// Total bytes of all declared surfaces (RGBA8, 4 bytes per pixel).
+    pub fn surface_bytes(&self) -> u64 {
+        self.surfaces
+            .iter()
+            .map(|s| u64::from(s.width) * u64::from(s.height) * 4)
+            .sum()
+    }
+
+    /// Deterministic line-oriented dump of the plan, for debugging.
+    ///
+    /// One header line, then every surface and pass in plan order; ops are indented under their
+    /// pass and clipped draws nest one level deeper under their `clip` line:
+    ///
+    /// ```text
+    /// plan 64x64 final=s0 clear=none
+    /// surface s0 64x64 at 0,0
+    /// surface s1 64x64 at 0,0
+    /// pass 0 scene -> s1 clear
+    ///   draw fill_path z=0 blend=Normal opacity=1 transform=[1 0 0 1 0 0] elements=5 color=255,0,0,255
+    /// pass 1 offscreen s1 -> s2 blur radius_px=2 sigma=1 alpha_only=false
+    /// pass 2 composite -> s0
+    ///   over s2 opacity=1
+    /// ```
+    ///
+    /// Floats use Rust's shortest round-trip formatting and assets print as their hex
+    /// [`AssetId`], so equal plans always dump to identical bytes.
+    pub fn dump(&self) -> String {
+        use std::fmt::Write as _;
+
+        let mut out = String::new();
+        let clear = match self.clear_rgba {
+            Some([r, g, b, a]) => format!("{r},{g},{b},{a}"),
+            None => "none".to_string(),
+        };
+        let _ = writeln!(
+            out,
+            "plan {}x{} final=s{} clear={clear}",
+            self.canvas.width, self.canvas.height, self.final_surface.0
+        );
+        for (i, s) in self.surfaces.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "surface s{i} {}x{} at {},{}",
+                s.width, s.height, s.origin[0], s.origin[1]
+            );
+        }
+        for (i, pass) in self.passes.iter().enumerate() {
+            match pass {
+                Pass::Scene(p) => {
+                    let clear = if p.clear_to_transparent { " clear" } else { "" };
+                    let _ = writeln!(out, "pass {i} scene -> s{}{clear}", p.target.0);
+                    for op in &p.ops {
+                        dump_draw_op(&mut out, op, 1);
+                    }
+                }
+                Pass::Offscreen(p) => {
+                    let _ = writeln!(
+                        out,
+                        "pass {i} offscreen s{} -> s{} {}",
+                        p.input.0,
+                        p.output.0,
+                        dump_pass_fx(&p.fx)
+                    );
+                }
+                Pass::Composite(p) => {
+                    let _ = writeln!(out, "pass {i} composite -> s{}", p.target.0);
+                    for op in &p.ops {
+                        let _ = match *op {
+                            CompositeOp::Over { src, opacity } => {
+                                writeln!(out, "  over s{} opacity={opacity}", src.0)
+                            }
+                            CompositeOp::Blend { src, opacity, mode } => {
+                                writeln!(out, "  blend s{} opacity={opacity} mode={mode:?}", src.0)
+                            }
+                            CompositeOp::Crossfade { a, b, t } => {
+                                writeln!(out, "  crossfade s{} s{} t={t}", a.0, b.0)
+                            }
+                            CompositeOp::Wipe {
+                                a,
+                                b,
+                                t,
+                                dir,
+                                soft_edge,
+                                ..
+                            } => writeln!(
+                                out,
+                                "  wipe s{} s{} t={t} dir={dir:?} soft_edge={soft_edge}",
+                                a.0, b.0
+                            ),
+                        };
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+fn dump_draw_op(out: &mut String, op: &DrawOp, depth: usize) {
+    use std::fmt::Write as _;
+
+    let indent = "  ".repeat(depth);
+    let (kind, z, blend, opacity, detail) = match op {
+        DrawOp::Clipped { clip, op } => {
+            let _ = writeln!(
+                out,
+                "{indent}clip [{} {} {} {}]",
+                clip.x0, clip.y0, clip.x1, clip.y1
+            );
+            dump_draw_op(out, op, depth + 1);
+            return;
+        }
+        DrawOp::FillPath {
+            path,
+            color,
+            fill_rule,
+            stroke,
+            opacity,
+            blend,
+            z,
+            ..
+        } => {
+            let mut detail = format!(
+                "elements={} color={},{},{},{}",
+                path.elements().len(),
+                color.r,
+                color.g,
+                color.b,
+                color.a
+            );
+            if *fill_rule != FillRule::NonZero {
+                let _ = write!(detail, " fill_rule={fill_rule:?}");
+            }
+            if let Some(s) = stroke {
+                let c = s.color;
+                let _ = write!(
+                    detail,
+                    " stroke={},{},{},{}/{}",
+                    c.r, c.g, c.b, c.a, s.width
+                );
+            }
+            ("fill_path", z, blend, opacity, detail)
+        }
+        DrawOp::Image {
+            asset,
+            opacity,
+            blend,
+            z,
+            ..
+        } => (
+            "image",
+            z,
+            blend,
+            opacity,
+            format!("asset={:016x}", asset.0),
+        ),
+        DrawOp::Svg {
+            asset,
+            opacity,
+            blend,
+            z,
+            ..
+        } => ("svg", z, blend, opacity, format!("asset={:016x}", asset.0)),
+        DrawOp::Text {
+            asset,
+            opacity,
+            blend,
+            z,
+            ..
+        } => ("text", z, blend, opacity, format!("asset={:016x}", asset.0)),
+        DrawOp::Video {
+            asset,
+            source_time_s,
+            opacity,
+            blend,
+            z,
+            ..
+        } => (
+            "video",
+            z,
+            blend,
+            opacity,
+            format!("asset={:016x} t={source_time_s}", asset.0),
+        ),
+        DrawOp::RadialGradient {
+            asset,
+            opacity,
+            blend,
+            z,
+            ..
+        } => (
+            "radial_gradient",
+            z,
+            blend,
+            opacity,
+            format!("asset={:016x}", asset.0),
+        ),
+    };
+    let [a, b, c, d, e, f] = op.transform().as_coeffs();
+    let _ = writeln!(
+        out,
+        "{indent}draw {kind} z={z} blend={blend:?} opacity={opacity} transform=[{a} {b} {c} {d} {e} {f}] {detail}"
+    );
+}
+
+/// `kind key=value ...` form of an offscreen pass; vectors print as `[x y]`, colors as `r,g,b,a`
+/// and a linear-light pass prefixes the pass it wraps.
+fn dump_pass_fx(fx: &PassFx) -> String {
+    let list = |v: &[f32]| {
+        let items = v.iter().map(f32::to_string).collect::<Vec<_>>();
+        format!("[{}]", items.join(" "))
+    };
+    let rgba = |[r, g, b, a]: [u8; 4]| format!("{r},{g},{b},{a}");
+    match fx {
+        PassFx::Blur {
+            radius_px,
+            sigma,
+            alpha_only,
+        } => format!("blur radius_px={radius_px} sigma={sigma} alpha_only={alpha_only}"),
+        PassFx::Sharpen {
+            amount,
+            radius_px,
+            sigma,
+        } => format!("sharpen amount={amount} radius_px={radius_px} sigma={sigma}"),
+        PassFx::Pixelate { block_px } => format!("pixelate block_px={block_px}"),
+        PassFx::DropShadow {
+            offset: [dx, dy],
+            blur_radius_px,
+            sigma,
+            color,
+        } => format!(
+            "drop_shadow offset=[{dx} {dy}] blur_radius_px={blur_radius_px} sigma={sigma} color={}",
+            rgba(*color)
+        ),
+        PassFx::ColorMatrix { matrix } => format!("color_matrix matrix={}", list(matrix)),
+        PassFx::Threshold { cutoff } => format!("threshold cutoff={cutoff}"),
+        PassFx::RadialBlur {
+            center,
+            strength,
+            samples,
+        } => format!(
+            "radial_blur center={} strength={strength} samples={samples}",
+            list(center)
+        ),
+        PassFx::MotionBlur {
+            angle_deg,
+            distance_px,
+            samples,
+        } => {
+            format!("motion_blur angle_deg={angle_deg} distance_px={distance_px} samples={samples}")
+        }
+        PassFx::Vignette {
+            radius,
+            softness,
+            color,
+        } => format!(
+            "vignette radius={radius} softness={softness} color={}",
+            rgba(*color)
+        ),
+        PassFx::LinearLight(inner) => format!("linear_light {}", dump_pass_fx(inner)),
+        PassFx::ClipRect { rect } => format!("clip_rect rect={}", list(rect)),
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -115,6 +384,11 @@ pub struct SurfaceDesc {
     pub height: u32,
     /// Surface pixel format.
     pub format: PixelFormat,
+    /// Top-left corner of the surface in canvas pixels; `[0, 0]` for canvas-sized surfaces.
+    ///
+    /// Scene passes drawing into an offset surface already have the offset folded into their
+    /// transforms; composites place the surface at this position.
+    pub origin: [u32; 2],
 }
 
 #[derive(Clone, Debug)]
@@ -126,6 +400,9 @@ pub struct OffscreenPass {
     pub output: SurfaceId,
     /// Effect operation to run.
     pub fx: PassFx,
+    /// Full canvas the effect runs across; `x`/`y` place the input's top-left pixel on it, so
+    /// position-dependent effects (vignette, radial blur, pixelate tiles) line up across regions.
+    pub frame: PixelRect,
 }
 
 #[derive(Clone, Debug)]
@@ -133,7 +410,10 @@ pub struct OffscreenPass {
 pub struct CompositePass {
     /// Output surface.
     pub target: SurfaceId,
-    /// Ordered compositing operations.
+    /// Ordered compositing operations, applied back to front.
+    ///
+    /// Order is significant: blends and transitions do not commute, so plan rewrites must keep
+    /// this order unless they can prove the reordered ops touch disjoint regions.
     pub ops: Vec<CompositeOp>,
 }
 
@@ -147,6 +427,15 @@ pub enum CompositeOp {
         /// Extra opacity multiplier in `[0, 1]`.
         opacity: f32,
     },
+    /// Combine `src` onto target with a non-normal blend mode.
+    Blend {
+        /// Source surface.
+        src: SurfaceId,
+        /// Extra opacity multiplier in `[0, 1]`.
+        opacity: f32,
+        /// Blend mode applied against the target contents.
+        mode: BlendMode,
+    },
     /// Crossfade between two source surfaces.
     Crossfade {
         /// Outgoing surface.
@@ -168,20 +457,36 @@ pub enum CompositeOp {
         dir: WipeDir,
         /// Edge softness in `[0, 1]`.
         soft_edge: f32,
+        /// Full canvas the wipe sweeps across; `x`/`y` place the target's top-left pixel on it,
+        /// so a region render shows the same part of the wipe as the full frame.
+        sweep: PixelRect,
     },
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Stroke parameters of a [`DrawOp::FillPath`].
+pub struct StrokeStyle {
+    /// Stroke color in premultiplied RGBA8.
+    pub color: Rgba8Premul,
+    /// Stroke width in local path units.
+    pub width: f64,
+}
+
 #[derive(Clone, Debug)]
 /// Draw operation emitted by the compiler.
 pub enum DrawOp {
-    /// Fill vector path geometry.
+    /// Fill, then optionally stroke, vector path geometry.
     FillPath {
         /// Path geometry in local space.
         path: BezPath,
         /// Local-to-canvas transform.
         transform: Affine,
-        /// Fill color in premultiplied RGBA8.
+        /// Fill color in premultiplied RGBA8 (fully transparent skips the fill).
         color: Rgba8Premul,
+        /// Fill rule for self-overlapping geometry.
+        fill_rule: FillRule,
+        /// Outline drawn over the fill.
+        stroke: Option<StrokeStyle>,
         /// Opacity multiplier in `[0, 1]`.
         opacity: f32,
         /// Blend mode.
@@ -228,7 +533,7 @@ pub enum DrawOp {
         /// Draw order key.
         z: i32,
     },
-    /// Draw decoded frame from prepared video asset.
+    /// Draw decoded frame from prepared video or image-sequence asset.
     Video {
         /// Prepared asset identifier.
         asset: AssetId,
@@ -243,6 +548,41 @@ pub enum DrawOp {
         /// Draw order key.
         z: i32,
     },
+    /// Draw prepared radial gradient asset.
+    RadialGradient {
+        /// Prepared asset identifier.
+        asset: AssetId,
+        /// Local-to-canvas transform.
+        transform: Affine,
+        /// Opacity multiplier in `[0, 1]`.
+        opacity: f32,
+        /// Blend mode.
+        blend: BlendMode,
+        /// Draw order key.
+        z: i32,
+    },
+    /// Draw `op` clipped to a rectangle in its local space.
+    Clipped {
+        /// Visible region, in the wrapped op's local coordinates.
+        clip: Rect,
+        /// Wrapped draw op.
+        op: Box<DrawOp>,
+    },
+}
+
+impl DrawOp {
+    /// Local-to-canvas transform of this op (of the wrapped op for [`DrawOp::Clipped`]).
+    pub fn transform(&self) -> Affine {
+        match self {
+            DrawOp::FillPath { transform, .. }
+            | DrawOp::Image { transform, .. }
+            | DrawOp::Svg { transform, .. }
+            | DrawOp::Text { transform, .. }
+            | DrawOp::Video { transform, .. }
+            | DrawOp::RadialGradient { transform, .. } => *transform,
+            DrawOp::Clipped { op, .. } => op.transform(),
+        }
+    }
 }
 
 /// Compile one evaluated frame graph into backend-agnostic render plan.
@@ -252,27 +592,155 @@ pub fn compile_frame(
     assets: &PreparedAssetStore,
 ) -> WavyteResult<RenderPlan> {
     let mut cache = CompileCache::default();
-    compile_frame_with_cache(comp, eval, assets, &mut cache)
+    compile_frame_with_cache(comp, eval, assets, &mut cache, PlanView::scaled(1.0))
+}
+
+#[derive(Clone, Debug)]
+struct Layer {
+    surface: SurfaceId,
+    opacity: f32,
+    blend: BlendMode,
+    transition_in: Option<crate::eval::evaluator::ResolvedTransition>,
+    transition_out: Option<crate::eval::evaluator::ResolvedTransition>,
+    isolation: Vec<crate::eval::evaluator::IsolationGroup>,
+}
+
+/// Canvas size after applying render `scale`: dimensions are rounded to even values (at least 2),
+/// and `scale == 1.0` leaves the canvas untouched.
+pub(crate) fn scaled_canvas(canvas: Canvas, scale: f32) -> Canvas {
+    if scale == 1.0 {
+        return canvas;
+    }
+    let dim = |d: u32| ((f64::from(d) * f64::from(scale) / 2.0).round() as u32 * 2).max(2);
+    Canvas {
+        width: dim(canvas.width),
+        height: dim(canvas.height),
+    }
+}
+
+/// Output window of a compiled plan.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct PlanView {
+    /// Resolution multiplier (see [`scaled_canvas`]).
+    pub(crate) scale: f32,
+    /// Sub-rectangle of the scaled canvas to render; `None` renders all of it.
+    pub(crate) region: Option<PixelRect>,
+}
+
+impl PlanView {
+    /// Whole canvas at `scale`.
+    pub(crate) fn scaled(scale: f32) -> Self {
+        Self {
+            scale,
+            region: None,
+        }
+    }
+}
+
+/// Reject a render region that is empty or extends past the `scaled` canvas.
+pub(crate) fn check_region(r: PixelRect, scaled: Canvas) -> WavyteResult<()> {
+    let fits =
+        r.x.checked_add(r.width)
+            .is_some_and(|x1| x1 <= scaled.width)
+            && r.y
+                .checked_add(r.height)
+                .is_some_and(|y1| y1 <= scaled.height);
+    if r.width == 0 || r.height == 0 || !fits {
+        return Err(WavyteError::validation(format!(
+            "render region {}x{}+{}+{} is empty or outside the {}x{} canvas",
+            r.width, r.height, r.x, r.y, scaled.width, scaled.height
+        )));
+    }
+    Ok(())
 }
 
+/// Pixels a region render must add on every side so the pass effects of `eval` read the same
+/// neighbours they do in the full frame: the largest summed reach of any leaf's passes at
+/// `scale`.
+pub(crate) fn region_apron_px(
+    eval: &EvaluatedGraph,
+    cache: &mut CompileCache,
+    scale: f32,
+    scaled: Canvas,
+) -> WavyteResult<u32> {
+    let mut apron = 0u32;
+    for node in &eval.nodes {
+        let mut parsed = Vec::with_capacity(node.effects.len());
+        for e in &node.effects {
+            parsed.push(parse_effect_cached(cache, e)?);
+        }
+        let reach = normalize_effects(&parsed)
+            .passes
+            .iter()
+            .map(|pass| region_reach_px(&pass.scaled(scale), scaled))
+            .fold(0u32, u32::saturating_add);
+        apron = apron.max(reach);
+    }
+    Ok(apron)
+}
+
+/// Like [`pass_reach_px`], but for passes that already see full-canvas positions through
+/// [`OffscreenPass::frame`]: only the distance to the pixels each output pixel reads counts.
+fn region_reach_px(pass: &PassFx, scaled: Canvas) -> u32 {
+    match *pass {
+        PassFx::Pixelate { block_px } => block_px.saturating_sub(1),
+        PassFx::RadialBlur { strength, .. } => {
+            let diag = f64::from(scaled.width).hypot(f64::from(scaled.height));
+            (f64::from(strength) * diag).ceil() as u32 + 1
+        }
+        PassFx::Vignette { .. } | PassFx::ColorMatrix { .. } => 0,
+        PassFx::LinearLight(ref inner) => region_reach_px(inner, scaled),
+        _ => pass_reach_px(pass).unwrap_or(0),
+    }
+}
+
+/// Compile `eval` for the output window `view`.
+///
+/// Scaling pre-multiplies every leaf transform and scales pixel-valued pass parameters, so the
+/// plan renders the same picture at the reduced size. A region sizes the root surface to the
+/// region and shifts the world by its negated origin, so content outside it is clipped.
 pub(crate) fn compile_frame_with_cache(
     comp: &Composition,
     eval: &EvaluatedGraph,
     assets: &PreparedAssetStore,
     cache: &mut CompileCache,
+    view: PlanView,
 ) -> WavyteResult<RenderPlan> {
-    #[derive(Clone, Debug)]
-    struct Layer {
-        surface: SurfaceId,
-        transition_in: Option<crate::eval::evaluator::ResolvedTransition>,
-        transition_out: Option<crate::eval::evaluator::ResolvedTransition>,
-    }
+    let scale = view.scale;
+    let scaled = scaled_canvas(comp.canvas, scale);
+    let mut root = if scaled == comp.canvas {
+        Affine::IDENTITY
+    } else {
+        Affine::scale_non_uniform(
+            f64::from(scaled.width) / f64::from(comp.canvas.width),
+            f64::from(scaled.height) / f64::from(comp.canvas.height),
+        )
+    };
+    let mut sweep = PixelRect {
+        x: 0,
+        y: 0,
+        width: scaled.width,
+        height: scaled.height,
+    };
+    let canvas = match view.region {
+        None => scaled,
+        Some(r) => {
+            check_region(r, scaled)?;
+            root = Affine::translate((-f64::from(r.x), -f64::from(r.y))) * root;
+            (sweep.x, sweep.y) = (r.x, r.y);
+            Canvas {
+                width: r.width,
+                height: r.height,
+            }
+        }
+    };
 
     let mut surfaces = Vec::<SurfaceDesc>::new();
     surfaces.push(SurfaceDesc {
-        width: comp.canvas.width,
-        height: comp.canvas.height,
+        width: canvas.width,
+        height: canvas.height,
         format: PixelFormat::Rgba8Premul,
+        origin: [0, 0],
     });
 
     let mut scene_passes = Vec::<Pass>::with_capacity(eval.nodes.len());
@@ -283,24 +751,82 @@ pub(crate) fn compile_frame_with_cache(
         for e in &node.effects {
             parsed.push(parse_effect_cached(cache, e)?);
         }
-        let fx = normalize_effects(&parsed);
+        let mut fx = normalize_effects(&parsed);
+        if scale != 1.0 {
+            for pass in &mut fx.passes {
+                *pass = pass.scaled(scale);
+            }
+        }
 
         // Transitions are handled during composition. Keep DrawOp opacity for "intrinsic" opacity
-        // only (clip opacity + inline opacity effect).
-        let opacity = ((node.opacity as f32) * fx.inline.opacity_mul).clamp(0.0, 1.0);
+        // only (clip opacity + inline opacity effect), unless the clip defers its own opacity to
+        // composite time so pass effects see full-opacity content.
+        let (opacity, layer_opacity) = if node.opacity_at_composite {
+            (
+                fx.inline.opacity_mul.clamp(0.0, 1.0),
+                (node.opacity as f32).clamp(0.0, 1.0),
+            )
+        } else {
+            (
+                ((node.opacity as f32) * fx.inline.opacity_mul).clamp(0.0, 1.0),
+                1.0,
+            )
+        };
 
-        if opacity <= 0.0 {
+        if opacity <= 0.0 || layer_opacity <= 0.0 {
             continue;
         }
 
-        let transform = node.transform * fx.inline.transform_post;
-
         let asset_id = assets.id_for_key(&node.asset)?;
-        let op = match assets.get(asset_id)? {
+        let prepared = assets.get(asset_id)?;
+        let transform = root * node.transform * fx.inline.transform_post;
+
+        // Leaves with bounds-local pass effects get an isolation surface covering only their
+        // padded bounding box instead of the whole canvas. Transitions composite against
+        // canvas-sized partners, so transitioning clips keep full-canvas surfaces.
+        let isolation = if fx.passes.is_empty()
+            || node.transition_in.is_some()
+            || node.transition_out.is_some()
+        {
+            None
+        } else {
+            tight_isolation_rect(prepared, node.content_clip, transform, &fx.passes, canvas)
+        };
+        let (desc, transform) = match isolation {
+            Some(Some(desc)) => {
+                let offset =
+                    Affine::translate((-f64::from(desc.origin[0]), -f64::from(desc.origin[1])));
+                (desc, offset * transform)
+            }
+            // Nothing of the leaf (or its effects) lands on the canvas.
+            Some(None) => continue,
+            None => (
+                SurfaceDesc {
+                    width: canvas.width,
+                    height: canvas.height,
+                    format: PixelFormat::Rgba8Premul,
+                    origin: [0, 0],
+                },
+                transform,
+            ),
+        };
+
+        let op = match prepared {
             PreparedAsset::Path(a) => DrawOp::FillPath {
                 path: a.path.clone(),
                 transform,
-                color: Rgba8Premul::from_straight_rgba(255, 255, 255, 255),
+                color: a
+                    .fill
+                    .map_or(Rgba8Premul::from_straight_rgba(0, 0, 0, 0), |c| {
+                        Rgba8Premul::from_straight_rgba(c[0], c[1], c[2], c[3])
+                    }),
+                fill_rule: a.fill_rule,
+                stroke: a.stroke.as_ref().map(|s| StrokeStyle {
+                    color: Rgba8Premul::from_straight_rgba(
+                        s.color[0], s.color[1], s.color[2], s.color[3],
+                    ),
+                    width: s.width_px,
+                }),
                 opacity,
                 blend: node.blend,
                 z: node.z,
@@ -326,7 +852,7 @@ pub(crate) fn compile_frame_with_cache(
                 blend: node.blend,
                 z: node.z,
             },
-            PreparedAsset::Video(_) => DrawOp::Video {
+            PreparedAsset::Video(_) | PreparedAsset::ImageSequence(_) => DrawOp::Video {
                 asset: asset_id,
                 source_time_s: node.source_time_s.unwrap_or(0.0),
                 transform,
@@ -334,15 +860,44 @@ pub(crate) fn compile_frame_with_cache(
                 blend: node.blend,
                 z: node.z,
             },
-            PreparedAsset::Audio(_) => continue,
+            PreparedAsset::SolidRect(r) => DrawOp::FillPath {
+                path: if r.corner_radius > 0.0 {
+                    kurbo::Shape::to_path(
+                        &kurbo::RoundedRect::new(0.0, 0.0, r.width, r.height, r.corner_radius),
+                        0.1,
+                    )
+                } else {
+                    kurbo::Shape::to_path(&Rect::new(0.0, 0.0, r.width, r.height), 0.1)
+                },
+                transform,
+                color: Rgba8Premul::from_straight_rgba(
+                    r.color[0], r.color[1], r.color[2], r.color[3],
+                ),
+                fill_rule: FillRule::NonZero,
+                stroke: None,
+                opacity,
+                blend: node.blend,
+                z: node.z,
+            },
+            PreparedAsset::RadialGradient(_) => DrawOp::RadialGradient {
+                asset: asset_id,
+                transform,
+                opacity,
+                blend: node.blend,
+                z: node.z,
+            },
+            PreparedAsset::Audio(_) | PreparedAsset::Composition(_) => continue,
+        };
+        let op = match node.content_clip {
+            Some(clip) => DrawOp::Clipped {
+                clip,
+                op: Box::new(op),
+            },
+            None => op,
         };
 
         let surf_id = SurfaceId((surfaces.len()) as u32);
-        surfaces.push(SurfaceDesc {
-            width: comp.canvas.width,
-            height: comp.canvas.height,
-            format: PixelFormat::Rgba8Premul,
-        });
+        surfaces.push(desc.clone());
 
         scene_passes.push(Pass::Scene(ScenePass {
             target: surf_id,
@@ -353,15 +908,16 @@ pub(crate) fn compile_frame_with_cache(
         let mut post_fx = surf_id;
         for fx in &fx.passes {
             let out_id = SurfaceId((surfaces.len()) as u32);
-            surfaces.push(SurfaceDesc {
-                width: comp.canvas.width,
-                height: comp.canvas.height,
-                format: PixelFormat::Rgba8Premul,
-            });
+            surfaces.push(desc.clone());
             scene_passes.push(Pass::Offscreen(OffscreenPass {
                 input: post_fx,
                 output: out_id,
                 fx: fx.clone(),
+                frame: PixelRect {
+                    x: sweep.x + desc.origin[0],
+                    y: sweep.y + desc.origin[1],
+                    ..sweep
+                },
             }));
             post_fx = out_id;
         }
@@ -369,18 +925,174 @@ pub(crate) fn compile_frame_with_cache(
         let _ = idx;
         layers.push(Layer {
             surface: post_fx,
+            opacity: layer_opacity,
+            blend: node.blend,
             transition_in: node.transition_in.clone(),
             transition_out: node.transition_out.clone(),
+            isolation: node.isolation.clone(),
+        });
+    }
+
+    // Consecutive layers of an isolation group composite onto their own stack surface first; the
+    // stack then composites over everything below it as one layer.
+    let mut stack_passes = Vec::<Pass>::new();
+    let composite_ops = stack_composite_ops(
+        &layers,
+        0,
+        StackTarget {
+            root,
+            size: canvas,
+            sweep,
+            surfaces: &mut surfaces,
+            passes: &mut stack_passes,
+        },
+        cache,
+    );
+
+    Ok(RenderPlan {
+        canvas,
+        surfaces,
+        passes: {
+            let mut out = scene_passes;
+            out.extend(stack_passes);
+            out.push(Pass::Composite(CompositePass {
+                target: SurfaceId(0),
+                ops: composite_ops,
+            }));
+            out
+        },
+        final_surface: SurfaceId(0),
+        clear_rgba: comp.background,
+    })
+}
+
+/// Where [`stack_composite_ops`] allocates stack surfaces and records their passes.
+struct StackTarget<'a> {
+    root: Affine,
+    size: Canvas,
+    sweep: PixelRect,
+    surfaces: &'a mut Vec<SurfaceDesc>,
+    passes: &'a mut Vec<Pass>,
+}
+
+/// Composite ops for `layers`, stacking runs that share an isolation group at `depth`.
+///
+/// Each run is composited onto its own canvas-sized surface (recursing for groups nested
+/// deeper), optionally clipped, and then composited as one layer with the group's composite.
+/// Inner stacks are recorded before the passes that read them.
+fn stack_composite_ops(
+    layers: &[Layer],
+    depth: usize,
+    target: StackTarget<'_>,
+    cache: &mut CompileCache,
+) -> Vec<CompositeOp> {
+    let StackTarget {
+        root,
+        size,
+        sweep,
+        surfaces,
+        passes,
+    } = target;
+    let mut composite_ops = Vec::<CompositeOp>::with_capacity(layers.len());
+    let mut start = 0usize;
+    while start < layers.len() {
+        let group = layers[start].isolation.get(depth);
+        let id = group.map(|g| g.id);
+        let end = start
+            + layers[start..]
+                .iter()
+                .take_while(|l| l.isolation.get(depth).map(|g| g.id) == id)
+                .count();
+        let Some(group) = group else {
+            composite_ops.extend(layer_composite_ops(&layers[start..end], sweep, cache));
+            start = end;
+            continue;
+        };
+
+        let ops = stack_composite_ops(
+            &layers[start..end],
+            depth + 1,
+            StackTarget {
+                root,
+                size,
+                sweep,
+                surfaces: &mut *surfaces,
+                passes: &mut *passes,
+            },
+            cache,
+        );
+        let stack = SurfaceId(surfaces.len() as u32);
+        surfaces.push(SurfaceDesc {
+            width: size.width,
+            height: size.height,
+            format: PixelFormat::Rgba8Premul,
+            origin: [0, 0],
         });
+        passes.push(Pass::Scene(ScenePass {
+            target: stack,
+            ops: Vec::new(),
+            clear_to_transparent: true,
+        }));
+        passes.push(Pass::Composite(CompositePass { target: stack, ops }));
+        let mut out = stack;
+        if let Some(clip) = group.clip {
+            let r = root.transform_rect_bbox(clip);
+            out = SurfaceId(surfaces.len() as u32);
+            surfaces.push(surfaces[stack.0 as usize].clone());
+            passes.push(Pass::Offscreen(OffscreenPass {
+                input: stack,
+                output: out,
+                fx: PassFx::ClipRect {
+                    rect: [r.x0 as f32, r.y0 as f32, r.x1 as f32, r.y1 as f32],
+                },
+                frame: sweep,
+            }));
+        }
+        match &group.composite {
+            Some(g) => composite_ops.extend(layer_composite_ops(
+                &[Layer {
+                    surface: out,
+                    opacity: (g.opacity as f32).clamp(0.0, 1.0),
+                    blend: g.blend,
+                    transition_in: g.transition_in.clone(),
+                    transition_out: g.transition_out.clone(),
+                    isolation: Vec::new(),
+                }],
+                sweep,
+                cache,
+            )),
+            None => composite_ops.push(CompositeOp::Over {
+                src: out,
+                opacity: 1.0,
+            }),
+        }
+        start = end;
     }
+    composite_ops
+}
 
+/// Composite ops for `layers` in order, pairing adjacent matching transitions.
+///
+/// Wipes sweep across `sweep`, the full canvas in target-surface coordinates.
+fn layer_composite_ops(
+    layers: &[Layer],
+    sweep: PixelRect,
+    cache: &mut CompileCache,
+) -> Vec<CompositeOp> {
     let mut composite_ops = Vec::<CompositeOp>::with_capacity(layers.len());
     let mut i = 0usize;
     while i < layers.len() {
         let layer = &layers[i];
 
         let mut paired = false;
-        if i + 1 < layers.len() {
+        // Paired transitions have no opacity slot, so deferred opacity falls back to two `Over`s.
+        // Likewise, blended layers always composite on their own.
+        if i + 1 < layers.len()
+            && layer.opacity == 1.0
+            && layers[i + 1].opacity == 1.0
+            && layer.blend == BlendMode::Normal
+            && layers[i + 1].blend == BlendMode::Normal
+        {
             let next = &layers[i + 1];
 
             if let (Some(out_tr), Some(in_tr)) =
@@ -424,6 +1136,7 @@ pub(crate) fn compile_frame_with_cache(
                                         t: t_in,
                                         dir: dir_a,
                                         soft_edge: soft_a,
+                                        sweep,
                                     });
                                     paired = true;
                                 }
@@ -440,7 +1153,7 @@ pub(crate) fn compile_frame_with_cache(
             continue;
         }
 
-        let mut layer_opacity = 1.0f32;
+        let mut layer_opacity = layer.opacity;
         if let Some(tr) = &layer.transition_in {
             layer_opacity *= tr.progress as f32;
         }
@@ -450,28 +1163,117 @@ pub(crate) fn compile_frame_with_cache(
         layer_opacity = layer_opacity.clamp(0.0, 1.0);
 
         if layer_opacity > 0.0 {
-            composite_ops.push(CompositeOp::Over {
-                src: layer.surface,
-                opacity: layer_opacity,
+            composite_ops.push(match layer.blend {
+                BlendMode::Normal => CompositeOp::Over {
+                    src: layer.surface,
+                    opacity: layer_opacity,
+                },
+                mode => CompositeOp::Blend {
+                    src: layer.surface,
+                    opacity: layer_opacity,
+                    mode,
+                },
             });
         }
 
         i += 1;
     }
+    composite_ops
+}
 
-    Ok(RenderPlan {
-        canvas: comp.canvas,
-        surfaces,
-        passes: {
-            let mut out = scene_passes;
-            out.push(Pass::Composite(CompositePass {
-                target: SurfaceId(0),
-                ops: composite_ops,
-            }));
-            out
-        },
-        final_surface: SurfaceId(0),
-    })
+/// Padded, canvas-clipped isolation surface for a leaf whose pass effects only spread content by
+/// a bounded distance.
+///
+/// Returns `None` when tight bounds do not apply (unknown leaf bounds, effects that depend on the
+/// surface size, or no savings over the canvas) and `Some(None)` when nothing would be visible.
+fn tight_isolation_rect(
+    prepared: &PreparedAsset,
+    content_clip: Option<Rect>,
+    transform: Affine,
+    passes: &[PassFx],
+    canvas: Canvas,
+) -> Option<Option<SurfaceDesc>> {
+    let local = match prepared {
+        PreparedAsset::Path(a) => a.paint_bounds(),
+        PreparedAsset::Image(a) => Rect::new(0.0, 0.0, f64::from(a.width), f64::from(a.height)),
+        PreparedAsset::Svg(a) => {
+            let size = a.tree.size();
+            Rect::new(
+                0.0,
+                0.0,
+                f64::from(size.width()).ceil() + 1.0,
+                f64::from(size.height()).ceil() + 1.0,
+            )
+        }
+        PreparedAsset::Video(a) => {
+            Rect::new(0.0, 0.0, f64::from(a.info.width), f64::from(a.info.height))
+        }
+        PreparedAsset::ImageSequence(a) => {
+            Rect::new(0.0, 0.0, f64::from(a.width), f64::from(a.height))
+        }
+        PreparedAsset::SolidRect(a) => Rect::new(0.0, 0.0, a.width, a.height),
+        PreparedAsset::RadialGradient(a) => {
+            Rect::new(0.0, 0.0, f64::from(a.width), f64::from(a.height))
+        }
+        PreparedAsset::Text(_) | PreparedAsset::Audio(_) | PreparedAsset::Composition(_) => {
+            return None;
+        }
+    };
+    let local = content_clip.map_or(local, |c| local.intersect(c));
+
+    let mut reach = 0u32;
+    for pass in passes {
+        reach = reach.saturating_add(pass_reach_px(pass)?);
+    }
+
+    // Two extra pixels cover antialiasing on the bounding box edges.
+    let pad = f64::from(reach) + 2.0;
+    let bounds = transform.transform_rect_bbox(local).inflate(pad, pad);
+    if !bounds.is_finite() {
+        return None;
+    }
+    let (cw, ch) = (f64::from(canvas.width), f64::from(canvas.height));
+    let x0 = bounds.x0.floor().clamp(0.0, cw);
+    let y0 = bounds.y0.floor().clamp(0.0, ch);
+    let x1 = bounds.x1.ceil().clamp(0.0, cw);
+    let y1 = bounds.y1.ceil().clamp(0.0, ch);
+    if x1 <= x0 || y1 <= y0 {
+        return Some(None);
+    }
+    let (width, height) = ((x1 - x0) as u32, (y1 - y0) as u32);
+    if u64::from(width) * u64::from(height) >= u64::from(canvas.width) * u64::from(canvas.height) {
+        return None;
+    }
+    Some(Some(SurfaceDesc {
+        width,
+        height,
+        format: PixelFormat::Rgba8Premul,
+        origin: [x0 as u32, y0 as u32],
+    }))
+}
+
+/// How far a pass can move visible content, in pixels, or `None` if its output depends on the
+/// surface extent (so it must run on a canvas-sized surface).
+fn pass_reach_px(pass: &PassFx) -> Option<u32> {
+    match *pass {
+        PassFx::Blur { radius_px, .. } | PassFx::Sharpen { radius_px, .. } => Some(radius_px),
+        PassFx::DropShadow {
+            offset,
+            blur_radius_px,
+            ..
+        } => Some(
+            offset[0]
+                .unsigned_abs()
+                .max(offset[1].unsigned_abs())
+                .saturating_add(blur_radius_px),
+        ),
+        PassFx::MotionBlur { distance_px, .. } => Some((distance_px / 2.0).ceil() as u32 + 1),
+        // A positive alpha offset makes transparent pixels visible everywhere.
+        PassFx::ColorMatrix { matrix } => (matrix[19] <= 0.0).then_some(0),
+        PassFx::Threshold { .. } | PassFx::ClipRect { .. } => Some(0),
+        PassFx::Pixelate { .. } | PassFx::RadialBlur { .. } | PassFx::Vignette { .. } => None,
+        PassFx::LinearLight(ref inner) => pass_reach_px(inner),
+    }
 }
 
 fn parse_effect_cached(
@@ -543,7 +1345,7 @@ fn hash_str64(value: &str) -> u64 {
     h.finish()
 }
 
-fn fingerprint_json_value(v: &serde_json::Value) -> JsonFingerprint {
+pub(crate) fn fingerprint_json_value(v: &serde_json::Value) -> JsonFingerprint {
     let mut a = Fnv1a64::new(0xcbf29ce484222325);
     let mut b = Fnv1a64::new(0x9ae16a3b2f90404f);
     hash_json_value_pair(&mut a, &mut b, v);
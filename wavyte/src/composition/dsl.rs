@@ -2,11 +2,15 @@ use std::collections::BTreeMap;
 
 use crate::{
     animation::anim::Anim,
+    animation::ease::Ease,
+    animation::proc::Wiggle,
     composition::model::{
-        Asset, AudioAsset, BlendMode, Clip, ClipProps, Composition, EffectInstance, Track,
-        TransitionSpec, VideoAsset,
+        Asset, AudioAsset, AudioFadeCurve, BlendMode, Clip, ClipProps, Composition, ContentFit,
+        EffectInstance, FillRule, ImageAsset, PathAsset, SvgAsset, TextAlign, TextAsset,
+        TextVAlign, Track, TransitionSpec, VideoAsset, VideoLoopMode,
     },
-    foundation::core::{Canvas, FrameIndex, FrameRange, Transform2D},
+    effects::transitions::WipeDir,
+    foundation::core::{Affine, Canvas, FrameIndex, FrameRange, Transform2D, Vec2},
     foundation::error::{WavyteError, WavyteResult},
 };
 
@@ -16,6 +20,9 @@ pub struct CompositionBuilder {
     canvas: Canvas,
     duration: FrameIndex,
     seed: u64,
+    background: Option<[u8; 4]>,
+    title: Option<String>,
+    description: Option<String>,
     assets: BTreeMap<String, Asset>,
     tracks: Vec<Track>,
 }
@@ -28,6 +35,9 @@ impl CompositionBuilder {
             canvas,
             duration,
             seed: 0,
+            background: None,
+            title: None,
+            description: None,
             assets: BTreeMap::new(),
             tracks: Vec::new(),
         }
@@ -39,6 +49,24 @@ impl CompositionBuilder {
         self
     }
 
+    /// Set default background color (straight-alpha RGBA8).
+    pub fn background(mut self, rgba: [u8; 4]) -> Self {
+        self.background = Some(rgba);
+        self
+    }
+
+    /// Set composition title metadata.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set composition description metadata.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
     /// Insert an asset under unique key.
     pub fn asset(mut self, key: impl Into<String>, asset: Asset) -> WavyteResult<Self> {
         let key = key.into();
@@ -75,6 +103,63 @@ impl CompositionBuilder {
         self.asset(key, Asset::Audio(audio_asset(source)))
     }
 
+    /// Convenience helper to add a [`TextAsset`](crate::TextAsset).
+    pub fn text_asset(
+        self,
+        key: impl Into<String>,
+        text: impl Into<String>,
+        font_source: impl Into<String>,
+        size_px: f32,
+    ) -> WavyteResult<Self> {
+        self.asset(key, Asset::Text(text_asset(text, font_source, size_px)))
+    }
+
+    /// Convenience helper to add an [`ImageAsset`](crate::ImageAsset).
+    pub fn image_asset(
+        self,
+        key: impl Into<String>,
+        source: impl Into<String>,
+    ) -> WavyteResult<Self> {
+        self.asset(
+            key,
+            Asset::Image(ImageAsset {
+                source: source.into(),
+                sampler: None,
+            }),
+        )
+    }
+
+    /// Convenience helper to add an [`SvgAsset`](crate::SvgAsset).
+    pub fn svg_asset(
+        self,
+        key: impl Into<String>,
+        source: impl Into<String>,
+    ) -> WavyteResult<Self> {
+        self.asset(
+            key,
+            Asset::Svg(SvgAsset {
+                source: source.into(),
+            }),
+        )
+    }
+
+    /// Convenience helper to add a [`PathAsset`](crate::PathAsset) from SVG path data.
+    pub fn path_asset(
+        self,
+        key: impl Into<String>,
+        svg_path_d: impl Into<String>,
+    ) -> WavyteResult<Self> {
+        self.asset(
+            key,
+            Asset::Path(PathAsset {
+                svg_path_d: svg_path_d.into(),
+                fill: None,
+                stroke: None,
+                fill_rule: FillRule::NonZero,
+            }),
+        )
+    }
+
     /// Build and validate final [`Composition`](crate::Composition).
     pub fn build(self) -> WavyteResult<Composition> {
         let comp = Composition {
@@ -84,12 +169,184 @@ impl CompositionBuilder {
             assets: self.assets,
             tracks: self.tracks,
             seed: self.seed,
+            background: self.background,
+            title: self.title,
+            description: self.description,
         };
         comp.validate()?;
         Ok(comp)
     }
 }
 
+/// Create text asset configuration with default color and no wrapping.
+pub fn text_asset(
+    text: impl Into<String>,
+    font_source: impl Into<String>,
+    size_px: f32,
+) -> TextAsset {
+    TextAsset {
+        text: text.into(),
+        font_source: font_source.into(),
+        size_px,
+        max_width_px: None,
+        color_rgba8: [255, 255, 255, 255],
+        align: TextAlign::Left,
+        valign: TextVAlign::Top,
+        spans: vec![],
+        line_height: None,
+        letter_spacing_px: None,
+        fallback_fonts: vec![],
+    }
+}
+
+/// Create a `blur` effect instance. `sigma` defaults to `radius_px / 2` when `None`.
+pub fn blur_effect(radius_px: u32, sigma: Option<f32>) -> EffectInstance {
+    let mut params = serde_json::json!({ "radius_px": radius_px });
+    if let Some(sigma) = sigma {
+        params["sigma"] = serde_json::json!(sigma);
+    }
+    EffectInstance {
+        kind: "blur".to_string(),
+        params,
+    }
+}
+
+/// Create a `sharpen` (unsharp mask) effect instance with the default detail radius.
+pub fn sharpen_effect(amount: f32) -> EffectInstance {
+    EffectInstance {
+        kind: "sharpen".to_string(),
+        params: serde_json::json!({ "amount": amount }),
+    }
+}
+
+/// Create a `pixelate` (mosaic) effect instance.
+pub fn pixelate_effect(block_px: u32) -> EffectInstance {
+    EffectInstance {
+        kind: "pixelate".to_string(),
+        params: serde_json::json!({ "block_px": block_px }),
+    }
+}
+
+/// Create a `drop_shadow` effect instance. `color` is straight-alpha RGBA8.
+pub fn drop_shadow_effect(offset: [i32; 2], blur_radius_px: u32, color: [u8; 4]) -> EffectInstance {
+    EffectInstance {
+        kind: "drop_shadow".to_string(),
+        params: serde_json::json!({
+            "offset": offset,
+            "blur_radius_px": blur_radius_px,
+            "color": color,
+        }),
+    }
+}
+
+/// Create a `hue_rotate` effect instance.
+pub fn hue_rotate_effect(angle_deg: f32) -> EffectInstance {
+    EffectInstance {
+        kind: "hue_rotate".to_string(),
+        params: serde_json::json!({ "angle_deg": angle_deg }),
+    }
+}
+
+/// Create a `channel_mixer` effect instance from per-channel RGB gains and offsets.
+///
+/// Offsets are in normalized `0..1` units; `([1.0; 3], [0.0; 3])` leaves the image unchanged.
+pub fn channel_mixer_effect(gain: [f32; 3], offset: [f32; 3]) -> EffectInstance {
+    EffectInstance {
+        kind: "channel_mixer".to_string(),
+        params: serde_json::json!({
+            "r_gain": gain[0],
+            "g_gain": gain[1],
+            "b_gain": gain[2],
+            "r_offset": offset[0],
+            "g_offset": offset[1],
+            "b_offset": offset[2],
+        }),
+    }
+}
+
+/// Create a `threshold` effect instance. `cutoff` is a luma level in `0..=1`.
+pub fn threshold_effect(cutoff: f32) -> EffectInstance {
+    EffectInstance {
+        kind: "threshold".to_string(),
+        params: serde_json::json!({ "cutoff": cutoff }),
+    }
+}
+
+/// Create a `motion_blur` effect instance streaking `distance` pixels along `angle_deg`.
+pub fn motion_blur_effect(angle_deg: f32, distance: f32) -> EffectInstance {
+    EffectInstance {
+        kind: "motion_blur".to_string(),
+        params: serde_json::json!({ "angle": angle_deg, "distance": distance }),
+    }
+}
+
+/// Create a `radial_blur` (zoom blur) effect instance centered at normalized `center`.
+pub fn radial_blur_effect(center: [f32; 2], strength: f32) -> EffectInstance {
+    EffectInstance {
+        kind: "radial_blur".to_string(),
+        params: serde_json::json!({ "center": center, "strength": strength }),
+    }
+}
+
+/// Create a `vignette` effect instance. `color` is straight-alpha RGBA8.
+pub fn vignette_effect(radius: f32, softness: f32, color: [u8; 4]) -> EffectInstance {
+    EffectInstance {
+        kind: "vignette".to_string(),
+        params: serde_json::json!({
+            "radius": radius,
+            "softness": softness,
+            "color": color,
+        }),
+    }
+}
+
+/// Create an `opacity_mul` effect instance.
+pub fn opacity_mul_effect(value: f32) -> EffectInstance {
+    EffectInstance {
+        kind: "opacity_mul".to_string(),
+        params: serde_json::json!({ "value": value }),
+    }
+}
+
+/// Create a `transform_post` effect instance from an affine matrix.
+pub fn transform_post_effect(value: Affine) -> EffectInstance {
+    EffectInstance {
+        kind: "transform_post".to_string(),
+        params: serde_json::json!({ "affine": value.as_coeffs() }),
+    }
+}
+
+/// Create a crossfade transition specification.
+pub fn crossfade_transition(duration_frames: u64, ease: Ease) -> TransitionSpec {
+    TransitionSpec {
+        kind: "crossfade".to_string(),
+        duration_frames,
+        ease,
+        params: serde_json::Value::Null,
+    }
+}
+
+/// Create a wipe transition specification.
+pub fn wipe_transition(
+    duration_frames: u64,
+    ease: Ease,
+    dir: WipeDir,
+    soft_edge: f32,
+) -> TransitionSpec {
+    let dir = match dir {
+        WipeDir::LeftToRight => "left_to_right",
+        WipeDir::RightToLeft => "right_to_left",
+        WipeDir::TopToBottom => "top_to_bottom",
+        WipeDir::BottomToTop => "bottom_to_top",
+    };
+    TransitionSpec {
+        kind: "wipe".to_string(),
+        duration_frames,
+        ease,
+        params: serde_json::json!({ "dir": dir, "soft_edge": soft_edge }),
+    }
+}
+
 /// Create video asset configuration with default trims/playback controls.
 pub fn video_asset(source: impl Into<String>) -> VideoAsset {
     VideoAsset {
@@ -100,7 +357,11 @@ pub fn video_asset(source: impl Into<String>) -> VideoAsset {
         volume: 1.0,
         fade_in_sec: 0.0,
         fade_out_sec: 0.0,
+        fade_curve: AudioFadeCurve::Linear,
         muted: false,
+        frame_blend: false,
+        sampler: None,
+        loop_mode: VideoLoopMode::None,
     }
 }
 
@@ -114,7 +375,11 @@ pub fn audio_asset(source: impl Into<String>) -> AudioAsset {
         volume: 1.0,
         fade_in_sec: 0.0,
         fade_out_sec: 0.0,
+        fade_curve: AudioFadeCurve::Linear,
         muted: false,
+        pan: 0.0,
+        width: 1.0,
+        duck: None,
     }
 }
 
@@ -128,6 +393,8 @@ pub struct TrackBuilder {
     layout_align_x: crate::LayoutAlignX,
     layout_align_y: crate::LayoutAlignY,
     layout_grid_columns: u32,
+    isolate: bool,
+    clip_to: Option<crate::TrackClip>,
     clips: Vec<Clip>,
 }
 
@@ -143,6 +410,8 @@ impl TrackBuilder {
             layout_align_x: crate::LayoutAlignX::Start,
             layout_align_y: crate::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: Vec::new(),
         }
     }
@@ -190,6 +459,18 @@ impl TrackBuilder {
         self
     }
 
+    /// Composite the track as an isolated blend stack (see [`Track::isolate`](crate::Track)).
+    pub fn isolate(mut self, enabled: bool) -> Self {
+        self.isolate = enabled;
+        self
+    }
+
+    /// Clip the track stack to a region (see [`Track::clip_to`](crate::Track)).
+    pub fn clip_to(mut self, clip: crate::TrackClip) -> Self {
+        self.clip_to = Some(clip);
+        self
+    }
+
     /// Build validated [`Track`](crate::Track).
     pub fn build(self) -> WavyteResult<Track> {
         if self.name.trim().is_empty() {
@@ -204,6 +485,8 @@ impl TrackBuilder {
             layout_align_x: self.layout_align_x,
             layout_align_y: self.layout_align_y,
             layout_grid_columns: self.layout_grid_columns,
+            isolate: self.isolate,
+            clip_to: self.clip_to,
             clips: self.clips,
         })
     }
@@ -218,9 +501,18 @@ pub struct ClipBuilder {
     opacity: Anim<f64>,
     transform: Anim<Transform2D>,
     blend: BlendMode,
+    opacity_at_composite: bool,
+    wiggle: Option<Wiggle>,
+    box_anchor: Option<Vec2>,
+    aspect_ratio: Option<f64>,
+    fit: ContentFit,
     effects: Vec<EffectInstance>,
     transition_in: Option<TransitionSpec>,
     transition_out: Option<TransitionSpec>,
+    seed: Option<u64>,
+    loop_content: bool,
+    time_remap: Option<Anim<f64>>,
+    reverse: bool,
 }
 
 impl ClipBuilder {
@@ -234,9 +526,18 @@ impl ClipBuilder {
             opacity: Anim::constant(1.0),
             transform: Anim::constant(Transform2D::default()),
             blend: BlendMode::Normal,
+            opacity_at_composite: false,
+            wiggle: None,
+            box_anchor: None,
+            aspect_ratio: None,
+            fit: ContentFit::None,
             effects: Vec::new(),
             transition_in: None,
             transition_out: None,
+            seed: None,
+            loop_content: false,
+            time_remap: None,
+            reverse: false,
         }
     }
 
@@ -258,6 +559,42 @@ impl ClipBuilder {
         self
     }
 
+    /// Set blend mode.
+    pub fn blend(mut self, blend: BlendMode) -> Self {
+        self.blend = blend;
+        self
+    }
+
+    /// Apply opacity after pass effects, when compositing, instead of at draw time.
+    pub fn opacity_at_composite(mut self, enabled: bool) -> Self {
+        self.opacity_at_composite = enabled;
+        self
+    }
+
+    /// Jitter the transform each frame (see [`Wiggle`](crate::Wiggle)).
+    pub fn wiggle(mut self, wiggle: Wiggle) -> Self {
+        self.wiggle = Some(wiggle);
+        self
+    }
+
+    /// Anchor the transform at a fraction of the clip's layout box (`(0.5, 0.5)` = center).
+    pub fn box_anchor(mut self, x: f64, y: f64) -> Self {
+        self.box_anchor = Some(Vec2::new(x, y));
+        self
+    }
+
+    /// Give the clip's layout box a fixed `width / height` ratio (see [`ClipProps::aspect_ratio`]).
+    pub fn aspect_ratio(mut self, ratio: f64) -> Self {
+        self.aspect_ratio = Some(ratio);
+        self
+    }
+
+    /// Set how the asset fits its layout box (see [`ContentFit`]).
+    pub fn fit(mut self, fit: ContentFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
     /// Append effect instance.
     pub fn effect(mut self, fx: EffectInstance) -> Self {
         self.effects.push(fx);
@@ -276,6 +613,30 @@ impl ClipBuilder {
         self
     }
 
+    /// Set per-clip procedural seed (combined with the composition seed).
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Repeat content shorter than the clip range (see [`Clip::loop_content`](crate::Clip)).
+    pub fn loop_content(mut self, enabled: bool) -> Self {
+        self.loop_content = enabled;
+        self
+    }
+
+    /// Remap clip-local time (see [`Clip::time_remap`](crate::Clip)).
+    pub fn time_remap(mut self, remap: Anim<f64>) -> Self {
+        self.time_remap = Some(remap);
+        self
+    }
+
+    /// Play the clip backward (see [`Clip::reverse`](crate::Clip)).
+    pub fn reverse(mut self, enabled: bool) -> Self {
+        self.reverse = enabled;
+        self
+    }
+
     /// Build validated [`Clip`](crate::Clip).
     pub fn build(self) -> WavyteResult<Clip> {
         if self.id.trim().is_empty() {
@@ -285,7 +646,13 @@ impl ClipBuilder {
             return Err(WavyteError::validation("clip asset key must be non-empty"));
         }
         self.opacity.validate()?;
+        if let Some(remap) = &self.time_remap {
+            remap.validate()?;
+        }
         self.transform.validate()?;
+        if let Some(w) = &self.wiggle {
+            w.validate()?;
+        }
 
         Ok(Clip {
             id: self.id,
@@ -295,11 +662,20 @@ impl ClipBuilder {
                 transform: self.transform,
                 opacity: self.opacity,
                 blend: self.blend,
+                opacity_at_composite: self.opacity_at_composite,
+                wiggle: self.wiggle,
+                box_anchor: self.box_anchor,
+                aspect_ratio: self.aspect_ratio,
+                fit: self.fit,
             },
             z_offset: self.z_offset,
             effects: self.effects,
             transition_in: self.transition_in,
             transition_out: self.transition_out,
+            seed: self.seed,
+            loop_content: self.loop_content,
+            time_remap: self.time_remap,
+            reverse: self.reverse,
         })
     }
 }
@@ -1,2 +1,3 @@
 pub mod dsl;
 pub mod model;
+pub mod validate;
@@ -3,7 +3,8 @@ use std::collections::BTreeMap;
 use crate::{
     animation::anim::Anim,
     animation::ease::Ease,
-    foundation::core::{Canvas, Fps, FrameIndex, FrameRange, Transform2D},
+    animation::proc::Wiggle,
+    foundation::core::{Canvas, Fps, FrameIndex, FrameRange, Transform2D, Vec2},
     foundation::error::{WavyteError, WavyteResult},
 };
 
@@ -29,6 +30,22 @@ pub struct Composition {
     pub tracks: Vec<Track>,
     /// Global deterministic seed used by procedural animation sources.
     pub seed: u64, // global determinism seed
+    /// Default background as straight-alpha RGBA8 (`"#rrggbb"`, `"#rrggbbaa"` or `[r,g,b,a]`).
+    ///
+    /// Backends clear the final frame to this color unless
+    /// [`RenderSettings::clear_rgba`](crate::RenderSettings::clear_rgba) overrides it.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_color_rgba8"
+    )]
+    pub background: Option<[u8; 4]>,
+    /// Optional human-readable title, written to encoder metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Optional description, written to encoder metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -56,6 +73,17 @@ pub struct Track {
     /// Column count used for grid layout.
     #[serde(default = "default_layout_grid_columns")]
     pub layout_grid_columns: u32,
+    /// Composite this track's clips as an isolated stack.
+    ///
+    /// Each clip composites onto the accumulated stack (starting from transparent) with its own
+    /// blend mode, and the finished stack is then composited over lower tracks as one unit. For
+    /// example, a multiply clip multiplies only the clips below it in the same track. Clips from
+    /// other tracks that interleave by z split the stack.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub isolate: bool,
+    /// Clip the track's composited stack to a region; implies [`Track::isolate`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clip_to: Option<TrackClip>,
     /// Clips contained in this track.
     pub clips: Vec<Clip>,
 }
@@ -76,6 +104,48 @@ pub enum LayoutMode {
     Center,
 }
 
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+/// Region a track stack is clipped to (see [`Track::clip_to`]).
+pub enum TrackClip {
+    /// The track's layout box: the canvas inset by `layout_padding`.
+    LayoutBox,
+    /// Axis-aligned rectangle in canvas pixels.
+    Rect {
+        /// Left edge.
+        x: f64,
+        /// Top edge.
+        y: f64,
+        /// Width in pixels.
+        width: f64,
+        /// Height in pixels.
+        height: f64,
+    },
+}
+
+impl TrackClip {
+    /// Resolve the clip region in canvas pixels for `track` on `canvas`.
+    pub fn rect(&self, track: &Track, canvas: Canvas) -> kurbo::Rect {
+        match *self {
+            TrackClip::LayoutBox => {
+                let p = track.layout_padding;
+                kurbo::Rect::new(
+                    p.left,
+                    p.top,
+                    f64::from(canvas.width) - p.right,
+                    f64::from(canvas.height) - p.bottom,
+                )
+            }
+            TrackClip::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => kurbo::Rect::new(x, y, x + width, y + height),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, serde::Serialize, serde::Deserialize, PartialEq)]
 /// Padding edges in pixels.
 pub struct Edges {
@@ -117,6 +187,50 @@ pub enum LayoutAlignY {
     End,
 }
 
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum ColorRepr {
+    Hex(String),
+    Rgba([u8; 4]),
+}
+
+impl ColorRepr {
+    fn into_rgba8<E: serde::de::Error>(self) -> Result<[u8; 4], E> {
+        match self {
+            ColorRepr::Rgba(rgba) => Ok(rgba),
+            ColorRepr::Hex(s) => parse_hex_color_rgba8(&s)
+                .ok_or_else(|| E::custom(format!("invalid hex color '{s}'"))),
+        }
+    }
+}
+
+fn deserialize_color_rgba8<'de, D>(deserializer: D) -> Result<[u8; 4], D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    <ColorRepr as serde::Deserialize>::deserialize(deserializer)?.into_rgba8()
+}
+
+fn deserialize_opt_color_rgba8<'de, D>(deserializer: D) -> Result<Option<[u8; 4]>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    <Option<ColorRepr> as serde::Deserialize>::deserialize(deserializer)?
+        .map(ColorRepr::into_rgba8)
+        .transpose()
+}
+
+/// Parse `#rrggbb` / `#rrggbbaa` into straight-alpha RGBA8.
+pub(crate) fn parse_hex_color_rgba8(s: &str) -> Option<[u8; 4]> {
+    let hex = s.trim().strip_prefix('#')?;
+    if !hex.is_ascii() || (hex.len() != 6 && hex.len() != 8) {
+        return None;
+    }
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let a = if hex.len() == 8 { byte(6)? } else { 255 };
+    Some([byte(0)?, byte(2)?, byte(4)?, a])
+}
+
 fn default_layout_grid_columns() -> u32 {
     2
 }
@@ -140,6 +254,35 @@ pub struct Clip {
     pub transition_in: Option<TransitionSpec>,
     /// Optional transition-out specification.
     pub transition_out: Option<TransitionSpec>,
+    /// Optional per-clip seed for procedural animation.
+    ///
+    /// By default procedurals are seeded from the composition seed and the clip id. When set,
+    /// this seed is combined with the composition seed instead, so clips sharing a seed move in
+    /// lockstep and clips with different seeds move independently regardless of their ids.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Repeat the clip's content when `range` outlasts it, instead of holding the last state.
+    ///
+    /// The content duration is the longest of the keyframe cycles in `props` (a keyframe lane's
+    /// cycle ends on its last key) and, for video assets with `trim_end_sec`, the trimmed source
+    /// length, or for composition assets, the child duration. The clip-local frame wraps at that
+    /// duration for animation and video sampling; audio is not looped.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub loop_content: bool,
+    /// Remap the clip-local frame to a content frame, sampled at the clip-local frame.
+    ///
+    /// The (possibly fractional) content frame drives video and image-sequence timing and nested
+    /// compositions; it applies before `loop_content` wrapping and on top of `playback_rate`.
+    /// `props` sample the whole content frame below it, so a slowed remap holds each keyframed
+    /// value for several output frames. Negative values clamp to `0`. Audio cannot be remapped:
+    /// validation rejects a remap on clips of unmuted audio or video assets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_remap: Option<Anim<f64>>,
+    /// Play the clip backward: clip-local frame `f` of an `n`-frame range becomes `n - 1 - f`
+    /// before `time_remap`, `loop_content` and all content sampling. Audio cannot play backward:
+    /// validation rejects `reverse` on clips of unmuted audio or video assets.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub reverse: bool,
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -151,13 +294,79 @@ pub struct ClipProps {
     pub opacity: Anim<f64>, // 0..1 clamped in eval
     /// Blend mode.
     pub blend: BlendMode,
+    /// Apply `opacity` when compositing the clip's surface instead of when drawing it.
+    ///
+    /// With pass effects this fades the already-processed result, so e.g. a blur sees
+    /// full-opacity content. Defaults to `false` (opacity is baked into the draw).
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub opacity_at_composite: bool,
+    /// Seeded jitter added on top of the sampled `transform` each frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wiggle: Option<Wiggle>,
+    /// Anchor the transform at this fraction of the clip's layout box (its asset's intrinsic
+    /// size at the resolved layout position), e.g. `(0.5, 0.5)` to scale/rotate about the box
+    /// center. Overrides `transform.anchor` when layout has been resolved against prepared
+    /// assets (all render paths).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub box_anchor: Option<Vec2>,
+    /// Constrain the clip's layout box to this `width / height` ratio: the box keeps the asset's
+    /// intrinsic width and its height becomes `width / aspect_ratio` (e.g. `16.0 / 9.0` for a
+    /// media frame). Affects stacking, grid cells, alignment, and `box_anchor`, not how the asset
+    /// is drawn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aspect_ratio: Option<f64>,
+    /// How the asset is fitted into its layout box when the box differs from the asset's
+    /// intrinsic size (see [`ClipProps::aspect_ratio`]). Defaults to [`ContentFit::None`].
+    #[serde(default, skip_serializing_if = "ContentFit::is_none")]
+    pub fit: ContentFit,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// Fitting of a clip's asset into its layout box, like CSS `object-fit`.
+pub enum ContentFit {
+    /// Draw at intrinsic size from the box origin.
+    #[default]
+    None,
+    /// Stretch to the box on both axes.
+    Fill,
+    /// Scale uniformly to fit inside the box, centered; uncovered box area stays transparent.
+    Contain,
+    /// Scale uniformly to cover the box, centered; overflow outside the box is clipped.
+    Cover,
+}
+
+impl ContentFit {
+    fn is_none(&self) -> bool {
+        *self == ContentFit::None
+    }
+}
+
+fn is_false(v: &bool) -> bool {
+    !*v
 }
 
-#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+fn is_default<T: Default + PartialEq>(v: &T) -> bool {
+    *v == T::default()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 /// Blend mode used when compositing a clip.
 pub enum BlendMode {
     /// Standard “source over destination” (premultiplied alpha).
     Normal,
+    /// Multiply source and destination colors where both are present (darkens).
+    ///
+    /// Where either side is transparent this reduces to normal alpha-over.
+    Multiply,
+    /// Source hue with destination saturation and luminosity (non-separable).
+    Hue,
+    /// Source saturation with destination hue and luminosity (non-separable).
+    Saturation,
+    /// Source hue and saturation with destination luminosity (non-separable).
+    Color,
+    /// Source luminosity with destination hue and saturation (non-separable).
+    Luminosity,
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -173,25 +382,154 @@ pub enum Asset {
     Image(ImageAsset),
     /// Video file asset.
     Video(VideoAsset),
+    /// Numbered raster frames played back like a video.
+    ImageSequence(ImageSequenceAsset),
     /// Audio file asset.
     Audio(AudioAsset),
+    /// Procedural radial gradient asset.
+    RadialGradient(RadialGradientAsset),
+    /// Constant-color rectangle asset.
+    SolidRect(SolidRectAsset),
+    /// Constant-color rectangle asset with rounded corners.
+    RoundedRect(RoundedRectAsset),
+    /// Nested composition rendered as one isolated layer.
+    Composition(CompositionAsset),
+}
+
+impl Asset {
+    /// Whether this asset contributes to the audio mix: an unmuted audio or video asset.
+    pub fn is_audible(&self) -> bool {
+        match self {
+            Self::Audio(a) => !a.muted,
+            Self::Video(v) => !v.muted,
+            _ => false,
+        }
+    }
+
+    /// Relative file paths this asset loads from directly.
+    ///
+    /// Text reports its `font_source` followed by each fallback font. Image sequences report
+    /// their first frame, the only one that must exist; an invalid pattern reports none.
+    /// Nested compositions report nothing here; their assets are listed separately.
+    pub fn source_paths(&self) -> Vec<std::borrow::Cow<'_, str>> {
+        match self {
+            Self::Text(a) => std::iter::once(&a.font_source)
+                .chain(&a.fallback_fonts)
+                .map(|p| p.as_str().into())
+                .collect(),
+            Self::Svg(a) => vec![a.source.as_str().into()],
+            Self::Image(a) => vec![a.source.as_str().into()],
+            Self::Video(a) => vec![a.source.as_str().into()],
+            Self::Audio(a) => vec![a.source.as_str().into()],
+            Self::ImageSequence(a) => a
+                .frame_path(a.start_index)
+                .ok()
+                .map(Into::into)
+                .into_iter()
+                .collect(),
+            Self::Path(_)
+            | Self::RadialGradient(_)
+            | Self::SolidRect(_)
+            | Self::RoundedRect(_)
+            | Self::Composition(_) => Vec::new(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 /// Text asset configuration.
 pub struct TextAsset {
-    /// UTF-8 text content.
+    /// UTF-8 text content. Ignored when `spans` is non-empty.
+    #[serde(default)]
     pub text: String,
     /// Relative path to font file.
     pub font_source: String,
     /// Font size in pixels.
     pub size_px: f32,
+    /// Relative paths of fonts used, in order, for characters `font_source` has no glyph for.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_fonts: Vec<String>,
     /// Optional max line width in pixels (for wrapping).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_width_px: Option<f32>,
     /// Text color as straight-alpha RGBA8.
     #[serde(default = "default_text_color_rgba8")]
     pub color_rgba8: [u8; 4],
+    /// Horizontal alignment of lines, within `max_width_px` when set (else the widest line).
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub align: TextAlign,
+    /// Vertical anchoring of the text block relative to the clip origin.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub valign: TextVAlign,
+    /// Line height as a multiple of `size_px` (e.g. `1.5`); unset uses the font's metrics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_height: Option<f32>,
+    /// Extra horizontal space between characters in pixels (may be negative).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub letter_spacing_px: Option<f32>,
+    /// Styled runs laid out back to back in place of `text`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<TextSpan>,
+}
+
+impl TextAsset {
+    /// The laid-out string: the concatenated `spans` when present, else `text`.
+    pub fn content(&self) -> std::borrow::Cow<'_, str> {
+        if self.spans.is_empty() {
+            std::borrow::Cow::Borrowed(&self.text)
+        } else {
+            std::borrow::Cow::Owned(self.spans.iter().map(|s| s.text.as_str()).collect())
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+/// One styled run of a [`TextAsset`]; unset fields inherit the asset's style.
+pub struct TextSpan {
+    /// UTF-8 text of this run.
+    pub text: String,
+    /// Run color as straight-alpha RGBA8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_rgba8: Option<[u8; 4]>,
+    /// Run font size in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_px: Option<f32>,
+    /// CSS-style font weight in `[1, 1000]` (`400` regular, `700` bold).
+    ///
+    /// Only the faces in `font_source` are available; a weight it lacks resolves to the nearest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f32>,
+    /// Use the italic face.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub italic: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// Horizontal text alignment.
+pub enum TextAlign {
+    /// Flush left.
+    #[default]
+    Left,
+    /// Centered.
+    Center,
+    /// Flush right.
+    Right,
+    /// Stretched to the full width; the last line of each paragraph stays left-aligned.
+    Justify,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// Which edge of a text block sits at the clip origin.
+pub enum TextVAlign {
+    /// Top of the first line at the origin.
+    #[default]
+    Top,
+    /// Vertical center of the block at the origin.
+    Middle,
+    /// Bottom of the last line at the origin.
+    Bottom,
 }
 
 fn default_text_color_rgba8() -> [u8; 4] {
@@ -210,6 +548,97 @@ pub struct SvgAsset {
 pub struct PathAsset {
     /// SVG path `d` attribute string.
     pub svg_path_d: String,
+    /// Fill color as straight-alpha RGBA8. When unset the path is filled white, unless it has a
+    /// `stroke`, in which case it is not filled.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_color_rgba8"
+    )]
+    pub fill: Option<[u8; 4]>,
+    /// Outline drawn over the fill.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stroke: Option<PathStroke>,
+    /// Rule deciding which regions of self-overlapping paths are filled.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub fill_rule: FillRule,
+}
+
+impl PathAsset {
+    /// Effective fill color, applying the white default described on [`PathAsset::fill`].
+    pub fn resolved_fill(&self) -> Option<[u8; 4]> {
+        match (self.fill, &self.stroke) {
+            (Some(c), _) => Some(c),
+            (None, Some(_)) => None,
+            (None, None) => Some([255, 255, 255, 255]),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+/// Outline style for [`PathAsset`].
+pub struct PathStroke {
+    /// Stroke color as straight-alpha RGBA8.
+    #[serde(deserialize_with = "deserialize_color_rgba8")]
+    pub color: [u8; 4],
+    /// Stroke width in local path units (pixels at unit scale).
+    pub width_px: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// Path fill rule.
+pub enum FillRule {
+    /// Fill regions with a non-zero winding number.
+    #[default]
+    NonZero,
+    /// Fill regions crossed an odd number of times.
+    EvenOdd,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+/// Frame-numbered raster images played back as a clip.
+pub struct ImageSequenceAsset {
+    /// Relative path with one frame-number placeholder, `{}` or zero-padded `{:0N}`
+    /// (e.g. `"frames/img_{:04}.png"`).
+    pub pattern: String,
+    /// Playback rate of the sequence; clip-local time maps to frames at this rate.
+    pub fps: Fps,
+    /// Number substituted for the first frame.
+    #[serde(default)]
+    pub start_index: u64,
+    /// What the clip shows after the last frame (default: hold it).
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub loop_mode: VideoLoopMode,
+    /// Sampling used when frames are drawn scaled or at fractional offsets (default bilinear).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampler: Option<ImageSampling>,
+}
+
+impl ImageSequenceAsset {
+    /// Relative path of the frame numbered `index`, substituted into [`Self::pattern`].
+    pub fn frame_path(&self, index: u64) -> WavyteResult<String> {
+        let invalid = || {
+            WavyteError::validation(format!(
+                "image sequence pattern '{}' must contain one '{{}}' or '{{:0N}}' placeholder",
+                self.pattern
+            ))
+        };
+        let open = self.pattern.find('{').ok_or_else(invalid)?;
+        let close = open + self.pattern[open..].find('}').ok_or_else(invalid)?;
+        let (prefix, suffix) = (&self.pattern[..open], &self.pattern[close + 1..]);
+        if prefix.contains('}') || suffix.contains(['{', '}']) {
+            return Err(invalid());
+        }
+        let width = match &self.pattern[open + 1..close] {
+            "" => 0,
+            spec => spec
+                .strip_prefix(":0")
+                .and_then(|w| w.parse::<usize>().ok())
+                .ok_or_else(invalid)?,
+        };
+        Ok(format!("{prefix}{index:0width$}{suffix}"))
+    }
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -217,6 +646,157 @@ pub struct PathAsset {
 pub struct ImageAsset {
     /// Relative path to image file.
     pub source: String,
+    /// Sampling used when the image is drawn scaled or at fractional offsets (default bilinear).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampler: Option<ImageSampling>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// Pixel sampling filter for raster image and video assets.
+pub enum ImageSampling {
+    /// Nearest-neighbor sampling (hard pixel edges when scaled up).
+    Nearest,
+    /// Bilinear filtering (the renderer default).
+    #[default]
+    Bilinear,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// How a video continues past the end of its trimmed source window.
+pub enum VideoLoopMode {
+    /// Hold the last frame.
+    #[default]
+    None,
+    /// Restart from the trimmed start.
+    Loop,
+    /// Play backwards to the trimmed start, then forwards again.
+    PingPong,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+/// Procedural radial gradient asset.
+///
+/// Renders at `width` x `height` when set; each omitted dimension falls back to the canvas size.
+pub struct RadialGradientAsset {
+    /// Gradient center in normalized `0..1` surface coordinates.
+    #[serde(default = "default_gradient_center")]
+    pub center: [f64; 2],
+    /// Gradient radius in normalized surface units; colors clamp to the last stop beyond it.
+    #[serde(default = "default_gradient_radius")]
+    pub radius: f64,
+    /// Center color shorthand for a two-stop gradient (`"#rrggbb"`, `"#rrggbbaa"` or `[r,g,b,a]`).
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_color_rgba8"
+    )]
+    pub start: Option<[u8; 4]>,
+    /// Outer color shorthand for a two-stop gradient.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_color_rgba8"
+    )]
+    pub end: Option<[u8; 4]>,
+    /// Explicit color stops, ordered by offset. Mutually exclusive with `start`/`end`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stops: Vec<GradientStop>,
+    /// Optional intrinsic width in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+    /// Optional intrinsic height in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+}
+
+impl RadialGradientAsset {
+    /// Color stops with the `start`/`end` shorthand expanded to stops at `0.0` and `1.0`.
+    pub fn resolved_stops(&self) -> Vec<GradientStop> {
+        if !self.stops.is_empty() {
+            return self.stops.clone();
+        }
+        let start = self.start.unwrap_or([0, 0, 0, 0]);
+        vec![
+            GradientStop {
+                offset: 0.0,
+                color: start,
+            },
+            GradientStop {
+                offset: 1.0,
+                color: self.end.unwrap_or(start),
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+/// Single gradient color stop.
+pub struct GradientStop {
+    /// Position along the gradient in `0..1`.
+    pub offset: f64,
+    /// Straight-alpha RGBA8 color at `offset`.
+    #[serde(deserialize_with = "deserialize_color_rgba8")]
+    pub color: [u8; 4],
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+/// Constant-color rectangle asset drawn from the local origin.
+///
+/// Renders at `width` x `height` when set; each omitted dimension falls back to the canvas size.
+pub struct SolidRectAsset {
+    /// Fill color as straight-alpha RGBA8 (`"#rrggbb"`, `"#rrggbbaa"` or `[r,g,b,a]`).
+    #[serde(deserialize_with = "deserialize_color_rgba8")]
+    pub color: [u8; 4],
+    /// Optional intrinsic width in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+    /// Optional intrinsic height in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+}
+
+/// Maximum [`Composition::nesting_depth`] accepted by validation.
+pub const MAX_COMPOSITION_NESTING: usize = 8;
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+/// Nested composition drawn like a clip: its tracks render into an isolated stack that is
+/// composited with the referencing clip's transform, opacity, blend and transitions.
+///
+/// Frame 0 of the child plays at the clip's start; clip frames are mapped through the child's
+/// own `fps`, and nothing is drawn past its `duration` unless the clip sets `loop_content`.
+/// Child asset keys are addressed as `"<key>/<child key>"` in the prepared asset store. Child
+/// audio is not mixed, so validation rejects unmuted audio and video assets in the child.
+pub struct CompositionAsset {
+    /// The child composition.
+    pub composition: Box<Composition>,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+/// Constant-color rectangle with rounded corners, drawn from the local origin.
+///
+/// Sized like [`SolidRectAsset`]. `radius_px` is clamped to half the smaller side.
+pub struct RoundedRectAsset {
+    /// Fill color as straight-alpha RGBA8 (`"#rrggbb"`, `"#rrggbbaa"` or `[r,g,b,a]`).
+    #[serde(deserialize_with = "deserialize_color_rgba8")]
+    pub color: [u8; 4],
+    /// Corner radius in pixels.
+    pub radius_px: f64,
+    /// Optional intrinsic width in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+    /// Optional intrinsic height in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+}
+
+fn default_gradient_center() -> [f64; 2] {
+    [0.5, 0.5]
+}
+
+fn default_gradient_radius() -> f64 {
+    0.5
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -242,9 +822,24 @@ pub struct VideoAsset {
     /// Audio fade-out duration in seconds.
     #[serde(default)]
     pub fade_out_sec: f64,
+    /// Shape of the audio fade-in/fade-out ramps.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub fade_curve: AudioFadeCurve,
     /// Disable video-audio contribution when `true`.
     #[serde(default)]
     pub muted: bool,
+    /// Crossfade the two source frames bracketing each sample time instead of snapping to one.
+    ///
+    /// Smooths frame-rate conversion when composition and source fps differ.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub frame_blend: bool,
+    /// Sampling used when frames are drawn scaled or at fractional offsets (default bilinear).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampler: Option<ImageSampling>,
+    /// What video frames show once source time passes the trimmed end (default: hold the last
+    /// frame). Audio is not looped.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub loop_mode: VideoLoopMode,
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -270,9 +865,22 @@ pub struct AudioAsset {
     /// Fade-out duration in seconds.
     #[serde(default)]
     pub fade_out_sec: f64,
+    /// Shape of the fade-in/fade-out ramps.
+    #[serde(default)]
+    pub fade_curve: AudioFadeCurve,
     /// Disable contribution when `true`.
     #[serde(default)]
     pub muted: bool,
+    /// Stereo position in `[-1, 1]` (`-1` hard left, `1` hard right), constant-power.
+    #[serde(default)]
+    pub pan: f64,
+    /// Stereo width in `[0, 2]`: `0` folds to mono, `1` keeps the source image, `2` doubles the
+    /// side signal.
+    #[serde(default = "default_width")]
+    pub width: f64,
+    /// Sidechain ducking: attenuate this asset while another asset is audible.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duck: Option<AudioDuck>,
 }
 
 fn default_playback_rate() -> f64 {
@@ -283,6 +891,57 @@ fn default_volume() -> f64 {
     1.0
 }
 
+fn default_width() -> f64 {
+    1.0
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// Gain curve used for audio fades.
+pub enum AudioFadeCurve {
+    /// Gain ramps linearly with time.
+    #[default]
+    Linear,
+    /// Quarter-wave `sin`/`cos` ramp; overlapping in/out fades keep constant power.
+    EqualPower,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+/// Sidechain ducking settings for an [`AudioAsset`] (e.g. music under a voiceover).
+pub struct AudioDuck {
+    /// Asset key (audio, or video with audio) whose level drives the ducking.
+    pub trigger_asset: String,
+    /// Fraction of amplitude removed while the trigger is loud, in `[0, 1]`.
+    pub amount: f64,
+    /// Time for the ducking to engage once the trigger becomes loud, in milliseconds.
+    ///
+    /// Defaults to 10 ms; `0` switches instantly, which can click.
+    #[serde(default = "default_duck_attack_ms")]
+    pub attack_ms: f64,
+    /// Time to recover after the trigger falls silent, in milliseconds.
+    ///
+    /// Defaults to 200 ms.
+    #[serde(default = "default_duck_release_ms")]
+    pub release_ms: f64,
+    /// Trigger peak amplitude at which ducking reaches its full `amount`, in `(0, 1]`.
+    ///
+    /// Quieter trigger peaks duck proportionally less. Defaults to `0.05` (about -26 dBFS).
+    #[serde(default = "default_duck_threshold")]
+    pub threshold: f64,
+}
+
+fn default_duck_threshold() -> f64 {
+    0.05
+}
+
+fn default_duck_attack_ms() -> f64 {
+    10.0
+}
+
+fn default_duck_release_ms() -> f64 {
+    200.0
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 /// Raw effect instance attached to a clip.
 pub struct EffectInstance {
@@ -309,100 +968,238 @@ pub struct TransitionSpec {
 
 impl Composition {
     /// Validate composition invariants and asset/clip references.
+    ///
+    /// Stops at the first problem; see [`Composition::validation_issues`] to collect them all.
     pub fn validate(&self) -> WavyteResult<()> {
-        if self.fps.num == 0 || self.fps.den == 0 {
-            return Err(WavyteError::validation("fps must have num>0 and den>0"));
-        }
-        if self.canvas.width == 0 || self.canvas.height == 0 {
-            return Err(WavyteError::validation("canvas width/height must be > 0"));
-        }
-        if self.duration.0 == 0 {
-            return Err(WavyteError::validation("duration must be > 0 frames"));
+        for (_, ok, msg) in self.header_checks() {
+            if !ok {
+                return Err(WavyteError::validation(msg));
+            }
         }
-
+        let first = |errors: Vec<WavyteError>| errors.into_iter().next().map_or(Ok(()), Err);
         for track in &self.tracks {
-            if !track.layout_gap_px.is_finite() || track.layout_gap_px < 0.0 {
-                return Err(WavyteError::validation(
-                    "track layout_gap_px must be finite and >= 0",
-                ));
+            first(self.track_errors(track))?;
+            for clip in &track.clips {
+                first(self.clip_errors(clip))?;
             }
-            for (name, value) in [
-                ("left", track.layout_padding.left),
-                ("right", track.layout_padding.right),
-                ("top", track.layout_padding.top),
-                ("bottom", track.layout_padding.bottom),
-            ] {
-                if !value.is_finite() || value < 0.0 {
-                    return Err(WavyteError::validation(format!(
-                        "track layout_padding.{name} must be finite and >= 0",
-                    )));
-                }
+        }
+        for (key, asset) in &self.assets {
+            first(self.asset_errors(key, asset))?;
+            if let Asset::Composition(a) = asset {
+                a.composition.validate()?;
             }
-            if track.layout_mode == LayoutMode::Grid && track.layout_grid_columns == 0 {
-                return Err(WavyteError::validation(
-                    "track layout_grid_columns must be > 0 for Grid layout",
-                ));
+        }
+        self.validate_nesting()
+    }
+
+    /// `(JSON pointer, passes, message)` for the top-level fields.
+    pub(crate) fn header_checks(&self) -> [(&'static str, bool, &'static str); 3] {
+        [
+            (
+                "/fps",
+                self.fps.num != 0 && self.fps.den != 0,
+                "fps must have num>0 and den>0",
+            ),
+            (
+                "/canvas",
+                self.canvas.width != 0 && self.canvas.height != 0,
+                "canvas width/height must be > 0",
+            ),
+            (
+                "/duration",
+                self.duration.0 != 0,
+                "duration must be > 0 frames",
+            ),
+        ]
+    }
+
+    /// Every problem with track-level settings (not its clips), in check order.
+    pub(crate) fn track_errors(&self, track: &Track) -> Vec<WavyteError> {
+        let mut errors = Vec::new();
+        if !track.layout_gap_px.is_finite() || track.layout_gap_px < 0.0 {
+            errors.push(WavyteError::validation(
+                "track layout_gap_px must be finite and >= 0",
+            ));
+        }
+        for (name, value) in [
+            ("left", track.layout_padding.left),
+            ("right", track.layout_padding.right),
+            ("top", track.layout_padding.top),
+            ("bottom", track.layout_padding.bottom),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                errors.push(WavyteError::validation(format!(
+                    "track layout_padding.{name} must be finite and >= 0",
+                )));
             }
+        }
+        if let Some(TrackClip::Rect {
+            x,
+            y,
+            width,
+            height,
+        }) = track.clip_to
+            && !([x, y, width, height].iter().all(|v| v.is_finite()) && width > 0.0 && height > 0.0)
+        {
+            errors.push(WavyteError::validation(
+                "track clip_to rect must be finite with positive size",
+            ));
+        }
+        if track.layout_mode == LayoutMode::Grid && track.layout_grid_columns == 0 {
+            errors.push(WavyteError::validation(
+                "track layout_grid_columns must be > 0 for Grid layout",
+            ));
+        }
+        errors
+    }
 
-            for clip in &track.clips {
-                if !self.assets.contains_key(&clip.asset) {
-                    return Err(WavyteError::validation(format!(
-                        "clip '{}' references missing asset key '{}'",
-                        clip.id, clip.asset
-                    )));
-                }
-                if clip.range.start.0 > clip.range.end.0 {
-                    return Err(WavyteError::validation(format!(
-                        "clip '{}' has invalid range (start > end)",
-                        clip.id
-                    )));
-                }
-                if clip.range.end.0 > self.duration.0 {
-                    return Err(WavyteError::validation(format!(
-                        "clip '{}' range exceeds composition duration",
-                        clip.id
-                    )));
-                }
+    /// Every problem with one clip against this composition's assets and duration.
+    pub(crate) fn clip_errors(&self, clip: &Clip) -> Vec<WavyteError> {
+        let mut errors = Vec::new();
+        if !self.assets.contains_key(&clip.asset) {
+            errors.push(WavyteError::validation(format!(
+                "clip '{}' references missing asset key '{}'",
+                clip.id, clip.asset
+            )));
+        }
+        if clip.range.start.0 > clip.range.end.0 {
+            errors.push(WavyteError::validation(format!(
+                "clip '{}' has invalid range (start > end)",
+                clip.id
+            )));
+        }
+        if clip.range.end.0 > self.duration.0 {
+            errors.push(WavyteError::validation(format!(
+                "clip '{}' range exceeds composition duration",
+                clip.id
+            )));
+        }
+
+        errors.extend(clip.props.opacity.validate().err());
+        errors.extend(clip.props.transform.validate().err());
+        if let Some(w) = &clip.props.wiggle {
+            errors.extend(w.validate().err());
+        }
+        if let Some(a) = clip.props.box_anchor
+            && !(a.x.is_finite() && a.y.is_finite())
+        {
+            errors.push(WavyteError::validation(format!(
+                "clip '{}' box_anchor must be finite",
+                clip.id
+            )));
+        }
+        if let Some(r) = clip.props.aspect_ratio
+            && !(r.is_finite() && r > 0.0)
+        {
+            errors.push(WavyteError::validation(format!(
+                "clip '{}' aspect_ratio must be finite and > 0",
+                clip.id
+            )));
+        }
 
-                clip.props.opacity.validate()?;
-                clip.props.transform.validate()?;
+        if matches!(self.assets.get(&clip.asset), Some(Asset::Composition(_)))
+            && !clip.effects.is_empty()
+        {
+            errors.push(WavyteError::validation(format!(
+                "clip '{}' references a composition asset and cannot have effects",
+                clip.id
+            )));
+        }
 
-                if let Some(tr) = &clip.transition_in {
-                    tr.validate()?;
-                }
-                if let Some(tr) = &clip.transition_out {
-                    tr.validate()?;
-                }
+        if let Some(remap) = &clip.time_remap {
+            errors.extend(remap.validate().err());
+            if self.assets.get(&clip.asset).is_some_and(Asset::is_audible) {
+                errors.push(WavyteError::validation(format!(
+                    "clip '{}' cannot time_remap audio: mute asset '{}' or drop the remap",
+                    clip.id, clip.asset
+                )));
             }
         }
 
-        for (key, asset) in &self.assets {
-            if key.trim().is_empty() {
-                return Err(WavyteError::validation("asset key must be non-empty"));
-            }
-            match asset {
-                Asset::Text(a) => {
-                    if a.text.trim().is_empty() {
-                        return Err(WavyteError::validation("text asset text must be non-empty"));
-                    }
-                    validate_rel_source(&a.font_source, "text asset font_source")?;
-                    if !a.size_px.is_finite() || a.size_px <= 0.0 {
-                        return Err(WavyteError::validation(
-                            "text asset size_px must be finite and > 0",
+        if clip.reverse && self.assets.get(&clip.asset).is_some_and(Asset::is_audible) {
+            errors.push(WavyteError::validation(format!(
+                "clip '{}' cannot reverse audio: mute asset '{}' or drop reverse",
+                clip.id, clip.asset
+            )));
+        }
+
+        if let Some(tr) = &clip.transition_in {
+            errors.extend(tr.validate().err());
+        }
+        if let Some(tr) = &clip.transition_out {
+            errors.extend(tr.validate().err());
+        }
+        errors
+    }
+
+    /// Every problem with one asset; nested compositions are not descended into.
+    pub(crate) fn asset_errors(&self, key: &str, asset: &Asset) -> Vec<WavyteError> {
+        let mut errors = Vec::new();
+        if key.trim().is_empty() {
+            errors.push(WavyteError::validation("asset key must be non-empty"));
+        }
+        match asset {
+            Asset::Text(a) => {
+                if a.content().trim().is_empty() {
+                    errors.push(WavyteError::validation("text asset text must be non-empty"));
+                }
+                if a.line_height.is_some_and(|v| !v.is_finite() || v <= 0.0) {
+                    errors.push(WavyteError::validation(
+                        "text asset line_height must be finite and > 0",
+                    ));
+                }
+                if a.letter_spacing_px.is_some_and(|v| !v.is_finite()) {
+                    errors.push(WavyteError::validation(
+                        "text asset letter_spacing_px must be finite",
+                    ));
+                }
+                for span in &a.spans {
+                    if span.size_px.is_some_and(|v| !v.is_finite() || v <= 0.0) {
+                        errors.push(WavyteError::validation(
+                            "text span size_px must be finite and > 0",
                         ));
                     }
-                    if let Some(w) = a.max_width_px
-                        && (!w.is_finite() || w <= 0.0)
+                    if span
+                        .weight
+                        .is_some_and(|v| !v.is_finite() || !(1.0..=1000.0).contains(&v))
                     {
-                        return Err(WavyteError::validation(
-                            "text asset max_width_px must be finite and > 0 when set",
+                        errors.push(WavyteError::validation(
+                            "text span weight must be in [1, 1000]",
                         ));
                     }
                 }
-                Asset::Svg(a) => validate_rel_source(&a.source, "svg asset source")?,
-                Asset::Image(a) => validate_rel_source(&a.source, "image asset source")?,
-                Asset::Video(a) => {
-                    validate_rel_source(&a.source, "video asset source")?;
+                errors.extend(validate_rel_source(&a.font_source, "text asset font_source").err());
+                for p in &a.fallback_fonts {
+                    errors.extend(validate_rel_source(p, "text asset fallback_fonts").err());
+                }
+                if !a.size_px.is_finite() || a.size_px <= 0.0 {
+                    errors.push(WavyteError::validation(
+                        "text asset size_px must be finite and > 0",
+                    ));
+                }
+                if let Some(w) = a.max_width_px
+                    && (!w.is_finite() || w <= 0.0)
+                {
+                    errors.push(WavyteError::validation(
+                        "text asset max_width_px must be finite and > 0 when set",
+                    ));
+                }
+            }
+            Asset::Svg(a) => {
+                errors.extend(validate_rel_source(&a.source, "svg asset source").err())
+            }
+            Asset::Image(a) => {
+                errors.extend(validate_rel_source(&a.source, "image asset source").err())
+            }
+            Asset::ImageSequence(a) => match a.frame_path(a.start_index) {
+                Ok(first) => {
+                    errors.extend(validate_rel_source(&first, "image sequence asset pattern").err())
+                }
+                Err(e) => errors.push(e),
+            },
+            Asset::Video(a) => {
+                errors.extend(validate_rel_source(&a.source, "video asset source").err());
+                errors.extend(
                     validate_media_controls(
                         a.trim_start_sec,
                         a.trim_end_sec,
@@ -411,10 +1208,13 @@ impl Composition {
                         a.fade_in_sec,
                         a.fade_out_sec,
                         "video asset",
-                    )?;
-                }
-                Asset::Audio(a) => {
-                    validate_rel_source(&a.source, "audio asset source")?;
+                    )
+                    .err(),
+                );
+            }
+            Asset::Audio(a) => {
+                errors.extend(validate_rel_source(&a.source, "audio asset source").err());
+                errors.extend(
                     validate_media_controls(
                         a.trim_start_sec,
                         a.trim_end_sec,
@@ -423,20 +1223,158 @@ impl Composition {
                         a.fade_in_sec,
                         a.fade_out_sec,
                         "audio asset",
-                    )?;
+                    )
+                    .err(),
+                );
+                if !(a.pan.is_finite() && (-1.0..=1.0).contains(&a.pan)) {
+                    errors.push(WavyteError::validation(
+                        "audio asset pan must be finite and in [-1, 1]",
+                    ));
                 }
-                Asset::Path(a) => {
-                    if a.svg_path_d.trim().is_empty() {
-                        return Err(WavyteError::validation(
-                            "path asset svg_path_d must be non-empty",
-                        ));
-                    }
+                if !(a.width.is_finite() && (0.0..=2.0).contains(&a.width)) {
+                    errors.push(WavyteError::validation(
+                        "audio asset width must be finite and in [0, 2]",
+                    ));
+                }
+                if let Some(duck) = &a.duck {
+                    errors.extend(validate_audio_duck(key, duck, &self.assets).err());
                 }
             }
+            Asset::Path(a) => {
+                if a.svg_path_d.trim().is_empty() {
+                    errors.push(WavyteError::validation(
+                        "path asset svg_path_d must be non-empty",
+                    ));
+                }
+                if let Some(stroke) = &a.stroke
+                    && (!stroke.width_px.is_finite() || stroke.width_px <= 0.0)
+                {
+                    errors.push(WavyteError::validation(
+                        "path asset stroke width_px must be finite and > 0",
+                    ));
+                }
+            }
+            Asset::RadialGradient(a) => {
+                if !a.center[0].is_finite() || !a.center[1].is_finite() {
+                    errors.push(WavyteError::validation(
+                        "radial gradient center must be finite",
+                    ));
+                }
+                if !a.radius.is_finite() || a.radius <= 0.0 {
+                    errors.push(WavyteError::validation(
+                        "radial gradient radius must be finite and > 0",
+                    ));
+                }
+                errors.extend(validate_gradient_stops(a).err());
+                errors.extend(validate_intrinsic_size(a.width, a.height, "radial gradient").err());
+            }
+            Asset::SolidRect(a) => {
+                errors.extend(validate_intrinsic_size(a.width, a.height, "solid rect").err());
+            }
+            Asset::RoundedRect(a) => {
+                errors.extend(validate_intrinsic_size(a.width, a.height, "rounded rect").err());
+                if !a.radius_px.is_finite() || a.radius_px < 0.0 {
+                    errors.push(WavyteError::validation(
+                        "rounded rect radius_px must be finite and >= 0",
+                    ));
+                }
+            }
+            Asset::Composition(a) => {
+                let prefix = format!("{key}/");
+                if self.assets.keys().any(|k| k.starts_with(&prefix)) {
+                    errors.push(WavyteError::validation(format!(
+                        "asset keys must not start with '{prefix}': it addresses assets of \
+                             nested composition '{key}'"
+                    )));
+                }
+                let audible = a
+                    .composition
+                    .assets
+                    .iter()
+                    .find(|(_, child)| child.is_audible());
+                if let Some((child_key, _)) = audible {
+                    errors.push(WavyteError::validation(format!(
+                        "nested composition '{key}' cannot play audio: asset '{child_key}' must \
+                         be muted or moved to the parent composition"
+                    )));
+                }
+            }
+        }
+        errors
+    }
+
+    pub(crate) fn validate_nesting(&self) -> WavyteResult<()> {
+        if self.nesting_depth() > MAX_COMPOSITION_NESTING {
+            return Err(WavyteError::validation(format!(
+                "compositions nest more than {MAX_COMPOSITION_NESTING} levels deep"
+            )));
         }
+        Ok(())
+    }
 
+    /// Replace assets by key, e.g. to render one template with per-render text or colors.
+    ///
+    /// A key of the form `"comp/key"` reaches into the nested composition asset `comp`, the same
+    /// addressing the prepared asset store uses. Every key must name an existing asset, no key
+    /// may reach inside another overridden key (`"comp"` together with `"comp/key"`), and the
+    /// result must [`validate`](Self::validate); the composition is left unchanged otherwise.
+    pub fn override_assets(&mut self, overrides: BTreeMap<String, Asset>) -> WavyteResult<()> {
+        for key in overrides.keys() {
+            let prefix = format!("{key}/");
+            if let Some(nested) = overrides.keys().find(|k| k.starts_with(&prefix)) {
+                return Err(WavyteError::validation(format!(
+                    "asset overrides '{key}' and '{nested}' overlap: override one or the other"
+                )));
+            }
+        }
+        let mut next = self.clone();
+        for (key, asset) in overrides {
+            let Some(slot) = next.asset_slot(&key) else {
+                return Err(WavyteError::validation(format!(
+                    "cannot override missing asset '{key}'"
+                )));
+            };
+            *slot = asset;
+        }
+        next.validate()?;
+        *self = next;
         Ok(())
     }
+
+    fn asset_slot(&mut self, key: &str) -> Option<&mut Asset> {
+        if self.assets.contains_key(key) {
+            return self.assets.get_mut(key);
+        }
+        let (outer, inner) = key.split_once('/')?;
+        match self.assets.get_mut(outer)? {
+            Asset::Composition(a) => a.composition.asset_slot(inner),
+            _ => None,
+        }
+    }
+
+    /// Number of nested composition levels below this one (`0` when none are referenced).
+    pub fn nesting_depth(&self) -> usize {
+        self.assets
+            .values()
+            .filter_map(|a| match a {
+                Asset::Composition(c) => Some(c.composition.nesting_depth() + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Stable 128-bit hash of the composition definition.
+    ///
+    /// Computed over the typed model rather than source text, so JSON whitespace, key order and
+    /// spelled-out defaults do not affect it. Custom eases contribute the shape of their curve,
+    /// not just their name. Suitable as a cache key for build systems; not cryptographic.
+    pub fn content_hash(&self) -> u128 {
+        let value = crate::animation::ease::with_curve_identity(|| serde_json::to_value(self))
+            .expect("composition serializes to a JSON value tree");
+        let fp = crate::compile::plan::fingerprint_json_value(&value);
+        (u128::from(fp.hi) << 64) | u128::from(fp.lo)
+    }
 }
 
 fn validate_rel_source(source: &str, field: &str) -> WavyteResult<()> {
@@ -461,6 +1399,99 @@ fn validate_rel_source(source: &str, field: &str) -> WavyteResult<()> {
     Ok(())
 }
 
+fn validate_gradient_stops(a: &RadialGradientAsset) -> WavyteResult<()> {
+    if a.stops.is_empty() {
+        if a.start.is_none() || a.end.is_none() {
+            return Err(WavyteError::validation(
+                "radial gradient requires either stops or both start and end",
+            ));
+        }
+        return Ok(());
+    }
+    if a.start.is_some() || a.end.is_some() {
+        return Err(WavyteError::validation(
+            "radial gradient stops cannot be combined with start/end",
+        ));
+    }
+    if a.stops.len() < 2 {
+        return Err(WavyteError::validation(
+            "radial gradient requires at least 2 stops",
+        ));
+    }
+    let mut prev = 0.0;
+    for stop in &a.stops {
+        if !stop.offset.is_finite() || !(0.0..=1.0).contains(&stop.offset) {
+            return Err(WavyteError::validation(
+                "radial gradient stop offset must be in [0, 1]",
+            ));
+        }
+        if stop.offset < prev {
+            return Err(WavyteError::validation(
+                "radial gradient stops must be sorted by offset",
+            ));
+        }
+        prev = stop.offset;
+    }
+    Ok(())
+}
+
+fn validate_intrinsic_size(
+    width: Option<f64>,
+    height: Option<f64>,
+    kind: &str,
+) -> WavyteResult<()> {
+    for (name, value) in [("width", width), ("height", height)] {
+        if let Some(v) = value
+            && (!v.is_finite() || v <= 0.0)
+        {
+            return Err(WavyteError::validation(format!(
+                "{kind} {name} must be finite and > 0 when set"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_audio_duck(
+    key: &str,
+    duck: &AudioDuck,
+    assets: &BTreeMap<String, Asset>,
+) -> WavyteResult<()> {
+    if duck.trigger_asset == key {
+        return Err(WavyteError::validation(format!(
+            "audio asset '{key}' cannot duck under itself"
+        )));
+    }
+    match assets.get(&duck.trigger_asset) {
+        Some(Asset::Audio(_) | Asset::Video(_)) => {}
+        _ => {
+            return Err(WavyteError::validation(format!(
+                "audio asset '{key}' duck trigger '{}' must be an audio or video asset",
+                duck.trigger_asset
+            )));
+        }
+    }
+    if !(duck.amount.is_finite() && (0.0..=1.0).contains(&duck.amount)) {
+        return Err(WavyteError::validation(
+            "audio duck amount must be finite and in [0, 1]",
+        ));
+    }
+    if ![duck.attack_ms, duck.release_ms]
+        .iter()
+        .all(|v| v.is_finite() && *v >= 0.0)
+    {
+        return Err(WavyteError::validation(
+            "audio duck attack_ms/release_ms must be finite and >= 0",
+        ));
+    }
+    if !(duck.threshold.is_finite() && duck.threshold > 0.0 && duck.threshold <= 1.0) {
+        return Err(WavyteError::validation(
+            "audio duck threshold must be finite and in (0, 1]",
+        ));
+    }
+    Ok(())
+}
+
 fn validate_media_controls(
     trim_start_sec: f64,
     trim_end_sec: Option<f64>,
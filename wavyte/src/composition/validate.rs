@@ -0,0 +1,404 @@
+use crate::{
+    composition::model::{Asset, Clip, Composition, Track},
+    foundation::core::{Canvas, Fps, FrameIndex},
+    foundation::error::{WavyteError, WavyteResult},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+/// Category of a [`ValidationIssue`].
+pub enum IssueCode {
+    /// The value does not have the shape of the addressed model type.
+    Parse,
+    /// The value parsed but breaks a composition invariant.
+    Invalid,
+    /// An animation at the path is malformed.
+    Animation,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+/// One problem found while validating a composition.
+pub struct ValidationIssue {
+    /// JSON Pointer (RFC 6901) to the offending value; `""` is the document root.
+    pub path: String,
+    /// Problem category.
+    pub code: IssueCode,
+    /// Human-readable description, without the error-kind prefix.
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn from_error(path: String, err: WavyteError) -> Self {
+        let (code, message) = match err {
+            WavyteError::Animation(m) => (IssueCode::Animation, m),
+            WavyteError::Validation(m) => (IssueCode::Invalid, m),
+            other => (IssueCode::Invalid, other.to_string()),
+        };
+        Self {
+            path,
+            code,
+            message,
+        }
+    }
+
+    fn parse(path: String, err: serde_json::Error) -> Self {
+        Self {
+            path,
+            code: IssueCode::Parse,
+            message: err.to_string(),
+        }
+    }
+}
+
+/// Escape one JSON Pointer reference token.
+fn pointer_token(s: &str) -> String {
+    s.replace('~', "~0").replace('/', "~1")
+}
+
+impl Composition {
+    /// Validate a JSON document without stopping at the first problem.
+    ///
+    /// Shape errors are localized to the smallest asset, track, clip or header field that
+    /// fails to deserialize; once the document parses, every invariant checked by
+    /// [`Composition::validate`] is reported.
+    pub fn validate_value(v: &serde_json::Value) -> Result<(), Vec<ValidationIssue>> {
+        let issues = match serde_json::from_value::<Composition>(v.clone()) {
+            Ok(comp) => comp.validation_issues(),
+            Err(err) => {
+                let mut issues = parse_issues(v);
+                if issues.is_empty() {
+                    issues.push(ValidationIssue::parse(String::new(), err));
+                }
+                issues
+            }
+        };
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Every invariant violation in this composition, in document order.
+    ///
+    /// Empty exactly when [`Composition::validate`] succeeds.
+    pub fn validation_issues(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        self.collect_issues("", &mut issues);
+        issues
+    }
+
+    fn collect_issues(&self, root: &str, out: &mut Vec<ValidationIssue>) {
+        for (path, ok, msg) in self.header_checks() {
+            if !ok {
+                out.push(ValidationIssue {
+                    path: format!("{root}{path}"),
+                    code: IssueCode::Invalid,
+                    message: msg.to_owned(),
+                });
+            }
+        }
+        for (ti, track) in self.tracks.iter().enumerate() {
+            let track_path = format!("{root}/tracks/{ti}");
+            for e in self.track_errors(track) {
+                out.push(ValidationIssue::from_error(track_path.clone(), e));
+            }
+            for (ci, clip) in track.clips.iter().enumerate() {
+                let clip_path = format!("{track_path}/clips/{ci}");
+                for e in self.clip_errors(clip) {
+                    out.push(ValidationIssue::from_error(clip_path.clone(), e));
+                }
+            }
+        }
+        for (key, asset) in &self.assets {
+            let asset_path = format!("{root}/assets/{}", pointer_token(key));
+            for e in self.asset_errors(key, asset) {
+                out.push(ValidationIssue::from_error(asset_path.clone(), e));
+            }
+            if let Asset::Composition(a) = asset {
+                a.composition
+                    .collect_issues(&format!("{asset_path}/Composition/composition"), out);
+            }
+        }
+        if root.is_empty()
+            && let Err(e) = self.validate_nesting()
+        {
+            out.push(ValidationIssue::from_error("/assets".to_owned(), e));
+        }
+    }
+
+    /// Parse a composition, rejecting JSON fields the schema does not know.
+    ///
+    /// Regular serde parsing ignores unknown fields, so a typo such as `"colour"` silently
+    /// falls back to the default. This returns a validation error listing the JSON Pointer of
+    /// every ignored field instead. The composition is not [`validate`](Composition::validate)d.
+    pub fn from_reader_strict<R: std::io::Read>(reader: R) -> WavyteResult<Self> {
+        let v: serde_json::Value = serde_json::from_reader(reader)?;
+        let unknown = std::cell::RefCell::new(Vec::new());
+        let comp = <Composition as serde::Deserialize>::deserialize(Tracked {
+            value: &v,
+            path: String::new(),
+            unknown: &unknown,
+        })?;
+        let unknown = unknown.into_inner();
+        if !unknown.is_empty() {
+            return Err(WavyteError::validation(format!(
+                "unknown fields: {}",
+                unknown.join(", ")
+            )));
+        }
+        Ok(comp)
+    }
+}
+
+/// Deserializer over a JSON value that records the JSON Pointer of every value the target type
+/// skips with `IgnoredAny`, which is how derived structs consume unknown fields.
+///
+/// The walk is driven by the schema itself, so each field is visited once. Values buffered by
+/// untagged enums (colors, string-or-object interpolation modes) are not descended into.
+struct Tracked<'a, 'u> {
+    value: &'a serde_json::Value,
+    path: String,
+    unknown: &'u std::cell::RefCell<Vec<String>>,
+}
+
+impl<'a, 'u> Tracked<'a, 'u> {
+    fn child(&self, value: &'a serde_json::Value, token: &str) -> Self {
+        Self {
+            value,
+            path: format!("{}/{token}", self.path),
+            unknown: self.unknown,
+        }
+    }
+}
+
+impl<'de, 'u> serde::Deserializer<'de> for Tracked<'de, 'u> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.value {
+            serde_json::Value::Object(map) => visitor.visit_map(TrackedMap {
+                iter: map.iter(),
+                pending: None,
+                parent: self,
+            }),
+            serde_json::Value::Array(items) => visitor.visit_seq(TrackedSeq {
+                iter: items.iter().enumerate(),
+                parent: self,
+            }),
+            scalar => serde::Deserializer::deserialize_any(scalar, visitor),
+        }
+    }
+
+    fn deserialize_option<V: serde::de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.value {
+            serde_json::Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: serde::de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: serde::de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.value {
+            serde_json::Value::Object(map) if map.len() == 1 => {
+                let (variant, value) = map.iter().next().expect("map has one entry");
+                let body = self.child(value, &pointer_token(variant));
+                visitor.visit_enum(TrackedEnum { variant, body })
+            }
+            other => serde::Deserializer::deserialize_enum(other, name, variants, visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V: serde::de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.unknown.borrow_mut().push(self.path);
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct TrackedMap<'a, 'u> {
+    iter: serde_json::map::Iter<'a>,
+    pending: Option<(&'a String, &'a serde_json::Value)>,
+    parent: Tracked<'a, 'u>,
+}
+
+impl<'de, 'u> serde::de::MapAccess<'de> for TrackedMap<'de, 'u> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: serde::de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.iter.next() else {
+            return Ok(None);
+        };
+        self.pending = Some((key, value));
+        seed.deserialize(serde::de::value::BorrowedStrDeserializer::new(key))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: serde::de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .pending
+            .take()
+            .ok_or_else(|| serde::de::Error::custom("map value requested before its key"))?;
+        seed.deserialize(self.parent.child(value, &pointer_token(key)))
+    }
+}
+
+struct TrackedSeq<'a, 'u> {
+    iter: std::iter::Enumerate<std::slice::Iter<'a, serde_json::Value>>,
+    parent: Tracked<'a, 'u>,
+}
+
+impl<'de, 'u> serde::de::SeqAccess<'de> for TrackedSeq<'de, 'u> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: serde::de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.iter.next() {
+            Some((i, value)) => seed
+                .deserialize(self.parent.child(value, &i.to_string()))
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct TrackedEnum<'a, 'u> {
+    variant: &'a str,
+    body: Tracked<'a, 'u>,
+}
+
+impl<'de, 'u> serde::de::EnumAccess<'de> for TrackedEnum<'de, 'u> {
+    type Error = serde_json::Error;
+    type Variant = Tracked<'de, 'u>;
+
+    fn variant_seed<V: serde::de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant =
+            seed.deserialize(serde::de::value::BorrowedStrDeserializer::new(self.variant))?;
+        Ok((variant, self.body))
+    }
+}
+
+impl<'de, 'u> serde::de::VariantAccess<'de> for Tracked<'de, 'u> {
+    type Error = serde_json::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        serde::Deserialize::deserialize(self.value)
+    }
+
+    fn newtype_variant_seed<T: serde::de::DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: serde::de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        serde::Deserializer::deserialize_any(self, visitor)
+    }
+
+    fn struct_variant<V: serde::de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        serde::Deserializer::deserialize_any(self, visitor)
+    }
+}
+
+/// Localize deserialization failures to the parts of `v` that do not parse.
+fn parse_issues(v: &serde_json::Value) -> Vec<ValidationIssue> {
+    fn check<T: serde::de::DeserializeOwned>(
+        path: String,
+        v: Option<&serde_json::Value>,
+        out: &mut Vec<ValidationIssue>,
+    ) {
+        let v = v.cloned().unwrap_or(serde_json::Value::Null);
+        if let Err(e) = serde_json::from_value::<T>(v) {
+            out.push(ValidationIssue::parse(path, e));
+        }
+    }
+
+    let mut out = Vec::new();
+    let Some(obj) = v.as_object() else {
+        return out;
+    };
+    check::<Fps>("/fps".to_owned(), obj.get("fps"), &mut out);
+    check::<Canvas>("/canvas".to_owned(), obj.get("canvas"), &mut out);
+    check::<FrameIndex>("/duration".to_owned(), obj.get("duration"), &mut out);
+    match obj.get("assets").and_then(|a| a.as_object()) {
+        Some(assets) => {
+            for (key, asset) in assets {
+                check::<Asset>(
+                    format!("/assets/{}", pointer_token(key)),
+                    Some(asset),
+                    &mut out,
+                );
+            }
+        }
+        None => check::<std::collections::BTreeMap<String, Asset>>(
+            "/assets".to_owned(),
+            obj.get("assets"),
+            &mut out,
+        ),
+    }
+    match obj.get("tracks").and_then(|t| t.as_array()) {
+        Some(tracks) => {
+            for (ti, track) in tracks.iter().enumerate() {
+                let clips = track.get("clips").and_then(|c| c.as_array());
+                let before = out.len();
+                for (ci, clip) in clips.into_iter().flatten().enumerate() {
+                    check::<Clip>(format!("/tracks/{ti}/clips/{ci}"), Some(clip), &mut out);
+                }
+                if out.len() == before {
+                    check::<Track>(format!("/tracks/{ti}"), Some(track), &mut out);
+                }
+            }
+        }
+        None => check::<Vec<Track>>("/tracks".to_owned(), obj.get("tracks"), &mut out),
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/composition/validate.rs"]
+mod tests;
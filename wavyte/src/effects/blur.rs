@@ -1,4 +1,4 @@
-use crate::{WavyteError, WavyteResult};
+use crate::{WavyteError, WavyteResult, foundation::core::PixelRect};
 
 pub fn blur_rgba8_premul(
     src: &[u8],
@@ -24,12 +24,241 @@ pub fn blur_rgba8_premul(
     let mut tmp = vec![0u8; expected_len];
     let mut out = vec![0u8; expected_len];
 
-    horizontal_pass(src, &mut tmp, width, height, &kernel);
-    vertical_pass(&tmp, &mut out, width, height, &kernel);
+    horizontal_pass::<4>(src, &mut tmp, width, height, &kernel);
+    vertical_pass::<4>(&tmp, &mut out, width, height, &kernel);
     Ok(out)
 }
 
-fn gaussian_kernel_q16(radius: u32, sigma: f32) -> WavyteResult<Vec<u32>> {
+/// Gaussian blur of a single-channel 8-bit mask (`width * height` bytes).
+///
+/// Same kernel as [`blur_rgba8_premul`] at a quarter of the work; used for shadow masks.
+pub fn blur_alpha8(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    radius: u32,
+    sigma: f32,
+) -> WavyteResult<Vec<u8>> {
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .ok_or_else(|| WavyteError::evaluation("blur buffer size overflow"))?;
+    if src.len() != expected_len {
+        return Err(WavyteError::evaluation(
+            "blur_alpha8 expects src matching width*height",
+        ));
+    }
+    if radius == 0 {
+        return Ok(src.to_vec());
+    }
+
+    let kernel = gaussian_kernel_q16(radius, sigma)?;
+    let mut tmp = vec![0u8; expected_len];
+    let mut out = vec![0u8; expected_len];
+
+    horizontal_pass::<1>(src, &mut tmp, width, height, &kernel);
+    vertical_pass::<1>(&tmp, &mut out, width, height, &kernel);
+    Ok(out)
+}
+
+/// Blur only the coverage of a premultiplied RGBA8 surface.
+///
+/// Alpha is blurred with the [`blur_alpha8`] kernel; each covered pixel keeps its own straight
+/// color, so edges soften without neighbouring colors bleeding into each other. Pixels that were
+/// fully transparent take the coverage-weighted color of the content they now overlap (the
+/// full-color blur), so the spread edge keeps its color instead of fading through black.
+pub fn blur_alpha_only_rgba8_premul(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    radius: u32,
+    sigma: f32,
+) -> WavyteResult<Vec<u8>> {
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|v| v.checked_mul(4))
+        .ok_or_else(|| WavyteError::evaluation("blur buffer size overflow"))?;
+    if src.len() != expected_len {
+        return Err(WavyteError::evaluation(
+            "blur_alpha_only_rgba8_premul expects src matching width*height*4",
+        ));
+    }
+    if radius == 0 {
+        return Ok(src.to_vec());
+    }
+
+    // Every channel uses the same kernel, so the blurred alpha matches `blur_alpha8`.
+    let mut out = blur_rgba8_premul(src, width, height, radius, sigma)?;
+    for (dst, s) in out.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+        if s[3] == 0 {
+            continue;
+        }
+        let a = dst[3];
+        for c in 0..3 {
+            let v = u32::from(s[c]) * u32::from(a) / u32::from(s[3]);
+            dst[c] = v.min(u32::from(a)) as u8;
+        }
+    }
+    Ok(out)
+}
+
+/// Unsharp mask: `src + amount * (src - blur(src))` per premultiplied channel.
+///
+/// Color channels are clamped to the resulting alpha so the output stays valid premultiplied data.
+pub fn sharpen_rgba8_premul(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    radius: u32,
+    sigma: f32,
+    amount: f32,
+) -> WavyteResult<Vec<u8>> {
+    if !amount.is_finite() || amount < 0.0 {
+        return Err(WavyteError::validation(
+            "sharpen amount must be finite and >= 0",
+        ));
+    }
+    let mut out = blur_rgba8_premul(src, width, height, radius, sigma)?;
+    for (dst, s) in out.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+        let boost = |s: u8, b: u8| -> f32 {
+            let s = f32::from(s);
+            s + amount * (s - f32::from(b))
+        };
+        let a = boost(s[3], dst[3]).round().clamp(0.0, 255.0);
+        for c in 0..3 {
+            dst[c] = boost(s[c], dst[c]).round().clamp(0.0, a) as u8;
+        }
+        dst[3] = a as u8;
+    }
+    Ok(out)
+}
+
+/// Zoom blur: average `samples` nearest-neighbour taps along the segment from each pixel toward
+/// `center` (normalized `0..1` coordinates of `frame`), covering `strength` of that distance.
+///
+/// `frame` is the full canvas the surface is a window of, with `x`/`y` placing the surface's
+/// top-left pixel on it, so a region of the canvas blurs exactly like the full frame.
+/// `strength == 0` returns the input unchanged.
+pub fn radial_blur_rgba8_premul(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    frame: PixelRect,
+    center: [f32; 2],
+    strength: f32,
+    samples: u32,
+) -> WavyteResult<Vec<u8>> {
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|v| v.checked_mul(4))
+        .ok_or_else(|| WavyteError::evaluation("radial blur buffer size overflow"))?;
+    if src.len() != expected_len {
+        return Err(WavyteError::evaluation(
+            "radial_blur_rgba8_premul expects src matching width*height*4",
+        ));
+    }
+    if !strength.is_finite() || !(0.0..=1.0).contains(&strength) {
+        return Err(WavyteError::validation(
+            "radial blur strength must be in [0, 1]",
+        ));
+    }
+    if strength == 0.0 || samples <= 1 {
+        return Ok(src.to_vec());
+    }
+
+    let cx = center[0] * frame.width as f32;
+    let cy = center[1] * frame.height as f32;
+    let mut out = vec![0u8; expected_len];
+    for y in 0..height {
+        for x in 0..width {
+            let (px, py) = ((x + frame.x) as f32 + 0.5, (y + frame.y) as f32 + 0.5);
+            let mut acc = [0u32; 4];
+            for k in 0..samples {
+                let t = strength * (k as f32) / (samples as f32);
+                let tap = (px + (cx - px) * t, py + (cy - py) * t);
+                let s = sample_px(src, width, height, frame, tap);
+                for c in 0..4 {
+                    acc[c] += u32::from(s[c]);
+                }
+            }
+            let idx = ((y * width + x) as usize) * 4;
+            for c in 0..4 {
+                out[idx + c] = ((acc[c] + samples / 2) / samples) as u8;
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Directional blur: average `samples` nearest-neighbour taps spread evenly over a segment of
+/// `distance` pixels centered on each pixel and oriented at `angle_deg` (`0` is horizontal, `90`
+/// points down). Taps past the surface edge are clamped.
+///
+/// Taps are placed in `frame` coordinates (see [`radial_blur_rgba8_premul`]).
+/// `distance == 0` returns the input unchanged.
+pub fn motion_blur_rgba8_premul(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    frame: PixelRect,
+    angle_deg: f32,
+    distance: f32,
+    samples: u32,
+) -> WavyteResult<Vec<u8>> {
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|v| v.checked_mul(4))
+        .ok_or_else(|| WavyteError::evaluation("motion blur buffer size overflow"))?;
+    if src.len() != expected_len {
+        return Err(WavyteError::evaluation(
+            "motion_blur_rgba8_premul expects src matching width*height*4",
+        ));
+    }
+    if !distance.is_finite() || distance < 0.0 || !angle_deg.is_finite() {
+        return Err(WavyteError::validation(
+            "motion blur distance must be finite and >= 0",
+        ));
+    }
+    if distance == 0.0 || samples <= 1 {
+        return Ok(src.to_vec());
+    }
+
+    let (sin, cos) = angle_deg.to_radians().sin_cos();
+    let offsets = (0..samples)
+        .map(|k| {
+            let t = (k as f32) / ((samples - 1) as f32) - 0.5;
+            (cos * distance * t, sin * distance * t)
+        })
+        .collect::<Vec<_>>();
+
+    let mut out = vec![0u8; expected_len];
+    for y in 0..height {
+        for x in 0..width {
+            let (px, py) = ((x + frame.x) as f32 + 0.5, (y + frame.y) as f32 + 0.5);
+            let mut acc = [0u32; 4];
+            for &(dx, dy) in &offsets {
+                let s = sample_px(src, width, height, frame, (px + dx, py + dy));
+                for c in 0..4 {
+                    acc[c] += u32::from(s[c]);
+                }
+            }
+            let idx = ((y * width + x) as usize) * 4;
+            for c in 0..4 {
+                out[idx + c] = ((acc[c] + samples / 2) / samples) as u8;
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Nearest-neighbour fetch at `frame` coordinates `(x, y)`, clamped to the surface edges.
+fn sample_px(src: &[u8], width: u32, height: u32, frame: PixelRect, (x, y): (f32, f32)) -> [u8; 4] {
+    let sx = (x.floor() as i64 - i64::from(frame.x)).clamp(0, i64::from(width) - 1) as usize;
+    let sy = (y.floor() as i64 - i64::from(frame.y)).clamp(0, i64::from(height) - 1) as usize;
+    let i = (sy * width as usize + sx) * 4;
+    [src[i], src[i + 1], src[i + 2], src[i + 3]]
+}
+
+pub(crate) fn gaussian_kernel_q16(radius: u32, sigma: f32) -> WavyteResult<Vec<u32>> {
     if radius == 0 {
         return Ok(vec![1 << 16]);
     }
@@ -72,45 +301,45 @@ fn gaussian_kernel_q16(radius: u32, sigma: f32) -> WavyteResult<Vec<u32>> {
     Ok(weights)
 }
 
-fn horizontal_pass(src: &[u8], dst: &mut [u8], width: u32, height: u32, k: &[u32]) {
+fn horizontal_pass<const N: usize>(src: &[u8], dst: &mut [u8], width: u32, height: u32, k: &[u32]) {
     let radius = (k.len() / 2) as i32;
     let w = width as i32;
     for y in 0..height as i32 {
         for x in 0..w {
-            let mut acc = [0u64; 4];
+            let mut acc = [0u64; N];
             for (ki, &kw) in k.iter().enumerate() {
                 let dx = ki as i32 - radius;
                 let sx = (x + dx).clamp(0, w - 1);
-                let idx = ((y * w + sx) as usize) * 4;
-                for c in 0..4 {
+                let idx = ((y * w + sx) as usize) * N;
+                for c in 0..N {
                     acc[c] += (kw as u64) * (src[idx + c] as u64);
                 }
             }
-            let out_idx = ((y * w + x) as usize) * 4;
-            for c in 0..4 {
+            let out_idx = ((y * w + x) as usize) * N;
+            for c in 0..N {
                 dst[out_idx + c] = q16_to_u8(acc[c]);
             }
         }
     }
 }
 
-fn vertical_pass(src: &[u8], dst: &mut [u8], width: u32, height: u32, k: &[u32]) {
+fn vertical_pass<const N: usize>(src: &[u8], dst: &mut [u8], width: u32, height: u32, k: &[u32]) {
     let radius = (k.len() / 2) as i32;
     let w = width as i32;
     let h = height as i32;
     for y in 0..h {
         for x in 0..w {
-            let mut acc = [0u64; 4];
+            let mut acc = [0u64; N];
             for (ki, &kw) in k.iter().enumerate() {
                 let dy = ki as i32 - radius;
                 let sy = (y + dy).clamp(0, h - 1);
-                let idx = ((sy * w + x) as usize) * 4;
-                for c in 0..4 {
+                let idx = ((sy * w + x) as usize) * N;
+                for c in 0..N {
                     acc[c] += (kw as u64) * (src[idx + c] as u64);
                 }
             }
-            let out_idx = ((y * w + x) as usize) * 4;
-            for c in 0..4 {
+            let out_idx = ((y * w + x) as usize) * N;
+            for c in 0..N {
                 dst[out_idx + c] = q16_to_u8(acc[c]);
             }
         }
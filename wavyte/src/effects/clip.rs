@@ -0,0 +1,50 @@
+use crate::{WavyteError, WavyteResult};
+
+/// Keep only the part of a surface inside an axis-aligned rectangle.
+///
+/// `rect` is `[x0, y0, x1, y1]` in surface pixels. Each pixel is scaled by the fraction of its
+/// area covered by the rectangle, so fractional edges stay antialiased; pixels fully outside
+/// become transparent.
+pub fn clip_rect_rgba8_premul(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    rect: [f32; 4],
+) -> WavyteResult<Vec<u8>> {
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|v| v.checked_mul(4))
+        .ok_or_else(|| WavyteError::evaluation("clip buffer size overflow"))?;
+    if src.len() != expected_len {
+        return Err(WavyteError::evaluation(
+            "clip_rect_rgba8_premul expects src matching width*height*4",
+        ));
+    }
+
+    let coverage = |lo: f32, hi: f32, i: usize| {
+        let p = i as f32;
+        ((p + 1.0).min(hi) - p.max(lo)).clamp(0.0, 1.0)
+    };
+    let cols: Vec<f32> = (0..width as usize)
+        .map(|x| coverage(rect[0], rect[2], x))
+        .collect();
+
+    let mut out = src.to_vec();
+    for (y, row) in out.chunks_exact_mut(width as usize * 4).enumerate() {
+        let cy = coverage(rect[1], rect[3], y);
+        for (px, &cx) in row.chunks_exact_mut(4).zip(cols.iter()) {
+            let k = cx * cy;
+            if k >= 1.0 {
+                continue;
+            }
+            for c in px.iter_mut() {
+                *c = (f32::from(*c) * k).round() as u8;
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/effects/clip.rs"]
+mod tests;
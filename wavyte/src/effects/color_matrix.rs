@@ -0,0 +1,193 @@
+use crate::{WavyteError, WavyteResult};
+
+/// Identity 4x5 color matrix.
+pub const IDENTITY: [f32; 20] = [
+    1.0, 0.0, 0.0, 0.0, 0.0, //
+    0.0, 1.0, 0.0, 0.0, 0.0, //
+    0.0, 0.0, 1.0, 0.0, 0.0, //
+    0.0, 0.0, 0.0, 1.0, 0.0,
+];
+
+pub(crate) const LUMA_REC709: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Saturation matrix using Rec.709 luma weights. `0` is grayscale, `1` is identity.
+pub fn saturate_matrix(amount: f32) -> [f32; 20] {
+    let [lr, lg, lb] = LUMA_REC709;
+    let s = amount;
+    let i = 1.0 - s;
+    [
+        lr * i + s,
+        lg * i,
+        lb * i,
+        0.0,
+        0.0, //
+        lr * i,
+        lg * i + s,
+        lb * i,
+        0.0,
+        0.0, //
+        lr * i,
+        lg * i,
+        lb * i + s,
+        0.0,
+        0.0, //
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+        0.0,
+    ]
+}
+
+/// Grayscale matrix (Rec.709 luma).
+pub fn grayscale_matrix() -> [f32; 20] {
+    saturate_matrix(0.0)
+}
+
+/// Scale RGB by `value`, leaving alpha untouched.
+pub fn brightness_matrix(value: f32) -> [f32; 20] {
+    let mut m = IDENTITY;
+    m[0] = value;
+    m[6] = value;
+    m[12] = value;
+    m
+}
+
+/// Hue rotation matrix (SVG `feColorMatrix type="hueRotate"`). Multiples of 360° are identity.
+pub fn hue_rotate_matrix(angle_deg: f32) -> [f32; 20] {
+    let deg = angle_deg.rem_euclid(360.0);
+    if deg == 0.0 {
+        return IDENTITY;
+    }
+    let (sin, cos) = deg.to_radians().sin_cos();
+    [
+        0.213 + cos * 0.787 - sin * 0.213,
+        0.715 - cos * 0.715 - sin * 0.715,
+        0.072 - cos * 0.072 + sin * 0.928,
+        0.0,
+        0.0, //
+        0.213 - cos * 0.213 + sin * 0.143,
+        0.715 + cos * 0.285 + sin * 0.140,
+        0.072 - cos * 0.072 - sin * 0.283,
+        0.0,
+        0.0, //
+        0.213 - cos * 0.213 - sin * 0.787,
+        0.715 - cos * 0.715 + sin * 0.715,
+        0.072 + cos * 0.928 + sin * 0.072,
+        0.0,
+        0.0, //
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+        0.0,
+    ]
+}
+
+/// Invert RGB (`1 - c`), leaving alpha untouched.
+pub fn invert_matrix() -> [f32; 20] {
+    let mut m = IDENTITY;
+    for i in 0..3 {
+        m[i * 6] = -1.0;
+        m[i * 5 + 4] = 1.0;
+    }
+    m
+}
+
+/// Per-channel gain and offset (`c * gain + offset`), leaving alpha untouched.
+///
+/// Offsets are in normalized `0..1` units. `([1; 3], [0; 3])` is the identity.
+pub fn channel_gain_offset_matrix(gain: [f32; 3], offset: [f32; 3]) -> [f32; 20] {
+    let mut m = IDENTITY;
+    for i in 0..3 {
+        m[i * 6] = gain[i];
+        m[i * 5 + 4] = offset[i];
+    }
+    m
+}
+
+/// Apply a row-major 4x5 color matrix to premultiplied RGBA8 pixels.
+///
+/// Like SVG `feColorMatrix`, the matrix operates on straight-alpha channels normalized to `0..1`;
+/// the fifth column is an additive offset. Pixels are unpremultiplied before and premultiplied
+/// after the transform.
+pub fn color_matrix_rgba8_premul(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    m: &[f32; 20],
+) -> WavyteResult<Vec<u8>> {
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|v| v.checked_mul(4))
+        .ok_or_else(|| WavyteError::evaluation("color matrix buffer size overflow"))?;
+    if src.len() != expected_len {
+        return Err(WavyteError::evaluation(
+            "color_matrix_rgba8_premul expects src matching width*height*4",
+        ));
+    }
+
+    let mut out = vec![0u8; expected_len];
+    for (dst, px) in out.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+        let a = f32::from(px[3]) / 255.0;
+        let unpremul = |c: u8| -> f32 {
+            if a > 0.0 {
+                (f32::from(c) / 255.0 / a).min(1.0)
+            } else {
+                0.0
+            }
+        };
+        let v = [unpremul(px[0]), unpremul(px[1]), unpremul(px[2]), a];
+
+        let row = |r: usize| -> f32 {
+            let k = &m[r * 5..r * 5 + 5];
+            (k[0] * v[0] + k[1] * v[1] + k[2] * v[2] + k[3] * v[3] + k[4]).clamp(0.0, 1.0)
+        };
+        let out_a = row(3);
+        for (c, d) in dst[..3].iter_mut().enumerate() {
+            *d = (row(c) * out_a * 255.0).round() as u8;
+        }
+        dst[3] = (out_a * 255.0).round() as u8;
+    }
+    Ok(out)
+}
+
+/// Map each pixel to black or white by comparing its straight-alpha Rec.709 luma to `cutoff`.
+///
+/// Pixels with luma `>= cutoff` become white; alpha is preserved.
+pub fn threshold_rgba8_premul(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    cutoff: f32,
+) -> WavyteResult<Vec<u8>> {
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|v| v.checked_mul(4))
+        .ok_or_else(|| WavyteError::evaluation("threshold buffer size overflow"))?;
+    if src.len() != expected_len {
+        return Err(WavyteError::evaluation(
+            "threshold_rgba8_premul expects src matching width*height*4",
+        ));
+    }
+
+    let [lr, lg, lb] = LUMA_REC709;
+    let mut out = vec![0u8; expected_len];
+    for (dst, px) in out.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+        let a = px[3];
+        if a == 0 {
+            continue;
+        }
+        // Premultiplied luma divided by alpha is the straight-alpha luma.
+        let luma =
+            (lr * f32::from(px[0]) + lg * f32::from(px[1]) + lb * f32::from(px[2])) / f32::from(a);
+        let v = if luma >= cutoff { a } else { 0 };
+        dst[..3].fill(v);
+        dst[3] = a;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/effects/color_matrix.rs"]
+mod tests;
@@ -0,0 +1,71 @@
+use crate::{WavyteError, WavyteResult};
+
+/// Run `pass` on the linear-light version of a premultiplied sRGB buffer and convert back.
+///
+/// `pass` sees premultiplied linear RGBA as `0..=1` floats (see
+/// [`crate::effects::linear_pass::apply_pass_fx_f32`]), so dark levels that an 8-bit linear
+/// encoding would round to zero survive the pass; output is rounded to 8 bits once, after
+/// re-encoding to sRGB. Pixels the pass leaves unchanged round-trip exactly.
+pub fn linear_light_rgba8_premul(
+    src: &[u8],
+    pass: impl FnOnce(&[f32]) -> WavyteResult<Vec<f32>>,
+) -> WavyteResult<Vec<u8>> {
+    if !src.len().is_multiple_of(4) {
+        return Err(WavyteError::evaluation(
+            "color space conversion expects RGBA8 pixels",
+        ));
+    }
+    let mut linear = vec![0.0f32; src.len()];
+    for (dst, px) in linear.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+        if px[3] == 0 {
+            continue;
+        }
+        let a = f32::from(px[3]) / 255.0;
+        for c in 0..3 {
+            dst[c] = srgb_to_linear((f32::from(px[c]) / 255.0 / a).min(1.0)) * a;
+        }
+        dst[3] = a;
+    }
+
+    let processed = pass(&linear)?;
+    if processed.len() != src.len() {
+        return Err(WavyteError::evaluation(
+            "linear-light pass must preserve the buffer size",
+        ));
+    }
+
+    let mut out = vec![0u8; src.len()];
+    for (dst, p) in out.chunks_exact_mut(4).zip(processed.chunks_exact(4)) {
+        let a = p[3].clamp(0.0, 1.0);
+        let a8 = (a * 255.0).round();
+        if a8 <= 0.0 {
+            continue;
+        }
+        for c in 0..3 {
+            let straight = (p[c] / a).clamp(0.0, 1.0);
+            dst[c] = (linear_to_srgb(straight) * a8).round().clamp(0.0, a8) as u8;
+        }
+        dst[3] = a8 as u8;
+    }
+    Ok(out)
+}
+
+pub(crate) fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(v: f32) -> f32 {
+    if v <= 0.003_130_8 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/effects/color_space.rs"]
+mod tests;
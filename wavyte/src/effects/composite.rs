@@ -1,6 +1,7 @@
+use crate::composition::model::BlendMode;
 use crate::effects::transitions::WipeDir;
 use crate::foundation::error::WavyteResult;
-use crate::foundation::math::mul_div255_u8;
+use crate::foundation::math::{mul_div255_u8, mul_div255_u16};
 
 pub type PremulRgba8 = [u8; 4];
 
@@ -29,6 +30,115 @@ pub fn over(dst: PremulRgba8, src: PremulRgba8, opacity: f32) -> PremulRgba8 {
     out
 }
 
+/// Separable multiply blend of premultiplied `src` (scaled by `opacity`) onto `dst`:
+/// `co = cs * (1 - ad) + cd * (1 - as) + cs * cd`, `ao = as + ad - as * ad`.
+pub fn multiply(dst: PremulRgba8, src: PremulRgba8, opacity: f32) -> PremulRgba8 {
+    let opacity = opacity.clamp(0.0, 1.0);
+    if opacity <= 0.0 || src[3] == 0 {
+        return dst;
+    }
+
+    let op = ((opacity * 255.0).round() as i32).clamp(0, 255) as u16;
+    let s = src.map(|c| mul_div255_u16(u16::from(c), op));
+    let (sa, da) = (s[3], u16::from(dst[3]));
+
+    let mut out = [0u8; 4];
+    out[3] = (sa + da - mul_div255_u16(sa, da)).min(255) as u8;
+    for i in 0..3 {
+        let d = u16::from(dst[i]);
+        let v =
+            mul_div255_u16(s[i], 255 - da) + mul_div255_u16(d, 255 - sa) + mul_div255_u16(s[i], d);
+        out[i] = v.min(255) as u8;
+    }
+    out
+}
+
+/// Blend one premultiplied pixel onto another with `mode`.
+pub fn blend(dst: PremulRgba8, src: PremulRgba8, opacity: f32, mode: BlendMode) -> PremulRgba8 {
+    match mode {
+        BlendMode::Normal => over(dst, src, opacity),
+        BlendMode::Multiply => multiply(dst, src, opacity),
+        BlendMode::Hue | BlendMode::Saturation | BlendMode::Color | BlendMode::Luminosity => {
+            non_separable(dst, src, opacity, mode)
+        }
+    }
+}
+
+/// Non-separable (HSL) blend of premultiplied `src` (scaled by `opacity`) onto `dst`, following
+/// the W3C compositing spec: `co = cs * (1 - ad) + cd * (1 - as) + as * ad * B(Cb, Cs)` where
+/// `B` operates on the whole unpremultiplied RGB triple.
+fn non_separable(dst: PremulRgba8, src: PremulRgba8, opacity: f32, mode: BlendMode) -> PremulRgba8 {
+    let opacity = opacity.clamp(0.0, 1.0);
+    if opacity <= 0.0 || src[3] == 0 {
+        return dst;
+    }
+
+    let sa = f32::from(src[3]) / 255.0 * opacity;
+    let da = f32::from(dst[3]) / 255.0;
+    let s = [0, 1, 2].map(|i| f32::from(src[i]) / 255.0 * opacity);
+    let d = [0, 1, 2].map(|i| f32::from(dst[i]) / 255.0);
+    if da <= 0.0 {
+        return over(dst, src, opacity);
+    }
+
+    let cs = s.map(|c| (c / sa).clamp(0.0, 1.0));
+    let cb = d.map(|c| (c / da).clamp(0.0, 1.0));
+    let b = match mode {
+        BlendMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+        BlendMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+        BlendMode::Color => set_lum(cs, lum(cb)),
+        BlendMode::Luminosity => set_lum(cb, lum(cs)),
+        BlendMode::Normal | BlendMode::Multiply => cs,
+    };
+
+    let to_u8 = |v: f32| (v * 255.0).round().clamp(0.0, 255.0) as u8;
+    let mut out = [0u8; 4];
+    out[3] = to_u8(sa + da - sa * da);
+    for i in 0..3 {
+        out[i] = to_u8(s[i] * (1.0 - da) + d[i] * (1.0 - sa) + sa * da * b[i]);
+    }
+    out
+}
+
+fn lum(c: [f32; 3]) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn clip_color(c: [f32; 3]) -> [f32; 3] {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    let mut c = c;
+    if n < 0.0 {
+        c = c.map(|v| l + (v - l) * l / (l - n));
+    }
+    if x > 1.0 {
+        c = c.map(|v| l + (v - l) * (1.0 - l) / (x - l));
+    }
+    c
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    clip_color(c.map(|v| v + d))
+}
+
+fn sat(c: [f32; 3]) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let mut idx = [0usize, 1, 2];
+    idx.sort_by(|&a, &b| c[a].total_cmp(&c[b]));
+    let [min, mid, max] = idx;
+    let mut out = [0.0f32; 3];
+    if c[max] > c[min] {
+        out[mid] = (c[mid] - c[min]) * s / (c[max] - c[min]);
+        out[max] = s;
+    }
+    out
+}
+
 pub fn crossfade(a: PremulRgba8, b: PremulRgba8, t: f32) -> PremulRgba8 {
     let t = t.clamp(0.0, 1.0);
     let tt = ((t * 255.0).round() as i32).clamp(0, 255) as u16;
@@ -56,6 +166,65 @@ pub fn over_in_place(dst: &mut [u8], src: &[u8], opacity: f32) -> WavyteResult<(
     Ok(())
 }
 
+/// Alpha-over a smaller `src` (row-major, `src_width` wide) onto `dst` with its top-left corner
+/// at `origin`. `src` must lie entirely within `dst`.
+pub fn over_at_in_place(
+    dst: &mut [u8],
+    dst_width: u32,
+    src: &[u8],
+    src_width: u32,
+    origin: [u32; 2],
+    opacity: f32,
+) -> WavyteResult<()> {
+    blend_at_in_place(
+        dst,
+        dst_width,
+        src,
+        src_width,
+        origin,
+        opacity,
+        BlendMode::Normal,
+    )
+}
+
+/// Like [`over_at_in_place`], combining pixels with `mode` instead of plain alpha-over.
+pub fn blend_at_in_place(
+    dst: &mut [u8],
+    dst_width: u32,
+    src: &[u8],
+    src_width: u32,
+    origin: [u32; 2],
+    opacity: f32,
+    mode: BlendMode,
+) -> WavyteResult<()> {
+    let (dw, sw) = (dst_width as usize * 4, src_width as usize * 4);
+    if dw == 0 || sw == 0 || !dst.len().is_multiple_of(dw) || !src.len().is_multiple_of(sw) {
+        return Err(crate::WavyteError::evaluation(
+            "blend_at_in_place expects whole rgba8 rows",
+        ));
+    }
+    let (ox, oy) = (origin[0] as usize * 4, origin[1] as usize);
+    if ox + sw > dw || oy + src.len() / sw > dst.len() / dw {
+        return Err(crate::WavyteError::evaluation(
+            "blend_at_in_place source does not fit in target",
+        ));
+    }
+    for (row, s_row) in src.chunks_exact(sw).enumerate() {
+        let start = (oy + row) * dw + ox;
+        let d_row = &mut dst[start..start + sw];
+        for (d, s) in d_row.chunks_exact_mut(4).zip(s_row.chunks_exact(4)) {
+            let out = blend(
+                [d[0], d[1], d[2], d[3]],
+                [s[0], s[1], s[2], s[3]],
+                opacity,
+                mode,
+            );
+            d.copy_from_slice(&out);
+        }
+    }
+    Ok(())
+}
+
 pub fn crossfade_over_in_place(dst: &mut [u8], a: &[u8], b: &[u8], t: f32) -> WavyteResult<()> {
     if dst.len() != a.len() || dst.len() != b.len() || !dst.len().is_multiple_of(4) {
         return Err(crate::WavyteError::evaluation(
@@ -86,6 +255,8 @@ pub fn wipe_over_in_place(
         t,
         dir,
         soft_edge,
+        sweep_origin: [ox, oy],
+        sweep_size: [sw, sh],
     } = params;
     let expected_len = (width as usize)
         .checked_mul(height as usize)
@@ -102,8 +273,8 @@ pub fn wipe_over_in_place(
     let soft_edge = soft_edge.max(0.0);
 
     let axis_len = match dir {
-        WipeDir::LeftToRight | WipeDir::RightToLeft => width as f32,
-        WipeDir::TopToBottom | WipeDir::BottomToTop => height as f32,
+        WipeDir::LeftToRight | WipeDir::RightToLeft => sw as f32,
+        WipeDir::TopToBottom | WipeDir::BottomToTop => sh as f32,
     };
     let soft_px = soft_edge * axis_len;
 
@@ -113,11 +284,12 @@ pub fn wipe_over_in_place(
 
     for y in 0..height {
         for x in 0..width {
+            let (sx, sy) = ((ox + x) as f32, (oy + y) as f32);
             let pos = match dir {
-                WipeDir::LeftToRight => x as f32,
-                WipeDir::RightToLeft => (width - 1 - x) as f32,
-                WipeDir::TopToBottom => y as f32,
-                WipeDir::BottomToTop => (height - 1 - y) as f32,
+                WipeDir::LeftToRight => sx,
+                WipeDir::RightToLeft => sw as f32 - 1.0 - sx,
+                WipeDir::TopToBottom => sy,
+                WipeDir::BottomToTop => sh as f32 - 1.0 - sy,
             };
 
             let m = if soft_px <= 0.0 {
@@ -146,6 +318,10 @@ pub struct WipeParams {
     pub t: f32,
     pub dir: WipeDir,
     pub soft_edge: f32,
+    /// Position of the buffers' top-left pixel within the swept area.
+    pub sweep_origin: [u32; 2],
+    /// Size of the swept area; `[width, height]` when the buffers cover all of it.
+    pub sweep_size: [u32; 2],
 }
 
 fn mul_div255(x: u16, y: u16) -> u8 {
@@ -156,7 +332,7 @@ fn add_sat_u8(a: u8, b: u8) -> u8 {
     a.saturating_add(b)
 }
 
-fn smoothstep(a: f32, b: f32, x: f32) -> f32 {
+pub(crate) fn smoothstep(a: f32, b: f32, x: f32) -> f32 {
     if x <= a {
         return 0.0;
     }
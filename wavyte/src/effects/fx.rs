@@ -23,7 +23,75 @@ pub enum Effect {
         radius_px: u32,
         /// Standard deviation in pixels.
         sigma: f32,
+        /// Blur coverage only, keeping each pixel's own color.
+        alpha_only: bool,
     },
+    /// Unsharp-mask sharpen pass parameters.
+    Sharpen {
+        /// Strength of the high-frequency boost; `0` is a no-op.
+        amount: f32,
+        /// Radius of the blur used to extract detail, in pixels.
+        radius_px: u32,
+        /// Standard deviation of that blur in pixels.
+        sigma: f32,
+    },
+    /// Mosaic pass parameters.
+    Pixelate {
+        /// Edge length of each averaged block in pixels.
+        block_px: u32,
+    },
+    /// Drop shadow pass parameters.
+    DropShadow {
+        /// Shadow offset in pixels (`[x, y]`, positive is down-right).
+        offset: [i32; 2],
+        /// Shadow blur radius in pixels.
+        blur_radius_px: u32,
+        /// Shadow blur standard deviation in pixels.
+        sigma: f32,
+        /// Shadow color (straight-alpha RGBA8).
+        color: [u8; 4],
+    },
+    /// Row-major 4x5 color matrix over straight-alpha `0..1` channels.
+    ColorMatrix {
+        /// Matrix coefficients; the fifth column of each row is an additive offset.
+        matrix: [f32; 20],
+    },
+    /// Black/white luma threshold pass parameters.
+    Threshold {
+        /// Luma cutoff in `0..=1`; pixels at or above it become white.
+        cutoff: f32,
+    },
+    /// Zoom blur pass parameters.
+    RadialBlur {
+        /// Blur center in normalized `0..1` surface coordinates.
+        center: [f32; 2],
+        /// Fraction of the distance to `center` covered by the samples, in `0..=1`.
+        strength: f32,
+        /// Number of taps per pixel.
+        samples: u32,
+    },
+    /// Directional motion blur pass parameters.
+    MotionBlur {
+        /// Blur direction in degrees (`0` is horizontal, `90` points down).
+        angle_deg: f32,
+        /// Length of the blur streak in pixels.
+        distance_px: f32,
+        /// Number of taps along the streak.
+        samples: u32,
+    },
+    /// Edge-darkening vignette pass parameters.
+    Vignette {
+        /// Normalized distance from center (corners are `1`) where the falloff starts.
+        radius: f32,
+        /// Width of the falloff band in the same normalized units.
+        softness: f32,
+        /// Vignette color (straight-alpha RGBA8); alpha scales the strength.
+        color: [u8; 4],
+    },
+    /// An effect that processes linear-light pixels (`"color_space": "linear"`).
+    ///
+    /// Inline effects are color-space independent, so only the wrapped pass is affected.
+    LinearLight(Box<Effect>),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -53,9 +121,135 @@ pub enum PassFx {
         radius_px: u32,
         /// Standard deviation in pixels.
         sigma: f32,
+        /// Blur the alpha channel only (see [`crate::effects::blur::blur_alpha_only_rgba8_premul`]).
+        alpha_only: bool,
+    },
+    /// Unsharp mask applied to a surface.
+    Sharpen {
+        /// Strength of the high-frequency boost.
+        amount: f32,
+        /// Blur radius in pixels.
+        radius_px: u32,
+        /// Blur standard deviation in pixels.
+        sigma: f32,
+    },
+    /// Block-average mosaic applied to a surface.
+    Pixelate {
+        /// Edge length of each averaged block in pixels.
+        block_px: u32,
+    },
+    /// Offset, blurred, tinted alpha shadow drawn beneath the surface.
+    DropShadow {
+        /// Shadow offset in pixels.
+        offset: [i32; 2],
+        /// Shadow blur radius in pixels.
+        blur_radius_px: u32,
+        /// Shadow blur standard deviation in pixels.
+        sigma: f32,
+        /// Shadow color (straight-alpha RGBA8).
+        color: [u8; 4],
+    },
+    /// Per-pixel 4x5 color matrix applied to a surface.
+    ColorMatrix {
+        /// Row-major matrix coefficients.
+        matrix: [f32; 20],
+    },
+    /// Per-pixel black/white luma threshold applied to a surface.
+    Threshold {
+        /// Luma cutoff in `0..=1`.
+        cutoff: f32,
+    },
+    /// Zoom blur toward a center point applied to a surface.
+    RadialBlur {
+        /// Normalized blur center.
+        center: [f32; 2],
+        /// Fraction of the distance to `center` covered by the samples.
+        strength: f32,
+        /// Number of taps per pixel.
+        samples: u32,
+    },
+    /// Directional blur applied to a surface.
+    MotionBlur {
+        /// Blur direction in degrees.
+        angle_deg: f32,
+        /// Streak length in pixels.
+        distance_px: f32,
+        /// Number of taps along the streak.
+        samples: u32,
+    },
+    /// Radial blend toward a color away from the surface center.
+    Vignette {
+        /// Normalized falloff start distance.
+        radius: f32,
+        /// Normalized falloff width.
+        softness: f32,
+        /// Vignette color (straight-alpha RGBA8).
+        color: [u8; 4],
+    },
+    /// Wrapped pass run on linear-light pixels; the surface is converted from sRGB before the
+    /// pass and back after it.
+    LinearLight(Box<PassFx>),
+    /// Rectangular clip of an isolated track stack (see [`crate::Track::clip_to`]).
+    ClipRect {
+        /// `[x0, y0, x1, y1]` in surface pixels.
+        rect: [f32; 4],
     },
 }
 
+impl PassFx {
+    /// Return this pass with pixel-valued parameters multiplied by `scale`.
+    ///
+    /// Used when rendering at a reduced resolution; normalized and per-pixel parameters are kept.
+    pub fn scaled(&self, scale: f32) -> Self {
+        let px = |v: u32| (v as f32 * scale).round() as u32;
+        match self.clone() {
+            PassFx::Blur {
+                radius_px,
+                sigma,
+                alpha_only,
+            } => PassFx::Blur {
+                radius_px: px(radius_px),
+                sigma: sigma * scale,
+                alpha_only,
+            },
+            PassFx::Sharpen {
+                amount,
+                radius_px,
+                sigma,
+            } => PassFx::Sharpen {
+                amount,
+                radius_px: px(radius_px),
+                sigma: sigma * scale,
+            },
+            PassFx::Pixelate { block_px } => PassFx::Pixelate {
+                block_px: px(block_px).max(1),
+            },
+            PassFx::DropShadow {
+                offset,
+                blur_radius_px,
+                sigma,
+                color,
+            } => PassFx::DropShadow {
+                offset: offset.map(|o| (o as f32 * scale).round() as i32),
+                blur_radius_px: px(blur_radius_px),
+                sigma: sigma * scale,
+                color,
+            },
+            PassFx::MotionBlur {
+                angle_deg,
+                distance_px,
+                samples,
+            } => PassFx::MotionBlur {
+                angle_deg,
+                distance_px: distance_px * scale,
+                samples,
+            },
+            PassFx::LinearLight(inner) => PassFx::LinearLight(Box::new(inner.scaled(scale))),
+            other => other,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 /// Normalized effect pipeline for one clip.
 pub struct FxPipeline {
@@ -72,6 +266,22 @@ pub fn parse_effect(inst: &EffectInstance) -> WavyteResult<Effect> {
         return Err(WavyteError::validation("effect kind must be non-empty"));
     }
 
+    match inst.params.get("color_space").map(|v| v.as_str()) {
+        None | Some(Some("srgb")) => {}
+        Some(Some("linear")) => {
+            let mut srgb = inst.clone();
+            if let Some(obj) = srgb.params.as_object_mut() {
+                obj.remove("color_space");
+            }
+            return Ok(Effect::LinearLight(Box::new(parse_effect(&srgb)?)));
+        }
+        Some(_) => {
+            return Err(WavyteError::validation(
+                "effect param 'color_space' must be \"srgb\" or \"linear\"",
+            ));
+        }
+    }
+
     match kind.as_str() {
         "opacitymul" | "opacity_mul" | "opacity-mul" => {
             let value = get_f32(&inst.params, "value")?;
@@ -106,7 +316,227 @@ pub fn parse_effect(inst: &EffectInstance) -> WavyteResult<Effect> {
                 }
                 None => (radius_px as f32) / 2.0,
             };
-            Ok(Effect::Blur { radius_px, sigma })
+            let alpha_only = match inst.params.get("alpha_only") {
+                Some(v) => v
+                    .as_bool()
+                    .ok_or_else(|| WavyteError::validation("Blur.alpha_only must be a bool"))?,
+                None => false,
+            };
+            Ok(Effect::Blur {
+                radius_px,
+                sigma,
+                alpha_only,
+            })
+        }
+        "sharpen" => {
+            let amount = get_f32(&inst.params, "amount")?;
+            if amount < 0.0 {
+                return Err(WavyteError::validation("Sharpen.amount must be >= 0"));
+            }
+            let radius_px = match inst.params.get("radius_px") {
+                Some(_) => get_u32(&inst.params, "radius_px")?,
+                None => 2,
+            };
+            if radius_px > 256 {
+                return Err(WavyteError::validation(
+                    "Sharpen.radius_px must be <= 256 in v0.2.1",
+                ));
+            }
+            Ok(Effect::Sharpen {
+                amount,
+                radius_px,
+                sigma: (radius_px as f32) / 2.0,
+            })
+        }
+        "pixelate" => {
+            let block_px = get_u32(&inst.params, "block_px")?;
+            if block_px == 0 || block_px > 1024 {
+                return Err(WavyteError::validation(
+                    "Pixelate.block_px must be in [1, 1024]",
+                ));
+            }
+            Ok(Effect::Pixelate { block_px })
+        }
+        "colormatrix" | "color_matrix" | "color-matrix" => {
+            let matrix = parse_matrix(&inst.params)?;
+            Ok(Effect::ColorMatrix { matrix })
+        }
+        "grayscale" => Ok(Effect::ColorMatrix {
+            matrix: crate::effects::color_matrix::grayscale_matrix(),
+        }),
+        "saturate" => {
+            let amount = get_f32(&inst.params, "amount")?;
+            if amount < 0.0 {
+                return Err(WavyteError::validation("Saturate.amount must be >= 0"));
+            }
+            Ok(Effect::ColorMatrix {
+                matrix: crate::effects::color_matrix::saturate_matrix(amount),
+            })
+        }
+        "brightness" => {
+            let value = get_f32(&inst.params, "value")?;
+            if value < 0.0 {
+                return Err(WavyteError::validation("Brightness.value must be >= 0"));
+            }
+            Ok(Effect::ColorMatrix {
+                matrix: crate::effects::color_matrix::brightness_matrix(value),
+            })
+        }
+        "huerotate" | "hue_rotate" | "hue-rotate" => {
+            let angle_deg = get_f32(&inst.params, "angle_deg")?;
+            Ok(Effect::ColorMatrix {
+                matrix: crate::effects::color_matrix::hue_rotate_matrix(angle_deg),
+            })
+        }
+        "invert" => Ok(Effect::ColorMatrix {
+            matrix: crate::effects::color_matrix::invert_matrix(),
+        }),
+        "channelmixer" | "channel_mixer" | "channel-mixer" => {
+            let mut gain = [1.0f32; 3];
+            let mut offset = [0.0f32; 3];
+            for (i, ch) in ["r", "g", "b"].into_iter().enumerate() {
+                let gain_key = format!("{ch}_gain");
+                if inst.params.get(&gain_key).is_some() {
+                    gain[i] = get_f32(&inst.params, &gain_key)?;
+                }
+                let offset_key = format!("{ch}_offset");
+                if inst.params.get(&offset_key).is_some() {
+                    offset[i] = get_f32(&inst.params, &offset_key)?;
+                }
+            }
+            if gain.iter().any(|g| *g < 0.0) || offset.iter().any(|o| !(-1.0..=1.0).contains(o)) {
+                return Err(WavyteError::validation(
+                    "ChannelMixer gains must be >= 0 and offsets must be in [-1, 1]",
+                ));
+            }
+            Ok(Effect::ColorMatrix {
+                matrix: crate::effects::color_matrix::channel_gain_offset_matrix(gain, offset),
+            })
+        }
+        "threshold" => {
+            let cutoff = get_f32(&inst.params, "cutoff")?;
+            if !(0.0..=1.0).contains(&cutoff) {
+                return Err(WavyteError::validation(
+                    "Threshold.cutoff must be in [0, 1]",
+                ));
+            }
+            Ok(Effect::Threshold { cutoff })
+        }
+        "radialblur" | "radial_blur" | "radial-blur" => {
+            let strength = get_f32(&inst.params, "strength")?;
+            if !(0.0..=1.0).contains(&strength) {
+                return Err(WavyteError::validation(
+                    "RadialBlur.strength must be in [0, 1]",
+                ));
+            }
+            let samples = match inst.params.get("samples") {
+                Some(_) => get_u32(&inst.params, "samples")?,
+                None => 16,
+            };
+            if samples == 0 || samples > 64 {
+                return Err(WavyteError::validation(
+                    "RadialBlur.samples must be in [1, 64]",
+                ));
+            }
+            let center = match inst.params.get("center") {
+                Some(v) => serde_json::from_value::<[f32; 2]>(v.clone())
+                    .ok()
+                    .filter(|c| c.iter().all(|v| v.is_finite()))
+                    .ok_or_else(|| WavyteError::validation("RadialBlur.center must be [x,y]"))?,
+                None => [0.5, 0.5],
+            };
+            Ok(Effect::RadialBlur {
+                center,
+                strength,
+                samples,
+            })
+        }
+        "motionblur" | "motion_blur" | "motion-blur" => {
+            let angle_deg = match inst.params.get("angle") {
+                Some(_) => get_f32(&inst.params, "angle")?,
+                None => 0.0,
+            };
+            let distance_px = get_f32(&inst.params, "distance")?;
+            if !(0.0..=512.0).contains(&distance_px) {
+                return Err(WavyteError::validation(
+                    "MotionBlur.distance must be in [0, 512]",
+                ));
+            }
+            let samples = match inst.params.get("samples") {
+                Some(_) => get_u32(&inst.params, "samples")?,
+                None => 16,
+            };
+            if samples == 0 || samples > 64 {
+                return Err(WavyteError::validation(
+                    "MotionBlur.samples must be in [1, 64]",
+                ));
+            }
+            Ok(Effect::MotionBlur {
+                angle_deg,
+                distance_px,
+                samples,
+            })
+        }
+        "vignette" => {
+            let radius = match inst.params.get("radius") {
+                Some(_) => get_f32(&inst.params, "radius")?,
+                None => 0.5,
+            };
+            let softness = match inst.params.get("softness") {
+                Some(_) => get_f32(&inst.params, "softness")?,
+                None => 0.5,
+            };
+            if radius < 0.0 || softness <= 0.0 {
+                return Err(WavyteError::validation(
+                    "Vignette.radius must be >= 0 and Vignette.softness must be > 0",
+                ));
+            }
+            let color = match inst.params.get("color") {
+                Some(v) => parse_color(v)?,
+                None => [0, 0, 0, 255],
+            };
+            Ok(Effect::Vignette {
+                radius,
+                softness,
+                color,
+            })
+        }
+        "dropshadow" | "drop_shadow" | "drop-shadow" => {
+            let offset = match inst.params.get("offset") {
+                Some(v) => parse_offset(v)?,
+                None => [4, 4],
+            };
+            let blur_radius_px = match inst.params.get("blur_radius_px") {
+                Some(_) => get_u32(&inst.params, "blur_radius_px")?,
+                None => 4,
+            };
+            if blur_radius_px > 256 {
+                return Err(WavyteError::validation(
+                    "DropShadow.blur_radius_px must be <= 256 in v0.2.1",
+                ));
+            }
+            let sigma = match inst.params.get("sigma") {
+                Some(_) => {
+                    let s = get_f32(&inst.params, "sigma")?;
+                    if s <= 0.0 {
+                        return Err(WavyteError::validation(
+                            "DropShadow.sigma must be finite and > 0",
+                        ));
+                    }
+                    s
+                }
+                None => ((blur_radius_px as f32) / 2.0).max(0.5),
+            };
+            let color = match inst.params.get("color") {
+                Some(v) => parse_color(v)?,
+                None => [0, 0, 0, 160],
+            };
+            Ok(Effect::DropShadow {
+                offset,
+                blur_radius_px,
+                sigma,
+                color,
+            })
         }
         _ => Err(WavyteError::validation(format!(
             "unknown effect kind '{kind}'"
@@ -123,11 +553,114 @@ pub fn normalize_effects(effects: &[Effect]) -> FxPipeline {
         match *e {
             Effect::OpacityMul { value } => inline.opacity_mul *= value,
             Effect::TransformPost { value } => inline.transform_post *= value,
-            Effect::Blur { radius_px, sigma } => {
+            Effect::Blur {
+                radius_px,
+                sigma,
+                alpha_only,
+            } => {
                 if radius_px == 0 {
                     continue;
                 }
-                passes.push(PassFx::Blur { radius_px, sigma });
+                passes.push(PassFx::Blur {
+                    radius_px,
+                    sigma,
+                    alpha_only,
+                });
+            }
+            Effect::Sharpen {
+                amount,
+                radius_px,
+                sigma,
+            } => {
+                if amount == 0.0 || radius_px == 0 {
+                    continue;
+                }
+                passes.push(PassFx::Sharpen {
+                    amount,
+                    radius_px,
+                    sigma,
+                });
+            }
+            Effect::Pixelate { block_px } => {
+                if block_px <= 1 {
+                    continue;
+                }
+                passes.push(PassFx::Pixelate { block_px });
+            }
+            Effect::DropShadow {
+                offset,
+                blur_radius_px,
+                sigma,
+                color,
+            } => {
+                if color[3] == 0 {
+                    continue;
+                }
+                passes.push(PassFx::DropShadow {
+                    offset,
+                    blur_radius_px,
+                    sigma,
+                    color,
+                });
+            }
+            Effect::ColorMatrix { matrix } => {
+                if matrix == crate::effects::color_matrix::IDENTITY {
+                    continue;
+                }
+                passes.push(PassFx::ColorMatrix { matrix });
+            }
+            Effect::Threshold { cutoff } => passes.push(PassFx::Threshold { cutoff }),
+            Effect::RadialBlur {
+                center,
+                strength,
+                samples,
+            } => {
+                if strength == 0.0 || samples <= 1 {
+                    continue;
+                }
+                passes.push(PassFx::RadialBlur {
+                    center,
+                    strength,
+                    samples,
+                });
+            }
+            Effect::MotionBlur {
+                angle_deg,
+                distance_px,
+                samples,
+            } => {
+                if distance_px == 0.0 || samples <= 1 {
+                    continue;
+                }
+                passes.push(PassFx::MotionBlur {
+                    angle_deg,
+                    distance_px,
+                    samples,
+                });
+            }
+            Effect::Vignette {
+                radius,
+                softness,
+                color,
+            } => {
+                if color[3] == 0 {
+                    continue;
+                }
+                passes.push(PassFx::Vignette {
+                    radius,
+                    softness,
+                    color,
+                });
+            }
+            Effect::LinearLight(ref inner) => {
+                let fx = normalize_effects(std::slice::from_ref(inner));
+                inline.opacity_mul *= fx.inline.opacity_mul;
+                inline.transform_post *= fx.inline.transform_post;
+                passes.extend(
+                    fx.passes
+                        .into_iter()
+                        .map(|p| PassFx::LinearLight(Box::new(p))),
+                );
             }
         }
     }
@@ -178,6 +711,52 @@ fn get_f32(obj: &serde_json::Value, key: &str) -> WavyteResult<f32> {
     Ok(n)
 }
 
+fn parse_matrix(params: &serde_json::Value) -> WavyteResult<[f32; 20]> {
+    let arr = params
+        .get("matrix")
+        .and_then(|v| v.as_array())
+        .filter(|a| a.len() == 20)
+        .ok_or_else(|| WavyteError::validation("ColorMatrix.matrix must be an array of 20"))?;
+    let mut out = [0.0f32; 20];
+    for (slot, v) in out.iter_mut().zip(arr) {
+        let n = v
+            .as_f64()
+            .ok_or_else(|| WavyteError::validation("ColorMatrix.matrix entries must be numbers"))?
+            as f32;
+        if !n.is_finite() {
+            return Err(WavyteError::validation(
+                "ColorMatrix.matrix entries must be finite",
+            ));
+        }
+        *slot = n;
+    }
+    Ok(out)
+}
+
+fn parse_offset(v: &serde_json::Value) -> WavyteResult<[i32; 2]> {
+    let arr = v
+        .as_array()
+        .filter(|a| a.len() == 2)
+        .ok_or_else(|| WavyteError::validation("DropShadow.offset must be [x,y]"))?;
+    let mut out = [0i32; 2];
+    for (slot, n) in out.iter_mut().zip(arr) {
+        *slot = n
+            .as_i64()
+            .and_then(|n| i32::try_from(n).ok())
+            .ok_or_else(|| WavyteError::validation("DropShadow.offset entries must be integers"))?;
+    }
+    Ok(out)
+}
+
+fn parse_color(v: &serde_json::Value) -> WavyteResult<[u8; 4]> {
+    if let Some(s) = v.as_str() {
+        return crate::composition::model::parse_hex_color_rgba8(s)
+            .ok_or_else(|| WavyteError::validation(format!("invalid hex color '{s}'")));
+    }
+    serde_json::from_value::<[u8; 4]>(v.clone())
+        .map_err(|_| WavyteError::validation("effect color must be \"#rrggbb[aa]\" or [r,g,b,a]"))
+}
+
 fn parse_affine(params: &serde_json::Value) -> WavyteResult<Affine> {
     if let Some(a) = params.get("affine") {
         let Some(arr) = a.as_array() else {
@@ -0,0 +1,328 @@
+use std::ops::Range;
+
+use crate::{
+    WavyteError, WavyteResult,
+    effects::{blur::gaussian_kernel_q16, composite::smoothstep, fx::PassFx, pixelate::tile_spans},
+    foundation::core::PixelRect,
+};
+
+/// Apply `fx` to premultiplied linear-light RGBA in `0..=1` floats.
+///
+/// Mirrors the 8-bit passes dispatched by the CPU backend, without quantizing between steps, so
+/// dark linear levels that 8 bits cannot hold survive spatial passes. Colors given as straight
+/// sRGB RGBA8 (shadow and vignette tints) are decoded to linear light first. `frame` places the
+/// surface on the full canvas, as for the 8-bit positional passes.
+pub fn apply_pass_fx_f32(
+    fx: &PassFx,
+    src: &[f32],
+    width: u32,
+    height: u32,
+    frame: PixelRect,
+) -> WavyteResult<Vec<f32>> {
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|v| v.checked_mul(4))
+        .ok_or_else(|| WavyteError::evaluation("linear pass buffer size overflow"))?;
+    if src.len() != expected_len {
+        return Err(WavyteError::evaluation(
+            "linear pass expects src matching width*height*4",
+        ));
+    }
+    Ok(match *fx {
+        PassFx::Blur {
+            radius_px,
+            sigma,
+            alpha_only,
+        } => {
+            let mut out = blur::<4>(src, width, height, radius_px, sigma)?;
+            if alpha_only {
+                for (dst, s) in out.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+                    if s[3] <= 0.0 {
+                        continue;
+                    }
+                    let a = dst[3];
+                    for c in 0..3 {
+                        dst[c] = (s[c] * a / s[3]).min(a);
+                    }
+                }
+            }
+            out
+        }
+        PassFx::Sharpen {
+            amount,
+            radius_px,
+            sigma,
+        } => {
+            let mut out = blur::<4>(src, width, height, radius_px, sigma)?;
+            for (dst, s) in out.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+                let a = (s[3] + amount * (s[3] - dst[3])).clamp(0.0, 1.0);
+                for c in 0..3 {
+                    dst[c] = (s[c] + amount * (s[c] - dst[c])).clamp(0.0, a);
+                }
+                dst[3] = a;
+            }
+            out
+        }
+        PassFx::Pixelate { block_px } => pixelate(src, width, height, frame, block_px.max(1)),
+        PassFx::DropShadow {
+            offset,
+            blur_radius_px,
+            sigma,
+            color,
+        } => drop_shadow(src, width, height, offset, blur_radius_px, sigma, color)?,
+        PassFx::ColorMatrix { ref matrix } => {
+            let mut out = vec![0.0f32; src.len()];
+            for (dst, px) in out.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+                let a = px[3].clamp(0.0, 1.0);
+                let unpremul = |c: f32| if a > 0.0 { (c / a).min(1.0) } else { 0.0 };
+                let v = [unpremul(px[0]), unpremul(px[1]), unpremul(px[2]), a];
+                let row = |r: usize| -> f32 {
+                    let k = &matrix[r * 5..r * 5 + 5];
+                    (k[0] * v[0] + k[1] * v[1] + k[2] * v[2] + k[3] * v[3] + k[4]).clamp(0.0, 1.0)
+                };
+                let out_a = row(3);
+                for (c, d) in dst[..3].iter_mut().enumerate() {
+                    *d = row(c) * out_a;
+                }
+                dst[3] = out_a;
+            }
+            out
+        }
+        PassFx::Threshold { cutoff } => {
+            let [lr, lg, lb] = crate::effects::color_matrix::LUMA_REC709;
+            let mut out = vec![0.0f32; src.len()];
+            for (dst, px) in out.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+                let a = px[3];
+                if a <= 0.0 {
+                    continue;
+                }
+                let luma = (lr * px[0] + lg * px[1] + lb * px[2]) / a;
+                dst[..3].fill(if luma >= cutoff { a } else { 0.0 });
+                dst[3] = a;
+            }
+            out
+        }
+        PassFx::RadialBlur {
+            center,
+            strength,
+            samples,
+        } => {
+            if strength == 0.0 || samples <= 1 {
+                return Ok(src.to_vec());
+            }
+            let (cx, cy) = (
+                center[0] * frame.width as f32,
+                center[1] * frame.height as f32,
+            );
+            tap_average(src, width, height, frame, samples, |px, py, k| {
+                let t = strength * (k as f32) / (samples as f32);
+                (px + (cx - px) * t, py + (cy - py) * t)
+            })
+        }
+        PassFx::MotionBlur {
+            angle_deg,
+            distance_px,
+            samples,
+        } => {
+            if distance_px == 0.0 || samples <= 1 {
+                return Ok(src.to_vec());
+            }
+            let (sin, cos) = angle_deg.to_radians().sin_cos();
+            tap_average(src, width, height, frame, samples, |px, py, k| {
+                let t = (k as f32) / ((samples - 1) as f32) - 0.5;
+                (px + cos * distance_px * t, py + sin * distance_px * t)
+            })
+        }
+        PassFx::Vignette {
+            radius,
+            softness,
+            color,
+        } => {
+            let (cx, cy) = (frame.width as f32 / 2.0, frame.height as f32 / 2.0);
+            let half_diag = (cx * cx + cy * cy).sqrt().max(f32::EPSILON);
+            let strength = f32::from(color[3]) / 255.0;
+            let tint = linear_rgb(color);
+            let mut out = src.to_vec();
+            for (i, px) in out.chunks_exact_mut(4).enumerate() {
+                let x = (i % width as usize + frame.x as usize) as f32 + 0.5;
+                let y = (i / width as usize + frame.y as usize) as f32 + 0.5;
+                let dist = ((x - cx).powi(2) + (y - cy).powi(2)).sqrt() / half_diag;
+                let t = smoothstep(radius, radius + softness, dist) * strength;
+                if t <= 0.0 {
+                    continue;
+                }
+                let a = px[3];
+                for (c, k) in px[..3].iter_mut().zip(tint) {
+                    *c = (*c * (1.0 - t) + k * a * t).clamp(0.0, a);
+                }
+            }
+            out
+        }
+        PassFx::ClipRect { rect } => {
+            let coverage = |lo: f32, hi: f32, i: usize| {
+                let p = i as f32;
+                ((p + 1.0).min(hi) - p.max(lo)).clamp(0.0, 1.0)
+            };
+            let mut out = src.to_vec();
+            for (y, row) in out.chunks_exact_mut(width as usize * 4).enumerate() {
+                let cy = coverage(rect[1], rect[3], y);
+                for (x, px) in row.chunks_exact_mut(4).enumerate() {
+                    let k = coverage(rect[0], rect[2], x) * cy;
+                    px.iter_mut().for_each(|c| *c *= k);
+                }
+            }
+            out
+        }
+        // Already linear: run the wrapped pass directly.
+        PassFx::LinearLight(ref inner) => apply_pass_fx_f32(inner, src, width, height, frame)?,
+    })
+}
+
+/// Separable Gaussian blur with the 8-bit path's kernel over `N`-channel pixels.
+fn blur<const N: usize>(
+    src: &[f32],
+    width: u32,
+    height: u32,
+    radius: u32,
+    sigma: f32,
+) -> WavyteResult<Vec<f32>> {
+    if radius == 0 {
+        return Ok(src.to_vec());
+    }
+    let kernel = gaussian_kernel_q16(radius, sigma)?
+        .into_iter()
+        .map(|q| q as f32 / 65536.0)
+        .collect::<Vec<_>>();
+    let r = (kernel.len() / 2) as i64;
+    let (w, h) = (i64::from(width), i64::from(height));
+    let convolve = |src: &[f32], step: (i64, i64)| {
+        let mut dst = vec![0.0f32; src.len()];
+        for y in 0..h {
+            for x in 0..w {
+                let mut acc = [0.0f32; N];
+                for (ki, &kw) in kernel.iter().enumerate() {
+                    let d = ki as i64 - r;
+                    let sx = (x + d * step.0).clamp(0, w - 1);
+                    let sy = (y + d * step.1).clamp(0, h - 1);
+                    let idx = (sy * w + sx) as usize * N;
+                    for c in 0..N {
+                        acc[c] += kw * src[idx + c];
+                    }
+                }
+                let idx = (y * w + x) as usize * N;
+                dst[idx..idx + N].copy_from_slice(&acc);
+            }
+        }
+        dst
+    };
+    Ok(convolve(&convolve(src, (1, 0)), (0, 1)))
+}
+
+fn pixelate(src: &[f32], width: u32, height: u32, frame: PixelRect, block: u32) -> Vec<f32> {
+    let w = width as usize;
+    let mut out = vec![0.0f32; src.len()];
+    for Range { start: by, end: y1 } in tile_spans(frame.y, height, block) {
+        for Range { start: bx, end: x1 } in tile_spans(frame.x, width, block) {
+            let mut acc = [0.0f32; 4];
+            for y in by..y1 {
+                for x in bx..x1 {
+                    let idx = (y * w + x) * 4;
+                    for c in 0..4 {
+                        acc[c] += src[idx + c];
+                    }
+                }
+            }
+            let n = ((y1 - by) * (x1 - bx)) as f32;
+            let avg = acc.map(|v| v / n);
+            for y in by..y1 {
+                for x in bx..x1 {
+                    let idx = (y * w + x) * 4;
+                    out[idx..idx + 4].copy_from_slice(&avg);
+                }
+            }
+        }
+    }
+    out
+}
+
+fn drop_shadow(
+    src: &[f32],
+    width: u32,
+    height: u32,
+    offset: [i32; 2],
+    radius: u32,
+    sigma: f32,
+    color: [u8; 4],
+) -> WavyteResult<Vec<f32>> {
+    let (w, h) = (i64::from(width), i64::from(height));
+    let mut mask = vec![0.0f32; src.len() / 4];
+    for y in 0..h {
+        let sy = y - i64::from(offset[1]);
+        if sy < 0 || sy >= h {
+            continue;
+        }
+        for x in 0..w {
+            let sx = x - i64::from(offset[0]);
+            if sx < 0 || sx >= w {
+                continue;
+            }
+            mask[(y * w + x) as usize] = src[((sy * w + sx) as usize) * 4 + 3];
+        }
+    }
+    let mask = blur::<1>(&mask, width, height, radius, sigma)?;
+
+    let tint = linear_rgb(color);
+    let strength = f32::from(color[3]) / 255.0;
+    let mut out = vec![0.0f32; src.len()];
+    for ((dst, s), m) in out.chunks_exact_mut(4).zip(src.chunks_exact(4)).zip(&mask) {
+        let shadow_a = m * strength;
+        let inv_src_a = 1.0 - s[3];
+        for c in 0..3 {
+            dst[c] = s[c] + tint[c] * shadow_a * inv_src_a;
+        }
+        dst[3] = s[3] + shadow_a * inv_src_a;
+    }
+    Ok(out)
+}
+
+/// Average `samples` nearest-neighbour taps per pixel at `frame` positions `tap(x, y, k)`.
+fn tap_average(
+    src: &[f32],
+    width: u32,
+    height: u32,
+    frame: PixelRect,
+    samples: u32,
+    tap: impl Fn(f32, f32, u32) -> (f32, f32),
+) -> Vec<f32> {
+    let mut out = vec![0.0f32; src.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let (px, py) = ((x + frame.x) as f32 + 0.5, (y + frame.y) as f32 + 0.5);
+            let mut acc = [0.0f32; 4];
+            for k in 0..samples {
+                let (tx, ty) = tap(px, py, k);
+                let sx = (tx.floor() as i64 - i64::from(frame.x)).clamp(0, i64::from(width) - 1);
+                let sy = (ty.floor() as i64 - i64::from(frame.y)).clamp(0, i64::from(height) - 1);
+                let (sx, sy) = (sx as usize, sy as usize);
+                let i = (sy * width as usize + sx) * 4;
+                for c in 0..4 {
+                    acc[c] += src[i + c];
+                }
+            }
+            let idx = ((y * width + x) as usize) * 4;
+            for c in 0..4 {
+                out[idx + c] = acc[c] / samples as f32;
+            }
+        }
+    }
+    out
+}
+
+/// Linear-light RGB of a straight sRGB RGBA8 color.
+fn linear_rgb(color: [u8; 4]) -> [f32; 3] {
+    [0, 1, 2].map(|c| crate::effects::color_space::srgb_to_linear(f32::from(color[c]) / 255.0))
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/effects/linear_pass.rs"]
+mod tests;
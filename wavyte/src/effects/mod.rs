@@ -1,4 +1,11 @@
 pub mod blur;
+pub mod clip;
+pub mod color_matrix;
+pub mod color_space;
 pub mod composite;
 pub mod fx;
+pub mod linear_pass;
+pub mod pixelate;
+pub mod shadow;
 pub mod transitions;
+pub mod vignette;
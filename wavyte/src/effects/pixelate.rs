@@ -0,0 +1,83 @@
+use std::ops::Range;
+
+use crate::{WavyteError, WavyteResult, foundation::core::PixelRect};
+
+/// Replace each `block` x `block` tile with its average premultiplied color.
+///
+/// Tiles are aligned to the top-left corner of `frame`, the full canvas the surface is a window
+/// of (`x`/`y` place the surface on it); partial tiles at the surface edges average only the
+/// pixels they cover. `block == 1` returns the input unchanged.
+pub fn pixelate_rgba8_premul(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    frame: PixelRect,
+    block: u32,
+) -> WavyteResult<Vec<u8>> {
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|v| v.checked_mul(4))
+        .ok_or_else(|| WavyteError::evaluation("pixelate buffer size overflow"))?;
+    if src.len() != expected_len {
+        return Err(WavyteError::evaluation(
+            "pixelate_rgba8_premul expects src matching width*height*4",
+        ));
+    }
+    if block == 0 {
+        return Err(WavyteError::validation("pixelate block size must be > 0"));
+    }
+    if block == 1 {
+        return Ok(src.to_vec());
+    }
+
+    let w = width as usize;
+    let mut out = vec![0u8; expected_len];
+    for Range { start: by, end: y1 } in tile_spans(frame.y, height, block) {
+        for Range { start: bx, end: x1 } in tile_spans(frame.x, width, block) {
+            let mut acc = [0u64; 4];
+            for y in by..y1 {
+                for x in bx..x1 {
+                    let idx = (y * w + x) * 4;
+                    for c in 0..4 {
+                        acc[c] += u64::from(src[idx + c]);
+                    }
+                }
+            }
+            let n = ((y1 - by) * (x1 - bx)) as u64;
+            let avg = acc.map(|v| ((v + n / 2) / n) as u8);
+
+            for y in by..y1 {
+                for x in bx..x1 {
+                    let idx = (y * w + x) * 4;
+                    out[idx..idx + 4].copy_from_slice(&avg);
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Surface spans of the `block`-sized tiles along one axis of a surface `len` pixels long whose
+/// first pixel sits at canvas coordinate `origin`; tiles cut by the surface edges are shortened.
+pub(crate) fn tile_spans(origin: u32, len: u32, block: u32) -> impl Iterator<Item = Range<usize>> {
+    let (len, block) = (len as usize, block.max(1) as usize);
+    let first = (block - origin as usize % block) % block;
+    let mut start = 0;
+    std::iter::from_fn(move || {
+        (start < len).then(|| {
+            let end = if start == 0 && first > 0 {
+                first
+            } else {
+                start + block
+            }
+            .min(len);
+            let span = start..end;
+            start = end;
+            span
+        })
+    })
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/effects/pixelate.rs"]
+mod tests;
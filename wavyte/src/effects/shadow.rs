@@ -0,0 +1,60 @@
+use crate::{WavyteError, WavyteResult, foundation::math::mul_div255_u8};
+
+/// Composite `src` over a blurred, offset, tinted copy of its alpha.
+///
+/// `color` is straight-alpha RGBA8; its alpha scales the shadow's coverage. Input and output are
+/// premultiplied RGBA8 of the same size. Shadow pixels shifted in from outside the surface are
+/// transparent.
+pub fn drop_shadow_rgba8_premul(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    offset: [i32; 2],
+    radius: u32,
+    sigma: f32,
+    color: [u8; 4],
+) -> WavyteResult<Vec<u8>> {
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|v| v.checked_mul(4))
+        .ok_or_else(|| WavyteError::evaluation("drop shadow buffer size overflow"))?;
+    if src.len() != expected_len {
+        return Err(WavyteError::evaluation(
+            "drop_shadow_rgba8_premul expects src matching width*height*4",
+        ));
+    }
+
+    // Offset single-channel alpha mask.
+    let (w, h) = (width as i64, height as i64);
+    let mut mask = vec![0u8; expected_len / 4];
+    for y in 0..h {
+        let sy = y - i64::from(offset[1]);
+        if sy < 0 || sy >= h {
+            continue;
+        }
+        for x in 0..w {
+            let sx = x - i64::from(offset[0]);
+            if sx < 0 || sx >= w {
+                continue;
+            }
+            mask[(y * w + x) as usize] = src[((sy * w + sx) as usize) * 4 + 3];
+        }
+    }
+    let mask = crate::effects::blur::blur_alpha8(&mask, width, height, radius, sigma)?;
+
+    let mut out = vec![0u8; expected_len];
+    for ((dst, s), m) in out.chunks_exact_mut(4).zip(src.chunks_exact(4)).zip(&mask) {
+        let shadow_a = mul_div255_u8(u16::from(*m), u16::from(color[3]));
+        let inv_src_a = 255 - u16::from(s[3]);
+        for c in 0..3 {
+            let shadow_c = mul_div255_u8(u16::from(color[c]), u16::from(shadow_a));
+            dst[c] = s[c].saturating_add(mul_div255_u8(u16::from(shadow_c), inv_src_a));
+        }
+        dst[3] = s[3].saturating_add(mul_div255_u8(u16::from(shadow_a), inv_src_a));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/effects/shadow.rs"]
+mod tests;
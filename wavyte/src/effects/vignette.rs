@@ -0,0 +1,57 @@
+use crate::{
+    WavyteError, WavyteResult, effects::composite::smoothstep, foundation::core::PixelRect,
+};
+
+/// Blend pixels toward `color` by their distance from the center of `frame`.
+///
+/// `frame` is the full canvas the surface is a window of (`x`/`y` place the surface on it).
+/// Distance is normalized so the center is `0` and the corners are `1`; the blend weight is
+/// `smoothstep(radius, radius + softness, dist)` scaled by `color`'s alpha. `color` is
+/// straight-alpha RGBA8 and only its RGB is mixed in: pixel alpha is preserved, so transparent
+/// regions stay transparent.
+pub fn vignette_rgba8_premul(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    frame: PixelRect,
+    radius: f32,
+    softness: f32,
+    color: [u8; 4],
+) -> WavyteResult<Vec<u8>> {
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|v| v.checked_mul(4))
+        .ok_or_else(|| WavyteError::evaluation("vignette buffer size overflow"))?;
+    if src.len() != expected_len {
+        return Err(WavyteError::evaluation(
+            "vignette_rgba8_premul expects src matching width*height*4",
+        ));
+    }
+
+    let (cx, cy) = (frame.width as f32 / 2.0, frame.height as f32 / 2.0);
+    let half_diag = (cx * cx + cy * cy).sqrt().max(f32::EPSILON);
+    let strength = f32::from(color[3]) / 255.0;
+
+    let mut out = src.to_vec();
+    for (i, px) in out.chunks_exact_mut(4).enumerate() {
+        let x = (i % width as usize + frame.x as usize) as f32 + 0.5;
+        let y = (i / width as usize + frame.y as usize) as f32 + 0.5;
+        let dist = ((x - cx).powi(2) + (y - cy).powi(2)).sqrt() / half_diag;
+        let t = smoothstep(radius, radius + softness, dist) * strength;
+        if t <= 0.0 {
+            continue;
+        }
+        let a = f32::from(px[3]);
+        for (c, &k) in px[..3].iter_mut().zip(color[..3].iter()) {
+            let target = f32::from(k) * a / 255.0;
+            *c = (f32::from(*c) * (1.0 - t) + target * t)
+                .round()
+                .clamp(0.0, a) as u8;
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/effects/vignette.rs"]
+mod tests;
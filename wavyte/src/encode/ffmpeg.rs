@@ -1,30 +1,105 @@
 use std::{
+    collections::BTreeMap,
+    ffi::OsString,
     io::Read,
     path::{Path, PathBuf},
     process::{Child, ChildStdin, Command, Stdio},
 };
 
 use crate::{
+    encode::sink::{FrameSink, SinkConfig, unpremultiply_rgba8_in_place},
+    foundation::core::{Fps, FrameIndex},
     foundation::error::{WavyteError, WavyteResult},
     foundation::math::mul_div255_u16,
     render::backend::FrameRGBA,
 };
 
-/// Configuration for MP4 encoding via the system `ffmpeg` binary.
+/// Video codec used by [`FfmpegEncoder`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VideoCodec {
+    /// H.264 via `libx264` (`.mp4`, `.mov`, `.mkv`).
+    #[default]
+    H264,
+    /// VP9 via `libvpx-vp9` (`.webm`, `.mkv`); supports an alpha channel.
+    Vp9,
+    /// AV1 via `libaom-av1` (`.mp4`, `.webm`, `.mkv`).
+    Av1,
+    /// Lossless QuickTime Animation (`qtrle`, `.mov`); supports an alpha channel.
+    Qtrle,
+}
+
+impl VideoCodec {
+    /// Output file extensions (lowercase, without the dot) whose containers accept this codec.
+    pub fn container_extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::H264 => &["mp4", "mov", "mkv"],
+            Self::Vp9 => &["webm", "mkv"],
+            Self::Av1 => &["mp4", "webm", "mkv"],
+            Self::Qtrle => &["mov"],
+        }
+    }
+
+    /// Whether this codec can carry an alpha channel ([`EncodeConfig::preserve_alpha`]).
+    pub fn supports_alpha(self) -> bool {
+        matches!(self, Self::Vp9 | Self::Qtrle)
+    }
+
+    fn pix_fmt(self, alpha: bool) -> &'static str {
+        match (self, alpha) {
+            (Self::Qtrle, true) => "argb",
+            (Self::Qtrle, false) => "rgb24",
+            (_, true) => "yuva420p",
+            (_, false) => "yuv420p",
+        }
+    }
+
+    fn ffmpeg_encoder(self) -> &'static str {
+        match self {
+            Self::H264 => "libx264",
+            Self::Vp9 => "libvpx-vp9",
+            Self::Av1 => "libaom-av1",
+            Self::Qtrle => "qtrle",
+        }
+    }
+
+    fn ffmpeg_audio_encoder(self) -> &'static str {
+        match self {
+            Self::H264 | Self::Qtrle => "aac",
+            Self::Vp9 | Self::Av1 => "libopus",
+        }
+    }
+}
+
+/// Configuration for video encoding via the system `ffmpeg` binary.
 #[derive(Clone, Debug)]
 pub struct EncodeConfig {
     /// Output video width in pixels.
     pub width: u32,
     /// Output video height in pixels.
     pub height: u32,
-    /// Output frame rate (integer FPS).
-    pub fps: u32,
-    /// Output MP4 path.
+    /// Output frame rate, passed to ffmpeg as the exact `num/den` ratio.
+    pub fps: Fps,
+    /// Output path; its extension must match a container supported by `codec`.
     pub out_path: PathBuf,
     /// Overwrite existing output file when `true`.
     pub overwrite: bool,
     /// Optional external audio input stream configuration.
     pub audio: Option<AudioInputConfig>,
+    /// Container metadata tags (for example `title`, `description`).
+    pub metadata: BTreeMap<String, String>,
+    /// Video codec.
+    pub codec: VideoCodec,
+    /// Encode the alpha channel instead of flattening over the background.
+    ///
+    /// Only [`VideoCodec::Vp9`] (as `yuva420p`) and [`VideoCodec::Qtrle`] (as `argb`) accept it;
+    /// with [`VideoCodec::H264`] or [`VideoCodec::Av1`], [`EncodeConfig::validate`] returns a
+    /// validation error.
+    pub preserve_alpha: bool,
+    /// Presentation index of the first written frame.
+    ///
+    /// Output timestamps start at `first_frame_pts / fps` instead of zero, so chunks of a
+    /// distributed render carry their position on the shared timeline.
+    pub first_frame_pts: FrameIndex,
 }
 
 #[derive(Clone, Debug)]
@@ -39,22 +114,46 @@ pub struct AudioInputConfig {
 }
 
 impl EncodeConfig {
-    /// Validate invariants required by the current MP4 encoder configuration.
+    /// Validate invariants required by the current encoder configuration.
     pub fn validate(&self) -> WavyteResult<()> {
         if self.width == 0 || self.height == 0 {
             return Err(WavyteError::validation(
                 "encode width/height must be non-zero",
             ));
         }
-        if self.fps == 0 {
-            return Err(WavyteError::validation("encode fps must be non-zero"));
+        if self.fps.num == 0 || self.fps.den == 0 {
+            return Err(WavyteError::validation(
+                "encode fps num/den must be non-zero",
+            ));
         }
-        if !self.width.is_multiple_of(2) || !self.height.is_multiple_of(2) {
-            // With the default settings we target yuv420p output for maximum compatibility.
+        if self.codec != VideoCodec::Qtrle
+            && (!self.width.is_multiple_of(2) || !self.height.is_multiple_of(2))
+        {
+            // The 4:2:0 pixel formats used by the YUV codecs need even dimensions.
             return Err(WavyteError::validation(
-                "encode width/height must be even (required for yuv420p mp4 output)",
+                "encode width/height must be even (required for yuv420p output)",
             ));
         }
+        let ext = self
+            .out_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+        let allowed = self.codec.container_extensions();
+        if !allowed.contains(&ext.as_str()) {
+            return Err(WavyteError::validation(format!(
+                "output extension '.{ext}' does not match codec {:?} (expected one of: .{})",
+                self.codec,
+                allowed.join(", .")
+            )));
+        }
+        if self.preserve_alpha && !self.codec.supports_alpha() {
+            return Err(WavyteError::validation(format!(
+                "preserve_alpha requires an alpha-capable codec (Vp9 or Qtrle), not {:?}",
+                self.codec
+            )));
+        }
         if let Some(audio) = &self.audio {
             if audio.sample_rate == 0 {
                 return Err(WavyteError::validation(
@@ -82,7 +181,7 @@ pub fn default_mp4_config(
     out_path: impl Into<PathBuf>,
     width: u32,
     height: u32,
-    fps: u32,
+    fps: Fps,
 ) -> EncodeConfig {
     EncodeConfig {
         width,
@@ -91,6 +190,10 @@ pub fn default_mp4_config(
         out_path: out_path.into(),
         overwrite: true,
         audio: None,
+        metadata: BTreeMap::new(),
+        codec: VideoCodec::H264,
+        preserve_alpha: false,
+        first_frame_pts: FrameIndex(0),
     }
 }
 
@@ -115,7 +218,7 @@ pub fn ensure_parent_dir(path: &Path) -> WavyteResult<()> {
     Ok(())
 }
 
-/// Streaming MP4 encoder that wraps the system `ffmpeg` binary.
+/// Streaming video encoder that wraps the system `ffmpeg` binary.
 ///
 /// This encoder spawns `ffmpeg` and writes raw RGBA frames to stdin.
 /// It is intentionally implemented without linking to FFmpeg libraries to avoid native dependency
@@ -143,7 +246,7 @@ impl FfmpegEncoder {
         }
 
         if !is_ffmpeg_on_path() {
-            return Err(WavyteError::evaluation(
+            return Err(WavyteError::unsupported(
                 "ffmpeg is required for MP4 encoding, but was not found on PATH",
             ));
         }
@@ -155,63 +258,10 @@ impl FfmpegEncoder {
             .stdout(Stdio::null())
             .stderr(Stdio::piped());
 
-        if cfg.overwrite {
-            cmd.arg("-y");
-        } else {
-            cmd.arg("-n");
-        }
-
-        cmd.args([
-            "-loglevel",
-            "error",
-            "-f",
-            "rawvideo",
-            "-pix_fmt",
-            "rgba",
-            "-s",
-            &format!("{}x{}", cfg.width, cfg.height),
-            "-r",
-            &cfg.fps.to_string(),
-            "-i",
-            "pipe:0",
-        ]);
-        if let Some(audio) = &cfg.audio {
-            cmd.args([
-                "-f",
-                "f32le",
-                "-ar",
-                &audio.sample_rate.to_string(),
-                "-ac",
-                &audio.channels.to_string(),
-                "-i",
-            ])
-            .arg(&audio.path)
-            .args([
-                "-c:v",
-                "libx264",
-                "-pix_fmt",
-                "yuv420p",
-                "-c:a",
-                "aac",
-                "-shortest",
-                "-movflags",
-                "+faststart",
-            ]);
-        } else {
-            cmd.args([
-                "-an",
-                "-c:v",
-                "libx264",
-                "-pix_fmt",
-                "yuv420p",
-                "-movflags",
-                "+faststart",
-            ]);
-        }
-        cmd.arg(&cfg.out_path);
+        cmd.args(ffmpeg_args(&cfg));
 
         let mut child = cmd.spawn().map_err(|e| {
-            WavyteError::evaluation(format!(
+            WavyteError::encode(format!(
                 "failed to spawn ffmpeg (is it installed and on PATH?): {e}"
             ))
         })?;
@@ -219,11 +269,11 @@ impl FfmpegEncoder {
         let stdin = child
             .stdin
             .take()
-            .ok_or_else(|| WavyteError::evaluation("failed to open ffmpeg stdin (unexpected)"))?;
+            .ok_or_else(|| WavyteError::encode("failed to open ffmpeg stdin (unexpected)"))?;
         let mut stderr = child
             .stderr
             .take()
-            .ok_or_else(|| WavyteError::evaluation("failed to open ffmpeg stderr (unexpected)"))?;
+            .ok_or_else(|| WavyteError::encode("failed to open ffmpeg stderr (unexpected)"))?;
         let stderr_drain = std::thread::spawn(move || {
             let mut stderr_bytes = Vec::new();
             stderr.read_to_end(&mut stderr_bytes)?;
@@ -243,7 +293,8 @@ impl FfmpegEncoder {
     /// Encode a single rendered frame.
     ///
     /// Wavyte renderers output premultiplied RGBA8 by default; this method can flatten either
-    /// premultiplied or straight-alpha input over `bg_rgba`.
+    /// premultiplied or straight-alpha input over `bg_rgba`. With
+    /// [`EncodeConfig::preserve_alpha`] the frame is passed through as straight alpha instead.
     pub fn encode_frame(&mut self, frame: &FrameRGBA) -> WavyteResult<()> {
         if frame.width != self.cfg.width || frame.height != self.cfg.height {
             return Err(WavyteError::validation(format!(
@@ -258,12 +309,19 @@ impl FfmpegEncoder {
             ));
         }
 
-        flatten_to_opaque_rgba8(
-            &mut self.scratch,
-            &frame.data,
-            frame.premultiplied,
-            self.bg_rgba,
-        )?;
+        if self.cfg.preserve_alpha {
+            self.scratch.copy_from_slice(&frame.data);
+            if frame.premultiplied {
+                unpremultiply_rgba8_in_place(&mut self.scratch);
+            }
+        } else {
+            flatten_to_opaque_rgba8(
+                &mut self.scratch,
+                &frame.data,
+                frame.premultiplied,
+                self.bg_rgba,
+            )?;
+        }
 
         let Some(stdin) = self.stdin.as_mut() else {
             return Err(WavyteError::evaluation(
@@ -273,7 +331,7 @@ impl FfmpegEncoder {
 
         use std::io::Write as _;
         stdin.write_all(&self.scratch).map_err(|e| {
-            WavyteError::evaluation(format!("failed to write frame to ffmpeg stdin: {e}"))
+            WavyteError::encode(format!("failed to write frame to ffmpeg stdin: {e}"))
         })?;
 
         Ok(())
@@ -281,22 +339,26 @@ impl FfmpegEncoder {
 
     /// Finalize the stream and wait for `ffmpeg` to exit.
     pub fn finish(mut self) -> WavyteResult<()> {
+        self.finalize()
+    }
+
+    fn finalize(&mut self) -> WavyteResult<()> {
         drop(self.stdin.take());
 
         let status = self.child.wait().map_err(|e| {
-            WavyteError::evaluation(format!("failed to wait for ffmpeg to finish: {e}"))
+            WavyteError::encode(format!("failed to wait for ffmpeg to finish: {e}"))
         })?;
         let stderr_bytes = match self.stderr_drain.take() {
             Some(handle) => handle
                 .join()
-                .map_err(|_| WavyteError::evaluation("ffmpeg stderr drain thread panicked"))?
-                .map_err(|e| WavyteError::evaluation(format!("ffmpeg stderr read failed: {e}")))?,
+                .map_err(|_| WavyteError::encode("ffmpeg stderr drain thread panicked"))?
+                .map_err(|e| WavyteError::encode(format!("ffmpeg stderr read failed: {e}")))?,
             None => Vec::new(),
         };
 
         if !status.success() {
             let stderr = String::from_utf8_lossy(&stderr_bytes);
-            return Err(WavyteError::evaluation(format!(
+            return Err(WavyteError::encode(format!(
                 "ffmpeg exited with status {}: {}",
                 status,
                 stderr.trim()
@@ -307,6 +369,114 @@ impl FfmpegEncoder {
     }
 }
 
+impl FrameSink for FfmpegEncoder {
+    fn begin(&mut self, cfg: &SinkConfig) -> WavyteResult<()> {
+        if cfg.width != self.cfg.width || cfg.height != self.cfg.height {
+            return Err(WavyteError::validation(format!(
+                "sink size mismatch: got {}x{}, encoder expects {}x{}",
+                cfg.width, cfg.height, self.cfg.width, self.cfg.height
+            )));
+        }
+        if cfg.fps != self.cfg.fps {
+            return Err(WavyteError::validation(format!(
+                "sink fps mismatch: got {}/{}, encoder expects {}/{}",
+                cfg.fps.num, cfg.fps.den, self.cfg.fps.num, self.cfg.fps.den
+            )));
+        }
+        Ok(())
+    }
+
+    fn write_frame(&mut self, _frame: FrameIndex, rgba: &FrameRGBA) -> WavyteResult<()> {
+        self.encode_frame(rgba)
+    }
+
+    fn finish(&mut self) -> WavyteResult<()> {
+        self.finalize()
+    }
+}
+
+/// Build the ffmpeg argument list (excluding the program name) for `cfg`.
+fn ffmpeg_args(cfg: &EncodeConfig) -> Vec<OsString> {
+    fn push(args: &mut Vec<OsString>, items: &[&str]) {
+        args.extend(items.iter().map(OsString::from));
+    }
+
+    let mut args = Vec::new();
+    push(&mut args, &[if cfg.overwrite { "-y" } else { "-n" }]);
+    push(
+        &mut args,
+        &[
+            "-loglevel",
+            "error",
+            "-f",
+            "rawvideo",
+            "-pix_fmt",
+            "rgba",
+            "-s",
+            &format!("{}x{}", cfg.width, cfg.height),
+            "-r",
+            &format!("{}/{}", cfg.fps.num, cfg.fps.den),
+            "-i",
+            "pipe:0",
+        ],
+    );
+    if let Some(audio) = &cfg.audio {
+        push(
+            &mut args,
+            &[
+                "-f",
+                "f32le",
+                "-ar",
+                &audio.sample_rate.to_string(),
+                "-ac",
+                &audio.channels.to_string(),
+                "-i",
+            ],
+        );
+        args.push(audio.path.clone().into_os_string());
+    }
+
+    push(
+        &mut args,
+        &[
+            "-c:v",
+            cfg.codec.ffmpeg_encoder(),
+            "-pix_fmt",
+            cfg.codec.pix_fmt(cfg.preserve_alpha),
+        ],
+    );
+    if matches!(cfg.codec, VideoCodec::Vp9 | VideoCodec::Av1) {
+        // Constant-quality mode for libvpx/libaom (bitrate 0 + crf).
+        push(&mut args, &["-b:v", "0", "-crf", "32"]);
+    }
+    if cfg.audio.is_some() {
+        push(
+            &mut args,
+            &["-c:a", cfg.codec.ffmpeg_audio_encoder(), "-shortest"],
+        );
+    } else {
+        push(&mut args, &["-an"]);
+    }
+    let ext = cfg
+        .out_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    if matches!(ext.as_deref(), Some("mp4" | "mov")) {
+        push(&mut args, &["-movflags", "+faststart"]);
+    }
+    if cfg.first_frame_pts.0 > 0 {
+        let offset_s = cfg.fps.frames_to_secs(cfg.first_frame_pts.0);
+        push(&mut args, &["-output_ts_offset", &format!("{offset_s:.6}")]);
+    }
+    for (key, value) in &cfg.metadata {
+        args.push(OsString::from("-metadata"));
+        args.push(OsString::from(format!("{key}={value}")));
+    }
+    args.push(cfg.out_path.clone().into_os_string());
+    args
+}
+
 fn flatten_to_opaque_rgba8(
     dst: &mut [u8],
     src: &[u8],
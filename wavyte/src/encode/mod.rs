@@ -1 +1,4 @@
 pub mod ffmpeg;
+pub mod sidecar;
+pub mod sink;
+pub mod timecode;
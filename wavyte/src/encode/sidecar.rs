@@ -0,0 +1,145 @@
+use std::path::Path;
+
+use anyhow::Context as _;
+
+use crate::{
+    assets::store::{PreparedAsset, PreparedAssetStore},
+    compile::fingerprint::fingerprint_eval,
+    composition::model::Composition,
+    eval::evaluator::{EvaluatedClipNode, Evaluator},
+    foundation::core::{FrameIndex, FrameRange},
+    foundation::error::{WavyteError, WavyteResult},
+    layout::solver::{LayoutOffsets, intrinsic_size_for_asset_key, resolve_layout_offsets},
+};
+
+/// Axis-aligned on-canvas bounds of a node, in composition pixels.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NodeBounds {
+    /// Left edge.
+    pub x: f64,
+    /// Top edge.
+    pub y: f64,
+    /// Width.
+    pub width: f64,
+    /// Height.
+    pub height: f64,
+}
+
+/// One visible node in a [`FrameMetadata`] record.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NodeMetadata {
+    /// Clip identifier.
+    pub clip_id: String,
+    /// Referenced composition asset key.
+    pub asset: String,
+    /// Absolute z-order.
+    pub z: i32,
+    /// Asset-local to canvas transform as affine coefficients `[a, b, c, d, e, f]`.
+    pub transform: [f64; 6],
+    /// Evaluated opacity in `[0, 1]`.
+    pub opacity: f64,
+    /// Bounding box of the transformed asset (effects such as blur or shadows are not included).
+    pub bounds: NodeBounds,
+}
+
+/// Per-frame description of what is on screen, for overlays synced to a rendered video.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FrameMetadata {
+    /// Timeline frame index.
+    pub frame: FrameIndex,
+    /// [`FrameFingerprint`](crate::FrameFingerprint) of the evaluated frame as 32 hex digits.
+    pub fingerprint: String,
+    /// Visible nodes in paint order (bottom to top).
+    pub nodes: Vec<NodeMetadata>,
+}
+
+/// Describe the visible nodes of `frame` without rendering it.
+///
+/// Also serves tooling such as timeline editors that need each node's world transform and
+/// on-canvas bounds at a frame.
+pub fn frame_metadata(
+    comp: &Composition,
+    frame: FrameIndex,
+    assets: &PreparedAssetStore,
+) -> WavyteResult<FrameMetadata> {
+    comp.validate()?;
+    let layout_offsets = resolve_layout_offsets(comp, assets)?;
+    frame_metadata_with_layout(comp, frame, assets, &layout_offsets)
+}
+
+/// Write a JSON array with one [`FrameMetadata`] object per frame of `range` to `path`.
+pub fn write_frame_metadata_sidecar(
+    comp: &Composition,
+    range: FrameRange,
+    assets: &PreparedAssetStore,
+    path: &Path,
+) -> WavyteResult<()> {
+    comp.validate()?;
+    if range.end.0 > comp.duration.0 {
+        return Err(WavyteError::validation(
+            "metadata sidecar range must be within composition duration",
+        ));
+    }
+    let layout_offsets = resolve_layout_offsets(comp, assets)?;
+    let frames = (range.start.0..range.end.0)
+        .map(|f| frame_metadata_with_layout(comp, FrameIndex(f), assets, &layout_offsets))
+        .collect::<WavyteResult<Vec<_>>>()?;
+
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("failed to create metadata sidecar '{}'", path.display()))?;
+    serde_json::to_writer_pretty(std::io::BufWriter::new(file), &frames)?;
+    Ok(())
+}
+
+fn frame_metadata_with_layout(
+    comp: &Composition,
+    frame: FrameIndex,
+    assets: &PreparedAssetStore,
+    layout_offsets: &LayoutOffsets,
+) -> WavyteResult<FrameMetadata> {
+    let eval = Evaluator::eval_frame_with_layout_unchecked(comp, frame, layout_offsets)?;
+    let fp = fingerprint_eval(&eval);
+    let nodes = eval
+        .nodes
+        .iter()
+        .map(|node| {
+            Ok(NodeMetadata {
+                clip_id: node.clip_id.clone(),
+                asset: node.asset.clone(),
+                z: node.z,
+                transform: node.transform.as_coeffs(),
+                opacity: node.opacity,
+                bounds: node_bounds(node, assets)?,
+            })
+        })
+        .collect::<WavyteResult<Vec<_>>>()?;
+    Ok(FrameMetadata {
+        frame,
+        fingerprint: format!("{:016x}{:016x}", fp.hi, fp.lo),
+        nodes,
+    })
+}
+
+fn node_bounds(node: &EvaluatedClipNode, assets: &PreparedAssetStore) -> WavyteResult<NodeBounds> {
+    // Paths draw in their own coordinates rather than from the local origin.
+    let local = match assets.get(assets.id_for_key(&node.asset)?)? {
+        PreparedAsset::Path(p) => p.paint_bounds(),
+        PreparedAsset::Text(t) => {
+            let (w, h) = intrinsic_size_for_asset_key(&node.asset, assets)?;
+            let y0 = f64::from(t.origin_offset_y);
+            kurbo::Rect::new(0.0, y0, w, y0 + h)
+        }
+        _ => {
+            let (w, h) = intrinsic_size_for_asset_key(&node.asset, assets)?;
+            kurbo::Rect::new(0.0, 0.0, w, h)
+        }
+    };
+    let local = node.content_clip.map_or(local, |c| local.intersect(c));
+    let rect = node.transform.transform_rect_bbox(local);
+    Ok(NodeBounds {
+        x: rect.x0,
+        y: rect.y0,
+        width: rect.width(),
+        height: rect.height(),
+    })
+}
@@ -0,0 +1,951 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+
+use crate::{
+    foundation::core::{Fps, FrameIndex, PixelRect},
+    foundation::error::{WavyteError, WavyteResult},
+    foundation::math::mul_div255_u16,
+    render::backend::FrameRGBA,
+};
+
+/// Stream properties announced to a [`FrameSink`] before the first frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SinkConfig {
+    /// Frame width in pixels.
+    pub width: u32,
+    /// Frame height in pixels.
+    pub height: u32,
+    /// Timeline frame rate.
+    pub fps: Fps,
+}
+
+/// Destination for a stream of rendered frames.
+///
+/// [`crate::render_to_sink`] calls [`FrameSink::begin`] once, then hands over frames in ascending
+/// frame order, then calls [`FrameSink::finish`] once after the last frame has been written.
+pub trait FrameSink {
+    /// Receive stream properties before the first frame. Sinks may reject unsupported configs.
+    fn begin(&mut self, cfg: &SinkConfig) -> WavyteResult<()> {
+        let _ = cfg;
+        Ok(())
+    }
+
+    /// Consume one rendered frame. `frame` is the composition frame index it was rendered at.
+    fn write_frame(&mut self, frame: FrameIndex, rgba: &FrameRGBA) -> WavyteResult<()>;
+
+    /// Consume one rendered frame by value.
+    ///
+    /// The pipeline calls this instead of [`FrameSink::write_frame`] when it no longer needs the
+    /// frame. The default implementation forwards to [`FrameSink::write_frame`].
+    fn write_frame_owned(&mut self, frame: FrameIndex, rgba: FrameRGBA) -> WavyteResult<()> {
+        self.write_frame(frame, &rgba)
+    }
+
+    /// Flush and finalize the sink after the last frame.
+    fn finish(&mut self) -> WavyteResult<()> {
+        Ok(())
+    }
+
+    /// Region of the rendered canvas this sink's output covers, when it crops frames.
+    ///
+    /// Reported in [`crate::RenderStats::crop_rect`] after [`FrameSink::finish`]. Wrapper sinks
+    /// forward their inner sink's value.
+    fn crop_rect(&self) -> Option<PixelRect> {
+        None
+    }
+}
+
+/// Options for [`PngSequenceSink`].
+#[derive(Clone, Debug)]
+pub struct PngSequenceSinkOpts {
+    /// Output directory. Created if it does not exist.
+    pub out_dir: PathBuf,
+    /// File name prefix placed before the zero-padded frame index.
+    pub file_prefix: String,
+    /// Minimum number of digits used for the frame index.
+    pub zero_pad: usize,
+    /// Replace existing files with the same prefix when `true`.
+    pub overwrite: bool,
+}
+
+impl Default for PngSequenceSinkOpts {
+    fn default() -> Self {
+        Self {
+            out_dir: PathBuf::from("frames"),
+            file_prefix: "frame_".to_string(),
+            zero_pad: 6,
+            overwrite: true,
+        }
+    }
+}
+
+/// Lossless frame sink writing one straight-alpha PNG per frame.
+///
+/// Files are named `{file_prefix}{frame:0zero_pad}.png` inside `out_dir`.
+pub struct PngSequenceSink {
+    opts: PngSequenceSinkOpts,
+    scratch: Vec<u8>,
+    frames_written: u64,
+}
+
+impl PngSequenceSink {
+    /// Create the output directory and check it for conflicting files.
+    pub fn new(opts: PngSequenceSinkOpts) -> WavyteResult<Self> {
+        if opts.file_prefix.contains(['/', '\\']) {
+            return Err(WavyteError::validation(
+                "png sequence file_prefix must not contain path separators",
+            ));
+        }
+
+        std::fs::create_dir_all(&opts.out_dir).with_context(|| {
+            format!(
+                "failed to create png sequence directory '{}'",
+                opts.out_dir.display()
+            )
+        })?;
+
+        if !opts.overwrite
+            && let Some(existing) = find_conflicting_file(&opts.out_dir, &opts.file_prefix)?
+        {
+            return Err(WavyteError::validation(format!(
+                "png sequence directory '{}' already contains '{}' (enable overwrite to replace)",
+                opts.out_dir.display(),
+                existing
+            )));
+        }
+
+        Ok(Self {
+            opts,
+            scratch: Vec::new(),
+            frames_written: 0,
+        })
+    }
+
+    /// Path of the PNG file written for `frame`.
+    pub fn path_for(&self, frame: FrameIndex) -> PathBuf {
+        self.opts.out_dir.join(format!(
+            "{}{:0width$}.png",
+            self.opts.file_prefix,
+            frame.0,
+            width = self.opts.zero_pad
+        ))
+    }
+
+    /// Number of frames written so far.
+    pub fn frames_written(&self) -> u64 {
+        self.frames_written
+    }
+}
+
+impl FrameSink for PngSequenceSink {
+    fn write_frame(&mut self, frame: FrameIndex, rgba: &FrameRGBA) -> WavyteResult<()> {
+        let expected = (rgba.width as usize) * (rgba.height as usize) * 4;
+        if rgba.data.len() != expected {
+            return Err(WavyteError::validation(
+                "frame.data size mismatch with width*height*4",
+            ));
+        }
+
+        let path = self.path_for(frame);
+        if !self.opts.overwrite && path.exists() {
+            return Err(WavyteError::validation(format!(
+                "output file '{}' already exists",
+                path.display()
+            )));
+        }
+
+        self.scratch.clear();
+        self.scratch.extend_from_slice(&rgba.data);
+        if rgba.premultiplied {
+            unpremultiply_rgba8_in_place(&mut self.scratch);
+        }
+
+        image::save_buffer_with_format(
+            &path,
+            &self.scratch,
+            rgba.width,
+            rgba.height,
+            image::ExtendedColorType::Rgba8,
+            image::ImageFormat::Png,
+        )
+        .with_context(|| format!("failed to write png '{}'", path.display()))?;
+
+        self.frames_written += 1;
+        Ok(())
+    }
+}
+
+/// Options for [`GifSink`].
+#[derive(Clone, Debug)]
+pub struct GifSinkOpts {
+    /// Output `.gif` path. Parent directories are created if needed.
+    pub out_path: PathBuf,
+    /// Replace an existing file at `out_path` when `true`.
+    pub overwrite: bool,
+    /// Loop the animation forever instead of playing it once.
+    pub repeat: bool,
+    /// NeuQuant sampling factor in `1..=30` (lower is slower and more accurate).
+    pub quant_sample_factor: i32,
+    /// Maximum number of frames (evenly spaced) used to train the palette.
+    pub palette_sample_frames: usize,
+}
+
+impl Default for GifSinkOpts {
+    fn default() -> Self {
+        Self {
+            out_path: PathBuf::from("out.gif"),
+            overwrite: true,
+            repeat: true,
+            quant_sample_factor: 10,
+            palette_sample_frames: 16,
+        }
+    }
+}
+
+/// Frame sink writing an animated GIF without external tools.
+///
+/// Frames are buffered until [`FrameSink::finish`], where one global 255-color palette is built
+/// with NeuQuant over a sample of the frames and every frame is written as indexed pixels.
+/// Pixels with alpha below 128 map to a reserved transparent index. Frame delays follow the
+/// stream fps in GIF's centisecond units, rounded cumulatively so the total duration does not
+/// drift. Any dimensions up to 65535 are accepted.
+pub struct GifSink {
+    opts: GifSinkOpts,
+    cfg: Option<SinkConfig>,
+    frames: Vec<Vec<u8>>,
+}
+
+impl GifSink {
+    /// Size of the palette trained from frame content; index 255 is reserved for transparency.
+    const PALETTE_COLORS: usize = 255;
+    const TRANSPARENT_INDEX: u8 = 255;
+
+    /// Validate `opts` and check the output path.
+    pub fn new(opts: GifSinkOpts) -> WavyteResult<Self> {
+        if !(1..=30).contains(&opts.quant_sample_factor) {
+            return Err(WavyteError::validation(
+                "gif quant_sample_factor must be in [1, 30]",
+            ));
+        }
+        if opts.palette_sample_frames == 0 {
+            return Err(WavyteError::validation(
+                "gif palette_sample_frames must be > 0",
+            ));
+        }
+        if !opts.overwrite && opts.out_path.exists() {
+            return Err(WavyteError::validation(format!(
+                "output file '{}' already exists",
+                opts.out_path.display()
+            )));
+        }
+        Ok(Self {
+            opts,
+            cfg: None,
+            frames: Vec::new(),
+        })
+    }
+
+    fn build_palette(&self) -> color_quant::NeuQuant {
+        let step = self
+            .frames
+            .len()
+            .div_ceil(self.opts.palette_sample_frames)
+            .max(1);
+        let mut samples = Vec::new();
+        for frame in self.frames.iter().step_by(step) {
+            samples.extend(
+                frame
+                    .chunks_exact(4)
+                    .filter(|px| px[3] >= 128)
+                    .flat_map(|px| [px[0], px[1], px[2], 255]),
+            );
+        }
+        if samples.is_empty() {
+            samples.extend_from_slice(&[0, 0, 0, 255]);
+        }
+        color_quant::NeuQuant::new(
+            self.opts.quant_sample_factor,
+            Self::PALETTE_COLORS,
+            &samples,
+        )
+    }
+}
+
+impl FrameSink for GifSink {
+    fn begin(&mut self, cfg: &SinkConfig) -> WavyteResult<()> {
+        if cfg.width == 0
+            || cfg.height == 0
+            || cfg.width > u32::from(u16::MAX)
+            || cfg.height > u32::from(u16::MAX)
+        {
+            return Err(WavyteError::validation(format!(
+                "gif dimensions must be in 1..=65535, got {}x{}",
+                cfg.width, cfg.height
+            )));
+        }
+        self.cfg = Some(*cfg);
+        self.frames.clear();
+        Ok(())
+    }
+
+    fn write_frame(&mut self, frame: FrameIndex, rgba: &FrameRGBA) -> WavyteResult<()> {
+        self.write_frame_owned(frame, rgba.clone())
+    }
+
+    fn write_frame_owned(&mut self, _frame: FrameIndex, mut rgba: FrameRGBA) -> WavyteResult<()> {
+        let cfg = self
+            .cfg
+            .ok_or_else(|| WavyteError::evaluation("GifSink received a frame before begin"))?;
+        if rgba.width != cfg.width
+            || rgba.height != cfg.height
+            || rgba.data.len() != (cfg.width as usize) * (cfg.height as usize) * 4
+        {
+            return Err(WavyteError::validation(format!(
+                "frame size mismatch: got {}x{}, expected {}x{}",
+                rgba.width, rgba.height, cfg.width, cfg.height
+            )));
+        }
+        if rgba.premultiplied {
+            unpremultiply_rgba8_in_place(&mut rgba.data);
+        }
+        self.frames.push(rgba.data);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> WavyteResult<()> {
+        let cfg = self
+            .cfg
+            .ok_or_else(|| WavyteError::evaluation("GifSink finished before begin"))?;
+        let gif_err = |e: gif::EncodingError| WavyteError::encode(format!("gif encoding: {e}"));
+
+        let quant = self.build_palette();
+        let mut palette = quant.color_map_rgb();
+        palette.resize(256 * 3, 0);
+
+        let path = &self.opts.out_path;
+        crate::encode::ffmpeg::ensure_parent_dir(path)?;
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("failed to create gif '{}'", path.display()))?;
+        let mut encoder = gif::Encoder::new(
+            std::io::BufWriter::new(file),
+            cfg.width as u16,
+            cfg.height as u16,
+            &palette,
+        )
+        .map_err(gif_err)?;
+        if self.opts.repeat {
+            encoder.set_repeat(gif::Repeat::Infinite).map_err(gif_err)?;
+        }
+
+        // Centisecond timestamps of frame `i` are rounded from the exact time so the sum of
+        // delays tracks the real duration.
+        let centis = |i: u64| {
+            (i * 100 * u64::from(cfg.fps.den) + u64::from(cfg.fps.num) / 2) / u64::from(cfg.fps.num)
+        };
+        for (i, data) in std::mem::take(&mut self.frames).into_iter().enumerate() {
+            let indices = data
+                .chunks_exact(4)
+                .map(|px| {
+                    if px[3] < 128 {
+                        Self::TRANSPARENT_INDEX
+                    } else {
+                        quant.index_of(&[px[0], px[1], px[2], 255]) as u8
+                    }
+                })
+                .collect::<Vec<u8>>();
+            let i = i as u64;
+            let delay = (centis(i + 1) - centis(i)).min(u64::from(u16::MAX)) as u16;
+            let frame = gif::Frame {
+                width: cfg.width as u16,
+                height: cfg.height as u16,
+                delay,
+                dispose: gif::DisposalMethod::Background,
+                transparent: Some(Self::TRANSPARENT_INDEX),
+                buffer: std::borrow::Cow::Owned(indices),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&frame).map_err(gif_err)?;
+        }
+        use std::io::Write as _;
+        encoder
+            .into_inner()
+            .map_err(gif_err)?
+            .flush()
+            .with_context(|| format!("failed to write gif '{}'", path.display()))?;
+        Ok(())
+    }
+}
+
+/// Byte layout written by [`RawSink`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RawFormat {
+    /// Straight-alpha RGBA8 frames back to back, with no header or framing.
+    #[default]
+    Rgba,
+    /// YUV4MPEG2 stream: one header, then `FRAME` + planar 8-bit YUV 4:2:0 per frame.
+    ///
+    /// Colors are BT.601 limited range, alpha is flattened over black, and odd dimensions use
+    /// rounded-up chroma planes.
+    Y4m,
+}
+
+/// Frame sink writing uncompressed frames to any [`Write`](std::io::Write) target.
+///
+/// Intended for piping into external tools (stdout, a named pipe, a socket) without
+/// re-encoding. The stream layout is fixed by [`RawFormat`]; for `Y4m` the header carrying size,
+/// frame rate, pixel format and color range is written once in [`FrameSink::begin`].
+pub struct RawSink<W: std::io::Write> {
+    out: W,
+    format: RawFormat,
+    cfg: Option<SinkConfig>,
+    scratch: Vec<u8>,
+    frames_written: u64,
+}
+
+impl<W: std::io::Write> RawSink<W> {
+    /// Write frames to `out` using `format`.
+    pub fn new(out: W, format: RawFormat) -> Self {
+        Self {
+            out,
+            format,
+            cfg: None,
+            scratch: Vec::new(),
+            frames_written: 0,
+        }
+    }
+
+    /// Number of frames written so far.
+    pub fn frames_written(&self) -> u64 {
+        self.frames_written
+    }
+
+    /// Consume the sink and return the writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn write_all(&mut self, bytes: &[u8]) -> WavyteResult<()> {
+        self.out
+            .write_all(bytes)
+            .map_err(|e| WavyteError::io(format!("raw sink write failed: {e}")))
+    }
+}
+
+/// Convert RGBA8 to planar BT.601 limited-range YUV 4:2:0, flattening alpha over black.
+pub(crate) fn rgba_to_yuv420(rgba: &[u8], premultiplied: bool, width: u32, height: u32) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let (cw, ch) = (w.div_ceil(2), h.div_ceil(2));
+    let rgb = |x: usize, y: usize| -> [i32; 3] {
+        let px = &rgba[(y * w + x) * 4..(y * w + x) * 4 + 4];
+        let a = u16::from(px[3]);
+        let c = |v: u8| {
+            if premultiplied {
+                i32::from(v)
+            } else {
+                i32::from(mul_div255_u16(u16::from(v), a))
+            }
+        };
+        [c(px[0]), c(px[1]), c(px[2])]
+    };
+
+    let mut out = Vec::with_capacity(w * h + 2 * cw * ch);
+    for y in 0..h {
+        for x in 0..w {
+            let [r, g, b] = rgb(x, y);
+            out.push((((66 * r + 129 * g + 25 * b + 128) >> 8) + 16) as u8);
+        }
+    }
+    let mut u_plane = Vec::with_capacity(cw * ch);
+    let mut v_plane = Vec::with_capacity(cw * ch);
+    for cy in 0..ch {
+        for cx in 0..cw {
+            let mut sum = [0i32; 3];
+            let mut n = 0;
+            for y in (cy * 2)..(cy * 2 + 2).min(h) {
+                for x in (cx * 2)..(cx * 2 + 2).min(w) {
+                    let px = rgb(x, y);
+                    for (s, v) in sum.iter_mut().zip(px) {
+                        *s += v;
+                    }
+                    n += 1;
+                }
+            }
+            let [r, g, b] = sum.map(|s| (s + n / 2) / n);
+            u_plane.push((((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128) as u8);
+            v_plane.push((((112 * r - 94 * g - 18 * b + 128) >> 8) + 128) as u8);
+        }
+    }
+    out.extend_from_slice(&u_plane);
+    out.extend_from_slice(&v_plane);
+    out
+}
+
+impl<W: std::io::Write> FrameSink for RawSink<W> {
+    fn begin(&mut self, cfg: &SinkConfig) -> WavyteResult<()> {
+        self.cfg = Some(*cfg);
+        if self.format == RawFormat::Y4m {
+            let header = format!(
+                "YUV4MPEG2 W{} H{} F{}:{} Ip A1:1 C420jpeg XCOLORRANGE=LIMITED\n",
+                cfg.width, cfg.height, cfg.fps.num, cfg.fps.den
+            );
+            self.write_all(header.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn write_frame(&mut self, _frame: FrameIndex, rgba: &FrameRGBA) -> WavyteResult<()> {
+        let cfg = self
+            .cfg
+            .ok_or_else(|| WavyteError::evaluation("RawSink received a frame before begin"))?;
+        if rgba.width != cfg.width
+            || rgba.height != cfg.height
+            || rgba.data.len() != (cfg.width as usize) * (cfg.height as usize) * 4
+        {
+            return Err(WavyteError::validation(format!(
+                "frame size mismatch: got {}x{}, expected {}x{}",
+                rgba.width, rgba.height, cfg.width, cfg.height
+            )));
+        }
+
+        match self.format {
+            RawFormat::Rgba => {
+                let mut scratch = std::mem::take(&mut self.scratch);
+                scratch.clear();
+                scratch.extend_from_slice(&rgba.data);
+                if rgba.premultiplied {
+                    unpremultiply_rgba8_in_place(&mut scratch);
+                }
+                let res = self.write_all(&scratch);
+                self.scratch = scratch;
+                res?;
+            }
+            RawFormat::Y4m => {
+                self.write_all(b"FRAME\n")?;
+                let yuv = rgba_to_yuv420(&rgba.data, rgba.premultiplied, rgba.width, rgba.height);
+                self.write_all(&yuv)?;
+            }
+        }
+        self.frames_written += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> WavyteResult<()> {
+        self.out
+            .flush()
+            .map_err(|e| WavyteError::io(format!("raw sink flush failed: {e}")))
+    }
+}
+
+/// Frame sink forwarding each frame by value to a closure.
+///
+/// Useful for piping frames into custom encoders without buffering the whole range. Errors
+/// returned by the closure abort the render.
+pub struct CallbackSink<F>
+where
+    F: FnMut(FrameIndex, FrameRGBA) -> WavyteResult<()>,
+{
+    callback: F,
+    config: Option<SinkConfig>,
+}
+
+impl<F> CallbackSink<F>
+where
+    F: FnMut(FrameIndex, FrameRGBA) -> WavyteResult<()>,
+{
+    /// Wrap `callback` as a frame sink.
+    pub fn new(callback: F) -> Self {
+        Self {
+            callback,
+            config: None,
+        }
+    }
+
+    /// Stream configuration received in [`FrameSink::begin`], if any.
+    pub fn config(&self) -> Option<SinkConfig> {
+        self.config
+    }
+
+    /// Consume the sink and return the wrapped closure.
+    pub fn into_inner(self) -> F {
+        self.callback
+    }
+}
+
+impl<F> FrameSink for CallbackSink<F>
+where
+    F: FnMut(FrameIndex, FrameRGBA) -> WavyteResult<()>,
+{
+    fn begin(&mut self, cfg: &SinkConfig) -> WavyteResult<()> {
+        self.config = Some(*cfg);
+        Ok(())
+    }
+
+    fn write_frame(&mut self, frame: FrameIndex, rgba: &FrameRGBA) -> WavyteResult<()> {
+        (self.callback)(frame, rgba.clone())
+    }
+
+    fn write_frame_owned(&mut self, frame: FrameIndex, rgba: FrameRGBA) -> WavyteResult<()> {
+        (self.callback)(frame, rgba)
+    }
+}
+
+/// Options for [`render_on_checkerboard`] and [`CheckerboardSink`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckerboardOpts {
+    /// Edge length of one checkerboard cell in pixels. `0` is treated as `1`.
+    pub cell_px: u32,
+    /// RGB of the cell at the top-left corner.
+    pub light: [u8; 3],
+    /// RGB of the alternating cells.
+    pub dark: [u8; 3],
+}
+
+impl Default for CheckerboardOpts {
+    fn default() -> Self {
+        Self {
+            cell_px: 8,
+            light: [204, 204, 204],
+            dark: [153, 153, 153],
+        }
+    }
+}
+
+/// Composite `frame` over a gray checkerboard, producing an opaque preview frame.
+///
+/// Transparent regions show the checkerboard, opaque regions are unchanged. This is a preview
+/// aid for inspecting alpha and is never applied by the render pipeline itself.
+pub fn render_on_checkerboard(frame: &FrameRGBA, opts: &CheckerboardOpts) -> FrameRGBA {
+    let cell = opts.cell_px.max(1);
+    let mut data = frame.data.clone();
+    for (i, px) in data.chunks_exact_mut(4).enumerate() {
+        let x = (i as u32) % frame.width.max(1);
+        let y = (i as u32) / frame.width.max(1);
+        let bg = if ((x / cell) + (y / cell)).is_multiple_of(2) {
+            opts.light
+        } else {
+            opts.dark
+        };
+        let a = u16::from(px[3]);
+        for (c, &b) in px[..3].iter_mut().zip(bg.iter()) {
+            let src = if frame.premultiplied {
+                u16::from(*c)
+            } else {
+                (u16::from(*c) * a + 127) / 255
+            };
+            *c = (src + (u16::from(b) * (255 - a) + 127) / 255).min(255) as u8;
+        }
+        px[3] = 255;
+    }
+    FrameRGBA {
+        width: frame.width,
+        height: frame.height,
+        data,
+        premultiplied: true,
+    }
+}
+
+/// Frame sink wrapper that composites every frame over a checkerboard before forwarding it.
+///
+/// Intended for previews (for example wrapping a [`PngSequenceSink`]) where transparent regions
+/// should be visibly distinguishable from black or white content.
+pub struct CheckerboardSink<S: FrameSink> {
+    inner: S,
+    opts: CheckerboardOpts,
+}
+
+impl<S: FrameSink> CheckerboardSink<S> {
+    /// Wrap `inner`, compositing frames over a checkerboard described by `opts`.
+    pub fn new(inner: S, opts: CheckerboardOpts) -> Self {
+        Self { inner, opts }
+    }
+
+    /// Consume the wrapper and return the inner sink.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: FrameSink> FrameSink for CheckerboardSink<S> {
+    fn begin(&mut self, cfg: &SinkConfig) -> WavyteResult<()> {
+        self.inner.begin(cfg)
+    }
+
+    fn write_frame(&mut self, frame: FrameIndex, rgba: &FrameRGBA) -> WavyteResult<()> {
+        self.inner
+            .write_frame_owned(frame, render_on_checkerboard(rgba, &self.opts))
+    }
+
+    fn finish(&mut self) -> WavyteResult<()> {
+        self.inner.finish()
+    }
+
+    fn crop_rect(&self) -> Option<PixelRect> {
+        self.inner.crop_rect()
+    }
+}
+
+/// Frame sink wrapper that crops every frame to the alpha bounding box of the whole range.
+///
+/// Frames are spilled to a temporary file while the union of all pixels with alpha above the
+/// threshold is accumulated, so memory stays at one frame regardless of render length. On
+/// [`FrameSink::finish`] every frame is streamed back and forwarded cropped to that (constant)
+/// box, grown outward to even width and height where the frame allows so 4:2:0 encoders accept
+/// it, with the inner sink's [`SinkConfig`] sized accordingly. Intended for alpha-preserving
+/// outputs such as [`PngSequenceSink`] (sticker-style assets). If every frame is fully
+/// transparent, frames are forwarded uncropped.
+pub struct AlphaTrimSink<S: FrameSink> {
+    inner: S,
+    alpha_threshold: u8,
+    spill_dir: PathBuf,
+    cfg: Option<SinkConfig>,
+    spill: Option<FrameSpill>,
+    bounds: Option<[u32; 4]>,
+    crop: Option<PixelRect>,
+}
+
+/// Raw frames written to disk by [`AlphaTrimSink`], in write order.
+struct FrameSpill {
+    path: PathBuf,
+    writer: Option<std::io::BufWriter<std::fs::File>>,
+    frames: Vec<(FrameIndex, u32, u32, bool)>,
+}
+
+impl FrameSpill {
+    fn create(dir: &Path) -> WavyteResult<Self> {
+        static NEXT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+        let n = NEXT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let path = dir.join(format!("wavyte_alpha_trim_{}_{n}.rgba", std::process::id()));
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .with_context(|| format!("failed to create spill file '{}'", path.display()))?;
+        Ok(Self {
+            path,
+            writer: Some(std::io::BufWriter::new(file)),
+            frames: Vec::new(),
+        })
+    }
+
+    fn push(&mut self, frame: FrameIndex, rgba: &FrameRGBA) -> WavyteResult<()> {
+        use std::io::Write as _;
+        let writer = self
+            .writer
+            .as_mut()
+            .ok_or_else(|| WavyteError::evaluation("frame spill is already closed"))?;
+        writer
+            .write_all(&rgba.data)
+            .map_err(|e| WavyteError::io(format!("spill write failed: {e}")))?;
+        self.frames
+            .push((frame, rgba.width, rgba.height, rgba.premultiplied));
+        Ok(())
+    }
+
+    /// Read the frames back in order, handing each to `f`.
+    fn drain(
+        &mut self,
+        mut f: impl FnMut(FrameIndex, FrameRGBA) -> WavyteResult<()>,
+    ) -> WavyteResult<()> {
+        use std::io::Read as _;
+        if let Some(mut writer) = self.writer.take() {
+            std::io::Write::flush(&mut writer)
+                .map_err(|e| WavyteError::io(format!("spill flush failed: {e}")))?;
+        }
+        let file = std::fs::File::open(&self.path)
+            .with_context(|| format!("failed to open spill file '{}'", self.path.display()))?;
+        let mut reader = std::io::BufReader::new(file);
+        for (frame, width, height, premultiplied) in std::mem::take(&mut self.frames) {
+            let mut data = vec![0u8; width as usize * height as usize * 4];
+            reader
+                .read_exact(&mut data)
+                .map_err(|e| WavyteError::io(format!("spill read failed: {e}")))?;
+            f(
+                frame,
+                FrameRGBA {
+                    width,
+                    height,
+                    data,
+                    premultiplied,
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for FrameSpill {
+    fn drop(&mut self) {
+        self.writer = None;
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+impl<S: FrameSink> AlphaTrimSink<S> {
+    /// Wrap `inner`; pixels with alpha greater than `alpha_threshold` count as content.
+    pub fn new(inner: S, alpha_threshold: u8) -> Self {
+        Self {
+            inner,
+            alpha_threshold,
+            spill_dir: std::env::temp_dir(),
+            cfg: None,
+            spill: None,
+            bounds: None,
+            crop: None,
+        }
+    }
+
+    /// Spill buffered frames into `dir` instead of the system temporary directory.
+    pub fn with_spill_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.spill_dir = dir.into();
+        self
+    }
+
+    /// Consume the wrapper and return the inner sink.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn extend_bounds(&mut self, rgba: &FrameRGBA) {
+        let w = rgba.width as usize;
+        for (i, px) in rgba.data.chunks_exact(4).enumerate() {
+            if px[3] <= self.alpha_threshold {
+                continue;
+            }
+            let (x, y) = ((i % w) as u32, (i / w) as u32);
+            self.bounds = Some(match self.bounds {
+                None => [x, y, x, y],
+                Some([x0, y0, x1, y1]) => [x0.min(x), y0.min(y), x1.max(x), y1.max(y)],
+            });
+        }
+    }
+}
+
+/// Grow the span `[start, start + len)` by one pixel to an even length, rightward when it fits
+/// inside `limit` and leftward otherwise; left odd only when it already covers an odd `limit`.
+fn even_span(start: u32, len: u32, limit: u32) -> (u32, u32) {
+    if len.is_multiple_of(2) {
+        (start, len)
+    } else if start + len < limit {
+        (start, len + 1)
+    } else if start > 0 {
+        (start - 1, len + 1)
+    } else {
+        (start, len)
+    }
+}
+
+/// Copy the `rect` region out of `rgba`.
+pub fn crop_frame(rgba: &FrameRGBA, rect: PixelRect) -> WavyteResult<FrameRGBA> {
+    if rect.x.saturating_add(rect.width) > rgba.width
+        || rect.y.saturating_add(rect.height) > rgba.height
+    {
+        return Err(WavyteError::validation(format!(
+            "crop rect {rect:?} exceeds {}x{} frame",
+            rgba.width, rgba.height
+        )));
+    }
+    let stride = rgba.width as usize * 4;
+    let row_len = rect.width as usize * 4;
+    let mut data = Vec::with_capacity(row_len * rect.height as usize);
+    for y in rect.y..rect.y + rect.height {
+        let start = y as usize * stride + rect.x as usize * 4;
+        data.extend_from_slice(&rgba.data[start..start + row_len]);
+    }
+    Ok(FrameRGBA {
+        width: rect.width,
+        height: rect.height,
+        data,
+        premultiplied: rgba.premultiplied,
+    })
+}
+
+impl<S: FrameSink> FrameSink for AlphaTrimSink<S> {
+    fn begin(&mut self, cfg: &SinkConfig) -> WavyteResult<()> {
+        self.cfg = Some(*cfg);
+        self.spill = Some(FrameSpill::create(&self.spill_dir)?);
+        self.bounds = None;
+        self.crop = None;
+        Ok(())
+    }
+
+    fn write_frame(&mut self, frame: FrameIndex, rgba: &FrameRGBA) -> WavyteResult<()> {
+        self.extend_bounds(rgba);
+        self.spill
+            .as_mut()
+            .ok_or_else(|| WavyteError::evaluation("AlphaTrimSink written before begin"))?
+            .push(frame, rgba)
+    }
+
+    fn finish(&mut self) -> WavyteResult<()> {
+        let cfg = self
+            .cfg
+            .ok_or_else(|| WavyteError::evaluation("AlphaTrimSink finished before begin"))?;
+        let rect = match self.bounds {
+            Some([x0, y0, x1, y1]) => {
+                let (x, width) = even_span(x0, x1 - x0 + 1, cfg.width);
+                let (y, height) = even_span(y0, y1 - y0 + 1, cfg.height);
+                PixelRect {
+                    x,
+                    y,
+                    width,
+                    height,
+                }
+            }
+            None => PixelRect {
+                x: 0,
+                y: 0,
+                width: cfg.width,
+                height: cfg.height,
+            },
+        };
+        self.crop = Some(rect);
+        self.inner.begin(&SinkConfig {
+            width: rect.width,
+            height: rect.height,
+            fps: cfg.fps,
+        })?;
+        let mut spill = self
+            .spill
+            .take()
+            .ok_or_else(|| WavyteError::evaluation("AlphaTrimSink finished before begin"))?;
+        let inner = &mut self.inner;
+        spill.drain(|frame, rgba| inner.write_frame_owned(frame, crop_frame(&rgba, rect)?))?;
+        drop(spill);
+        self.inner.finish()
+    }
+
+    fn crop_rect(&self) -> Option<PixelRect> {
+        self.crop
+    }
+}
+
+fn find_conflicting_file(dir: &Path, prefix: &str) -> WavyteResult<Option<String>> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read directory '{}'", dir.display()))?;
+    for entry in entries {
+        let entry =
+            entry.with_context(|| format!("failed to read directory '{}'", dir.display()))?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with(prefix) && name.ends_with(".png") {
+            return Ok(Some(name));
+        }
+    }
+    Ok(None)
+}
+
+pub(crate) fn unpremultiply_rgba8_in_place(rgba: &mut [u8]) {
+    for px in rgba.chunks_exact_mut(4) {
+        let a = px[3] as u16;
+        if a == 0 || a == 255 {
+            continue;
+        }
+        for c in &mut px[..3] {
+            *c = ((*c as u16 * 255 + a / 2) / a).min(255) as u8;
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/encode/sink.rs"]
+mod tests;
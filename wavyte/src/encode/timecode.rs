@@ -0,0 +1,283 @@
+use std::sync::Arc;
+
+use crate::{
+    assets::store::{TextBrushRgba8, TextLayoutEngine},
+    effects::composite::over_at_in_place,
+    encode::sink::{FrameSink, SinkConfig},
+    foundation::core::{Fps, FrameIndex, PixelRect},
+    foundation::error::{WavyteError, WavyteResult},
+    render::backend::FrameRGBA,
+};
+
+/// Frame corner used by [`TimecodeStyle`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimecodeCorner {
+    /// Top-left corner.
+    TopLeft,
+    /// Top-right corner.
+    TopRight,
+    /// Bottom-left corner.
+    #[default]
+    BottomLeft,
+    /// Bottom-right corner.
+    BottomRight,
+}
+
+/// Appearance of a burned-in timecode (see [`TimecodeSink`]).
+#[derive(Clone, Debug)]
+pub struct TimecodeStyle {
+    /// Font file bytes (TTF/OTF) used to lay out the timecode.
+    pub font_bytes: Arc<Vec<u8>>,
+    /// Font size in pixels.
+    pub size_px: f32,
+    /// Text color (RGBA8, straight alpha).
+    pub color: [u8; 4],
+    /// Color of the box behind the text (RGBA8, straight alpha).
+    pub background: [u8; 4],
+    /// Corner the box is anchored to.
+    pub corner: TimecodeCorner,
+    /// Distance between the box and the frame edges, in pixels.
+    pub margin_px: u32,
+    /// Padding between the text and the box edges, in pixels.
+    pub padding_px: u32,
+    /// Use drop-frame counting (`HH:MM:SS;FF`) for 29.97/59.94 fps. Ignored for other rates.
+    pub drop_frame: bool,
+}
+
+impl TimecodeStyle {
+    /// Default styling (white on translucent black, bottom-left) using `font_bytes`.
+    pub fn new(font_bytes: impl Into<Arc<Vec<u8>>>) -> Self {
+        Self {
+            font_bytes: font_bytes.into(),
+            size_px: 24.0,
+            color: [255, 255, 255, 255],
+            background: [0, 0, 0, 160],
+            corner: TimecodeCorner::default(),
+            margin_px: 16,
+            padding_px: 6,
+            drop_frame: false,
+        }
+    }
+}
+
+/// Format `frame` as SMPTE timecode at `fps`.
+///
+/// The frame field counts up to the nominal (rounded) rate. With `drop_frame` and an NTSC rate
+/// (30000/1001 or 60000/1001), frame numbers 0-1 (0-3 at 59.94) are skipped at the start of every
+/// minute except each tenth, and `;` separates the frame field. Hours wrap at 24.
+pub fn format_timecode(frame: FrameIndex, fps: Fps, drop_frame: bool) -> String {
+    let nominal = (u64::from(fps.num) + u64::from(fps.den) / 2) / u64::from(fps.den).max(1);
+    let nominal = nominal.max(1);
+    let drop = if drop_frame && fps.den == 1001 && (nominal == 30 || nominal == 60) {
+        nominal / 15
+    } else {
+        0
+    };
+
+    let mut f = frame.0;
+    if drop > 0 {
+        let per_10_min = nominal * 600 - drop * 9;
+        let per_min = nominal * 60 - drop;
+        let tens = f / per_10_min;
+        let rem = f % per_10_min;
+        f += drop * 9 * tens;
+        if rem > drop {
+            f += drop * ((rem - drop) / per_min);
+        }
+    }
+
+    let ff = f % nominal;
+    let total_secs = f / nominal;
+    let sep = if drop > 0 { ';' } else { ':' };
+    format!(
+        "{:02}:{:02}:{:02}{sep}{:02}",
+        (total_secs / 3600) % 24,
+        (total_secs / 60) % 60,
+        total_secs % 60,
+        ff
+    )
+}
+
+/// Burn the timecode of `frame` into `rgba` according to `style`.
+pub fn burn_in_timecode(
+    rgba: &mut FrameRGBA,
+    frame: FrameIndex,
+    fps: Fps,
+    style: &TimecodeStyle,
+) -> WavyteResult<()> {
+    TimecodeBurner::new(style.clone()).burn(rgba, frame, fps)
+}
+
+struct TimecodeBurner {
+    style: TimecodeStyle,
+    engine: TextLayoutEngine,
+    font: vello_cpu::peniko::FontData,
+}
+
+impl TimecodeBurner {
+    fn new(style: TimecodeStyle) -> Self {
+        let font = vello_cpu::peniko::FontData::new(
+            vello_cpu::peniko::Blob::from(style.font_bytes.as_ref().clone()),
+            0,
+        );
+        Self {
+            style,
+            engine: TextLayoutEngine::new(),
+            font,
+        }
+    }
+
+    fn burn(&mut self, rgba: &mut FrameRGBA, frame: FrameIndex, fps: Fps) -> WavyteResult<()> {
+        let style = &self.style;
+        let text = format_timecode(frame, fps, style.drop_frame);
+        let [r, g, b, a] = style.color;
+        let layout = self.engine.layout_plain(
+            &text,
+            style.font_bytes.as_slice(),
+            style.size_px,
+            TextBrushRgba8 { r, g, b, a },
+            None,
+        )?;
+
+        let pad = style.padding_px;
+        let box_w = layout.width().ceil().max(0.0) as u32 + 2 * pad;
+        let box_h = layout.height().ceil().max(0.0) as u32 + 2 * pad;
+        let origin = corner_origin(
+            style.corner,
+            [rgba.width, rgba.height],
+            [box_w, box_h],
+            style.margin_px,
+        )
+        .ok_or_else(|| {
+            WavyteError::validation(format!(
+                "timecode box {box_w}x{box_h} does not fit a {}x{} frame",
+                rgba.width, rgba.height
+            ))
+        })?;
+
+        let (w, h) = (to_u16(box_w)?, to_u16(box_h)?);
+        let mut ctx = vello_cpu::RenderContext::new(w, h);
+        let [br, bg, bb, ba] = style.background;
+        ctx.set_paint(vello_cpu::peniko::Color::from_rgba8(br, bg, bb, ba));
+        ctx.fill_rect(&vello_cpu::kurbo::Rect::new(
+            0.0,
+            0.0,
+            f64::from(box_w),
+            f64::from(box_h),
+        ));
+        ctx.set_transform(vello_cpu::kurbo::Affine::translate((
+            f64::from(pad),
+            f64::from(pad),
+        )));
+        ctx.set_paint(vello_cpu::peniko::Color::from_rgba8(r, g, b, a));
+        for line in layout.lines() {
+            for item in line.items() {
+                let parley::layout::PositionedLayoutItem::GlyphRun(run) = item else {
+                    continue;
+                };
+                let glyphs = run.positioned_glyphs().map(|g| vello_cpu::Glyph {
+                    id: g.id,
+                    x: g.x,
+                    y: g.y,
+                });
+                ctx.glyph_run(&self.font)
+                    .font_size(run.run().font_size())
+                    .fill_glyphs(glyphs);
+            }
+        }
+        let mut pixmap = vello_cpu::Pixmap::new(w, h);
+        ctx.flush();
+        ctx.render_to_pixmap(&mut pixmap);
+
+        over_at_in_place(
+            &mut rgba.data,
+            rgba.width,
+            pixmap.data_as_u8_slice(),
+            box_w,
+            origin,
+            1.0,
+        )
+    }
+}
+
+fn to_u16(v: u32) -> WavyteResult<u16> {
+    v.try_into()
+        .map_err(|_| WavyteError::validation("timecode box exceeds u16"))
+}
+
+/// Top-left position of a `size` box anchored to `corner` of a `frame` sized area, or `None`
+/// when the box plus margin does not fit.
+pub(crate) fn corner_origin(
+    corner: TimecodeCorner,
+    frame: [u32; 2],
+    size: [u32; 2],
+    margin: u32,
+) -> Option<[u32; 2]> {
+    let far = |extent: u32, len: u32| extent.checked_sub(len)?.checked_sub(margin);
+    let x = match corner {
+        TimecodeCorner::TopLeft | TimecodeCorner::BottomLeft => margin,
+        TimecodeCorner::TopRight | TimecodeCorner::BottomRight => far(frame[0], size[0])?,
+    };
+    let y = match corner {
+        TimecodeCorner::TopLeft | TimecodeCorner::TopRight => margin,
+        TimecodeCorner::BottomLeft | TimecodeCorner::BottomRight => far(frame[1], size[1])?,
+    };
+    (x + size[0] <= frame[0] && y + size[1] <= frame[1]).then_some([x, y])
+}
+
+/// Frame sink wrapper that burns the SMPTE timecode of each frame into a corner before
+/// forwarding it, for review copies.
+///
+/// The frame rate comes from [`SinkConfig::fps`] in [`FrameSink::begin`].
+pub struct TimecodeSink<S: FrameSink> {
+    inner: S,
+    burner: TimecodeBurner,
+    fps: Option<Fps>,
+}
+
+impl<S: FrameSink> TimecodeSink<S> {
+    /// Wrap `inner`, burning in timecode drawn with `style`.
+    pub fn new(inner: S, style: TimecodeStyle) -> Self {
+        Self {
+            inner,
+            burner: TimecodeBurner::new(style),
+            fps: None,
+        }
+    }
+
+    /// Consume the wrapper and return the inner sink.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: FrameSink> FrameSink for TimecodeSink<S> {
+    fn begin(&mut self, cfg: &SinkConfig) -> WavyteResult<()> {
+        self.fps = Some(cfg.fps);
+        self.inner.begin(cfg)
+    }
+
+    fn write_frame(&mut self, frame: FrameIndex, rgba: &FrameRGBA) -> WavyteResult<()> {
+        self.write_frame_owned(frame, rgba.clone())
+    }
+
+    fn write_frame_owned(&mut self, frame: FrameIndex, mut rgba: FrameRGBA) -> WavyteResult<()> {
+        let fps = self
+            .fps
+            .ok_or_else(|| WavyteError::evaluation("TimecodeSink received a frame before begin"))?;
+        self.burner.burn(&mut rgba, frame, fps)?;
+        self.inner.write_frame_owned(frame, rgba)
+    }
+
+    fn finish(&mut self) -> WavyteResult<()> {
+        self.inner.finish()
+    }
+
+    fn crop_rect(&self) -> Option<PixelRect> {
+        self.inner.crop_rect()
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/encode/timecode.rs"]
+mod tests;
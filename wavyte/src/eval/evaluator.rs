@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use crate::{
     animation::anim::SampleCtx,
     composition::model::{Asset, BlendMode, Clip, Composition, EffectInstance, TransitionSpec},
@@ -29,7 +31,10 @@ pub struct EvaluatedClipNode {
     pub opacity: f64,
     /// Blend mode for compositing.
     pub blend: BlendMode,
-    /// Source media time (for video clips), if applicable.
+    /// Apply `opacity` at composite time (after pass effects) instead of at draw time.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub opacity_at_composite: bool,
+    /// Source media time (for video and image-sequence clips), if applicable.
     pub source_time_s: Option<f64>,
     /// Effects copied from clip and validated for compile.
     pub effects: Vec<ResolvedEffect>,
@@ -37,6 +42,40 @@ pub struct EvaluatedClipNode {
     pub transition_in: Option<ResolvedTransition>,
     /// Optional resolved transition-out state.
     pub transition_out: Option<ResolvedTransition>,
+    /// Isolation stacks this node is drawn into, outermost first: isolated tracks (see
+    /// `Track::isolate`) and nested compositions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub isolation: Vec<IsolationGroup>,
+    /// Asset-local region left visible by `ContentFit::Cover`; drawing is clipped to it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_clip: Option<kurbo::Rect>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+/// A stack whose nodes composite onto their own surface, which then composites over the layers
+/// below as one.
+pub struct IsolationGroup {
+    /// Stack identifier, unique within the frame.
+    pub id: usize,
+    /// Canvas-space rect the finished stack is clipped to (see `Track::clip_to`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clip: Option<kurbo::Rect>,
+    /// How the finished stack composites, for nested compositions; `None` is plain source-over.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub composite: Option<GroupComposite>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+/// Compositing of a nested composition's stack, taken from the clip that references it.
+pub struct GroupComposite {
+    /// Stack opacity in `[0, 1]`.
+    pub opacity: f64,
+    /// Stack blend mode.
+    pub blend: BlendMode,
+    /// Optional resolved transition-in state.
+    pub transition_in: Option<ResolvedTransition>,
+    /// Optional resolved transition-out state.
+    pub transition_out: Option<ResolvedTransition>,
 }
 
 #[derive(Clone, Debug, serde::Serialize)]
@@ -101,6 +140,8 @@ impl Evaluator {
         }
 
         let mut nodes_with_key: Vec<((i32, usize, u64, String), EvaluatedClipNode)> = Vec::new();
+        // Isolated tracks use their index as group id; nested compositions are numbered after.
+        let mut next_group = comp.tracks.len();
 
         for (track_index, track) in comp.tracks.iter().enumerate() {
             for (clip_index, clip) in track.clips.iter().enumerate() {
@@ -113,7 +154,16 @@ impl Evaluator {
                     clip,
                     frame,
                     track.z_base,
-                    layout.offset_for(track_index, clip_index),
+                    layout,
+                    (track_index, clip_index),
+                    (track.isolate || track.clip_to.is_some())
+                        .then(|| IsolationGroup {
+                            id: track_index,
+                            clip: track.clip_to.map(|c| c.rect(track, comp.canvas)),
+                            composite: None,
+                        })
+                        .into_iter()
+                        .collect(),
                 )?;
                 let sort_key = (
                     node.z,
@@ -121,6 +171,20 @@ impl Evaluator {
                     clip.range.start.0,
                     node.clip_id.clone(),
                 );
+                if let Some(Asset::Composition(nested)) = comp.assets.get(&clip.asset) {
+                    let children = eval_nested(
+                        comp,
+                        clip,
+                        frame,
+                        &nested.composition,
+                        layout.nested_for(&clip.asset),
+                        &node,
+                        &mut next_group,
+                    )?;
+                    // `sort_by` is stable, so children keep their painter's order.
+                    nodes_with_key.extend(children.into_iter().map(|n| (sort_key.clone(), n)));
+                    continue;
+                }
                 nodes_with_key.push((sort_key, node));
             }
         }
@@ -137,10 +201,16 @@ fn eval_clip(
     clip: &Clip,
     frame: FrameIndex,
     track_z_base: i32,
-    layout_offset: crate::foundation::core::Vec2,
+    layout: &crate::LayoutOffsets,
+    (track_index, clip_index): (usize, usize),
+    isolation: Vec<IsolationGroup>,
 ) -> WavyteResult<EvaluatedClipNode> {
-    let clip_local = FrameIndex(frame.0 - clip.range.start.0);
-    let seed = stable_hash64(comp.seed, &clip.id);
+    let layout_offset = layout.offset_for(track_index, clip_index);
+    let layout_size = layout.size_for(track_index, clip_index);
+    let fit = layout.fit_for(track_index, clip_index);
+    let content = content_frame(comp, clip, frame)?;
+    let clip_local = FrameIndex(content as u64);
+    let seed = clip_seed(comp, clip);
     let ctx = SampleCtx {
         frame,
         fps: comp.fps,
@@ -149,13 +219,27 @@ fn eval_clip(
     };
 
     let opacity = clip.props.opacity.sample(ctx)?.clamp(0.0, 1.0);
+    let mut local = clip.props.transform.sample(ctx)?;
+    if let (Some(frac), Some(size)) = (clip.props.box_anchor, layout_size) {
+        // The box starts where the asset's bounds do.
+        let origin = layout.origin_for(track_index, clip_index);
+        local.anchor = crate::foundation::core::Vec2::new(
+            origin.x + size.x * frac.x,
+            origin.y + size.y * frac.y,
+        );
+    }
+    if let Some(w) = &clip.props.wiggle {
+        local = w.apply(local, ctx);
+    }
     let transform = kurbo::Affine::translate((layout_offset.x, layout_offset.y))
-        * clip.props.transform.sample(ctx)?.to_affine();
+        * local.to_affine()
+        * fit.transform;
     let source_time_s = match comp.assets.get(&clip.asset) {
         Some(Asset::Video(video)) => Some(crate::assets::media::video_source_time_sec(
-            video,
-            clip_local.0,
-            comp.fps,
+            video, content, comp.fps,
+        )),
+        Some(Asset::ImageSequence(seq)) => Some(seq.fps.frames_to_secs(
+            crate::assets::media::image_sequence_frame(seq, clip_local.0, comp.fps),
         )),
         _ => None,
     };
@@ -173,13 +257,146 @@ fn eval_clip(
         transform,
         opacity,
         blend: clip.props.blend,
+        opacity_at_composite: clip.props.opacity_at_composite,
         source_time_s,
         effects,
         transition_in: resolve_transition_in(clip, frame),
         transition_out: resolve_transition_out(clip, frame),
+        isolation,
+        content_clip: fit.clip,
     })
 }
 
+fn clip_seed(comp: &Composition, clip: &Clip) -> u64 {
+    match clip.seed {
+        Some(s) => stable_hash64(comp.seed ^ s.wrapping_mul(0x9E37_79B9_7F4A_7C15), ""),
+        None => stable_hash64(comp.seed, &clip.id),
+    }
+}
+
+/// Frame within the clip's content: reversed by [`Clip::reverse`], remapped by
+/// [`Clip::time_remap`], then wrapped by [`Clip::loop_content`]. Fractional only under a remap.
+fn content_frame(comp: &Composition, clip: &Clip, frame: FrameIndex) -> WavyteResult<f64> {
+    let mut clip_local = FrameIndex(frame.0 - clip.range.start.0);
+    if clip.reverse {
+        clip_local = FrameIndex(clip.range.len_frames() - 1 - clip_local.0);
+    }
+    let mut t = match &clip.time_remap {
+        Some(remap) => {
+            let v = remap
+                .sample(SampleCtx {
+                    frame,
+                    fps: comp.fps,
+                    clip_local,
+                    seed: clip_seed(comp, clip),
+                })?
+                .max(0.0);
+            // Keep identity remaps exact despite interpolation rounding.
+            if (v - v.round()).abs() < 1e-9 {
+                v.round()
+            } else {
+                v
+            }
+        }
+        None => clip_local.0 as f64,
+    };
+    if clip.loop_content
+        && let Some(period) = content_duration_frames(comp, clip)
+    {
+        t %= period as f64;
+    }
+    Ok(t)
+}
+
+/// Evaluate the nested composition referenced by `clip` into nodes stacked in a new group.
+///
+/// Child nodes take the parent clip's z and are transformed by its transform; the parent's
+/// opacity, blend and transitions apply to the finished stack. Groups inside the child (its
+/// isolated tracks and deeper nesting) stay nested within that stack, renumbered from
+/// `next_group` so ids remain unique across the frame.
+fn eval_nested(
+    comp: &Composition,
+    clip: &Clip,
+    frame: FrameIndex,
+    child: &Composition,
+    layout: Option<&crate::LayoutOffsets>,
+    parent: &EvaluatedClipNode,
+    next_group: &mut usize,
+) -> WavyteResult<Vec<EvaluatedClipNode>> {
+    let local = content_frame(comp, clip, frame)? as u64;
+    let child_frame = u128::from(local) * u128::from(child.fps.num) * u128::from(comp.fps.den)
+        / (u128::from(child.fps.den) * u128::from(comp.fps.num));
+    if child_frame >= u128::from(child.duration.0) {
+        return Ok(Vec::new());
+    }
+
+    let default_layout = crate::LayoutOffsets::default();
+    let graph = Evaluator::eval_frame_with_layout_impl(
+        child,
+        FrameIndex(child_frame as u64),
+        layout.unwrap_or(&default_layout),
+        false,
+    )?;
+    let mut stack = parent.isolation.clone();
+    stack.push(IsolationGroup {
+        id: *next_group,
+        clip: None,
+        composite: Some(GroupComposite {
+            opacity: parent.opacity,
+            blend: parent.blend,
+            transition_in: parent.transition_in.clone(),
+            transition_out: parent.transition_out.clone(),
+        }),
+    });
+    *next_group += 1;
+
+    let mut renumbered = BTreeMap::<usize, usize>::new();
+    let mut nodes = Vec::with_capacity(graph.nodes.len());
+    for mut n in graph.nodes {
+        n.clip_id = format!("{}/{}", parent.clip_id, n.clip_id);
+        n.asset = format!("{}/{}", parent.asset, n.asset);
+        n.z = parent.z;
+        n.transform = parent.transform * n.transform;
+        let inner = std::mem::replace(&mut n.isolation, stack.clone());
+        for mut g in inner {
+            g.id = *renumbered.entry(g.id).or_insert_with(|| {
+                *next_group += 1;
+                *next_group - 1
+            });
+            g.clip = g.clip.map(|r| parent.transform.transform_rect_bbox(r));
+            n.isolation.push(g);
+        }
+        nodes.push(n);
+    }
+    Ok(nodes)
+}
+
+/// Natural content length of `clip` in frames, used by [`Clip::loop_content`].
+fn content_duration_frames(comp: &Composition, clip: &Clip) -> Option<u64> {
+    let media = match comp.assets.get(&clip.asset) {
+        Some(Asset::Video(video)) => video.trim_end_sec.and_then(|end| {
+            let secs = (end - video.trim_start_sec) / video.playback_rate;
+            (secs.is_finite() && secs > 0.0).then(|| comp.fps.secs_to_frames_floor(secs).max(1))
+        }),
+        Some(Asset::Composition(nested)) => {
+            let child = &nested.composition;
+            let num =
+                u128::from(child.duration.0) * u128::from(child.fps.den) * u128::from(comp.fps.num);
+            let den = u128::from(child.fps.num) * u128::from(comp.fps.den);
+            Some(u64::try_from(num.div_ceil(den)).unwrap_or(u64::MAX).max(1))
+        }
+        _ => None,
+    };
+    [
+        clip.props.transform.natural_duration(),
+        clip.props.opacity.natural_duration(),
+        media,
+    ]
+    .into_iter()
+    .flatten()
+    .max()
+}
+
 fn resolve_effect(e: &EffectInstance) -> WavyteResult<ResolvedEffect> {
     if e.kind.trim().is_empty() {
         return Err(WavyteError::evaluation("effect kind must be non-empty"));
@@ -120,6 +120,19 @@ pub struct Canvas {
     pub height: u32,
 }
 
+/// Axis-aligned pixel rectangle within a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PixelRect {
+    /// Left edge in pixels.
+    pub x: u32,
+    /// Top edge in pixels.
+    pub y: u32,
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+}
+
 /// Premultiplied RGBA8 (r,g,b already multiplied by a).
 #[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Rgba8Premul {
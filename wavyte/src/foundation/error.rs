@@ -2,7 +2,11 @@
 pub type WavyteResult<T> = Result<T, WavyteError>;
 
 /// Top-level error taxonomy used by engine APIs.
+///
+/// New variants may be added in minor releases; match on [`WavyteError::kind`] for stable
+/// handling.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum WavyteError {
     /// Invalid user-provided or composition data.
     #[error("validation error: {0}")]
@@ -20,12 +24,92 @@ pub enum WavyteError {
     #[error("serialization error: {0}")]
     Serde(String),
 
+    /// Filesystem or process IO failures.
+    #[error("io error: {0}")]
+    Io(String),
+
+    /// Failures decoding source media (images, SVG, fonts, video, audio).
+    #[error("decode error: {0}")]
+    Decode(String),
+
+    /// Failures while encoding output (for example the `ffmpeg` process).
+    #[error("encode error: {0}")]
+    Encode(String),
+
+    /// The operation was cancelled before completion.
+    #[error("cancelled: {0}")]
+    Cancelled(String),
+
+    /// The requested operation is not available in this build or environment.
+    #[error("unsupported: {0}")]
+    Unsupported(String),
+
     /// Wrapped lower-level error from dependencies or IO.
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+impl From<serde_json::Error> for WavyteError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Serde(e.to_string())
+    }
+}
+
+/// Coarse, stable error category for programmatic handling.
+///
+/// New categories may be added in minor releases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Invalid composition data or a malformed document.
+    Schema,
+    /// Filesystem or process IO failure (for example a missing asset file).
+    Io,
+    /// Source media could not be decoded.
+    Decode,
+    /// Frame evaluation, compilation, or rendering failed.
+    Evaluation,
+    /// Output encoding failed.
+    Encode,
+    /// The operation was cancelled.
+    Cancelled,
+    /// The operation is not supported in this build or environment.
+    Unsupported,
+}
+
 impl WavyteError {
+    /// Return the stable category of this error.
+    ///
+    /// Wrapped [`WavyteError::Other`] errors are classified by the first recognized cause in their
+    /// source chain and fall back to [`ErrorKind::Evaluation`].
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Validation(_) | Self::Serde(_) => ErrorKind::Schema,
+            Self::Animation(_) | Self::Evaluation(_) => ErrorKind::Evaluation,
+            Self::Io(_) => ErrorKind::Io,
+            Self::Decode(_) => ErrorKind::Decode,
+            Self::Encode(_) => ErrorKind::Encode,
+            Self::Cancelled(_) => ErrorKind::Cancelled,
+            Self::Unsupported(_) => ErrorKind::Unsupported,
+            Self::Other(e) => e
+                .chain()
+                .find_map(|cause| {
+                    if let Some(inner) = cause.downcast_ref::<WavyteError>() {
+                        Some(inner.kind())
+                    } else if cause.is::<std::io::Error>() {
+                        Some(ErrorKind::Io)
+                    } else if cause.is::<serde_json::Error>() {
+                        Some(ErrorKind::Schema)
+                    } else if is_decode_error(cause) {
+                        Some(ErrorKind::Decode)
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(ErrorKind::Evaluation),
+        }
+    }
+
     /// Build a [`WavyteError::Validation`] value.
     pub fn validation(msg: impl Into<String>) -> Self {
         Self::Validation(msg.into())
@@ -45,6 +129,41 @@ impl WavyteError {
     pub fn serde(msg: impl Into<String>) -> Self {
         Self::Serde(msg.into())
     }
+
+    /// Build a [`WavyteError::Io`] value.
+    pub fn io(msg: impl Into<String>) -> Self {
+        Self::Io(msg.into())
+    }
+
+    /// Build a [`WavyteError::Decode`] value.
+    pub fn decode(msg: impl Into<String>) -> Self {
+        Self::Decode(msg.into())
+    }
+
+    /// Build a [`WavyteError::Encode`] value.
+    pub fn encode(msg: impl Into<String>) -> Self {
+        Self::Encode(msg.into())
+    }
+
+    /// Build a [`WavyteError::Cancelled`] value.
+    pub fn cancelled(msg: impl Into<String>) -> Self {
+        Self::Cancelled(msg.into())
+    }
+
+    /// Build a [`WavyteError::Unsupported`] value.
+    pub fn unsupported(msg: impl Into<String>) -> Self {
+        Self::Unsupported(msg.into())
+    }
+}
+
+#[cfg(feature = "render")]
+fn is_decode_error(cause: &(dyn std::error::Error + 'static)) -> bool {
+    cause.is::<image::ImageError>() || cause.is::<usvg::Error>()
+}
+
+#[cfg(not(feature = "render"))]
+fn is_decode_error(_cause: &(dyn std::error::Error + 'static)) -> bool {
+    false
 }
 
 #[cfg(test)]
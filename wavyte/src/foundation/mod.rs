@@ -1,3 +1,4 @@
 pub mod core;
 pub mod error;
+#[cfg_attr(not(feature = "render"), allow(dead_code))]
 pub mod math;
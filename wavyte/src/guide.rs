@@ -47,8 +47,16 @@
 //! Assets are loaded from a root directory via [`PreparedAssetStore::prepare`](crate::PreparedAssetStore::prepare)
 //! and shared immutably across compile/render steps.
 //!
-//! This design makes it straightforward to add a future asset cache that loads from:
-//! - an in-memory store
+//! Bytes (including images referenced from SVGs) are read through an
+//! [`AssetSource`](crate::AssetSource):
+//! [`FsAssetSource`](crate::FsAssetSource) backs `prepare`, while
+//! [`InMemoryAssetMap`](crate::InMemoryAssetMap) serves targets without a filesystem (e.g.
+//! `wasm32`) via [`PreparedAssetStore::prepare_with_source`](crate::PreparedAssetStore::prepare_with_source)
+//! or the one-shot [`render_frame_to_vec`](crate::render_frame_to_vec).
+//! [`ZipAssetSource`](crate::ZipAssetSource) reads a `.wavyte` zip bundle holding
+//! `composition.json` plus its media, checking up front that every referenced file is present.
+//!
+//! This design makes it straightforward to add further sources that load from:
 //! - a content-addressed CAS
 //! - a remote object store
 //! without changing renderer logic.
@@ -96,6 +104,9 @@
 //!     "rect",
 //!     Asset::Path(PathAsset {
 //!         svg_path_d: "M0,0 L120,0 L120,120 L0,120 Z".to_string(),
+//!         fill: None,
+//!         stroke: None,
+//!         fill_rule: wavyte::FillRule::NonZero,
 //!     }),
 //! )?
 //! .track(
@@ -119,6 +130,7 @@
 //!
 //! let settings = RenderSettings {
 //!     clear_rgba: Some([18, 20, 28, 255]),
+//!     ..RenderSettings::default()
 //! };
 //! let mut backend = create_backend(BackendKind::Cpu, &settings)?;
 //! let assets = PreparedAssetStore::prepare(&comp, ".")?;
@@ -180,7 +192,7 @@
 //! In v0.2.1, the plan uses these pass types:
 //!
 //! - [`ScenePass`](crate::ScenePass)
-//! - [`OffscreenPass`](crate::OffscreenPass) (currently used for blur)
+//! - [`OffscreenPass`](crate::OffscreenPass) (used for blur, sharpen, pixelate, drop shadow and color matrices)
 //! - [`CompositePass`](crate::CompositePass) (`Over`, `Crossfade`, `Wipe`)
 //!
 //! ### Draw operations and coordinate conventions
@@ -189,7 +201,7 @@
 //!
 //! - a [`Affine`](crate::Affine) transform
 //! - an opacity factor in `[0, 1]`
-//! - a blend mode (currently only [`BlendMode::Normal`](crate::BlendMode))
+//! - a blend mode ([`BlendMode`](crate::BlendMode); applied when the clip's surface is composited)
 //! - an integer `z` used for ordering within a pass
 //!
 //! v0.2.1 draw ops:
@@ -217,7 +229,9 @@
 //! - Effects:
 //!   - inline effects (folded into op transform/opacity at compile time)
 //!   - pass effects (implemented as an [`OffscreenPass`](crate::OffscreenPass))
-//!   - currently implemented pass effect: blur
+//!   - currently implemented pass effects: blur, motion blur, radial (zoom) blur, sharpen
+//!     (unsharp mask), pixelate, drop shadow, threshold, vignette, color matrix (plus
+//!     grayscale/saturate/brightness/hue_rotate/invert/channel_mixer presets)
 //! - Transitions:
 //!   - crossfade
 //!   - wipe (direction + soft edge)
@@ -252,7 +266,9 @@
 //!
 //! Parallel rendering is available via [`RenderThreading`](crate::RenderThreading), including
 //! optional static-frame elision using [`FrameFingerprint`](crate::FrameFingerprint) to skip
-//! duplicate frame graphs in chunked parallel runs.
+//! duplicate frame graphs in chunked parallel runs. Interactive previews can use
+//! [`render_frame_if_changed`](crate::render_frame_if_changed) to skip re-rendering while the
+//! fingerprint stays the same.
 //!
 //! ---
 //!
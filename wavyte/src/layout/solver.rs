@@ -1,6 +1,8 @@
 use crate::{
     assets::store::{PreparedAsset, PreparedAssetStore},
-    composition::model::{Composition, LayoutAlignX, LayoutAlignY, LayoutMode, Track},
+    composition::model::{
+        Asset, Composition, ContentFit, LayoutAlignX, LayoutAlignY, LayoutMode, Track,
+    },
     foundation::core::Vec2,
     foundation::error::WavyteResult,
 };
@@ -9,6 +11,28 @@ use crate::{
 #[derive(Clone, Debug, Default)]
 pub struct LayoutOffsets {
     per_track: Vec<Vec<Vec2>>,
+    sizes: Vec<Vec<Vec2>>,
+    origins: Vec<Vec<Vec2>>,
+    fits: Vec<Vec<FitPlacement>>,
+    nested: std::collections::BTreeMap<String, LayoutOffsets>,
+}
+
+/// Placement of a clip's asset inside its layout box, from [`ContentFit`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FitPlacement {
+    /// Asset-local to box-local transform.
+    pub transform: kurbo::Affine,
+    /// Region of the asset (in asset-local coordinates) left visible; `None` draws it whole.
+    pub clip: Option<kurbo::Rect>,
+}
+
+impl Default for FitPlacement {
+    fn default() -> Self {
+        Self {
+            transform: kurbo::Affine::IDENTITY,
+            clip: None,
+        }
+    }
 }
 
 impl LayoutOffsets {
@@ -22,6 +46,43 @@ impl LayoutOffsets {
             .copied()
             .unwrap_or_else(|| Vec2::new(0.0, 0.0))
     }
+
+    /// Get the clip's layout box size (its asset's intrinsic size, reshaped by
+    /// `ClipProps::aspect_ratio`), if it was resolved.
+    pub fn size_for(&self, track_idx: usize, clip_idx: usize) -> Option<Vec2> {
+        self.sizes
+            .get(track_idx)
+            .and_then(|v| v.get(clip_idx))
+            .copied()
+    }
+
+    /// Get the top-left corner of the clip asset's local bounds (non-zero for paths whose
+    /// bounding box does not start at the origin and for middle/bottom-aligned text).
+    ///
+    /// Unknown indices return `(0, 0)`.
+    pub fn origin_for(&self, track_idx: usize, clip_idx: usize) -> Vec2 {
+        self.origins
+            .get(track_idx)
+            .and_then(|v| v.get(clip_idx))
+            .copied()
+            .unwrap_or_else(|| Vec2::new(0.0, 0.0))
+    }
+
+    /// Get the layout of the nested composition asset `asset_key`, if it was resolved.
+    pub fn nested_for(&self, asset_key: &str) -> Option<&LayoutOffsets> {
+        self.nested.get(asset_key)
+    }
+
+    /// Get how the clip's asset is fitted into its layout box.
+    ///
+    /// Unknown indices return the identity placement.
+    pub fn fit_for(&self, track_idx: usize, clip_idx: usize) -> FitPlacement {
+        self.fits
+            .get(track_idx)
+            .and_then(|v| v.get(clip_idx))
+            .copied()
+            .unwrap_or_default()
+    }
 }
 
 /// Resolve per-clip layout offsets for all tracks in a composition.
@@ -31,26 +92,75 @@ pub fn resolve_layout_offsets(
     comp: &Composition,
     assets: &PreparedAssetStore,
 ) -> WavyteResult<LayoutOffsets> {
-    let mut per_track = Vec::<Vec<Vec2>>::with_capacity(comp.tracks.len());
-    for track in &comp.tracks {
-        per_track.push(resolve_track_offsets(comp, track, assets)?);
-    }
-    Ok(LayoutOffsets { per_track })
+    resolve_layout_offsets_in(comp, assets, "")
 }
 
-fn resolve_track_offsets(
+/// Resolve layout for `comp` whose asset keys are stored under `prefix` in `assets`.
+fn resolve_layout_offsets_in(
     comp: &Composition,
-    track: &Track,
     assets: &PreparedAssetStore,
-) -> WavyteResult<Vec<Vec2>> {
-    let mut sizes = Vec::<(f64, f64)>::with_capacity(track.clips.len());
-    for clip in &track.clips {
-        sizes.push(intrinsic_size_for_asset_key(&clip.asset, assets)?);
+    prefix: &str,
+) -> WavyteResult<LayoutOffsets> {
+    let mut per_track = Vec::<Vec<Vec2>>::with_capacity(comp.tracks.len());
+    let mut sizes = Vec::<Vec<Vec2>>::with_capacity(comp.tracks.len());
+    let mut origins = Vec::<Vec<Vec2>>::with_capacity(comp.tracks.len());
+    let mut fits = Vec::<Vec<FitPlacement>>::with_capacity(comp.tracks.len());
+    for track in &comp.tracks {
+        let mut track_sizes = Vec::with_capacity(track.clips.len());
+        let mut track_origins = Vec::with_capacity(track.clips.len());
+        let mut track_fits = Vec::with_capacity(track.clips.len());
+        for clip in &track.clips {
+            let key = format!("{prefix}{}", clip.asset);
+            let intrinsic = intrinsic_size_for_asset_key(&key, assets)?;
+            let origin = intrinsic_origin_for_asset_key(&key, assets)?;
+            let size = match clip.props.aspect_ratio {
+                Some(ratio) => (intrinsic.0, intrinsic.0 / ratio),
+                None => intrinsic,
+            };
+            track_sizes.push(size);
+            track_origins.push(origin);
+            track_fits.push(fit_placement(clip.props.fit, origin, intrinsic, size));
+        }
+        let mut offsets = resolve_track_offsets(comp, track, &track_sizes);
+        if track.layout_mode != LayoutMode::Absolute {
+            // Slots hold each asset's bounds, which need not start at its local origin.
+            for (offset, origin) in offsets.iter_mut().zip(&track_origins) {
+                *offset = Vec2::new(offset.x - origin.x, offset.y - origin.y);
+            }
+        }
+        per_track.push(offsets);
+        origins.push(track_origins);
+        fits.push(track_fits);
+        sizes.push(
+            track_sizes
+                .into_iter()
+                .map(|(w, h)| Vec2::new(w, h))
+                .collect(),
+        );
     }
+    let mut nested = std::collections::BTreeMap::new();
+    for (key, asset) in &comp.assets {
+        if let Asset::Composition(a) = asset {
+            let child_prefix = format!("{prefix}{key}/");
+            nested.insert(
+                key.clone(),
+                resolve_layout_offsets_in(&a.composition, assets, &child_prefix)?,
+            );
+        }
+    }
+    Ok(LayoutOffsets {
+        per_track,
+        sizes,
+        origins,
+        fits,
+        nested,
+    })
+}
 
+fn resolve_track_offsets(comp: &Composition, track: &Track, sizes: &[(f64, f64)]) -> Vec<Vec2> {
     let mut offsets = vec![Vec2::new(0.0, 0.0); track.clips.len()];
     if track.layout_mode == LayoutMode::Absolute || track.clips.is_empty() {
-        return Ok(offsets);
+        return offsets;
     }
 
     let x0 = track.layout_padding.left;
@@ -108,10 +218,68 @@ fn resolve_track_offsets(
             }
         }
     }
-    Ok(offsets)
+    offsets
+}
+
+/// Fit an asset of `intrinsic` size whose bounds start at `origin` into a layout box of `size`
+/// starting at the same point, centering uniform fits.
+fn fit_placement(
+    fit: ContentFit,
+    origin: Vec2,
+    intrinsic: (f64, f64),
+    size: (f64, f64),
+) -> FitPlacement {
+    let ((iw, ih), (bw, bh)) = (intrinsic, size);
+    if fit == ContentFit::None || iw <= 0.0 || ih <= 0.0 || (iw, ih) == (bw, bh) {
+        return FitPlacement::default();
+    }
+    // Fit about the bounds' corner so the box starts where the asset's bounds do.
+    let corner = kurbo::Affine::translate((origin.x, origin.y));
+    let (sx, sy) = (bw / iw, bh / ih);
+    let s = match fit {
+        ContentFit::None | ContentFit::Fill => {
+            return FitPlacement {
+                transform: corner * kurbo::Affine::scale_non_uniform(sx, sy) * corner.inverse(),
+                clip: None,
+            };
+        }
+        ContentFit::Contain => sx.min(sy),
+        ContentFit::Cover => sx.max(sy),
+    };
+    let transform = corner
+        * kurbo::Affine::translate(((bw - iw * s) * 0.5, (bh - ih * s) * 0.5))
+        * kurbo::Affine::scale(s)
+        * corner.inverse();
+    let clip = (fit == ContentFit::Cover).then(|| {
+        transform.inverse().transform_rect_bbox(kurbo::Rect::new(
+            origin.x,
+            origin.y,
+            origin.x + bw,
+            origin.y + bh,
+        ))
+    });
+    FitPlacement { transform, clip }
+}
+
+/// Top-left corner of a prepared asset's local bounds.
+///
+/// Paths start at their bounding box and middle/bottom-aligned text is drawn above its local
+/// origin; everything else starts at `(0, 0)`.
+fn intrinsic_origin_for_asset_key(key: &str, assets: &PreparedAssetStore) -> WavyteResult<Vec2> {
+    let id = assets.id_for_key(key)?;
+    Ok(match assets.get(id)? {
+        PreparedAsset::Path(p) => {
+            use kurbo::Shape;
+            let bbox = p.path.bounding_box();
+            Vec2::new(bbox.x0, bbox.y0)
+        }
+        PreparedAsset::Text(t) => Vec2::new(0.0, f64::from(t.origin_offset_y)),
+        _ => Vec2::new(0.0, 0.0),
+    })
 }
 
-fn intrinsic_size_for_asset_key(
+/// Intrinsic `(width, height)` in pixels of a prepared asset, as used for layout boxes.
+pub fn intrinsic_size_for_asset_key(
     key: &str,
     assets: &PreparedAssetStore,
 ) -> WavyteResult<(f64, f64)> {
@@ -129,7 +297,7 @@ fn intrinsic_size_for_asset_key(
             for line in t.layout.lines() {
                 let m = line.metrics();
                 w = w.max(f64::from(m.advance));
-                h += f64::from(m.ascent + m.descent + m.leading);
+                h += f64::from(m.line_height);
             }
             Ok((w.max(1.0), h.max(1.0)))
         }
@@ -139,6 +307,10 @@ fn intrinsic_size_for_asset_key(
             Ok((bbox.width().max(1.0), bbox.height().max(1.0)))
         }
         PreparedAsset::Video(v) => Ok((f64::from(v.info.width), f64::from(v.info.height))),
+        PreparedAsset::ImageSequence(q) => Ok((f64::from(q.width), f64::from(q.height))),
+        PreparedAsset::RadialGradient(g) => Ok((f64::from(g.width), f64::from(g.height))),
+        PreparedAsset::SolidRect(r) => Ok((r.width, r.height)),
+        PreparedAsset::Composition(c) => Ok((f64::from(c.width), f64::from(c.height))),
         PreparedAsset::Audio(_) => Ok((0.0, 0.0)),
     }
 }
@@ -21,76 +21,149 @@
 //!
 //! - For end-user usage, see the repository README.
 //! - For a detailed, standalone walkthrough of the API and architecture, see [`crate::guide`].
+//!
+//! # Cargo features
+//!
+//! - `render` (default): asset preparation, compilation, the CPU backend, and encoding.
+//! - `media-ffmpeg`: video/audio decoding through the system `ffmpeg` binaries (implies `render`).
+//!
+//! Building with `default-features = false` gives the lean `core-only` surface: the core value
+//! types ([`Vec2`], [`Transform2D`], [`FrameIndex`], ...), animation sampling ([`Anim`], [`Ease`],
+//! the `ops` combinators), errors, and [`transform`]. It compiles without the image, text, SVG,
+//! and raster dependencies.
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
 
 mod animation;
+#[cfg(feature = "render")]
 mod assets;
+#[cfg(feature = "render")]
 mod audio;
+#[cfg(feature = "render")]
 mod compile;
+#[cfg(feature = "render")]
 mod composition;
+#[cfg(feature = "render")]
 mod effects;
+#[cfg(feature = "render")]
 mod encode;
+#[cfg(feature = "render")]
 mod eval;
 mod foundation;
+#[cfg(feature = "render")]
 mod layout;
+#[cfg(feature = "render")]
 mod render;
 
 /// High-level, standalone documentation for Wavyte’s concepts and architecture.
+#[cfg(feature = "render")]
 pub mod guide;
 /// Shared transform helpers (linear, affine, non-linear utilities).
 pub mod transform;
 
 pub use animation::anim::{Anim, InterpMode, Keyframe, Keyframes, LoopMode, SampleCtx};
-pub use animation::ease::Ease;
+pub use animation::ease::{CustomEase, Ease, register_ease};
 pub use animation::ops::{delay, loop_, mix, reverse, sequence, speed, stagger};
+pub use animation::proc::Wiggle;
+#[cfg(feature = "render")]
 pub use assets::decode::{decode_image, parse_svg};
+#[cfg(feature = "render")]
 pub use assets::media::{
     AudioPcm, MIX_SAMPLE_RATE, VideoSourceInfo, audio_source_time_sec, decode_audio_f32_stereo,
-    decode_video_frame_rgba8, probe_video, video_source_time_sec,
+    decode_video_frame_rgba8, image_sequence_frame, loop_frame_index, loop_video_source_time,
+    probe_video, video_source_time_sec,
 };
+#[cfg(feature = "render")]
+pub use assets::source::{AssetSource, FsAssetSource, InMemoryAssetMap};
+#[cfg(feature = "render")]
 pub use assets::store::{
-    AssetId, AssetKey, PreparedAsset, PreparedAssetStore, PreparedAudio, PreparedImage,
-    PreparedPath, PreparedSvg, PreparedText, PreparedVideo, TextBrushRgba8, TextLayoutEngine,
+    AssetId, AssetKey, PreparedAsset, PreparedAssetStore, PreparedAudio, PreparedComposition,
+    PreparedImage, PreparedImageSequence, PreparedPath, PreparedRadialGradient, PreparedSolidRect,
+    PreparedSvg, PreparedText, PreparedVideo, TextBrushRgba8, TextLayoutEngine, TextParagraph,
     normalize_rel_path,
 };
+#[cfg(feature = "render")]
+pub use assets::zip_source::ZipAssetSource;
+#[cfg(feature = "render")]
 pub use audio::mix::{
-    AudioManifest, AudioSegment, build_audio_manifest, frame_to_sample, mix_manifest,
-    write_mix_to_f32le_file,
+    AudioBuffer, AudioManifest, AudioSegment, build_audio_manifest, frame_to_sample, mix_audio,
+    mix_manifest, write_mix_to_f32le_file,
 };
+#[cfg(feature = "render")]
 pub use compile::fingerprint::{FrameFingerprint, fingerprint_eval};
+#[cfg(feature = "render")]
 pub use compile::plan::{
     CompositeOp, CompositePass, DrawOp, OffscreenPass, Pass, PixelFormat, RenderPlan, ScenePass,
-    SurfaceDesc, SurfaceId, compile_frame,
+    StrokeStyle, SurfaceDesc, SurfaceId, compile_frame,
 };
+#[cfg(feature = "render")]
 pub use composition::dsl::{
-    ClipBuilder, CompositionBuilder, TrackBuilder, audio_asset, video_asset,
+    ClipBuilder, CompositionBuilder, TrackBuilder, audio_asset, blur_effect, channel_mixer_effect,
+    crossfade_transition, drop_shadow_effect, hue_rotate_effect, motion_blur_effect,
+    opacity_mul_effect, pixelate_effect, radial_blur_effect, sharpen_effect, text_asset,
+    threshold_effect, transform_post_effect, video_asset, vignette_effect, wipe_transition,
 };
+#[cfg(feature = "render")]
 pub use composition::model::{
-    Asset, AudioAsset, BlendMode, Clip, ClipProps, Composition, Edges, EffectInstance, ImageAsset,
-    LayoutAlignX, LayoutAlignY, LayoutMode, PathAsset, SvgAsset, TextAsset, Track, TransitionSpec,
-    VideoAsset,
+    Asset, AudioAsset, AudioDuck, AudioFadeCurve, BlendMode, Clip, ClipProps, Composition,
+    CompositionAsset, ContentFit, Edges, EffectInstance, FillRule, GradientStop, ImageAsset,
+    ImageSampling, ImageSequenceAsset, LayoutAlignX, LayoutAlignY, LayoutMode, PathAsset,
+    PathStroke, RadialGradientAsset, RoundedRectAsset, SolidRectAsset, SvgAsset, TextAlign,
+    TextAsset, TextSpan, TextVAlign, Track, TrackClip, TransitionSpec, VideoAsset, VideoLoopMode,
 };
+#[cfg(feature = "render")]
+pub use composition::validate::{IssueCode, ValidationIssue};
+#[cfg(feature = "render")]
 pub use effects::fx::{Effect, FxPipeline, InlineFx, PassFx, normalize_effects, parse_effect};
+#[cfg(feature = "render")]
 pub use effects::transitions::{TransitionKind, WipeDir, parse_transition};
+#[cfg(feature = "render")]
 pub use eval::evaluator::{
-    EvaluatedClipNode, EvaluatedGraph, Evaluator, ResolvedEffect, ResolvedTransition,
+    EvaluatedClipNode, EvaluatedGraph, Evaluator, GroupComposite, IsolationGroup, ResolvedEffect,
+    ResolvedTransition,
 };
 pub use foundation::core::{
-    Affine, BezPath, Canvas, Fps, FrameIndex, FrameRange, Point, Rect, Rgba8Premul, Transform2D,
-    Vec2,
+    Affine, BezPath, Canvas, Fps, FrameIndex, FrameRange, PixelRect, Point, Rect, Rgba8Premul,
+    Transform2D, Vec2,
+};
+pub use foundation::error::{ErrorKind, WavyteError, WavyteResult};
+#[cfg(feature = "render")]
+pub use layout::solver::{
+    FitPlacement, LayoutOffsets, intrinsic_size_for_asset_key, resolve_layout_offsets,
 };
-pub use foundation::error::{WavyteError, WavyteResult};
-pub use layout::solver::{LayoutOffsets, resolve_layout_offsets};
-pub use render::backend::{BackendKind, FrameRGBA, RenderBackend, RenderSettings, create_backend};
+#[cfg(feature = "render")]
+pub use render::backend::{
+    BackendKind, FrameRGBA, MissingGlyphPolicy, RenderBackend, RenderSettings, TextSnap,
+    create_backend,
+};
+#[cfg(feature = "render")]
 pub use render::cpu::CpuBackend;
-pub use render::passes::{PassBackend, execute_plan};
+#[cfg(feature = "render")]
+pub use render::passes::{OpKind, OpTimings, PassBackend, execute_plan, execute_plan_into};
+#[cfg(feature = "render")]
 pub use render::pipeline::{
-    RenderStats, RenderThreading, RenderToMp4Opts, render_frame, render_frames,
-    render_frames_with_stats, render_to_mp4, render_to_mp4_with_stats,
+    RenderStats, RenderThreading, RenderToMp4Opts, dump_plan, frame_fingerprint, render_frame,
+    render_frame_if_changed, render_frame_into, render_frame_region, render_frame_to_vec,
+    render_frames, render_frames_with_stats, render_to_mp4, render_to_mp4_with_stats,
+    render_to_sink, render_to_sink_with_stats,
 };
 
+#[cfg(feature = "render")]
 pub use encode::ffmpeg::{
-    AudioInputConfig, EncodeConfig, FfmpegEncoder, default_mp4_config, ensure_parent_dir,
-    is_ffmpeg_on_path,
+    AudioInputConfig, EncodeConfig, FfmpegEncoder, VideoCodec, default_mp4_config,
+    ensure_parent_dir, is_ffmpeg_on_path,
+};
+#[cfg(feature = "render")]
+pub use encode::sidecar::{
+    FrameMetadata, NodeBounds, NodeMetadata, frame_metadata, write_frame_metadata_sidecar,
+};
+#[cfg(feature = "render")]
+pub use encode::sink::{
+    AlphaTrimSink, CallbackSink, CheckerboardOpts, CheckerboardSink, FrameSink, GifSink,
+    GifSinkOpts, PngSequenceSink, PngSequenceSinkOpts, RawFormat, RawSink, SinkConfig, crop_frame,
+    render_on_checkerboard,
+};
+#[cfg(feature = "render")]
+pub use encode::timecode::{
+    TimecodeCorner, TimecodeSink, TimecodeStyle, burn_in_timecode, format_timecode,
 };
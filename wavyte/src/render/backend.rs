@@ -1,8 +1,8 @@
 use crate::{
     assets::store::PreparedAssetStore,
     compile::plan::RenderPlan,
-    foundation::error::WavyteResult,
-    render::passes::{PassBackend, execute_plan},
+    foundation::error::{WavyteError, WavyteResult},
+    render::passes::{OpTimings, PassBackend, execute_plan},
 };
 
 /// A rendered frame as RGBA8 pixels.
@@ -41,6 +41,30 @@ pub trait RenderBackend: PassBackend {
     fn worker_render_settings(&self) -> Option<RenderSettings> {
         None
     }
+
+    /// Return and reset the number of asset bytes decoded since the previous call.
+    ///
+    /// Counts pixel data produced by image uploads, SVG rasterization and video frame decoding.
+    /// Backends without such tracking report `0`.
+    fn take_bytes_decoded(&mut self) -> u64 {
+        0
+    }
+
+    /// Return and reset per-pass timings collected since the previous call.
+    ///
+    /// Empty unless profiling is enabled (see [`RenderSettings::profile_ops`]).
+    fn take_op_timings(&mut self) -> OpTimings {
+        self.op_timings_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Resolution multiplier applied when compiling plans for this backend.
+    ///
+    /// See [`RenderSettings::scale`]; backends without scaling support report `1.0`.
+    fn render_scale(&self) -> f32 {
+        1.0
+    }
 }
 
 /// Available backend kinds.
@@ -52,11 +76,59 @@ pub enum BackendKind {
     Cpu,
 }
 
+/// Glyph origin snapping applied before text is rasterized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextSnap {
+    /// Draw glyphs at the fractional positions produced by layout.
+    #[default]
+    None,
+    /// Round glyph origins to whole device pixels (crisper small UI text).
+    Pixel,
+    /// Round horizontal origins to quarter pixels and baselines to whole pixels.
+    Subpixel,
+}
+
+/// How text glyphs the font cannot draw are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MissingGlyphPolicy {
+    /// Draw the font's `.notdef` glyph (usually a box, "tofu").
+    #[default]
+    Tofu,
+    /// Leave the missing glyphs out.
+    Skip,
+    /// Leave them out and log a warning naming the text asset and the missing codepoints.
+    Warn,
+}
+
 /// Backend-agnostic settings.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct RenderSettings {
     /// If set, backends clear the final target to this RGBA8 color before drawing.
     pub clear_rgba: Option<[u8; 4]>,
+    /// Glyph origin snapping for text layers.
+    pub text_snap: TextSnap,
+    /// Resolution multiplier for proxy/thumbnail renders (default `1.0`).
+    ///
+    /// The canvas is scaled and rounded to even dimensions, and the whole composition is drawn
+    /// proportionally smaller; rendered frames have the scaled size. Must be finite and `> 0`.
+    pub scale: f32,
+    /// Time each render pass by [`OpKind`](crate::OpKind) and report it in
+    /// `RenderStats::op_timings`. Off by default; no clocks are read when disabled.
+    pub profile_ops: bool,
+    /// Handling of codepoints the text font has no glyph for.
+    pub missing_glyphs: MissingGlyphPolicy,
+}
+
+impl Default for RenderSettings {
+    fn default() -> Self {
+        Self {
+            clear_rgba: None,
+            text_snap: TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: MissingGlyphPolicy::Tofu,
+        }
+    }
 }
 
 /// Create a rendering backend implementation.
@@ -64,11 +136,16 @@ pub struct RenderSettings {
 /// - `BackendKind::Cpu` is always available.
 pub fn create_backend(
     kind: BackendKind,
-    _settings: &RenderSettings,
+    settings: &RenderSettings,
 ) -> WavyteResult<Box<dyn RenderBackend>> {
+    if !settings.scale.is_finite() || settings.scale <= 0.0 {
+        return Err(WavyteError::validation(
+            "render scale must be finite and > 0",
+        ));
+    }
     match kind {
         BackendKind::Cpu => Ok(Box::new(crate::render::cpu::CpuBackend::new(
-            _settings.clone(),
+            settings.clone(),
         ))),
     }
 }
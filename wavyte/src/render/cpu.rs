@@ -1,13 +1,18 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use crate::{
+    assets::decode as assets_decode,
+    assets::gradient::{GradientRasterKey, rasterize_radial_gradient_premul_rgba8},
     assets::media,
-    assets::store::{AssetId, PreparedAsset, PreparedAssetStore},
+    assets::store::{AssetId, PreparedAsset, PreparedAssetStore, PreparedImageSequence},
     assets::svg_raster::{SvgRasterKey, rasterize_svg_to_premul_rgba8, svg_raster_params},
     compile::plan::{CompositeOp, DrawOp, SurfaceDesc, SurfaceId},
+    composition::model::{FillRule, ImageSampling},
+    effects::fx::PassFx,
+    foundation::core::PixelRect,
     foundation::error::{WavyteError, WavyteResult},
-    render::backend::{FrameRGBA, RenderBackend, RenderSettings},
-    render::passes::PassBackend,
+    render::backend::{FrameRGBA, MissingGlyphPolicy, RenderBackend, RenderSettings, TextSnap},
+    render::passes::{OpTimings, PassBackend},
 };
 
 /// CPU renderer implementation backed by `vello_cpu`.
@@ -15,32 +20,44 @@ pub struct CpuBackend {
     settings: RenderSettings,
     image_cache: HashMap<AssetId, vello_cpu::Image>,
     svg_cache: HashMap<SvgRasterKey, vello_cpu::Image>,
-    font_cache: HashMap<AssetId, vello_cpu::peniko::FontData>,
+    gradient_cache: HashMap<GradientRasterKey, vello_cpu::Image>,
+    font_cache: HashMap<(AssetId, usize), vello_cpu::peniko::FontData>,
     video_decoders: HashMap<AssetId, VideoFrameDecoder>,
+    sequence_decoders: HashMap<AssetId, ImageSequenceDecoder>,
     surfaces: HashMap<SurfaceId, CpuSurface>,
+    plan_clear_rgba: Option<[u8; 4]>,
+    bytes_decoded: u64,
+    op_timings: OpTimings,
+    missing_glyph_warned: HashSet<AssetId>,
 }
 
 struct CpuSurface {
     width: u16,
     height: u16,
+    origin: [u32; 2],
     pixmap: vello_cpu::Pixmap,
 }
 
+/// Source frame index plus the weight (in 1/256) of the following frame blended into it; decoded
+/// frames use weight `0`.
+type VideoFrameKey = (u64, u16);
+
 struct VideoFrameDecoder {
     info: std::sync::Arc<media::VideoSourceInfo>,
-    frame_cache: HashMap<u64, vello_cpu::Image>,
-    lru: VecDeque<u64>,
+    frame_cache: HashMap<VideoFrameKey, vello_cpu::Image>,
+    lru: VecDeque<VideoFrameKey>,
     capacity: usize,
     prefetch_frames: u32,
+    bytes_decoded: u64,
+    sampler: vello_cpu::peniko::ImageSampler,
 }
 
 impl VideoFrameDecoder {
-    fn new(info: std::sync::Arc<media::VideoSourceInfo>) -> Self {
-        let capacity = std::env::var("WAVYTE_VIDEO_CACHE_CAPACITY")
-            .ok()
-            .and_then(|v| v.parse::<usize>().ok())
-            .filter(|&n| n > 0)
-            .unwrap_or(64);
+    fn new(
+        info: std::sync::Arc<media::VideoSourceInfo>,
+        sampler: vello_cpu::peniko::ImageSampler,
+    ) -> Self {
+        let capacity = frame_cache_capacity();
         let prefetch_frames = std::env::var("WAVYTE_VIDEO_PREFETCH_FRAMES")
             .ok()
             .and_then(|v| v.parse::<u32>().ok())
@@ -52,50 +69,88 @@ impl VideoFrameDecoder {
             lru: VecDeque::new(),
             capacity,
             prefetch_frames,
+            bytes_decoded: 0,
+            sampler,
         }
     }
 
     fn decode_at(&mut self, source_time_s: f64) -> WavyteResult<vello_cpu::Image> {
-        let key = self.key_for_time(source_time_s);
-        if let Some(img) = self.frame_cache.get(&key).cloned() {
-            self.touch(key);
+        let index = self.info.frame_index_at(source_time_s);
+        if let Some(img) = self.cached((index, 0)) {
             return Ok(img);
         }
 
-        if self.prefetch_for_key(key).is_ok()
-            && let Some(img) = self.frame_cache.get(&key).cloned()
+        if self.prefetch_for_index(index).is_ok()
+            && let Some(img) = self.cached((index, 0))
         {
-            self.touch(key);
             return Ok(img);
         }
 
-        // Fallback for sparse decode requests where batch prefetch didn't include the key.
-        let rgba = media::decode_video_frame_rgba8(&self.info, source_time_s)?;
+        // Fallback for sparse decode requests where batch prefetch didn't include the frame.
+        let rgba = media::decode_video_frame_rgba8(&self.info, self.info.frame_time_sec(index))?;
+        self.bytes_decoded += rgba.len() as u64;
         let image = self.rgba_to_image(&rgba)?;
-        self.insert_frame(key, image.clone());
+        self.insert_frame((index, 0), image.clone());
         Ok(image)
     }
 
-    fn key_for_time(&self, source_time_s: f64) -> u64 {
-        ((source_time_s.max(0.0)) * 1000.0).round() as u64
-    }
-
-    fn prefetch_for_key(&mut self, key_ms: u64) -> WavyteResult<()> {
+    /// Crossfade the source frames on either side of `source_time_s` by its fractional position.
+    fn decode_blended_at(&mut self, source_time_s: f64) -> WavyteResult<vello_cpu::Image> {
         let source_fps = self.info.source_fps();
-        let step_ms = if source_fps.is_finite() && source_fps > 0.0 {
-            1000.0 / source_fps
-        } else {
-            1.0
+        if !source_fps.is_finite() || source_fps <= 0.0 {
+            return self.decode_at(source_time_s);
+        }
+        let pos = source_time_s.max(0.0) * source_fps;
+        let i0 = pos.floor();
+        let t = pos - i0;
+        let t0 = i0 / source_fps;
+        let t1 = (i0 + 1.0) / source_fps;
+        // Treat sub-millisecond fractions as exact hits; also never sample past the last frame.
+        if t * 1000.0 / source_fps < 1.0
+            || (self.info.duration_sec > 0.0 && t1 >= self.info.duration_sec)
+        {
+            return self.decode_at(t0);
+        }
+        if (1.0 - t) * 1000.0 / source_fps < 1.0 {
+            return self.decode_at(t1);
+        }
+
+        let w = (t * 256.0).round() as u16;
+        let key = (self.info.frame_index_at(t0), w);
+        if let Some(img) = self.cached(key) {
+            return Ok(img);
+        }
+        let a = self.decode_at(t0)?;
+        let b = self.decode_at(t1)?;
+        let (vello_cpu::ImageSource::Pixmap(pa), vello_cpu::ImageSource::Pixmap(pb)) =
+            (&a.image, &b.image)
+        else {
+            return Ok(a);
         };
-        let window_ms = (step_ms * self.prefetch_frames as f64).max(step_ms);
-        let bucket = ((key_ms as f64) / window_ms).floor();
-        let start_key_ms = (bucket * window_ms).round().max(0.0) as u64;
-        let start_time_s = (start_key_ms as f64) / 1000.0;
-        let frames =
-            media::decode_video_frames_rgba8(&self.info, start_time_s, self.prefetch_frames)?;
+
+        let blended = pa
+            .data_as_u8_slice()
+            .iter()
+            .zip(pb.data_as_u8_slice())
+            .map(|(&x, &y)| ((u16::from(x) * (256 - w) + u16::from(y) * w + 128) >> 8) as u8)
+            .collect::<Vec<u8>>();
+        let image = self.rgba_to_image(&blended)?;
+        self.insert_frame(key, image.clone());
+        Ok(image)
+    }
+
+    fn prefetch_for_index(&mut self, index: u64) -> WavyteResult<()> {
+        let window = u64::from(self.prefetch_frames.max(1));
+        let start = index - index % window;
+        let frames = media::decode_video_frames_rgba8(
+            &self.info,
+            self.info.frame_time_sec(start),
+            self.prefetch_frames,
+        )?;
+        self.bytes_decoded += frames.iter().map(|f| f.len() as u64).sum::<u64>();
 
         for (offset, rgba) in frames.iter().enumerate() {
-            let key = ((start_key_ms as f64) + ((offset as f64) * step_ms)).round() as u64;
+            let key = (start + offset as u64, 0);
             if self.frame_cache.contains_key(&key) {
                 self.touch(key);
                 continue;
@@ -110,11 +165,17 @@ impl VideoFrameDecoder {
         let pixmap = image_premul_bytes_to_pixmap(rgba, self.info.width, self.info.height)?;
         Ok(vello_cpu::Image {
             image: vello_cpu::ImageSource::Pixmap(std::sync::Arc::new(pixmap)),
-            sampler: vello_cpu::peniko::ImageSampler::default(),
+            sampler: self.sampler,
         })
     }
 
-    fn insert_frame(&mut self, key: u64, image: vello_cpu::Image) {
+    fn cached(&mut self, key: VideoFrameKey) -> Option<vello_cpu::Image> {
+        let img = self.frame_cache.get(&key).cloned()?;
+        self.touch(key);
+        Some(img)
+    }
+
+    fn insert_frame(&mut self, key: VideoFrameKey, image: vello_cpu::Image) {
         self.frame_cache.insert(key, image);
         self.touch(key);
         while self.lru.len() > self.capacity {
@@ -124,7 +185,7 @@ impl VideoFrameDecoder {
         }
     }
 
-    fn touch(&mut self, key: u64) {
+    fn touch(&mut self, key: VideoFrameKey) {
         if let Some(pos) = self.lru.iter().position(|x| *x == key) {
             self.lru.remove(pos);
         }
@@ -132,6 +193,81 @@ impl VideoFrameDecoder {
     }
 }
 
+/// Decoded-frame LRU for one image sequence, the counterpart of [`VideoFrameDecoder`].
+struct ImageSequenceDecoder {
+    frame_cache: HashMap<usize, vello_cpu::Image>,
+    lru: VecDeque<usize>,
+    capacity: usize,
+    bytes_decoded: u64,
+    sampler: vello_cpu::peniko::ImageSampler,
+}
+
+impl ImageSequenceDecoder {
+    fn new(sampler: vello_cpu::peniko::ImageSampler) -> Self {
+        Self {
+            frame_cache: HashMap::new(),
+            lru: VecDeque::new(),
+            capacity: frame_cache_capacity(),
+            bytes_decoded: 0,
+            sampler,
+        }
+    }
+
+    fn decode(
+        &mut self,
+        seq: &PreparedImageSequence,
+        index: usize,
+    ) -> WavyteResult<vello_cpu::Image> {
+        if let Some(img) = self.frame_cache.get(&index).cloned() {
+            self.touch(index);
+            return Ok(img);
+        }
+
+        let bytes = seq.frames.get(index).ok_or_else(|| {
+            WavyteError::evaluation(format!("image sequence has no frame {index}"))
+        })?;
+        let img = assets_decode::decode_image(bytes)?;
+        if (img.width, img.height) != (seq.width, seq.height) {
+            return Err(WavyteError::decode(format!(
+                "image sequence frame {index} is {}x{}, expected {}x{}",
+                img.width, img.height, seq.width, seq.height
+            )));
+        }
+        self.bytes_decoded += img.rgba8_premul.len() as u64;
+        let pixmap =
+            image_premul_bytes_to_pixmap(img.rgba8_premul.as_slice(), img.width, img.height)?;
+        let image = vello_cpu::Image {
+            image: vello_cpu::ImageSource::Pixmap(std::sync::Arc::new(pixmap)),
+            sampler: self.sampler,
+        };
+
+        self.frame_cache.insert(index, image.clone());
+        self.touch(index);
+        while self.lru.len() > self.capacity {
+            if let Some(old) = self.lru.pop_front() {
+                self.frame_cache.remove(&old);
+            }
+        }
+        Ok(image)
+    }
+
+    fn touch(&mut self, index: usize) {
+        if let Some(pos) = self.lru.iter().position(|x| *x == index) {
+            self.lru.remove(pos);
+        }
+        self.lru.push_back(index);
+    }
+}
+
+/// Decoded frames kept per video or image sequence (`WAVYTE_VIDEO_CACHE_CAPACITY`, default 64).
+fn frame_cache_capacity() -> usize {
+    std::env::var("WAVYTE_VIDEO_CACHE_CAPACITY")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(64)
+}
+
 impl CpuBackend {
     /// Construct a CPU backend with the provided render settings.
     pub fn new(settings: RenderSettings) -> Self {
@@ -139,14 +275,29 @@ impl CpuBackend {
             settings,
             image_cache: HashMap::new(),
             svg_cache: HashMap::new(),
+            gradient_cache: HashMap::new(),
             font_cache: HashMap::new(),
             video_decoders: HashMap::new(),
+            sequence_decoders: HashMap::new(),
             surfaces: HashMap::new(),
+            plan_clear_rgba: None,
+            bytes_decoded: 0,
+            op_timings: OpTimings::default(),
+            missing_glyph_warned: HashSet::new(),
         }
     }
 }
 
 impl PassBackend for CpuBackend {
+    fn op_timings_mut(&mut self) -> Option<&mut OpTimings> {
+        self.settings.profile_ops.then_some(&mut self.op_timings)
+    }
+
+    fn begin_plan(&mut self, plan: &crate::compile::plan::RenderPlan) -> WavyteResult<()> {
+        self.plan_clear_rgba = plan.clear_rgba;
+        Ok(())
+    }
+
     fn ensure_surface(&mut self, id: SurfaceId, desc: &SurfaceDesc) -> WavyteResult<()> {
         let width_u16: u16 = desc
             .width
@@ -163,9 +314,11 @@ impl PassBackend for CpuBackend {
                     *surface = CpuSurface {
                         width: width_u16,
                         height: height_u16,
+                        origin: desc.origin,
                         pixmap: vello_cpu::Pixmap::new(width_u16, height_u16),
                     };
                 }
+                surface.origin = desc.origin;
             }
             None => {
                 self.surfaces.insert(
@@ -173,6 +326,7 @@ impl PassBackend for CpuBackend {
                     CpuSurface {
                         width: width_u16,
                         height: height_u16,
+                        origin: desc.origin,
                         pixmap: vello_cpu::Pixmap::new(width_u16, height_u16),
                     },
                 );
@@ -183,6 +337,7 @@ impl PassBackend for CpuBackend {
             let premul = self
                 .settings
                 .clear_rgba
+                .or(self.plan_clear_rgba)
                 .map(|[r, g, b, a]| premul_rgba8(r, g, b, a))
                 .unwrap_or([0, 0, 0, 0]);
             let s = self
@@ -250,16 +405,11 @@ impl PassBackend for CpuBackend {
             input.pixmap.data_as_u8_slice().to_vec()
         };
 
-        match pass.fx {
-            crate::effects::fx::PassFx::Blur { radius_px, sigma } => {
-                let blurred =
-                    crate::effects::blur::blur_rgba8_premul(&input_bytes, w, h, radius_px, sigma)?;
-                output
-                    .pixmap
-                    .data_as_u8_slice_mut()
-                    .copy_from_slice(&blurred);
-            }
-        }
+        let processed = apply_pass_fx(&pass.fx, &input_bytes, w, h, pass.frame)?;
+        output
+            .pixmap
+            .data_as_u8_slice_mut()
+            .copy_from_slice(&processed);
 
         self.surfaces.insert(pass.output, output);
         Ok(())
@@ -286,10 +436,38 @@ impl PassBackend for CpuBackend {
                             src
                         ))
                     })?;
-                    crate::effects::composite::over_in_place(
+                    if src.origin == [0, 0] && src.width == dst.width && src.height == dst.height {
+                        crate::effects::composite::over_in_place(
+                            dst.pixmap.data_as_u8_slice_mut(),
+                            src.pixmap.data_as_u8_slice(),
+                            opacity,
+                        )?;
+                    } else {
+                        crate::effects::composite::over_at_in_place(
+                            dst.pixmap.data_as_u8_slice_mut(),
+                            u32::from(dst.width),
+                            src.pixmap.data_as_u8_slice(),
+                            u32::from(src.width),
+                            src.origin,
+                            opacity,
+                        )?;
+                    }
+                }
+                CompositeOp::Blend { src, opacity, mode } => {
+                    let src = self.surfaces.get(&src).ok_or_else(|| {
+                        WavyteError::evaluation(format!(
+                            "composite src surface {:?} was not initialized",
+                            src
+                        ))
+                    })?;
+                    crate::effects::composite::blend_at_in_place(
                         dst.pixmap.data_as_u8_slice_mut(),
+                        u32::from(dst.width),
                         src.pixmap.data_as_u8_slice(),
+                        u32::from(src.width),
+                        src.origin,
                         opacity,
+                        mode,
                     )?;
                 }
                 CompositeOp::Crossfade { a, b, t } => {
@@ -318,6 +496,7 @@ impl PassBackend for CpuBackend {
                     t,
                     dir,
                     soft_edge,
+                    sweep,
                 } => {
                     let a = self.surfaces.get(&a).ok_or_else(|| {
                         WavyteError::evaluation(format!(
@@ -341,6 +520,8 @@ impl PassBackend for CpuBackend {
                             t,
                             dir,
                             soft_edge,
+                            sweep_origin: [sweep.x, sweep.y],
+                            sweep_size: [sweep.width, sweep.height],
                         },
                     )?;
                 }
@@ -373,12 +554,135 @@ impl PassBackend for CpuBackend {
             premultiplied: true,
         })
     }
+
+    fn readback_rgba8_into(
+        &mut self,
+        surface: SurfaceId,
+        plan: &crate::compile::plan::RenderPlan,
+        _assets: &PreparedAssetStore,
+        out: &mut Vec<u8>,
+    ) -> WavyteResult<(u32, u32)> {
+        let s = self.surfaces.get(&surface).ok_or_else(|| {
+            WavyteError::evaluation(format!(
+                "readback surface {:?} was not initialized",
+                surface
+            ))
+        })?;
+        out.clear();
+        out.extend_from_slice(s.pixmap.data_as_u8_slice());
+        let surface_cap = plan.surfaces.len() as u32;
+        self.surfaces.retain(|id, _| id.0 < surface_cap);
+
+        Ok((plan.canvas.width, plan.canvas.height))
+    }
 }
 
 impl RenderBackend for CpuBackend {
     fn worker_render_settings(&self) -> Option<RenderSettings> {
         Some(self.settings.clone())
     }
+
+    fn render_scale(&self) -> f32 {
+        self.settings.scale
+    }
+
+    fn take_bytes_decoded(&mut self) -> u64 {
+        let video = self
+            .video_decoders
+            .values_mut()
+            .map(|d| std::mem::take(&mut d.bytes_decoded))
+            .sum::<u64>();
+        let sequences = self
+            .sequence_decoders
+            .values_mut()
+            .map(|d| std::mem::take(&mut d.bytes_decoded))
+            .sum::<u64>();
+        std::mem::take(&mut self.bytes_decoded) + video + sequences
+    }
+}
+
+fn apply_pass_fx(
+    fx: &PassFx,
+    input: &[u8],
+    w: u32,
+    h: u32,
+    frame: PixelRect,
+) -> WavyteResult<Vec<u8>> {
+    Ok(match *fx {
+        PassFx::Blur {
+            radius_px,
+            sigma,
+            alpha_only: false,
+        } => crate::effects::blur::blur_rgba8_premul(input, w, h, radius_px, sigma)?,
+        PassFx::Blur {
+            radius_px,
+            sigma,
+            alpha_only: true,
+        } => crate::effects::blur::blur_alpha_only_rgba8_premul(input, w, h, radius_px, sigma)?,
+        PassFx::Sharpen {
+            amount,
+            radius_px,
+            sigma,
+        } => crate::effects::blur::sharpen_rgba8_premul(input, w, h, radius_px, sigma, amount)?,
+        PassFx::Pixelate { block_px } => {
+            crate::effects::pixelate::pixelate_rgba8_premul(input, w, h, frame, block_px)?
+        }
+        PassFx::DropShadow {
+            offset,
+            blur_radius_px,
+            sigma,
+            color,
+        } => crate::effects::shadow::drop_shadow_rgba8_premul(
+            input,
+            w,
+            h,
+            offset,
+            blur_radius_px,
+            sigma,
+            color,
+        )?,
+        PassFx::ColorMatrix { matrix } => {
+            crate::effects::color_matrix::color_matrix_rgba8_premul(input, w, h, &matrix)?
+        }
+        PassFx::Threshold { cutoff } => {
+            crate::effects::color_matrix::threshold_rgba8_premul(input, w, h, cutoff)?
+        }
+        PassFx::RadialBlur {
+            center,
+            strength,
+            samples,
+        } => crate::effects::blur::radial_blur_rgba8_premul(
+            input, w, h, frame, center, strength, samples,
+        )?,
+        PassFx::MotionBlur {
+            angle_deg,
+            distance_px,
+            samples,
+        } => crate::effects::blur::motion_blur_rgba8_premul(
+            input,
+            w,
+            h,
+            frame,
+            angle_deg,
+            distance_px,
+            samples,
+        )?,
+        PassFx::Vignette {
+            radius,
+            softness,
+            color,
+        } => crate::effects::vignette::vignette_rgba8_premul(
+            input, w, h, frame, radius, softness, color,
+        )?,
+        PassFx::ClipRect { rect } => {
+            crate::effects::clip::clip_rect_rgba8_premul(input, w, h, rect)?
+        }
+        PassFx::LinearLight(ref inner) => {
+            crate::effects::color_space::linear_light_rgba8_premul(input, |linear| {
+                crate::effects::linear_pass::apply_pass_fx_f32(inner, linear, w, h, frame)
+            })?
+        }
+    })
 }
 
 fn premul_rgba8(r: u8, g: u8, b: u8, a: u8) -> [u8; 4] {
@@ -407,19 +711,30 @@ fn draw_op(
             path,
             transform,
             color,
+            fill_rule,
+            stroke,
             opacity,
             blend: _,
             z: _,
         } => {
             ctx.set_transform(affine_to_cpu(*transform));
-            ctx.set_paint(vello_cpu::peniko::Color::from_rgba8(
-                color.r, color.g, color.b, color.a,
-            ));
             if *opacity < 1.0 {
                 ctx.push_opacity_layer(*opacity);
             }
             let cpu_path = bezpath_to_cpu(path);
-            ctx.fill_path(&cpu_path);
+            if color.a > 0 {
+                ctx.set_paint(premul_to_cpu(*color));
+                if *fill_rule == FillRule::EvenOdd {
+                    ctx.set_fill_rule(vello_cpu::peniko::Fill::EvenOdd);
+                }
+                ctx.fill_path(&cpu_path);
+                ctx.set_fill_rule(vello_cpu::peniko::Fill::NonZero);
+            }
+            if let Some(stroke) = stroke {
+                ctx.set_paint(premul_to_cpu(stroke.color));
+                ctx.set_stroke(vello_cpu::kurbo::Stroke::new(stroke.width));
+                ctx.stroke_path(&cpu_path);
+            }
             if *opacity < 1.0 {
                 ctx.pop_layer();
             }
@@ -460,8 +775,29 @@ fn draw_op(
                 return Err(WavyteError::evaluation("AssetId is not a PreparedText"));
             };
 
-            let font = backend.font_for_text_asset(*asset, assets)?;
-            ctx.set_transform(affine_to_cpu(*transform));
+            let text_transform = affine_to_cpu(
+                *transform * kurbo::Affine::translate((0.0, f64::from(t.origin_offset_y))),
+            );
+            let snap = backend.settings.text_snap;
+            let policy = backend.settings.missing_glyphs;
+            if policy == MissingGlyphPolicy::Warn
+                && !t.missing_codepoints.is_empty()
+                && backend.missing_glyph_warned.insert(*asset)
+            {
+                let codepoints = t
+                    .missing_codepoints
+                    .iter()
+                    .map(|c| format!("U+{:04X}", u32::from(*c)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                tracing::warn!(
+                    asset = assets.key_for_id(*asset).unwrap_or("<unknown>"),
+                    font = %t.font_family,
+                    "text font has no glyph for {codepoints}; skipping them"
+                );
+            }
+            let skip_notdef = policy != MissingGlyphPolicy::Tofu;
+            ctx.set_transform(text_transform);
 
             if *opacity < 1.0 {
                 ctx.push_opacity_layer(*opacity);
@@ -473,16 +809,20 @@ fn draw_op(
                         continue;
                     };
 
+                    let slot = t.font_slot(run.run().font());
+                    let font = backend.font_for_text_asset(*asset, slot, assets)?;
                     let brush = run.style().brush;
                     ctx.set_paint(vello_cpu::peniko::Color::from_rgba8(
                         brush.r, brush.g, brush.b, brush.a,
                     ));
 
-                    let glyphs = run.glyphs().map(|g| vello_cpu::Glyph {
-                        id: g.id,
-                        x: g.x,
-                        y: g.y,
-                    });
+                    let glyphs = run
+                        .positioned_glyphs()
+                        .filter(|g| !(skip_notdef && g.id == 0))
+                        .map(|g| {
+                            let (x, y) = snap_glyph_origin(snap, text_transform, g.x, g.y);
+                            vello_cpu::Glyph { id: g.id, x, y }
+                        });
                     ctx.glyph_run(&font)
                         .font_size(run.run().font_size())
                         .fill_glyphs(glyphs);
@@ -518,6 +858,27 @@ fn draw_op(
 
             Ok(())
         }
+        DrawOp::RadialGradient {
+            asset,
+            transform,
+            opacity,
+            blend: _,
+            z: _,
+        } => {
+            let gradient_paint = backend.radial_gradient_paint_for(*asset, assets)?;
+            let (w, h) = image_paint_size(&gradient_paint)?;
+
+            ctx.set_transform(affine_to_cpu(*transform));
+            ctx.set_paint(gradient_paint);
+            if *opacity < 1.0 {
+                ctx.push_opacity_layer(*opacity);
+            }
+            ctx.fill_rect(&vello_cpu::kurbo::Rect::new(0.0, 0.0, w, h));
+            if *opacity < 1.0 {
+                ctx.pop_layer();
+            }
+            Ok(())
+        }
         DrawOp::Video {
             asset,
             source_time_s,
@@ -540,13 +901,58 @@ fn draw_op(
             }
             Ok(())
         }
+        DrawOp::Clipped { clip, op } => {
+            ctx.set_transform(affine_to_cpu(op.transform()));
+            ctx.push_clip_layer(&bezpath_to_cpu(&kurbo::Shape::to_path(clip, 0.1)));
+            let res = draw_op(backend, ctx, op, assets);
+            ctx.pop_layer();
+            res
+        }
     }
 }
 
+/// Snap a layout-space glyph origin to the device grid selected by `snap`.
+///
+/// The origin is mapped through `transform`, rounded in device space, and mapped back so the
+/// glyph lands on the grid after the text transform is applied.
+pub(crate) fn snap_glyph_origin(
+    snap: TextSnap,
+    transform: vello_cpu::kurbo::Affine,
+    x: f32,
+    y: f32,
+) -> (f32, f32) {
+    let grid_x = match snap {
+        TextSnap::None => return (x, y),
+        TextSnap::Pixel => 1.0,
+        TextSnap::Subpixel => 4.0,
+    };
+    if transform.determinant().abs() < 1e-12 {
+        return (x, y);
+    }
+    let device = transform * vello_cpu::kurbo::Point::new(f64::from(x), f64::from(y));
+    let snapped =
+        vello_cpu::kurbo::Point::new((device.x * grid_x).round() / grid_x, device.y.round());
+    let local = transform.inverse() * snapped;
+    (local.x as f32, local.y as f32)
+}
+
 fn affine_to_cpu(a: crate::foundation::core::Affine) -> vello_cpu::kurbo::Affine {
     vello_cpu::kurbo::Affine::new(a.as_coeffs())
 }
 
+/// Paint color for a premultiplied plan color; peniko colors carry straight alpha.
+fn premul_to_cpu(c: crate::foundation::core::Rgba8Premul) -> vello_cpu::peniko::Color {
+    vello_cpu::peniko::color::PremulColor::<vello_cpu::peniko::color::Srgb>::from(
+        vello_cpu::peniko::color::PremulRgba8 {
+            r: c.r,
+            g: c.g,
+            b: c.b,
+            a: c.a,
+        },
+    )
+    .un_premultiply()
+}
+
 fn point_to_cpu(p: crate::foundation::core::Point) -> vello_cpu::kurbo::Point {
     vello_cpu::kurbo::Point::new(p.x, p.y)
 }
@@ -607,6 +1013,17 @@ fn image_premul_bytes_to_pixmap(
     ))
 }
 
+fn image_sampler(sampling: ImageSampling) -> vello_cpu::peniko::ImageSampler {
+    let quality = match sampling {
+        ImageSampling::Nearest => vello_cpu::peniko::ImageQuality::Low,
+        ImageSampling::Bilinear => vello_cpu::peniko::ImageQuality::Medium,
+    };
+    vello_cpu::peniko::ImageSampler {
+        quality,
+        ..Default::default()
+    }
+}
+
 fn image_paint_size(image: &vello_cpu::Image) -> WavyteResult<(f64, f64)> {
     match &image.image {
         vello_cpu::ImageSource::Pixmap(p) => Ok((f64::from(p.width()), f64::from(p.height()))),
@@ -633,9 +1050,10 @@ impl CpuBackend {
 
         let pixmap =
             image_premul_bytes_to_pixmap(img.rgba8_premul.as_slice(), img.width, img.height)?;
+        self.bytes_decoded += img.rgba8_premul.len() as u64;
         let paint = vello_cpu::Image {
             image: vello_cpu::ImageSource::Pixmap(std::sync::Arc::new(pixmap)),
-            sampler: vello_cpu::peniko::ImageSampler::default(),
+            sampler: image_sampler(img.sampling),
         };
 
         self.image_cache.insert(id, paint.clone());
@@ -645,9 +1063,10 @@ impl CpuBackend {
     fn font_for_text_asset(
         &mut self,
         id: AssetId,
+        slot: usize,
         assets: &PreparedAssetStore,
     ) -> WavyteResult<vello_cpu::peniko::FontData> {
-        if let Some(font) = self.font_cache.get(&id) {
+        if let Some(font) = self.font_cache.get(&(id, slot)) {
             return Ok(font.clone());
         }
 
@@ -656,9 +1075,9 @@ impl CpuBackend {
             return Err(WavyteError::evaluation("AssetId is not a PreparedText"));
         };
 
-        let font_bytes = t.font_bytes.as_ref().clone();
+        let font_bytes = t.font_bytes_for_slot(slot).as_ref().clone();
         let font = vello_cpu::peniko::FontData::new(vello_cpu::peniko::Blob::from(font_bytes), 0);
-        self.font_cache.insert(id, font.clone());
+        self.font_cache.insert((id, slot), font.clone());
         Ok(font)
     }
 
@@ -684,6 +1103,7 @@ impl CpuBackend {
         }
 
         let rgba8_premul = rasterize_svg_to_premul_rgba8(&svg.tree, w, h)?;
+        self.bytes_decoded += rgba8_premul.len() as u64;
         let pixmap = image_premul_bytes_to_pixmap(rgba8_premul.as_slice(), w, h)?;
 
         let paint = vello_cpu::Image {
@@ -695,6 +1115,38 @@ impl CpuBackend {
         Ok((paint, w as f64, h as f64, transform_adjust))
     }
 
+    fn radial_gradient_paint_for(
+        &mut self,
+        id: AssetId,
+        assets: &PreparedAssetStore,
+    ) -> WavyteResult<vello_cpu::Image> {
+        let prepared = assets.get(id)?;
+        let PreparedAsset::RadialGradient(g) = prepared else {
+            return Err(WavyteError::evaluation(
+                "AssetId is not a PreparedRadialGradient",
+            ));
+        };
+
+        let key = GradientRasterKey {
+            asset: id,
+            width: g.width,
+            height: g.height,
+        };
+        if let Some(paint) = self.gradient_cache.get(&key) {
+            return Ok(paint.clone());
+        }
+
+        let rgba8_premul = rasterize_radial_gradient_premul_rgba8(g)?;
+        let pixmap = image_premul_bytes_to_pixmap(rgba8_premul.as_slice(), g.width, g.height)?;
+        let paint = vello_cpu::Image {
+            image: vello_cpu::ImageSource::Pixmap(std::sync::Arc::new(pixmap)),
+            sampler: vello_cpu::peniko::ImageSampler::default(),
+        };
+
+        self.gradient_cache.insert(key, paint.clone());
+        Ok(paint)
+    }
+
     fn video_paint_for(
         &mut self,
         id: AssetId,
@@ -702,13 +1154,34 @@ impl CpuBackend {
         assets: &PreparedAssetStore,
     ) -> WavyteResult<vello_cpu::Image> {
         let prepared = assets.get(id)?;
+        if let PreparedAsset::ImageSequence(seq) = prepared {
+            let decoder = self
+                .sequence_decoders
+                .entry(id)
+                .or_insert_with(|| ImageSequenceDecoder::new(image_sampler(seq.sampling)));
+            return decoder.decode(seq, seq.frame_at(source_time_s));
+        }
         let PreparedAsset::Video(video) = prepared else {
             return Err(WavyteError::evaluation("AssetId is not a PreparedVideo"));
         };
-        let decoder = self
-            .video_decoders
-            .entry(id)
-            .or_insert_with(|| VideoFrameDecoder::new(video.info.clone()));
-        decoder.decode_at(source_time_s)
+        let decoder = self.video_decoders.entry(id).or_insert_with(|| {
+            VideoFrameDecoder::new(video.info.clone(), image_sampler(video.sampling))
+        });
+        let source_time_s = media::loop_video_source_time(
+            video.loop_mode,
+            video.trim_start_sec,
+            video.trim_end_sec,
+            &video.info,
+            source_time_s,
+        );
+        if video.frame_blend {
+            decoder.decode_blended_at(source_time_s)
+        } else {
+            decoder.decode_at(source_time_s)
+        }
     }
 }
+
+#[cfg(test)]
+#[path = "../../tests/unit/render/cpu.rs"]
+mod tests;
@@ -1,14 +1,104 @@
+use std::time::{Duration, Instant};
+
 use crate::{
     assets::store::PreparedAssetStore,
     compile::plan::{
         CompositePass, OffscreenPass, Pass, RenderPlan, ScenePass, SurfaceDesc, SurfaceId,
     },
+    effects::fx::PassFx,
     foundation::error::{WavyteError, WavyteResult},
     render::backend::FrameRGBA,
 };
 
+/// Category of render work measured by [`OpTimings`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OpKind {
+    /// Scene passes drawing assets into surfaces.
+    Draw,
+    /// Composite passes combining surfaces (including transitions).
+    Composite,
+    /// Blur-family pass effects (gaussian, motion, radial, sharpen, drop shadow).
+    Blur,
+    /// Color matrix pass effects.
+    ColorMatrix,
+    /// Any other pass effect.
+    Effect,
+}
+
+impl OpKind {
+    /// All kinds, in reporting order.
+    pub const ALL: [OpKind; 5] = [
+        OpKind::Draw,
+        OpKind::Composite,
+        OpKind::Blur,
+        OpKind::ColorMatrix,
+        OpKind::Effect,
+    ];
+
+    /// Kind of work performed by `pass`.
+    pub fn for_pass(pass: &Pass) -> Self {
+        match pass {
+            Pass::Scene(_) => OpKind::Draw,
+            Pass::Composite(_) => OpKind::Composite,
+            Pass::Offscreen(p) => Self::for_pass_fx(&p.fx),
+        }
+    }
+
+    fn for_pass_fx(fx: &PassFx) -> Self {
+        match fx {
+            PassFx::Blur { .. }
+            | PassFx::Sharpen { .. }
+            | PassFx::DropShadow { .. }
+            | PassFx::RadialBlur { .. }
+            | PassFx::MotionBlur { .. } => OpKind::Blur,
+            PassFx::ColorMatrix { .. } => OpKind::ColorMatrix,
+            PassFx::LinearLight(inner) => Self::for_pass_fx(inner),
+            _ => OpKind::Effect,
+        }
+    }
+}
+
+/// Wall-clock time spent per [`OpKind`], collected when `RenderSettings::profile_ops` is set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpTimings([Duration; 5]);
+
+impl OpTimings {
+    /// Time spent on `kind`.
+    pub fn get(&self, kind: OpKind) -> Duration {
+        self.0[kind as usize]
+    }
+
+    /// Add `elapsed` to `kind`.
+    pub fn add(&mut self, kind: OpKind, elapsed: Duration) {
+        self.0[kind as usize] += elapsed;
+    }
+
+    /// Add all timings from `other`.
+    pub fn merge(&mut self, other: &OpTimings) {
+        for kind in OpKind::ALL {
+            self.add(kind, other.get(kind));
+        }
+    }
+
+    /// Non-zero timings in [`OpKind::ALL`] order.
+    pub fn iter(&self) -> impl Iterator<Item = (OpKind, Duration)> + '_ {
+        OpKind::ALL
+            .into_iter()
+            .map(|kind| (kind, self.get(kind)))
+            .filter(|(_, d)| !d.is_zero())
+    }
+}
+
 /// Backend execution interface for individual render pass kinds.
 pub trait PassBackend {
+    /// Prepare for executing `plan`, before any surface is ensured.
+    ///
+    /// The default implementation does nothing.
+    fn begin_plan(&mut self, plan: &RenderPlan) -> WavyteResult<()> {
+        let _ = plan;
+        Ok(())
+    }
+
     /// Ensure backing storage exists for the declared render surface.
     fn ensure_surface(&mut self, id: SurfaceId, desc: &SurfaceDesc) -> WavyteResult<()>;
 
@@ -36,6 +126,32 @@ pub trait PassBackend {
         plan: &RenderPlan,
         assets: &PreparedAssetStore,
     ) -> WavyteResult<FrameRGBA>;
+
+    /// Read back final frame pixels from `surface` into `out`, returning `(width, height)`.
+    ///
+    /// `out` is cleared and refilled with premultiplied RGBA8, reusing its capacity. The default
+    /// implementation copies from [`PassBackend::readback_rgba8`]; backends override it to skip
+    /// the intermediate allocation.
+    fn readback_rgba8_into(
+        &mut self,
+        surface: SurfaceId,
+        plan: &RenderPlan,
+        assets: &PreparedAssetStore,
+        out: &mut Vec<u8>,
+    ) -> WavyteResult<(u32, u32)> {
+        let frame = self.readback_rgba8(surface, plan, assets)?;
+        out.clear();
+        out.extend_from_slice(&frame.data);
+        Ok((frame.width, frame.height))
+    }
+
+    /// Per-pass timing accumulator, or `None` when profiling is disabled.
+    ///
+    /// When this returns `Some`, [`execute_plan`] times each pass and adds it here. The default
+    /// implementation disables profiling.
+    fn op_timings_mut(&mut self) -> Option<&mut OpTimings> {
+        None
+    }
 }
 
 /// Execute all passes in a [`RenderPlan`] against a [`PassBackend`].
@@ -44,6 +160,29 @@ pub fn execute_plan<B: PassBackend + ?Sized>(
     plan: &RenderPlan,
     assets: &PreparedAssetStore,
 ) -> WavyteResult<FrameRGBA> {
+    run_passes(backend, plan, assets)?;
+    backend.readback_rgba8(plan.final_surface, plan, assets)
+}
+
+/// Execute all passes in a [`RenderPlan`] and read the final frame into `out`.
+///
+/// Like [`execute_plan`], but reuses the caller's buffer; returns `(width, height)`.
+pub fn execute_plan_into<B: PassBackend + ?Sized>(
+    backend: &mut B,
+    plan: &RenderPlan,
+    assets: &PreparedAssetStore,
+    out: &mut Vec<u8>,
+) -> WavyteResult<(u32, u32)> {
+    run_passes(backend, plan, assets)?;
+    backend.readback_rgba8_into(plan.final_surface, plan, assets, out)
+}
+
+fn run_passes<B: PassBackend + ?Sized>(
+    backend: &mut B,
+    plan: &RenderPlan,
+    assets: &PreparedAssetStore,
+) -> WavyteResult<()> {
+    backend.begin_plan(plan)?;
     for (idx, desc) in plan.surfaces.iter().enumerate() {
         let id = SurfaceId(
             idx.try_into()
@@ -52,15 +191,19 @@ pub fn execute_plan<B: PassBackend + ?Sized>(
         backend.ensure_surface(id, desc)?;
     }
 
+    let profile = backend.op_timings_mut().is_some();
     for pass in &plan.passes {
+        let started = profile.then(Instant::now);
         match pass {
             Pass::Scene(p) => backend.exec_scene(p, assets)?,
             Pass::Offscreen(p) => backend.exec_offscreen(p, assets)?,
             Pass::Composite(p) => backend.exec_composite(p, assets)?,
         }
+        if let (Some(started), Some(timings)) = (started, backend.op_timings_mut()) {
+            timings.add(OpKind::for_pass(pass), started.elapsed());
+        }
     }
-
-    backend.readback_rgba8(plan.final_surface, plan, assets)
+    Ok(())
 }
 
 #[cfg(test)]
@@ -1,17 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use rayon::prelude::*;
 
 use crate::{
+    assets::source::InMemoryAssetMap,
     assets::store::PreparedAssetStore,
     compile::fingerprint::{FrameFingerprint, fingerprint_eval},
-    compile::plan::{CompileCache, compile_frame_with_cache},
+    compile::plan::{
+        CompileCache, PlanView, check_region, compile_frame_with_cache, region_apron_px,
+        scaled_canvas,
+    },
     composition::model::Composition,
+    encode::sink::{FrameSink, SinkConfig, crop_frame},
     eval::evaluator::Evaluator,
-    foundation::core::{FrameIndex, FrameRange},
+    foundation::core::{FrameIndex, FrameRange, PixelRect},
     foundation::error::{WavyteError, WavyteResult},
-    render::backend::{FrameRGBA, RenderBackend, RenderSettings},
-    render::passes::execute_plan,
+    render::backend::{BackendKind, FrameRGBA, RenderBackend, RenderSettings, create_backend},
+    render::passes::{OpTimings, execute_plan, execute_plan_into},
 };
 
 /// Evaluate + compile + render a single frame.
@@ -34,13 +39,193 @@ pub fn render_frame(
     let layout_offsets = crate::resolve_layout_offsets(comp, assets)?;
     let eval = Evaluator::eval_frame_with_layout_unchecked(comp, frame, &layout_offsets)?;
     let mut compile_cache = CompileCache::default();
-    let plan = compile_frame_with_cache(comp, &eval, assets, &mut compile_cache)?;
+    let plan = compile_frame_with_cache(
+        comp,
+        &eval,
+        assets,
+        &mut compile_cache,
+        PlanView::scaled(backend.render_scale()),
+    )?;
     execute_plan(backend, &plan, assets)
 }
 
+/// Render only `region` of a single frame.
+///
+/// `region` is given in output pixels (after [`RenderSettings::scale`]) and must lie inside the
+/// canvas; the returned frame is `region.width x region.height`. Content outside the region is
+/// clipped, so adjacent regions tile into the full frame (for distributed tile rendering) with
+/// the same pixels as [`render_frame`]. Wipes, vignettes, radial blurs and pixelate tiles are
+/// positioned on the full canvas, and the region is rendered with an apron as wide as the
+/// largest pass reach (blur radius, shadow offset, ...) and then cropped, so effects near the
+/// seams see the same neighbours as in the full frame.
+pub fn render_frame_region(
+    comp: &Composition,
+    frame: FrameIndex,
+    region: PixelRect,
+    backend: &mut dyn RenderBackend,
+    assets: &PreparedAssetStore,
+) -> WavyteResult<FrameRGBA> {
+    comp.validate()?;
+    let layout_offsets = crate::resolve_layout_offsets(comp, assets)?;
+    let eval = Evaluator::eval_frame_with_layout_unchecked(comp, frame, &layout_offsets)?;
+    let mut compile_cache = CompileCache::default();
+    let scale = backend.render_scale();
+    let scaled = scaled_canvas(comp.canvas, scale);
+    check_region(region, scaled)?;
+    let apron = region_apron_px(&eval, &mut compile_cache, scale, scaled)?;
+    let (x0, y0) = (
+        region.x.saturating_sub(apron),
+        region.y.saturating_sub(apron),
+    );
+    let padded = PixelRect {
+        x: x0,
+        y: y0,
+        width: (region.x + region.width)
+            .saturating_add(apron)
+            .min(scaled.width)
+            - x0,
+        height: (region.y + region.height)
+            .saturating_add(apron)
+            .min(scaled.height)
+            - y0,
+    };
+    let view = PlanView {
+        scale,
+        region: Some(padded),
+    };
+    let plan = compile_frame_with_cache(comp, &eval, assets, &mut compile_cache, view)?;
+    let rendered = execute_plan(backend, &plan, assets)?;
+    if padded == region {
+        return Ok(rendered);
+    }
+    crop_frame(
+        &rendered,
+        PixelRect {
+            x: region.x - x0,
+            y: region.y - y0,
+            ..region
+        },
+    )
+}
+
+/// Render a single frame into a caller-provided buffer, returning `(width, height)`.
+///
+/// `out` is cleared and refilled with premultiplied RGBA8 pixels, reusing its allocation across
+/// calls (for example in a preview loop). Pixels are identical to [`render_frame`].
+pub fn render_frame_into(
+    comp: &Composition,
+    frame: FrameIndex,
+    backend: &mut dyn RenderBackend,
+    assets: &PreparedAssetStore,
+    out: &mut Vec<u8>,
+) -> WavyteResult<(u32, u32)> {
+    comp.validate()?;
+    let layout_offsets = crate::resolve_layout_offsets(comp, assets)?;
+    let eval = Evaluator::eval_frame_with_layout_unchecked(comp, frame, &layout_offsets)?;
+    let mut compile_cache = CompileCache::default();
+    let plan = compile_frame_with_cache(
+        comp,
+        &eval,
+        assets,
+        &mut compile_cache,
+        PlanView::scaled(backend.render_scale()),
+    )?;
+    execute_plan_into(backend, &plan, assets, out)
+}
+
+/// Render one frame of a JSON composition whose assets are provided in memory.
+///
+/// Filesystem-free entrypoint (e.g. for `wasm32`): parses `composition_json`, prepares assets
+/// from `assets` (keyed by the composition's relative asset paths), and renders `frame` on the CPU
+/// backend. Video assets are not supported here, and audio assets must be WAV.
+pub fn render_frame_to_vec(
+    composition_json: &str,
+    assets: &InMemoryAssetMap,
+    frame: FrameIndex,
+    opts: &RenderSettings,
+) -> WavyteResult<FrameRGBA> {
+    let comp: Composition = serde_json::from_str(composition_json)?;
+    comp.validate()?;
+    let store = PreparedAssetStore::prepare_with_source(&comp, assets)?;
+    let mut backend = create_backend(BackendKind::Cpu, opts)?;
+    render_frame(&comp, frame, backend.as_mut(), &store)
+}
+
+/// Fingerprint `frame` without rendering it.
+///
+/// Evaluates the frame (including track layout) and hashes the resulting graph with
+/// [`fingerprint_eval`], so external caches can key rendered pixels by content: frames that
+/// evaluate identically share a fingerprint, and any animated property change alters it.
+pub fn frame_fingerprint(
+    comp: &Composition,
+    frame: FrameIndex,
+    assets: &PreparedAssetStore,
+) -> WavyteResult<FrameFingerprint> {
+    comp.validate()?;
+    let layout_offsets = crate::resolve_layout_offsets(comp, assets)?;
+    let eval = Evaluator::eval_frame_with_layout_unchecked(comp, frame, &layout_offsets)?;
+    Ok(fingerprint_eval(&eval))
+}
+
+/// Compile `frame` at full resolution and return its [`RenderPlan::dump`](crate::RenderPlan).
+///
+/// Shows the surfaces, passes and ops a frame executes, for example to see why it is slow.
+pub fn dump_plan(
+    comp: &Composition,
+    frame: FrameIndex,
+    assets: &PreparedAssetStore,
+) -> WavyteResult<String> {
+    comp.validate()?;
+    let layout_offsets = crate::resolve_layout_offsets(comp, assets)?;
+    let eval = Evaluator::eval_frame_with_layout_unchecked(comp, frame, &layout_offsets)?;
+    let mut compile_cache = CompileCache::default();
+    let plan = compile_frame_with_cache(
+        comp,
+        &eval,
+        assets,
+        &mut compile_cache,
+        PlanView::scaled(1.0),
+    )?;
+    Ok(plan.dump())
+}
+
+/// Render a single frame unless its evaluated graph matches `prev`.
+///
+/// Intended for interactive scrubbing: pass the fingerprint returned by the previous call and
+/// keep displaying the previous pixels when this returns `None`. The fingerprint of `frame` is
+/// always returned so callers can thread it into the next call.
+pub fn render_frame_if_changed(
+    comp: &Composition,
+    frame: FrameIndex,
+    prev: Option<FrameFingerprint>,
+    backend: &mut dyn RenderBackend,
+    assets: &PreparedAssetStore,
+) -> WavyteResult<(Option<FrameRGBA>, FrameFingerprint)> {
+    comp.validate()?;
+    let layout_offsets = crate::resolve_layout_offsets(comp, assets)?;
+    let eval = Evaluator::eval_frame_with_layout_unchecked(comp, frame, &layout_offsets)?;
+    let fingerprint = fingerprint_eval(&eval);
+    if prev == Some(fingerprint) {
+        return Ok((None, fingerprint));
+    }
+    let mut compile_cache = CompileCache::default();
+    let plan = compile_frame_with_cache(
+        comp,
+        &eval,
+        assets,
+        &mut compile_cache,
+        PlanView::scaled(backend.render_scale()),
+    )?;
+    Ok((Some(execute_plan(backend, &plan, assets)?), fingerprint))
+}
+
 /// Render a range of frames (inclusive start, exclusive end).
 ///
 /// This is a convenience wrapper that repeatedly calls [`render_frame`].
+///
+/// `range` may be any sub-range of the composition (for example one shard of a distributed
+/// render). Every frame is evaluated at its global index, so transitions and clip timing that
+/// straddle the range boundary render identically to a full-length render of the same frames.
 pub fn render_frames(
     comp: &Composition,
     range: FrameRange,
@@ -62,6 +247,10 @@ pub struct RenderThreading {
     pub threads: Option<usize>,
     /// Enable static-frame fingerprint elision in parallel mode.
     pub static_frame_elision: bool,
+    /// With `static_frame_elision`, keep up to this many recently rendered frames keyed by
+    /// fingerprint so duplicates in later chunks (loops, holds) are reused instead of
+    /// re-rendered. `0` limits elision to duplicates within one chunk.
+    pub elision_cache_frames: usize,
 }
 
 impl Default for RenderThreading {
@@ -71,6 +260,7 @@ impl Default for RenderThreading {
             chunk_size: 64,
             threads: None,
             static_frame_elision: false,
+            elision_cache_frames: 0,
         }
     }
 }
@@ -84,9 +274,36 @@ pub struct RenderStats {
     pub frames_rendered: u64,
     /// Frames reused via static-frame elision.
     pub frames_elided: u64,
+    /// Largest total surface allocation of any single rendered frame, in bytes.
+    pub peak_surface_bytes: u64,
+    /// Asset pixel bytes decoded by the backend(s) while rendering the range.
+    pub bytes_decoded: u64,
+    /// Crop applied by the sink (for example [`crate::AlphaTrimSink`]), if any.
+    pub crop_rect: Option<PixelRect>,
+    /// Per-pass time summed over all rendered frames (and workers); zero unless
+    /// [`RenderSettings::profile_ops`] is set.
+    pub op_timings: OpTimings,
+}
+
+impl RenderStats {
+    fn accumulate(&mut self, other: &RenderStats) {
+        self.frames_total += other.frames_total;
+        self.frames_rendered += other.frames_rendered;
+        self.frames_elided += other.frames_elided;
+        self.peak_surface_bytes = self.peak_surface_bytes.max(other.peak_surface_bytes);
+        self.bytes_decoded += other.bytes_decoded;
+        self.op_timings.merge(&other.op_timings);
+    }
+
+    fn record_plan(&mut self, plan: &crate::compile::plan::RenderPlan) {
+        self.peak_surface_bytes = self.peak_surface_bytes.max(plan.surface_bytes());
+    }
 }
 
 /// Render a frame range and return both frame data and rendering stats.
+///
+/// Like [`render_frames`], sub-ranges are evaluated at global frame indices; chunking and
+/// static-frame elision only group frames within `range` and never change their pixels.
 pub fn render_frames_with_stats(
     comp: &Composition,
     range: FrameRange,
@@ -110,8 +327,17 @@ pub fn render_frames_with_stats(
         for f in range.start.0..range.end.0 {
             let eval =
                 Evaluator::eval_frame_with_layout_unchecked(comp, FrameIndex(f), &layout_offsets)?;
-            let plan = compile_frame_with_cache(comp, &eval, assets, &mut compile_cache)?;
+            let plan = compile_frame_with_cache(
+                comp,
+                &eval,
+                assets,
+                &mut compile_cache,
+                PlanView::scaled(backend.render_scale()),
+            )?;
+            stats.record_plan(&plan);
             out.push(execute_plan(backend, &plan, assets)?);
+            stats.bytes_decoded += backend.take_bytes_decoded();
+            stats.op_timings.merge(&backend.take_op_timings());
             stats.frames_total += 1;
             stats.frames_rendered += 1;
         }
@@ -119,11 +345,12 @@ pub fn render_frames_with_stats(
     }
 
     let worker_settings = backend.worker_render_settings().ok_or_else(|| {
-        WavyteError::evaluation(
+        WavyteError::unsupported(
             "parallel render requires backend worker settings support (CpuBackend)",
         )
     })?;
     let pool = build_thread_pool(threading.threads)?;
+    let mut elision_cache = EncodedFrameCache::new(threading.elision_cache_frames);
 
     let mut chunk_start = range.start.0;
     while chunk_start < range.end.0 {
@@ -138,11 +365,10 @@ pub fn render_frames_with_stats(
             threading,
             &pool,
             &layout_offsets,
+            &mut elision_cache,
         )?;
         out.append(&mut frames);
-        stats.frames_total += chunk_stats.frames_total;
-        stats.frames_rendered += chunk_stats.frames_rendered;
-        stats.frames_elided += chunk_stats.frames_elided;
+        stats.accumulate(&chunk_stats);
         chunk_start = chunk_end;
     }
 
@@ -162,6 +388,19 @@ pub struct RenderToMp4Opts {
     pub overwrite: bool,
     /// Render threading/chunking configuration.
     pub threading: RenderThreading,
+    /// Burn the SMPTE timecode of each frame into a corner (for review copies).
+    pub timecode: Option<crate::encode::timecode::TimecodeStyle>,
+    /// Video codec; the output path extension must match (for example `.webm` for VP9).
+    pub codec: crate::encode::ffmpeg::VideoCodec,
+    /// Keep the alpha channel instead of flattening over `bg_rgba`. Only `VideoCodec::Vp9` and
+    /// `VideoCodec::Qtrle` accept it; other codecs fail with a validation error before encoding.
+    pub preserve_alpha: bool,
+    /// Presentation index stamped on the first rendered frame (see
+    /// `EncodeConfig::first_frame_pts`); set it to `range.start` when rendering chunks to concat.
+    pub first_frame_pts: FrameIndex,
+    /// Also write a per-frame JSON metadata sidecar (visible node ids, bounds, fingerprints)
+    /// for `range` to this path; see [`write_frame_metadata_sidecar`](crate::write_frame_metadata_sidecar).
+    pub metadata_sidecar: Option<std::path::PathBuf>,
 }
 
 impl Default for RenderToMp4Opts {
@@ -174,6 +413,11 @@ impl Default for RenderToMp4Opts {
             bg_rgba: [0, 0, 0, 255],
             overwrite: true,
             threading: RenderThreading::default(),
+            timecode: None,
+            codec: crate::encode::ffmpeg::VideoCodec::H264,
+            preserve_alpha: false,
+            first_frame_pts: FrameIndex(0),
+            metadata_sidecar: None,
         }
     }
 }
@@ -184,7 +428,7 @@ impl Default for RenderToMp4Opts {
 /// error if it is not available.
 ///
 /// Notes:
-/// - v0.2.1 currently requires integer FPS (`comp.fps.den == 1`) for MP4 output.
+/// - Fractional frame rates (for example `24000/1001`) are passed to ffmpeg as exact ratios.
 /// - Frames are rendered as premultiplied RGBA8; the encoder can flatten alpha over `bg_rgba`.
 pub fn render_to_mp4(
     comp: &Composition,
@@ -217,21 +461,17 @@ pub fn render_to_mp4_with_stats(
         ));
     }
 
-    let fps = if comp.fps.den == 1 {
-        comp.fps.num
-    } else {
-        return Err(WavyteError::validation(
-            "render_to_mp4 currently requires integer fps (fps.den == 1)",
-        ));
-    };
-
     let out_path = out_path.into();
     if !crate::encode::ffmpeg::is_ffmpeg_on_path() {
-        return Err(WavyteError::evaluation(
+        return Err(WavyteError::unsupported(
             "ffmpeg is required for MP4 rendering, but was not found on PATH",
         ));
     }
 
+    if let Some(path) = &opts.metadata_sidecar {
+        crate::encode::sidecar::write_frame_metadata_sidecar(comp, opts.range, assets, path)?;
+    }
+
     let mut audio_tmp = TempFileGuard(None);
     let audio_manifest = crate::build_audio_manifest(comp, assets, opts.range)?;
     let audio_cfg = if audio_manifest.segments.is_empty() {
@@ -255,39 +495,140 @@ pub fn render_to_mp4_with_stats(
         })
     };
 
+    let canvas = scaled_canvas(comp.canvas, backend.render_scale());
     let cfg = crate::encode::ffmpeg::EncodeConfig {
-        width: comp.canvas.width,
-        height: comp.canvas.height,
-        fps,
+        width: canvas.width,
+        height: canvas.height,
+        fps: comp.fps,
         out_path,
         overwrite: opts.overwrite,
         audio: audio_cfg,
+        metadata: comp
+            .title
+            .iter()
+            .map(|t| ("title".to_string(), t.clone()))
+            .chain(
+                comp.description
+                    .iter()
+                    .map(|d| ("description".to_string(), d.clone())),
+            )
+            .collect(),
+        codec: opts.codec,
+        preserve_alpha: opts.preserve_alpha,
+        first_frame_pts: opts.first_frame_pts,
     };
 
     let mut enc = crate::encode::ffmpeg::FfmpegEncoder::new(cfg, opts.bg_rgba)?;
+    let stats = match opts.timecode {
+        Some(style) => {
+            let mut sink = crate::encode::timecode::TimecodeSink::new(enc, style);
+            let stats = stream_range_to_sink(
+                comp,
+                opts.range,
+                &opts.threading,
+                &mut sink,
+                backend,
+                assets,
+            )?;
+            enc = sink.into_inner();
+            stats
+        }
+        None => stream_range_to_sink(comp, opts.range, &opts.threading, &mut enc, backend, assets)?,
+    };
+    enc.finish()?;
+    drop(audio_tmp);
+    Ok(stats)
+}
+
+/// Render a frame range into a [`FrameSink`].
+///
+/// This is the sink-agnostic counterpart of [`render_to_mp4`]; [`FrameSink::finish`] is called
+/// after the last frame.
+pub fn render_to_sink(
+    comp: &Composition,
+    range: FrameRange,
+    sink: &mut dyn FrameSink,
+    backend: &mut dyn RenderBackend,
+    assets: &PreparedAssetStore,
+) -> WavyteResult<()> {
+    let _ = render_to_sink_with_stats(
+        comp,
+        range,
+        sink,
+        backend,
+        assets,
+        &RenderThreading::default(),
+    )?;
+    Ok(())
+}
+
+/// Render a frame range into a [`FrameSink`] and return rendering stats.
+pub fn render_to_sink_with_stats(
+    comp: &Composition,
+    range: FrameRange,
+    sink: &mut dyn FrameSink,
+    backend: &mut dyn RenderBackend,
+    assets: &PreparedAssetStore,
+    threading: &RenderThreading,
+) -> WavyteResult<RenderStats> {
+    comp.validate()?;
+    if range.end.0 > comp.duration.0 {
+        return Err(WavyteError::validation(
+            "render_to_sink range must be within composition duration",
+        ));
+    }
+    if range.is_empty() {
+        return Err(WavyteError::validation(
+            "render_to_sink range must be non-empty",
+        ));
+    }
+
+    let mut stats = stream_range_to_sink(comp, range, threading, sink, backend, assets)?;
+    sink.finish()?;
+    stats.crop_rect = sink.crop_rect();
+    Ok(stats)
+}
+
+fn stream_range_to_sink(
+    comp: &Composition,
+    range: FrameRange,
+    threading: &RenderThreading,
+    sink: &mut dyn FrameSink,
+    backend: &mut dyn RenderBackend,
+    assets: &PreparedAssetStore,
+) -> WavyteResult<RenderStats> {
+    let canvas = scaled_canvas(comp.canvas, backend.render_scale());
+    sink.begin(&SinkConfig {
+        width: canvas.width,
+        height: canvas.height,
+        fps: comp.fps,
+    })?;
+
     let mut stats = RenderStats::default();
-    let chunk_size = normalized_chunk_size(opts.threading.chunk_size);
+    let chunk_size = normalized_chunk_size(threading.chunk_size);
 
     let mut maybe_pool = None;
     let mut maybe_worker_settings = None;
     let layout_offsets = crate::resolve_layout_offsets(comp, assets)?;
     let mut compile_cache = CompileCache::default();
-    if opts.threading.parallel {
-        maybe_pool = Some(build_thread_pool(opts.threading.threads)?);
+    // Owned by this (sink-feeding) thread; workers never see it, so it needs no locking.
+    let mut elision_cache = EncodedFrameCache::new(threading.elision_cache_frames);
+    if threading.parallel {
+        maybe_pool = Some(build_thread_pool(threading.threads)?);
         maybe_worker_settings = Some(backend.worker_render_settings().ok_or_else(|| {
-            WavyteError::evaluation(
-                "parallel render_to_mp4 requires backend worker settings support (CpuBackend)",
+            WavyteError::unsupported(
+                "parallel streaming render requires backend worker settings support (CpuBackend)",
             )
         })?);
     }
 
-    let mut chunk_start = opts.range.start.0;
-    while chunk_start < opts.range.end.0 {
-        let chunk_end = (chunk_start + chunk_size).min(opts.range.end.0);
+    let mut chunk_start = range.start.0;
+    while chunk_start < range.end.0 {
+        let chunk_end = (chunk_start + chunk_size).min(range.end.0);
         let chunk = FrameRange::new(FrameIndex(chunk_start), FrameIndex(chunk_end))
             .map_err(|e| WavyteError::evaluation(format!("invalid chunk range: {e}")))?;
 
-        let chunk_out = if opts.threading.parallel {
+        let chunk_out = if threading.parallel {
             render_chunk_parallel_cpu_unique(
                 comp,
                 chunk,
@@ -295,9 +636,10 @@ pub fn render_to_mp4_with_stats(
                 maybe_worker_settings
                     .as_ref()
                     .expect("worker settings present when parallel"),
-                &opts.threading,
+                threading,
                 maybe_pool.as_ref().expect("pool present when parallel"),
                 &layout_offsets,
+                &mut elision_cache,
             )?
         } else {
             let (frames, stats_chunk) = render_chunk_sequential(
@@ -316,22 +658,42 @@ pub fn render_to_mp4_with_stats(
             }
         };
 
+        // Hand each unique frame over by value on its last use so sinks that take ownership avoid
+        // a copy; earlier uses (elided duplicates) are lent by reference.
+        let mut remaining = vec![0usize; chunk_out.unique_frames.len()];
         for &u in &chunk_out.frame_to_unique {
-            enc.encode_frame(chunk_out.unique_frames.get(u).ok_or_else(|| {
+            remaining[u] += 1;
+        }
+        let mut unique_frames = chunk_out
+            .unique_frames
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        for (offset, &u) in chunk_out.frame_to_unique.iter().enumerate() {
+            let idx = FrameIndex(chunk_start + offset as u64);
+            let slot = unique_frames.get_mut(u).ok_or_else(|| {
                 WavyteError::evaluation(
-                    "internal error: unique frame index out of range during encode",
+                    "internal error: unique frame index out of range while streaming",
                 )
-            })?)?;
+            })?;
+            remaining[u] -= 1;
+            if remaining[u] == 0 {
+                let frame = slot.take().ok_or_else(|| {
+                    WavyteError::evaluation("internal error: unique frame missing while streaming")
+                })?;
+                sink.write_frame_owned(idx, frame)?;
+            } else {
+                let frame = slot.as_ref().ok_or_else(|| {
+                    WavyteError::evaluation("internal error: unique frame missing while streaming")
+                })?;
+                sink.write_frame(idx, frame)?;
+            }
         }
 
-        stats.frames_total += chunk_out.stats.frames_total;
-        stats.frames_rendered += chunk_out.stats.frames_rendered;
-        stats.frames_elided += chunk_out.stats.frames_elided;
+        stats.accumulate(&chunk_out.stats);
         chunk_start = chunk_end;
     }
 
-    enc.finish()?;
-    drop(audio_tmp);
     Ok(stats)
 }
 
@@ -344,21 +706,28 @@ fn render_chunk_sequential(
     compile_cache: &mut CompileCache,
 ) -> WavyteResult<(Vec<FrameRGBA>, RenderStats)> {
     let mut out = Vec::with_capacity(range.len_frames() as usize);
+    let total = range.len_frames();
+    let mut stats = RenderStats {
+        frames_total: total,
+        frames_rendered: total,
+        ..RenderStats::default()
+    };
     for f in range.start.0..range.end.0 {
         let eval =
             Evaluator::eval_frame_with_layout_unchecked(comp, FrameIndex(f), layout_offsets)?;
-        let plan = compile_frame_with_cache(comp, &eval, assets, compile_cache)?;
+        let plan = compile_frame_with_cache(
+            comp,
+            &eval,
+            assets,
+            compile_cache,
+            PlanView::scaled(backend.render_scale()),
+        )?;
+        stats.record_plan(&plan);
         out.push(execute_plan(backend, &plan, assets)?);
+        stats.bytes_decoded += backend.take_bytes_decoded();
+        stats.op_timings.merge(&backend.take_op_timings());
     }
-    let total = range.len_frames();
-    Ok((
-        out,
-        RenderStats {
-            frames_total: total,
-            frames_rendered: total,
-            frames_elided: 0,
-        },
-    ))
+    Ok((out, stats))
 }
 
 struct ChunkParallelOut {
@@ -367,6 +736,51 @@ struct ChunkParallelOut {
     stats: RenderStats,
 }
 
+/// Bounded LRU of rendered frames keyed by fingerprint, carried across chunks for
+/// [`RenderThreading::elision_cache_frames`].
+struct EncodedFrameCache {
+    frames: HashMap<FrameFingerprint, FrameRGBA>,
+    lru: VecDeque<FrameFingerprint>,
+    capacity: usize,
+}
+
+impl EncodedFrameCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            frames: HashMap::new(),
+            lru: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    fn get(&mut self, key: &FrameFingerprint) -> Option<FrameRGBA> {
+        let frame = self.frames.get(key)?.clone();
+        self.touch(*key);
+        Some(frame)
+    }
+
+    fn insert(&mut self, key: FrameFingerprint, frame: &FrameRGBA) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.frames.insert(key, frame.clone());
+        self.touch(key);
+        while self.lru.len() > self.capacity {
+            if let Some(old) = self.lru.pop_front() {
+                self.frames.remove(&old);
+            }
+        }
+    }
+
+    fn touch(&mut self, key: FrameFingerprint) {
+        if let Some(pos) = self.lru.iter().position(|x| *x == key) {
+            self.lru.remove(pos);
+        }
+        self.lru.push_back(key);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn render_chunk_parallel_cpu_unique(
     comp: &Composition,
     range: FrameRange,
@@ -375,6 +789,7 @@ fn render_chunk_parallel_cpu_unique(
     threading: &RenderThreading,
     pool: &rayon::ThreadPool,
     layout_offsets: &crate::LayoutOffsets,
+    elision_cache: &mut EncodedFrameCache,
 ) -> WavyteResult<ChunkParallelOut> {
     let mut evals = Vec::with_capacity(range.len_frames() as usize);
     for f in range.start.0..range.end.0 {
@@ -386,6 +801,7 @@ fn render_chunk_parallel_cpu_unique(
     }
 
     let mut unique_indices = Vec::<usize>::with_capacity(evals.len());
+    let mut unique_fingerprints = Vec::<FrameFingerprint>::new();
     let mut frame_to_unique = Vec::<usize>::with_capacity(evals.len());
     if threading.static_frame_elision {
         let mut first = HashMap::<FrameFingerprint, usize>::new();
@@ -396,6 +812,7 @@ fn render_chunk_parallel_cpu_unique(
             } else {
                 let slot = unique_indices.len();
                 unique_indices.push(idx);
+                unique_fingerprints.push(fingerprint);
                 first.insert(fingerprint, slot);
                 frame_to_unique.push(slot);
             }
@@ -407,8 +824,23 @@ fn render_chunk_parallel_cpu_unique(
         }
     }
 
+    // Unique frames already rendered by an earlier chunk are taken from the cache.
+    let cached = (0..unique_indices.len())
+        .map(|slot| {
+            unique_fingerprints
+                .get(slot)
+                .and_then(|fp| elision_cache.get(fp))
+        })
+        .collect::<Vec<_>>();
+    let to_render = unique_indices
+        .iter()
+        .zip(&cached)
+        .filter(|(_, hit)| hit.is_none())
+        .map(|(idx, _)| *idx)
+        .collect::<Vec<_>>();
+
     let rendered = pool.install(|| {
-        unique_indices
+        to_render
             .par_iter()
             .map_init(
                 || {
@@ -417,33 +849,65 @@ fn render_chunk_parallel_cpu_unique(
                         CompileCache::default(),
                     )
                 },
-                |(worker_backend, worker_compile_cache), eval_idx| -> WavyteResult<FrameRGBA> {
+                |(worker_backend, worker_compile_cache),
+                 eval_idx|
+                 -> WavyteResult<(FrameRGBA, u64, u64, OpTimings)> {
                     let eval = &evals[*eval_idx];
-                    let plan = compile_frame_with_cache(comp, eval, assets, worker_compile_cache)?;
-                    worker_backend.render_plan(&plan, assets)
+                    let plan = compile_frame_with_cache(
+                        comp,
+                        eval,
+                        assets,
+                        worker_compile_cache,
+                        PlanView::scaled(settings.scale),
+                    )?;
+                    let frame = worker_backend.render_plan(&plan, assets)?;
+                    Ok((
+                        frame,
+                        plan.surface_bytes(),
+                        worker_backend.take_bytes_decoded(),
+                        worker_backend.take_op_timings(),
+                    ))
                 },
             )
             .collect::<Vec<_>>()
     });
 
-    let mut unique_frames = Vec::<FrameRGBA>::with_capacity(rendered.len());
-    for item in rendered {
-        unique_frames.push(item?);
+    let total = evals.len() as u64;
+    let rendered_count = to_render.len() as u64;
+    let mut stats = RenderStats {
+        frames_total: total,
+        frames_rendered: rendered_count,
+        frames_elided: total.saturating_sub(rendered_count),
+        ..RenderStats::default()
+    };
+    let mut rendered = rendered.into_iter();
+    let mut unique_frames = Vec::<FrameRGBA>::with_capacity(unique_indices.len());
+    for (slot, hit) in cached.into_iter().enumerate() {
+        if let Some(frame) = hit {
+            unique_frames.push(frame);
+            continue;
+        }
+        let (frame, surface_bytes, bytes_decoded, op_timings) =
+            rendered.next().ok_or_else(|| {
+                WavyteError::evaluation("internal error: rendered frame missing for unique slot")
+            })??;
+        stats.peak_surface_bytes = stats.peak_surface_bytes.max(surface_bytes);
+        stats.bytes_decoded += bytes_decoded;
+        stats.op_timings.merge(&op_timings);
+        if let Some(fp) = unique_fingerprints.get(slot) {
+            elision_cache.insert(*fp, &frame);
+        }
+        unique_frames.push(frame);
     }
 
-    let total = evals.len() as u64;
-    let rendered_count = unique_indices.len() as u64;
     Ok(ChunkParallelOut {
         unique_frames,
         frame_to_unique,
-        stats: RenderStats {
-            frames_total: total,
-            frames_rendered: rendered_count,
-            frames_elided: total.saturating_sub(rendered_count),
-        },
+        stats,
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_chunk_parallel_cpu(
     comp: &Composition,
     range: FrameRange,
@@ -452,6 +916,7 @@ fn render_chunk_parallel_cpu(
     threading: &RenderThreading,
     pool: &rayon::ThreadPool,
     layout_offsets: &crate::LayoutOffsets,
+    elision_cache: &mut EncodedFrameCache,
 ) -> WavyteResult<(Vec<FrameRGBA>, RenderStats)> {
     let chunk_out = render_chunk_parallel_cpu_unique(
         comp,
@@ -461,6 +926,7 @@ fn render_chunk_parallel_cpu(
         threading,
         pool,
         layout_offsets,
+        elision_cache,
     )?;
 
     let mut unique_frames = chunk_out
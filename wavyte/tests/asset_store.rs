@@ -1,8 +1,13 @@
-use std::io::Cursor;
+#![cfg(feature = "render")]
+
+use std::{collections::HashMap, io::Cursor};
 
 use wavyte::{
-    Asset, Canvas, Composition, Fps, FrameIndex, ImageAsset, PathAsset, PreparedAsset,
-    PreparedAssetStore, normalize_rel_path,
+    Asset, AssetSource, BackendKind, Canvas, ClipBuilder, Composition, CompositionBuilder,
+    ErrorKind, Fps, FrameIndex, FrameRange, ImageAsset, ImageSequenceAsset, InMemoryAssetMap,
+    PathAsset, PreparedAsset, PreparedAssetStore, RenderSettings, TrackBuilder, WavyteError,
+    WavyteResult, ZipAssetSource, create_backend, normalize_rel_path, render_frame,
+    render_frame_to_vec,
 };
 
 fn temp_dir(name: &str) -> std::path::PathBuf {
@@ -16,6 +21,57 @@ fn temp_dir(name: &str) -> std::path::PathBuf {
     ))
 }
 
+fn png_bytes(width: u32, height: u32, rgba: [u8; 4]) -> Vec<u8> {
+    let pixels = rgba.repeat((width * height) as usize);
+    let img = image::RgbaImage::from_raw(width, height, pixels).unwrap();
+    let mut buf = Vec::new();
+    image::DynamicImage::ImageRgba8(img)
+        .write_to(&mut Cursor::new(&mut buf), image::ImageFormat::Png)
+        .unwrap();
+    buf
+}
+
+fn single_clip_comp(asset_key: &str, asset: Asset, size: u32) -> Composition {
+    CompositionBuilder::new(
+        Fps::new(30, 1).unwrap(),
+        Canvas {
+            width: size,
+            height: size,
+        },
+        FrameIndex(1),
+    )
+    .asset(asset_key, asset)
+    .unwrap()
+    .track(
+        TrackBuilder::new("main")
+            .clip(
+                ClipBuilder::new(
+                    "c0",
+                    asset_key,
+                    FrameRange::new(FrameIndex(0), FrameIndex(1)).unwrap(),
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap(),
+    )
+    .build()
+    .unwrap()
+}
+
+/// Custom source used to check that every asset read goes through the trait.
+struct HashMapSource(HashMap<String, Vec<u8>>);
+
+impl AssetSource for HashMapSource {
+    fn read_bytes(&self, norm_path: &str) -> WavyteResult<Vec<u8>> {
+        self.0
+            .get(norm_path)
+            .cloned()
+            .ok_or_else(|| WavyteError::io(format!("no entry '{norm_path}'")))
+    }
+}
+
 #[test]
 fn prepare_loads_image_asset() {
     let tmp = temp_dir("asset_store_prepare_image");
@@ -34,6 +90,7 @@ fn prepare_loads_image_asset() {
         "img".to_string(),
         Asset::Image(ImageAsset {
             source: "img.png".to_string(),
+            sampler: None,
         }),
     );
     let comp = Composition {
@@ -46,6 +103,9 @@ fn prepare_loads_image_asset() {
         assets,
         tracks: vec![],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     };
 
     let store = PreparedAssetStore::prepare(&comp, &tmp).unwrap();
@@ -74,6 +134,9 @@ fn prepare_loads_inline_path_without_external_io() {
         "p0".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L10,0 L10,10 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
         }),
     );
     let comp = Composition {
@@ -86,6 +149,9 @@ fn prepare_loads_inline_path_without_external_io() {
         assets,
         tracks: vec![],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     };
 
     let store = PreparedAssetStore::prepare(&comp, ".").unwrap();
@@ -96,3 +162,406 @@ fn prepare_loads_inline_path_without_external_io() {
     };
     assert!(!path.path.is_empty());
 }
+
+#[test]
+fn missing_asset_file_reports_io_kind() {
+    let tmp = temp_dir("asset_store_missing");
+    std::fs::create_dir_all(&tmp).unwrap();
+
+    let mut assets = std::collections::BTreeMap::new();
+    assets.insert(
+        "img".to_string(),
+        Asset::Image(ImageAsset {
+            source: "does_not_exist.png".to_string(),
+            sampler: None,
+        }),
+    );
+    let comp = Composition {
+        fps: Fps::new(30, 1).unwrap(),
+        canvas: Canvas {
+            width: 1,
+            height: 1,
+        },
+        duration: FrameIndex(1),
+        assets,
+        tracks: vec![],
+        seed: 1,
+        background: None,
+        title: None,
+        description: None,
+    };
+
+    let err = PreparedAssetStore::prepare(&comp, &tmp).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Io, "{err}");
+
+    std::fs::remove_dir_all(&tmp).ok();
+}
+
+#[test]
+fn in_memory_assets_render_same_frame_as_disk() {
+    let tmp = temp_dir("asset_store_in_memory");
+    std::fs::create_dir_all(tmp.join("img")).unwrap();
+
+    let pixels = (0..4 * 4)
+        .flat_map(|i| [(i * 16) as u8, 200 - (i * 8) as u8, 90, 255])
+        .collect::<Vec<u8>>();
+    let img = image::RgbaImage::from_raw(4, 4, pixels).unwrap();
+    let mut png = Vec::new();
+    image::DynamicImage::ImageRgba8(img)
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    std::fs::write(tmp.join("img/a.png"), &png).unwrap();
+
+    let comp = CompositionBuilder::new(
+        Fps::new(30, 1).unwrap(),
+        Canvas {
+            width: 8,
+            height: 8,
+        },
+        FrameIndex(1),
+    )
+    .image_asset("img", "img/a.png")
+    .unwrap()
+    .track(
+        TrackBuilder::new("main")
+            .clip(
+                ClipBuilder::new(
+                    "c0",
+                    "img",
+                    FrameRange::new(FrameIndex(0), FrameIndex(1)).unwrap(),
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap(),
+    )
+    .build()
+    .unwrap();
+    let settings = RenderSettings {
+        clear_rgba: Some([0, 0, 0, 255]),
+        text_snap: wavyte::TextSnap::None,
+        scale: 1.0,
+        profile_ops: false,
+        missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
+    };
+
+    let store = PreparedAssetStore::prepare(&comp, &tmp).unwrap();
+    let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
+    let from_disk = render_frame(&comp, FrameIndex(0), backend.as_mut(), &store).unwrap();
+
+    let mut mem = InMemoryAssetMap::new();
+    mem.insert("./img/a.png", png).unwrap();
+    let json = serde_json::to_string(&comp).unwrap();
+    let from_memory = render_frame_to_vec(&json, &mem, FrameIndex(0), &settings).unwrap();
+
+    assert_eq!(from_memory.width, from_disk.width);
+    assert_eq!(from_memory.height, from_disk.height);
+    assert_eq!(from_memory.data, from_disk.data);
+    assert_ne!(from_disk.data[..4], [0, 0, 0, 255]);
+
+    let missing =
+        render_frame_to_vec(&json, &InMemoryAssetMap::new(), FrameIndex(0), &settings).unwrap_err();
+    assert_eq!(missing.kind(), ErrorKind::Io, "{missing}");
+
+    std::fs::remove_dir_all(&tmp).ok();
+}
+
+#[test]
+fn custom_asset_source_serves_images() {
+    let source = HashMapSource(HashMap::from([(
+        "embedded/red.png".to_string(),
+        png_bytes(2, 2, [255, 0, 0, 255]),
+    )]));
+    let comp = single_clip_comp(
+        "img",
+        Asset::Image(ImageAsset {
+            source: "embedded/red.png".to_string(),
+            sampler: None,
+        }),
+        2,
+    );
+
+    let store = PreparedAssetStore::prepare_with_source(&comp, &source).unwrap();
+    let settings = RenderSettings::default();
+    let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
+    let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &store).unwrap();
+    assert_eq!(frame.data, [255, 0, 0, 255].repeat(4));
+}
+
+/// In-memory source whose reads of `broken` fail even though the file exists.
+struct BrokenReadSource {
+    files: InMemoryAssetMap,
+    broken: &'static str,
+}
+
+impl AssetSource for BrokenReadSource {
+    fn read_bytes(&self, norm_path: &str) -> WavyteResult<Vec<u8>> {
+        if norm_path == self.broken {
+            return Err(WavyteError::decode(format!("corrupt entry '{norm_path}'")));
+        }
+        self.files.read_bytes(norm_path)
+    }
+
+    fn exists(&self, norm_path: &str) -> bool {
+        self.files.exists(norm_path)
+    }
+}
+
+fn sequence_asset() -> Asset {
+    Asset::ImageSequence(ImageSequenceAsset {
+        pattern: "frames/img_{:04}.png".to_string(),
+        fps: Fps::new(10, 1).unwrap(),
+        start_index: 1,
+        loop_mode: Default::default(),
+        sampler: None,
+    })
+}
+
+#[test]
+fn image_sequence_ends_only_at_a_missing_frame() {
+    let mut files = InMemoryAssetMap::new();
+    for i in 1..=3 {
+        files
+            .insert(
+                &format!("frames/img_{i:04}.png"),
+                png_bytes(2, 2, [255, 0, 0, 255]),
+            )
+            .unwrap();
+    }
+    let comp = single_clip_comp("seq", sequence_asset(), 2);
+
+    let store = PreparedAssetStore::prepare_with_source(&comp, &files).unwrap();
+    let PreparedAsset::ImageSequence(seq) = store.get(store.id_for_key("seq").unwrap()).unwrap()
+    else {
+        panic!("expected image sequence");
+    };
+    assert_eq!(seq.frames.len(), 3);
+
+    let broken = BrokenReadSource {
+        files,
+        broken: "frames/img_0002.png",
+    };
+    let err = PreparedAssetStore::prepare_with_source(&comp, &broken).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Decode, "{err}");
+}
+
+#[test]
+fn svg_image_hrefs_resolve_through_asset_source() {
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="4">
+        <image href="../tex/green.png" x="0" y="0" width="4" height="4"/>
+    </svg>"#;
+    let png = png_bytes(4, 4, [0, 255, 0, 255]);
+    let mut mem = InMemoryAssetMap::new();
+    mem.insert("art/logo.svg", svg.as_bytes()).unwrap();
+    mem.insert("tex/green.png", png.clone()).unwrap();
+    let comp = single_clip_comp(
+        "logo",
+        Asset::Svg(wavyte::SvgAsset {
+            source: "art/logo.svg".to_string(),
+        }),
+        4,
+    );
+
+    let store = PreparedAssetStore::prepare_with_source(&comp, &mem).unwrap();
+    let PreparedAsset::Svg(prepared) = store.get(store.id_for_key("logo").unwrap()).unwrap() else {
+        panic!("expected svg asset");
+    };
+    fn collect_images(group: &usvg::Group, out: &mut Vec<usvg::ImageKind>) {
+        for node in group.children() {
+            match node {
+                usvg::Node::Image(img) => out.push(img.kind().clone()),
+                usvg::Node::Group(g) => collect_images(g, out),
+                _ => {}
+            }
+        }
+    }
+    let mut images = Vec::new();
+    collect_images(prepared.tree.root(), &mut images);
+    assert_eq!(images.len(), 1);
+    let usvg::ImageKind::PNG(data) = &images[0] else {
+        panic!("expected png image");
+    };
+    assert_eq!(data.as_slice(), png.as_slice());
+}
+
+/// Minimal zip writer for bundle tests (stored or deflated entries, no zip64).
+fn zip_bundle(files: &[(&str, &[u8], bool)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+    for &(name, data, deflate) in files {
+        let (method, body) = if deflate {
+            (8u16, miniz_oxide::deflate::compress_to_vec(data, 6))
+        } else {
+            (0u16, data.to_vec())
+        };
+        let crc = crc32fast::hash(data);
+        let offset = out.len() as u32;
+        let common = |buf: &mut Vec<u8>| {
+            buf.extend_from_slice(&20u16.to_le_bytes()); // version needed
+            buf.extend_from_slice(&0u16.to_le_bytes()); // flags
+            buf.extend_from_slice(&method.to_le_bytes());
+            buf.extend_from_slice(&[0; 4]); // mod time/date
+            buf.extend_from_slice(&crc.to_le_bytes());
+            buf.extend_from_slice(&(body.len() as u32).to_le_bytes());
+            buf.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            buf.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            buf.extend_from_slice(&0u16.to_le_bytes()); // extra len
+        };
+        out.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        common(&mut out);
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(&body);
+
+        central.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        common(&mut central);
+        central.extend_from_slice(&[0; 6]); // comment len, disk, internal attrs
+        central.extend_from_slice(&0u32.to_le_bytes()); // external attrs
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+    let cd_offset = out.len() as u32;
+    out.extend_from_slice(&central);
+    out.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    out.extend_from_slice(&[0; 4]); // disk numbers
+    out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    out.extend_from_slice(&cd_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // comment len
+    out
+}
+
+#[test]
+fn zip_bundle_renders_same_frame_as_loose_directory() {
+    let tmp = temp_dir("asset_store_zip_bundle");
+    std::fs::create_dir_all(tmp.join("img")).unwrap();
+
+    let pixels = (0..8 * 8)
+        .flat_map(|i| [(i * 4) as u8, 255 - (i * 3) as u8, 40, 255])
+        .collect::<Vec<u8>>();
+    let img = image::RgbaImage::from_raw(8, 8, pixels).unwrap();
+    let mut png = Vec::new();
+    image::DynamicImage::ImageRgba8(img)
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    std::fs::write(tmp.join("img/a.png"), &png).unwrap();
+
+    let comp = single_clip_comp(
+        "img",
+        Asset::Image(ImageAsset {
+            source: "img/a.png".to_string(),
+            sampler: None,
+        }),
+        8,
+    );
+    let json = serde_json::to_vec(&comp).unwrap();
+    let bundle_path = tmp.join("scene.wavyte");
+    std::fs::write(
+        &bundle_path,
+        zip_bundle(&[
+            ("composition.json", &json, true),
+            ("img/", b"", false),
+            ("img/a.png", &png, false),
+        ]),
+    )
+    .unwrap();
+
+    let settings = RenderSettings::default();
+    let loose_store = PreparedAssetStore::prepare(&comp, &tmp).unwrap();
+    let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
+    let from_dir = render_frame(&comp, FrameIndex(0), backend.as_mut(), &loose_store).unwrap();
+
+    let bundle = ZipAssetSource::open(&bundle_path).unwrap();
+    assert_eq!(
+        bundle.entry_names().collect::<Vec<_>>(),
+        ["composition.json", "img/a.png"]
+    );
+    let bundled_comp = bundle.load_composition().unwrap();
+    let bundle_store = PreparedAssetStore::prepare_with_source(&bundled_comp, &bundle).unwrap();
+    let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
+    let from_zip = render_frame(
+        &bundled_comp,
+        FrameIndex(0),
+        backend.as_mut(),
+        &bundle_store,
+    )
+    .unwrap();
+
+    assert_eq!(from_zip.data, from_dir.data);
+    // Second read is served from the entry cache.
+    assert_eq!(bundle.read_bytes("img/a.png").unwrap(), png);
+
+    std::fs::remove_dir_all(&tmp).ok();
+}
+
+#[test]
+fn zip_bundle_reports_missing_assets_up_front() {
+    let comp = single_clip_comp(
+        "img",
+        Asset::Image(ImageAsset {
+            source: "img/missing.png".to_string(),
+            sampler: None,
+        }),
+        4,
+    );
+    let json = serde_json::to_vec(&comp).unwrap();
+    let bundle =
+        ZipAssetSource::from_bytes(zip_bundle(&[("composition.json", &json, false)])).unwrap();
+
+    let err = bundle.load_composition().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Schema, "{err}");
+    assert!(err.to_string().contains("img/missing.png"), "{err}");
+
+    let seq_comp = single_clip_comp("seq", sequence_asset(), 4);
+    let seq_json = serde_json::to_vec(&seq_comp).unwrap();
+    let seq_bundle =
+        ZipAssetSource::from_bytes(zip_bundle(&[("composition.json", &seq_json, false)])).unwrap();
+    let err = seq_bundle.load_composition().unwrap_err();
+    assert!(err.to_string().contains("frames/img_0001.png"), "{err}");
+
+    let font = std::fs::read("tests/data/fonts/Inconsolata-Regular.ttf").unwrap();
+    let text_comp = single_clip_comp(
+        "t",
+        Asset::Text(wavyte::TextAsset {
+            fallback_fonts: vec!["fonts/fallback.ttf".to_string()],
+            ..wavyte::text_asset("Hi", "fonts/main.ttf", 12.0)
+        }),
+        4,
+    );
+    let text_json = serde_json::to_vec(&text_comp).unwrap();
+    let text_bundle = ZipAssetSource::from_bytes(zip_bundle(&[
+        ("composition.json", &text_json, false),
+        ("fonts/main.ttf", &font, false),
+    ]))
+    .unwrap();
+    let err = text_bundle.load_composition().unwrap_err();
+    assert!(err.to_string().contains("fonts/fallback.ttf"), "{err}");
+
+    let mut outer = single_clip_comp(
+        "img",
+        Asset::Path(PathAsset {
+            svg_path_d: "M0,0 L4,0 L4,4 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
+        }),
+        4,
+    );
+    outer.assets.insert(
+        "inner".to_string(),
+        Asset::Composition(wavyte::CompositionAsset {
+            composition: Box::new(comp),
+        }),
+    );
+    let outer_json = serde_json::to_vec(&outer).unwrap();
+    let nested_bundle =
+        ZipAssetSource::from_bytes(zip_bundle(&[("composition.json", &outer_json, false)]))
+            .unwrap();
+    let err = nested_bundle.load_composition().unwrap_err();
+    assert!(err.to_string().contains("img/missing.png"), "{err}");
+
+    let corrupt = ZipAssetSource::from_bytes(b"not a zip".to_vec()).unwrap_err();
+    assert_eq!(corrupt.kind(), ErrorKind::Decode, "{corrupt}");
+}
@@ -0,0 +1,63 @@
+//! Exercises the lean `core-only` surface (`--no-default-features`): everything used here must
+//! stay available without the `render` feature.
+
+mod core_only {
+    use wavyte::{
+        Anim, Ease, FrameIndex, InterpMode, Keyframe, Keyframes, LoopMode, SampleCtx, Vec2,
+        WavyteError, delay, loop_,
+    };
+
+    fn ctx(frame: u64) -> SampleCtx {
+        SampleCtx {
+            frame: FrameIndex(frame),
+            fps: wavyte::Fps::new(30, 1).unwrap(),
+            clip_local: FrameIndex(frame),
+            seed: 0,
+        }
+    }
+
+    fn ramp() -> Anim<f64> {
+        Anim::Keyframes(Keyframes {
+            keys: vec![
+                Keyframe {
+                    frame: FrameIndex(0),
+                    value: 0.0,
+                    ease: Ease::Linear,
+                },
+                Keyframe {
+                    frame: FrameIndex(10),
+                    value: 10.0,
+                    ease: Ease::Linear,
+                },
+            ],
+            mode: InterpMode::Linear,
+            default: None,
+        })
+    }
+
+    #[test]
+    fn ease_sampling_is_available() {
+        assert_eq!(Ease::Linear.apply(0.25), 0.25);
+        assert_eq!(Ease::InOutQuad.apply(0.0), 0.0);
+        assert_eq!(Ease::InOutQuad.apply(1.0), 1.0);
+    }
+
+    #[test]
+    fn anim_sampling_and_ops_are_available() {
+        assert_eq!(ramp().sample(ctx(5)).unwrap(), 5.0);
+        assert_eq!(delay(ramp(), 4).sample(ctx(6)).unwrap(), 2.0);
+        assert_eq!(
+            loop_(ramp(), 5, LoopMode::Repeat).sample(ctx(7)).unwrap(),
+            2.0
+        );
+
+        let v = Anim::constant(Vec2::new(1.0, 2.0)).sample(ctx(3)).unwrap();
+        assert_eq!(v, Vec2::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn errors_are_available() {
+        let err = WavyteError::animation("bad key");
+        assert_eq!(err.kind(), wavyte::ErrorKind::Evaluation);
+    }
+}
@@ -0,0 +1,291 @@
+#![cfg(feature = "render")]
+
+use std::{collections::BTreeMap, process::Command};
+
+use wavyte::{
+    Anim, Asset, BackendKind, BlendMode, Canvas, Clip, ClipProps, Composition, FrameIndex,
+    FrameRange, PathAsset, PreparedAssetStore, RenderSettings, RenderToMp4Opts, Track, Transform2D,
+    VideoCodec, create_backend, render_to_mp4,
+};
+
+fn ffmpeg_tools_available() -> bool {
+    ["ffmpeg", "ffprobe"].iter().all(|tool| {
+        Command::new(tool)
+            .arg("-version")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+fn square_comp(frames: u64) -> Composition {
+    square_comp_with_opacity(frames, 1.0)
+}
+
+fn square_comp_with_opacity(frames: u64, opacity: f64) -> Composition {
+    let mut assets = BTreeMap::new();
+    assets.insert(
+        "square".to_string(),
+        Asset::Path(PathAsset {
+            svg_path_d: "M8,8 L40,8 L40,40 L8,40 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: wavyte::FillRule::NonZero,
+        }),
+    );
+    Composition {
+        fps: wavyte::Fps::new(30, 1).unwrap(),
+        canvas: Canvas {
+            width: 64,
+            height: 48,
+        },
+        duration: FrameIndex(frames),
+        assets,
+        tracks: vec![Track {
+            name: "main".to_string(),
+            z_base: 0,
+            layout_mode: wavyte::LayoutMode::Absolute,
+            layout_gap_px: 0.0,
+            layout_padding: wavyte::Edges::default(),
+            layout_align_x: wavyte::LayoutAlignX::Start,
+            layout_align_y: wavyte::LayoutAlignY::Start,
+            layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
+            clips: vec![Clip {
+                id: "c0".to_string(),
+                asset: "square".to_string(),
+                range: FrameRange::new(FrameIndex(0), FrameIndex(frames)).unwrap(),
+                props: ClipProps {
+                    transform: Anim::constant(Transform2D::default()),
+                    opacity: Anim::constant(opacity),
+                    blend: BlendMode::Normal,
+                    opacity_at_composite: false,
+                    wiggle: None,
+                    box_anchor: None,
+                    aspect_ratio: None,
+                    fit: wavyte::ContentFit::None,
+                },
+                z_offset: 0,
+                effects: vec![],
+                transition_in: None,
+                transition_out: None,
+                seed: None,
+                loop_content: false,
+                time_remap: None,
+                reverse: false,
+            }],
+        }],
+        seed: 1,
+        background: None,
+        title: None,
+        description: None,
+    }
+}
+
+#[test]
+fn webm_output_contains_vp9_stream() {
+    if !ffmpeg_tools_available() {
+        return;
+    }
+    let comp = square_comp(5);
+    let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+    let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+    let out = std::env::temp_dir().join(format!("wavyte_vp9_{}.webm", std::process::id()));
+
+    render_to_mp4(
+        &comp,
+        &out,
+        RenderToMp4Opts {
+            range: FrameRange::new(FrameIndex(0), comp.duration).unwrap(),
+            codec: VideoCodec::Vp9,
+            preserve_alpha: true,
+            ..RenderToMp4Opts::default()
+        },
+        backend.as_mut(),
+        &assets,
+    )
+    .unwrap();
+
+    let probe = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "stream=codec_name,nb_read_frames",
+            "-count_frames",
+            "-of",
+            "default=noprint_wrappers=1",
+        ])
+        .arg(&out)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_file(&out);
+    assert!(probe.status.success());
+    let info = String::from_utf8_lossy(&probe.stdout);
+    assert!(info.contains("codec_name=vp9"), "{info}");
+    assert!(info.contains("nb_read_frames=5"), "{info}");
+}
+
+#[test]
+fn qtrle_mov_output_keeps_alpha_pixel_format() {
+    if !ffmpeg_tools_available() {
+        return;
+    }
+    let comp = square_comp_with_opacity(3, 0.5);
+    let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+    let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+    let out = std::env::temp_dir().join(format!("wavyte_qtrle_{}.mov", std::process::id()));
+
+    render_to_mp4(
+        &comp,
+        &out,
+        RenderToMp4Opts {
+            range: FrameRange::new(FrameIndex(0), comp.duration).unwrap(),
+            codec: VideoCodec::Qtrle,
+            preserve_alpha: true,
+            ..RenderToMp4Opts::default()
+        },
+        backend.as_mut(),
+        &assets,
+    )
+    .unwrap();
+
+    let probe = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "stream=codec_name,pix_fmt",
+            "-of",
+            "default=noprint_wrappers=1",
+        ])
+        .arg(&out)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_file(&out);
+    assert!(probe.status.success());
+    let info = String::from_utf8_lossy(&probe.stdout);
+    assert!(info.contains("codec_name=qtrle"), "{info}");
+    assert!(info.contains("pix_fmt=argb"), "{info}");
+}
+
+#[test]
+fn mismatched_container_is_rejected_before_encoding() {
+    let comp = square_comp(2);
+    let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+    let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+    let out = std::env::temp_dir().join(format!("wavyte_vp9_bad_{}.mp4", std::process::id()));
+    let err = render_to_mp4(
+        &comp,
+        &out,
+        RenderToMp4Opts {
+            range: FrameRange::new(FrameIndex(0), comp.duration).unwrap(),
+            codec: VideoCodec::Vp9,
+            ..RenderToMp4Opts::default()
+        },
+        backend.as_mut(),
+        &assets,
+    )
+    .unwrap_err();
+    assert!(!out.exists());
+    if ffmpeg_tools_available() {
+        assert_eq!(err.kind(), wavyte::ErrorKind::Schema, "{err}");
+    }
+}
+
+/// Presentation time in seconds of each video frame in `path`, in decode order.
+fn frame_pts_secs(path: &std::path::Path) -> Vec<f64> {
+    let probe = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "frame=pts_time",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(path)
+        .output()
+        .unwrap();
+    assert!(probe.status.success());
+    String::from_utf8_lossy(&probe.stdout)
+        .lines()
+        .filter_map(|l| l.trim().trim_end_matches(',').parse().ok())
+        .collect()
+}
+
+#[test]
+fn chunks_with_consecutive_first_frame_pts_concat_into_continuous_timeline() {
+    if !ffmpeg_tools_available() {
+        return;
+    }
+    let comp = square_comp(10);
+    let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+    let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+    let dir = std::env::temp_dir().join(format!("wavyte_chunks_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut list = String::new();
+    for (i, (start, end)) in [(0, 4), (4, 10)].into_iter().enumerate() {
+        let chunk = dir.join(format!("chunk_{i}.mp4"));
+        render_to_mp4(
+            &comp,
+            &chunk,
+            RenderToMp4Opts {
+                range: FrameRange::new(FrameIndex(start), FrameIndex(end)).unwrap(),
+                first_frame_pts: FrameIndex(start),
+                ..RenderToMp4Opts::default()
+            },
+            backend.as_mut(),
+            &assets,
+        )
+        .unwrap();
+        // Each chunk starts at its place on the full timeline, not at zero.
+        let chunk_pts = frame_pts_secs(&chunk);
+        assert_eq!(chunk_pts.len() as u64, end - start, "{chunk_pts:?}");
+        assert!(
+            (chunk_pts[0] - start as f64 / 30.0).abs() < 1e-3,
+            "chunk {i} starts at {chunk_pts:?}"
+        );
+        list.push_str(&format!("file 'chunk_{i}.mp4'\n"));
+    }
+    let list_path = dir.join("list.txt");
+    std::fs::write(&list_path, list).unwrap();
+
+    let joined = dir.join("joined.mp4");
+    let status = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-loglevel",
+            "error",
+            "-f",
+            "concat",
+            "-safe",
+            "0",
+            "-i",
+        ])
+        .arg(&list_path)
+        .args(["-c", "copy"])
+        .arg(&joined)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let pts = frame_pts_secs(&joined);
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(pts.len(), 10, "{pts:?}");
+    assert!(pts[0].abs() < 1e-3, "{pts:?}");
+    for pair in pts.windows(2) {
+        let step = (pair[1] - pair[0]) * 30.0;
+        assert!((step - 1.0).abs() < 0.05, "gap or duplicate frame: {pts:?}");
+    }
+}
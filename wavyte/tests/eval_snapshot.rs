@@ -1,3 +1,5 @@
+#![cfg(feature = "render")]
+
 use wavyte::{Composition, Evaluator, FrameIndex};
 
 fn mix64(mut z: u64) -> u64 {
@@ -1,3 +1,5 @@
+#![cfg(feature = "render")]
+
 use wavyte::Composition;
 
 #[test]
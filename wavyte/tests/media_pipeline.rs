@@ -1,3 +1,5 @@
+#![cfg(feature = "render")]
+
 #[cfg(feature = "media-ffmpeg")]
 mod media_pipeline {
     use std::{collections::BTreeMap, path::Path, process::Command};
@@ -5,7 +7,7 @@ mod media_pipeline {
     use wavyte::{
         Anim, Asset, BackendKind, BlendMode, Canvas, Clip, ClipProps, Composition, FrameIndex,
         FrameRange, RenderSettings, RenderThreading, Track, Transform2D, VideoAsset,
-        build_audio_manifest, create_backend, mix_manifest, render_frames_with_stats,
+        build_audio_manifest, create_backend, mix_audio, mix_manifest, render_frames_with_stats,
         render_to_mp4_with_stats,
     };
 
@@ -91,7 +93,11 @@ mod media_pipeline {
                 volume: 0.8,
                 fade_in_sec: 0.05,
                 fade_out_sec: 0.05,
+                fade_curve: wavyte::AudioFadeCurve::Linear,
                 muted: false,
+                frame_blend: false,
+                sampler: None,
+                loop_mode: wavyte::VideoLoopMode::None,
             }),
         );
         assets.insert(
@@ -104,7 +110,11 @@ mod media_pipeline {
                 volume: 0.5,
                 fade_in_sec: 0.05,
                 fade_out_sec: 0.05,
+                fade_curve: wavyte::AudioFadeCurve::Linear,
                 muted: false,
+                pan: 0.0,
+                width: 1.0,
+                duck: None,
             }),
         );
 
@@ -127,6 +137,8 @@ mod media_pipeline {
                     layout_align_x: wavyte::LayoutAlignX::Start,
                     layout_align_y: wavyte::LayoutAlignY::Start,
                     layout_grid_columns: 2,
+                    isolate: false,
+                    clip_to: None,
                     clips: vec![Clip {
                         id: "c_video".to_string(),
                         asset: "v0".to_string(),
@@ -135,11 +147,20 @@ mod media_pipeline {
                             transform: Anim::constant(Transform2D::default()),
                             opacity: Anim::constant(1.0),
                             blend: BlendMode::Normal,
+                            opacity_at_composite: false,
+                            wiggle: None,
+                            box_anchor: None,
+                            aspect_ratio: None,
+                            fit: wavyte::ContentFit::None,
                         },
                         z_offset: 0,
                         effects: vec![],
                         transition_in: None,
                         transition_out: None,
+                        seed: None,
+                        loop_content: false,
+                        time_remap: None,
+                        reverse: false,
                     }],
                 },
                 Track {
@@ -151,6 +172,8 @@ mod media_pipeline {
                     layout_align_x: wavyte::LayoutAlignX::Start,
                     layout_align_y: wavyte::LayoutAlignY::Start,
                     layout_grid_columns: 2,
+                    isolate: false,
+                    clip_to: None,
                     clips: vec![Clip {
                         id: "c_audio".to_string(),
                         asset: "a0".to_string(),
@@ -159,15 +182,27 @@ mod media_pipeline {
                             transform: Anim::constant(Transform2D::default()),
                             opacity: Anim::constant(1.0),
                             blend: BlendMode::Normal,
+                            opacity_at_composite: false,
+                            wiggle: None,
+                            box_anchor: None,
+                            aspect_ratio: None,
+                            fit: wavyte::ContentFit::None,
                         },
                         z_offset: 0,
                         effects: vec![],
                         transition_in: None,
                         transition_out: None,
+                        seed: None,
+                        loop_content: false,
+                        time_remap: None,
+                        reverse: false,
                     }],
                 },
             ],
             seed: 1,
+            background: None,
+            title: None,
+            description: None,
         }
     }
 
@@ -191,6 +226,10 @@ mod media_pipeline {
         let assets = wavyte::PreparedAssetStore::prepare(&comp, &root).unwrap();
         let settings = RenderSettings {
             clear_rgba: Some([0, 0, 0, 255]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
         };
 
         let mut seq_backend = create_backend(BackendKind::Cpu, &settings).unwrap();
@@ -209,6 +248,7 @@ mod media_pipeline {
             chunk_size: 4,
             threads: Some(2),
             static_frame_elision: false,
+            elision_cache_frames: 0,
         };
         let (par_frames, _) =
             render_frames_with_stats(&comp, range, par_backend.as_mut(), &assets, &opts).unwrap();
@@ -248,6 +288,10 @@ mod media_pipeline {
 
         let settings = RenderSettings {
             clear_rgba: Some([0, 0, 0, 255]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
         };
         let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
         let out = root.join("out_with_audio.mp4");
@@ -259,6 +303,11 @@ mod media_pipeline {
                 bg_rgba: [0, 0, 0, 255],
                 overwrite: true,
                 threading: RenderThreading::default(),
+                timecode: None,
+                codec: wavyte::VideoCodec::H264,
+                preserve_alpha: false,
+                first_frame_pts: wavyte::FrameIndex(0),
+                metadata_sidecar: None,
             },
             backend.as_mut(),
             &assets,
@@ -268,6 +317,100 @@ mod media_pipeline {
         assert!(out.exists());
     }
 
+    #[test]
+    fn mix_audio_applies_asset_volume() {
+        if !ffmpeg_tools_available() {
+            return;
+        }
+        let root = std::env::temp_dir().join(format!(
+            "wavyte_media_mix_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        synth_media(&root).unwrap();
+
+        // One second of tone.wav on its own, without trims or fades.
+        let peak_at = |volume: f64| {
+            let mut comp = build_comp();
+            comp.duration = FrameIndex(30);
+            comp.tracks.remove(0);
+            comp.tracks[0].clips[0].range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+            comp.assets.remove("v0");
+            comp.assets.insert(
+                "a0".to_string(),
+                Asset::Audio(wavyte::AudioAsset {
+                    source: "tone.wav".to_string(),
+                    trim_start_sec: 0.0,
+                    trim_end_sec: None,
+                    playback_rate: 1.0,
+                    volume,
+                    fade_in_sec: 0.0,
+                    fade_out_sec: 0.0,
+                    fade_curve: wavyte::AudioFadeCurve::Linear,
+                    muted: false,
+                    pan: 0.0,
+                    width: 1.0,
+                    duck: None,
+                }),
+            );
+            let assets = wavyte::PreparedAssetStore::prepare(&comp, &root).unwrap();
+            let range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+            let mixed = mix_audio(&comp, &assets, range).unwrap();
+            assert_eq!((mixed.sample_rate, mixed.channels), (48_000, 2));
+            assert_eq!(mixed.samples, 48_000);
+            assert_eq!(mixed.interleaved_f32.len(), 96_000);
+            mixed
+                .interleaved_f32
+                .iter()
+                .fold(0.0f32, |m, v| m.max(v.abs()))
+        };
+
+        let full = peak_at(1.0);
+        let half = peak_at(0.5);
+        assert!(full > 0.1);
+        assert!((half / full - 0.5).abs() < 0.01, "{half} vs {full}");
+    }
+
+    #[test]
+    fn video_audio_track_contributes_to_the_mix() {
+        if !ffmpeg_tools_available() {
+            return;
+        }
+        let root = std::env::temp_dir().join(format!(
+            "wavyte_media_video_audio_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        synth_media(&root).unwrap();
+
+        // clip.mp4 on its own, so any mixed signal comes from its 440 Hz track.
+        let peak_with = |muted: bool| {
+            let mut comp = build_comp();
+            comp.tracks.remove(1);
+            comp.assets.remove("a0");
+            if let Some(Asset::Video(v)) = comp.assets.get_mut("v0") {
+                v.muted = muted;
+                v.fade_curve = wavyte::AudioFadeCurve::EqualPower;
+            }
+            let assets = wavyte::PreparedAssetStore::prepare(&comp, &root).unwrap();
+            let range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+            let mixed = mix_audio(&comp, &assets, range).unwrap();
+            mixed
+                .interleaved_f32
+                .iter()
+                .fold(0.0f32, |m, v| m.max(v.abs()))
+        };
+
+        assert!(peak_with(false) > 0.05);
+        assert_eq!(peak_with(true), 0.0);
+    }
+
     #[test]
     fn static_elision_still_works_when_audio_media_is_present() {
         if !ffmpeg_tools_available() {
@@ -289,6 +432,9 @@ mod media_pipeline {
             "p0".to_string(),
             Asset::Path(wavyte::PathAsset {
                 svg_path_d: "M0,0 L64,0 L64,64 L0,64 Z".to_string(),
+                fill: None,
+                stroke: None,
+                fill_rule: wavyte::FillRule::NonZero,
             }),
         );
         comp.tracks[0].clips[0].asset = "p0".to_string();
@@ -298,6 +444,10 @@ mod media_pipeline {
         let assets = wavyte::PreparedAssetStore::prepare(&comp, &root).unwrap();
         let settings = RenderSettings {
             clear_rgba: Some([0, 0, 0, 255]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
         };
         let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
         let opts = RenderThreading {
@@ -305,10 +455,91 @@ mod media_pipeline {
             chunk_size: comp.duration.0 as usize,
             threads: Some(2),
             static_frame_elision: true,
+            elision_cache_frames: 0,
         };
         let (_, stats) =
             render_frames_with_stats(&comp, range, backend.as_mut(), &assets, &opts).unwrap();
         assert_eq!(stats.frames_rendered, 1);
         assert_eq!(stats.frames_elided, comp.duration.0 - 1);
     }
+
+    fn synth_black_white_clip(root: &Path) -> anyhow::Result<()> {
+        std::fs::create_dir_all(root)?;
+        for (i, v) in [(1, 0u8), (2, 255u8)] {
+            image::save_buffer(
+                root.join(format!("bw_{i}.png")),
+                &[v; 16 * 16 * 3],
+                16,
+                16,
+                image::ExtendedColorType::Rgb8,
+            )?;
+        }
+        let status = Command::new("ffmpeg")
+            .args(["-v", "error", "-y", "-framerate", "1", "-i"])
+            .arg(root.join("bw_%d.png"))
+            .args(["-pix_fmt", "yuv420p", "-c:v", "libx264", "-r", "1"])
+            .arg(root.join("bw.mp4"))
+            .status()?;
+        anyhow::ensure!(status.success(), "ffmpeg failed creating bw.mp4");
+        Ok(())
+    }
+
+    #[test]
+    fn frame_blend_crossfades_between_source_frames() {
+        if !ffmpeg_tools_available() {
+            return;
+        }
+        let root = std::env::temp_dir().join(format!(
+            "wavyte_media_blend_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        synth_black_white_clip(&root).unwrap();
+
+        // Timeline frame 1 at 2 fps samples the 1 fps source halfway between black and white.
+        let center_luma = |frame_blend: bool| {
+            let mut comp = build_comp();
+            comp.fps = wavyte::Fps::new(2, 1).unwrap();
+            comp.canvas = Canvas {
+                width: 16,
+                height: 16,
+            };
+            comp.duration = FrameIndex(2);
+            comp.assets.remove("a0");
+            comp.assets.insert(
+                "v0".to_string(),
+                Asset::Video(VideoAsset {
+                    source: "bw.mp4".to_string(),
+                    trim_start_sec: 0.0,
+                    trim_end_sec: None,
+                    playback_rate: 1.0,
+                    volume: 1.0,
+                    fade_in_sec: 0.0,
+                    fade_out_sec: 0.0,
+                    fade_curve: wavyte::AudioFadeCurve::Linear,
+                    muted: true,
+                    frame_blend,
+                    sampler: None,
+                    loop_mode: wavyte::VideoLoopMode::None,
+                }),
+            );
+            comp.tracks.truncate(1);
+            comp.tracks[0].layout_mode = wavyte::LayoutMode::Absolute;
+            comp.tracks[0].clips[0].range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+
+            let assets = wavyte::PreparedAssetStore::prepare(&comp, &root).unwrap();
+            let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+            let frame =
+                wavyte::render_frame(&comp, FrameIndex(1), backend.as_mut(), &assets).unwrap();
+            frame.data[(8 * 16 + 8) * 4]
+        };
+
+        let snapped = center_luma(false);
+        assert!(!(48..=208).contains(&snapped), "snapped = {snapped}");
+        let blended = center_luma(true);
+        assert!((96..=160).contains(&blended), "blended = {blended}");
+    }
 }
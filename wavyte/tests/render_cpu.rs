@@ -1,10 +1,13 @@
+#![cfg(feature = "render")]
+
 mod cpu {
     use std::collections::BTreeMap;
 
     use wavyte::{
-        Anim, Asset, BackendKind, BlendMode, Canvas, Clip, ClipProps, Composition, FrameIndex,
-        FrameRange, PathAsset, PreparedAssetStore, RenderSettings, Track, Transform2D,
-        create_backend, render_frame,
+        Anim, Asset, BackendKind, BlendMode, Canvas, Clip, ClipBuilder, ClipProps, Composition,
+        CompositionBuilder, FrameIndex, FrameRange, ImageAsset, ImageSampling, InMemoryAssetMap,
+        PathAsset, PixelRect, PreparedAssetStore, RenderSettings, Track, TrackBuilder, Transform2D,
+        create_backend, render_frame, render_frame_into, render_frame_region, render_frame_to_vec,
     };
 
     fn mix64(mut z: u64) -> u64 {
@@ -35,6 +38,9 @@ mod cpu {
             "p0".to_string(),
             Asset::Path(PathAsset {
                 svg_path_d: "M10,10 L54,10 L54,54 L10,54 Z".to_string(),
+                fill: None,
+                stroke: None,
+                fill_rule: wavyte::FillRule::NonZero,
             }),
         );
 
@@ -55,6 +61,8 @@ mod cpu {
                 layout_align_x: wavyte::LayoutAlignX::Start,
                 layout_align_y: wavyte::LayoutAlignY::Start,
                 layout_grid_columns: 2,
+                isolate: false,
+                clip_to: None,
                 clips: vec![Clip {
                     id: "c0".to_string(),
                     asset: "p0".to_string(),
@@ -63,14 +71,26 @@ mod cpu {
                         transform: Anim::constant(Transform2D::default()),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: wavyte::ContentFit::None,
                     },
                     z_offset: 0,
                     effects: vec![],
                     transition_in: None,
                     transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 }],
             }],
             seed: 1,
+            background: None,
+            title: None,
+            description: None,
         }
     }
 
@@ -80,12 +100,18 @@ mod cpu {
             "p0".to_string(),
             Asset::Path(PathAsset {
                 svg_path_d: "M0,0 L64,0 L64,64 L0,64 Z".to_string(),
+                fill: None,
+                stroke: None,
+                fill_rule: wavyte::FillRule::NonZero,
             }),
         );
         assets.insert(
             "p1".to_string(),
             Asset::Path(PathAsset {
                 svg_path_d: "M16,16 L48,16 L48,48 L16,48 Z".to_string(),
+                fill: None,
+                stroke: None,
+                fill_rule: wavyte::FillRule::NonZero,
             }),
         );
 
@@ -107,6 +133,8 @@ mod cpu {
                     layout_align_x: wavyte::LayoutAlignX::Start,
                     layout_align_y: wavyte::LayoutAlignY::Start,
                     layout_grid_columns: 2,
+                    isolate: false,
+                    clip_to: None,
                     clips: vec![Clip {
                         id: "c0".to_string(),
                         asset: "p0".to_string(),
@@ -115,11 +143,20 @@ mod cpu {
                             transform: Anim::constant(Transform2D::default()),
                             opacity: Anim::constant(1.0),
                             blend: BlendMode::Normal,
+                            opacity_at_composite: false,
+                            wiggle: None,
+                            box_anchor: None,
+                            aspect_ratio: None,
+                            fit: wavyte::ContentFit::None,
                         },
                         z_offset: 0,
                         effects: vec![],
                         transition_in: None,
                         transition_out: None,
+                        seed: None,
+                        loop_content: false,
+                        time_remap: None,
+                        reverse: false,
                     }],
                 },
                 Track {
@@ -131,6 +168,8 @@ mod cpu {
                     layout_align_x: wavyte::LayoutAlignX::Start,
                     layout_align_y: wavyte::LayoutAlignY::Start,
                     layout_grid_columns: 2,
+                    isolate: false,
+                    clip_to: None,
                     clips: vec![Clip {
                         id: "c1".to_string(),
                         asset: "p1".to_string(),
@@ -139,15 +178,27 @@ mod cpu {
                             transform: Anim::constant(Transform2D::default()),
                             opacity: Anim::constant(1.0),
                             blend: BlendMode::Normal,
+                            opacity_at_composite: false,
+                            wiggle: None,
+                            box_anchor: None,
+                            aspect_ratio: None,
+                            fit: wavyte::ContentFit::None,
                         },
                         z_offset: 0,
                         effects: vec![],
                         transition_in: None,
                         transition_out: None,
+                        seed: None,
+                        loop_content: false,
+                        time_remap: None,
+                        reverse: false,
                     }],
                 },
             ],
             seed: 1,
+            background: None,
+            title: None,
+            description: None,
         }
     }
 
@@ -157,6 +208,10 @@ mod cpu {
 
         let settings = RenderSettings {
             clear_rgba: Some([0, 0, 0, 255]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
         };
         let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
         let assets = store_for(&comp);
@@ -171,12 +226,204 @@ mod cpu {
         assert!(a.data.iter().any(|&x| x != 0));
     }
 
+    #[test]
+    fn render_frame_into_reuses_buffer_and_matches_render_frame() {
+        let comp = two_layer_path_comp();
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let assets = store_for(&comp);
+
+        let expected = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        let mut buf = Vec::new();
+        let dims =
+            render_frame_into(&comp, FrameIndex(0), backend.as_mut(), &assets, &mut buf).unwrap();
+        assert_eq!(dims, (64, 64));
+        assert_eq!(buf, expected.data);
+
+        let ptr = buf.as_ptr();
+        render_frame_into(&comp, FrameIndex(0), backend.as_mut(), &assets, &mut buf).unwrap();
+        assert_eq!(buf.as_ptr(), ptr, "buffer should be reused");
+        assert_eq!(buf, expected.data);
+    }
+
+    #[test]
+    fn half_scale_render_matches_downsampled_full_render() {
+        let comp = two_layer_path_comp();
+        let assets = store_for(&comp);
+        let full_settings = RenderSettings {
+            clear_rgba: Some([0, 0, 0, 255]),
+            ..RenderSettings::default()
+        };
+        let half_settings = RenderSettings {
+            scale: 0.5,
+            ..full_settings.clone()
+        };
+
+        let mut backend = create_backend(BackendKind::Cpu, &full_settings).unwrap();
+        let full = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        let mut backend = create_backend(BackendKind::Cpu, &half_settings).unwrap();
+        let half = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        assert_eq!((half.width, half.height), (32, 32));
+
+        let mut max_diff = 0i32;
+        let mut total_diff = 0i64;
+        for y in 0..32usize {
+            for x in 0..32usize {
+                for c in 0..4 {
+                    let mut sum = 0i32;
+                    for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                        sum += i32::from(full.data[((2 * y + dy) * 64 + 2 * x + dx) * 4 + c]);
+                    }
+                    let diff = (sum / 4 - i32::from(half.data[(y * 32 + x) * 4 + c])).abs();
+                    max_diff = max_diff.max(diff);
+                    total_diff += i64::from(diff);
+                }
+            }
+        }
+        // Edges may differ by antialiasing, but content must land in the same place.
+        assert!(max_diff <= 96, "max channel diff {max_diff}");
+        assert!(total_diff / (32 * 32 * 4) <= 4, "mean diff too large");
+
+        assert!(
+            create_backend(
+                BackendKind::Cpu,
+                &RenderSettings {
+                    scale: 0.0,
+                    ..RenderSettings::default()
+                }
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn quadrant_regions_reassemble_into_full_frame() {
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let effect = |kind: &str, params: serde_json::Value| wavyte::EffectInstance {
+            kind: kind.to_string(),
+            params,
+        };
+        let effect_sets = [
+            vec![],
+            vec![effect(
+                "blur",
+                serde_json::json!({ "radius_px": 6, "sigma": 3.0 }),
+            )],
+            vec![
+                effect(
+                    "drop_shadow",
+                    serde_json::json!({ "offset": [5, 3], "blur_radius_px": 4 }),
+                ),
+                effect("vignette", serde_json::json!({ "radius": 0.2 })),
+            ],
+            vec![effect("pixelate", serde_json::json!({ "block_px": 5 }))],
+            vec![effect(
+                "radial_blur",
+                serde_json::json!({ "strength": 0.2, "center": [0.3, 0.6] }),
+            )],
+        ];
+        for effects in effect_sets {
+            let mut comp = two_layer_path_comp();
+            comp.tracks[1].clips[0].effects = effects.clone();
+            let assets = store_for(&comp);
+            let full = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+
+            let mut tiled = vec![0u8; full.data.len()];
+            for (x, y) in [(0, 0), (32, 0), (0, 32), (32, 32)] {
+                let region = PixelRect {
+                    x,
+                    y,
+                    width: 32,
+                    height: 32,
+                };
+                let tile =
+                    render_frame_region(&comp, FrameIndex(0), region, backend.as_mut(), &assets)
+                        .unwrap();
+                assert_eq!((tile.width, tile.height), (32, 32));
+                for row in 0..32usize {
+                    let dst = ((y as usize + row) * 64 + x as usize) * 4;
+                    tiled[dst..dst + 32 * 4]
+                        .copy_from_slice(&tile.data[row * 32 * 4..(row + 1) * 32 * 4]);
+                }
+            }
+            assert!(tiled == full.data, "tiles differ with {effects:?}");
+        }
+
+        let comp = two_layer_path_comp();
+        let assets = store_for(&comp);
+        let outside = PixelRect {
+            x: 40,
+            y: 0,
+            width: 32,
+            height: 8,
+        };
+        assert!(
+            render_frame_region(&comp, FrameIndex(0), outside, backend.as_mut(), &assets).is_err()
+        );
+    }
+
+    #[test]
+    fn region_matches_full_frame_crop_during_a_wipe() {
+        let mut comp = simple_path_comp();
+        comp.duration = FrameIndex(14);
+        for (key, fill) in [("p0", [255, 0, 0, 255]), ("p1", [0, 0, 255, 255])] {
+            comp.assets.insert(
+                key.to_string(),
+                Asset::Path(PathAsset {
+                    svg_path_d: "M0,0 L64,0 L64,64 L0,64 Z".to_string(),
+                    fill: Some(fill),
+                    stroke: None,
+                    fill_rule: wavyte::FillRule::NonZero,
+                }),
+            );
+        }
+        let wipe = wavyte::TransitionSpec {
+            kind: "wipe".to_string(),
+            duration_frames: 6,
+            ease: wavyte::Ease::Linear,
+            params: serde_json::json!({ "dir": "ltr", "soft_edge": 0.1 }),
+        };
+        let clips = &mut comp.tracks[0].clips;
+        clips[0].range = FrameRange::new(FrameIndex(0), FrameIndex(10)).unwrap();
+        clips[0].transition_out = Some(wipe.clone());
+        let mut incoming = clips[0].clone();
+        incoming.id = "c1".to_string();
+        incoming.asset = "p1".to_string();
+        incoming.range = FrameRange::new(FrameIndex(4), FrameIndex(14)).unwrap();
+        incoming.z_offset = 1;
+        incoming.transition_out = None;
+        incoming.transition_in = Some(wipe);
+        clips.push(incoming);
+
+        let assets = store_for(&comp);
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let full = render_frame(&comp, FrameIndex(7), backend.as_mut(), &assets).unwrap();
+        let region = PixelRect {
+            x: 24,
+            y: 16,
+            width: 32,
+            height: 32,
+        };
+        let tile =
+            render_frame_region(&comp, FrameIndex(7), region, backend.as_mut(), &assets).unwrap();
+        let crop = (16..48usize)
+            .flat_map(|y| full.data[(y * 64 + 24) * 4..(y * 64 + 56) * 4].to_vec())
+            .collect::<Vec<_>>();
+        // The wipe edge is mid-canvas, so the region holds both sides of it.
+        assert!(crop.chunks_exact(4).any(|p| p == [255, 0, 0, 255]));
+        assert!(crop.chunks_exact(4).any(|p| p == [0, 0, 255, 255]));
+        assert_eq!(tile.data, crop);
+    }
+
     #[test]
     fn cpu_render_two_layers_is_nonempty() {
         let comp = two_layer_path_comp();
 
         let settings = RenderSettings {
             clear_rgba: Some([0, 0, 0, 255]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
         };
         let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
         let assets = store_for(&comp);
@@ -187,4 +434,1026 @@ mod cpu {
         assert!(frame.premultiplied);
         assert!(frame.data.iter().any(|&x| x != 0));
     }
+
+    #[test]
+    fn composition_background_sets_default_clear_color() {
+        let mut json = serde_json::to_value(simple_path_comp()).unwrap();
+        json["background"] = serde_json::json!("#112233");
+        let comp: Composition = serde_json::from_value(json).unwrap();
+        assert_eq!(comp.background, Some([0x11, 0x22, 0x33, 0xff]));
+
+        let assets = store_for(&comp);
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        assert_eq!(&frame.data[0..4], &[0x11, 0x22, 0x33, 0xff]);
+
+        let settings = RenderSettings {
+            clear_rgba: Some([0, 0, 0, 255]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
+        };
+        let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
+        let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        assert_eq!(&frame.data[0..4], &[0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn radial_gradient_renders_start_at_center_and_end_at_corner() {
+        let mut comp = simple_path_comp();
+        comp.canvas = Canvas {
+            width: 32,
+            height: 32,
+        };
+        comp.assets.insert(
+            "p0".to_string(),
+            Asset::RadialGradient(wavyte::RadialGradientAsset {
+                center: [0.5, 0.5],
+                radius: 0.5,
+                start: Some([255, 200, 0, 255]),
+                end: Some([0, 0, 255, 255]),
+                stops: vec![],
+                width: None,
+                height: None,
+            }),
+        );
+
+        let assets = store_for(&comp);
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        assert_eq!((frame.width, frame.height), (32, 32));
+
+        let px = |x: usize, y: usize| {
+            let i = (y * 32 + x) * 4;
+            [
+                frame.data[i],
+                frame.data[i + 1],
+                frame.data[i + 2],
+                frame.data[i + 3],
+            ]
+        };
+        let close = |a: [u8; 4], b: [u8; 4], tol: i32| {
+            a.iter()
+                .zip(b.iter())
+                .all(|(&x, &y)| (i32::from(x) - i32::from(y)).abs() <= tol)
+        };
+        // The pixel whose center is nearest the gradient center sits ~0.04 radii away.
+        assert!(
+            close(px(16, 16), [255, 200, 0, 255], 12),
+            "{:?}",
+            px(16, 16)
+        );
+        assert_eq!(px(0, 0), [0, 0, 255, 255]);
+        assert_eq!(px(31, 31), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn solid_rect_with_explicit_size_fills_box_at_origin() {
+        let mut comp = simple_path_comp();
+        comp.canvas = Canvas {
+            width: 160,
+            height: 120,
+        };
+        comp.assets.insert(
+            "p0".to_string(),
+            Asset::SolidRect(wavyte::SolidRectAsset {
+                color: [10, 200, 30, 255],
+                width: Some(100.0),
+                height: Some(50.0),
+            }),
+        );
+
+        let assets = store_for(&comp);
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+
+        let px = |x: usize, y: usize| {
+            let i = (y * 160 + x) * 4;
+            [
+                frame.data[i],
+                frame.data[i + 1],
+                frame.data[i + 2],
+                frame.data[i + 3],
+            ]
+        };
+        assert_eq!(px(0, 0), [10, 200, 30, 255]);
+        assert_eq!(px(99, 49), [10, 200, 30, 255]);
+        assert_eq!(px(100, 10), [0, 0, 0, 0]);
+        assert_eq!(px(10, 50), [0, 0, 0, 0]);
+        assert_eq!(px(150, 110), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn opacity_at_composite_fades_after_pass_effects() {
+        let render = |opacity: f64, at_composite: bool| {
+            let mut comp = simple_path_comp();
+            let clip = &mut comp.tracks[0].clips[0];
+            clip.props.opacity = Anim::constant(opacity);
+            clip.props.opacity_at_composite = at_composite;
+            clip.effects = vec![wavyte::sharpen_effect(3.0)];
+            let assets = store_for(&comp);
+            let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+            render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap()
+        };
+
+        let full = render(1.0, false);
+        let draw_faded = render(0.5, false);
+        let composite_faded = render(0.5, true);
+
+        // Sharpen clamps overshoot at the shape edge, so the order of fade vs effect matters.
+        assert_ne!(draw_faded.data, composite_faded.data);
+
+        // Fading at composite time equals fading the fully processed full-opacity result.
+        for (got, want) in composite_faded.data.iter().zip(full.data.iter()) {
+            let want = (f32::from(*want) * 0.5).round() as i32;
+            assert!((i32::from(*got) - want).abs() <= 1, "{got} vs {want}");
+        }
+    }
+
+    #[test]
+    fn linear_light_glow_has_brighter_halo_than_srgb_glow() {
+        let render = |color_space: &str| {
+            let mut comp = simple_path_comp();
+            // Fill the transparent surround with opaque black, then glow in the requested space.
+            let mut opaque = [0.0; 20];
+            (opaque[0], opaque[6], opaque[12], opaque[19]) = (1.0, 1.0, 1.0, 1.0);
+            comp.tracks[0].clips[0].effects = vec![
+                wavyte::EffectInstance {
+                    kind: "color_matrix".to_string(),
+                    params: serde_json::json!({ "matrix": opaque }),
+                },
+                wavyte::EffectInstance {
+                    kind: "blur".to_string(),
+                    params: serde_json::json!({
+                        "radius_px": 6,
+                        "sigma": 3.0,
+                        "color_space": color_space,
+                    }),
+                },
+            ];
+            let assets = store_for(&comp);
+            let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+            render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap()
+        };
+
+        let srgb = render("srgb");
+        let linear = render("linear");
+        let px = |f: &wavyte::FrameRGBA, x: usize, y: usize| {
+            let i = (y * f.width as usize + x) * 4;
+            [f.data[i], f.data[i + 1], f.data[i + 2], f.data[i + 3]]
+        };
+
+        // Just outside the square's left edge, inside the halo.
+        let (s, l) = (px(&srgb, 8, 32), px(&linear, 8, 32));
+        assert_eq!((s[3], l[3]), (255, 255));
+        assert!(s[0] > 0, "{s:?}");
+        assert!(l[0] > s[0] + 10, "{l:?} vs {s:?}");
+        // Flat regions are unaffected by the round trip.
+        assert_eq!(px(&srgb, 32, 32), px(&linear, 32, 32));
+        assert_eq!(px(&srgb, 0, 0), px(&linear, 0, 0));
+
+        let mut bad = simple_path_comp();
+        bad.tracks[0].clips[0].effects = vec![wavyte::EffectInstance {
+            kind: "blur".to_string(),
+            params: serde_json::json!({ "radius_px": 2, "color_space": "rec2020" }),
+        }];
+        let assets = store_for(&bad);
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        assert!(render_frame(&bad, FrameIndex(0), backend.as_mut(), &assets).is_err());
+    }
+
+    #[test]
+    fn tight_isolation_surface_matches_full_canvas_isolation() {
+        let render = |effects: Vec<wavyte::EffectInstance>| {
+            let mut comp = simple_path_comp();
+            comp.canvas = Canvas {
+                width: 160,
+                height: 120,
+            };
+            comp.assets.insert(
+                "p0".to_string(),
+                Asset::Path(PathAsset {
+                    svg_path_d: "M0,0 L20,0 L20,20 L0,20 Z".to_string(),
+                    fill: None,
+                    stroke: None,
+                    fill_rule: wavyte::FillRule::NonZero,
+                }),
+            );
+            let clip = &mut comp.tracks[0].clips[0];
+            clip.props.transform = Anim::constant(Transform2D {
+                translate: wavyte::Vec2::new(30.25, 40.5),
+                ..Transform2D::default()
+            });
+            clip.effects = effects;
+
+            let assets = store_for(&comp);
+            let eval = wavyte::Evaluator::eval_frame(&comp, FrameIndex(0)).unwrap();
+            let plan = wavyte::compile_frame(&comp, &eval, &assets).unwrap();
+            let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+            let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+            (plan.surfaces[1].clone(), frame)
+        };
+
+        let (tight, tight_frame) = render(vec![wavyte::blur_effect(3, None)]);
+        // A vignette that never reaches the corners is a no-op, but it depends on the surface
+        // extent and so forces full-canvas isolation.
+        let (full, full_frame) = render(vec![
+            wavyte::blur_effect(3, None),
+            wavyte::vignette_effect(2.0, 0.1, [0, 0, 0, 255]),
+        ]);
+
+        assert_eq!((full.width, full.height, full.origin), (160, 120, [0, 0]));
+        assert!(tight.width * tight.height * 8 < 160 * 120, "{tight:?}");
+        assert_eq!(tight.origin, [25, 35]);
+        assert_eq!(tight_frame.data, full_frame.data);
+    }
+
+    #[test]
+    fn multiply_leaf_multiplies_onto_sibling() {
+        let mut comp = two_layer_path_comp();
+        comp.assets.insert(
+            "p0".to_string(),
+            Asset::SolidRect(wavyte::SolidRectAsset {
+                color: [200, 100, 50, 255],
+                width: None,
+                height: None,
+            }),
+        );
+        comp.assets.insert(
+            "p1".to_string(),
+            Asset::SolidRect(wavyte::SolidRectAsset {
+                color: [128, 255, 64, 255],
+                width: Some(32.0),
+                height: Some(32.0),
+            }),
+        );
+        comp.tracks[1].clips[0].props.blend = BlendMode::Multiply;
+
+        let assets = store_for(&comp);
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        let px = |x: usize, y: usize| {
+            let i = (y * 64 + x) * 4;
+            [
+                frame.data[i],
+                frame.data[i + 1],
+                frame.data[i + 2],
+                frame.data[i + 3],
+            ]
+        };
+
+        let expected = [200u8, 100, 50, 255]
+            .iter()
+            .zip([128u8, 255, 64, 255])
+            .map(|(&d, s)| ((u32::from(d) * u32::from(s) + 127) / 255) as i32)
+            .collect::<Vec<_>>();
+        for (got, want) in px(8, 8).iter().zip(&expected) {
+            assert!((i32::from(*got) - want).abs() <= 1, "{:?}", px(8, 8));
+        }
+        assert_eq!(px(48, 48), [200, 100, 50, 255]);
+    }
+
+    #[test]
+    fn rounded_rect_leaves_corners_transparent() {
+        let clip = ClipBuilder::new(
+            "c0",
+            "panel",
+            FrameRange::new(FrameIndex(0), FrameIndex(1)).unwrap(),
+        )
+        .build()
+        .unwrap();
+        // The radius is clamped to half the 20px height.
+        let comp = CompositionBuilder::new(
+            wavyte::Fps::new(30, 1).unwrap(),
+            Canvas {
+                width: 40,
+                height: 20,
+            },
+            FrameIndex(1),
+        )
+        .asset(
+            "panel",
+            Asset::RoundedRect(wavyte::RoundedRectAsset {
+                color: [20, 120, 220, 255],
+                radius_px: 50.0,
+                width: None,
+                height: None,
+            }),
+        )
+        .unwrap()
+        .track(TrackBuilder::new("main").clip(clip).build().unwrap())
+        .build()
+        .unwrap();
+
+        let json = serde_json::to_string(&comp).unwrap();
+        let frame = render_frame_to_vec(
+            &json,
+            &InMemoryAssetMap::new(),
+            FrameIndex(0),
+            &RenderSettings::default(),
+        )
+        .unwrap();
+        let px = |x: usize, y: usize| {
+            let i = (y * 40 + x) * 4;
+            [
+                frame.data[i],
+                frame.data[i + 1],
+                frame.data[i + 2],
+                frame.data[i + 3],
+            ]
+        };
+        for (x, y) in [(0, 0), (39, 0), (0, 19), (39, 19), (1, 1)] {
+            assert_eq!(px(x, y), [0, 0, 0, 0], "({x}, {y})");
+        }
+        assert_eq!(px(20, 10), [20, 120, 220, 255]);
+        assert_eq!(px(10, 1), [20, 120, 220, 255]);
+    }
+
+    fn render_path_asset(path: PathAsset) -> Vec<u8> {
+        let clip = ClipBuilder::new(
+            "c0",
+            "shape",
+            FrameRange::new(FrameIndex(0), FrameIndex(1)).unwrap(),
+        )
+        .build()
+        .unwrap();
+        let comp = CompositionBuilder::new(
+            wavyte::Fps::new(30, 1).unwrap(),
+            Canvas {
+                width: 40,
+                height: 40,
+            },
+            FrameIndex(1),
+        )
+        .asset("shape", Asset::Path(path))
+        .unwrap()
+        .track(TrackBuilder::new("main").clip(clip).build().unwrap())
+        .build()
+        .unwrap();
+        let json = serde_json::to_string(&comp).unwrap();
+        render_frame_to_vec(
+            &json,
+            &InMemoryAssetMap::new(),
+            FrameIndex(0),
+            &RenderSettings::default(),
+        )
+        .unwrap()
+        .data
+    }
+
+    #[test]
+    fn stroke_only_path_leaves_its_interior_transparent() {
+        let px = |data: &[u8], x: usize, y: usize| {
+            let i = (y * 40 + x) * 4;
+            [data[i], data[i + 1], data[i + 2], data[i + 3]]
+        };
+
+        let outline = render_path_asset(PathAsset {
+            svg_path_d: "M5,5 L35,5 L35,35 L5,35 Z".to_string(),
+            fill: None,
+            stroke: Some(wavyte::PathStroke {
+                color: [255, 0, 0, 255],
+                width_px: 4.0,
+            }),
+            fill_rule: wavyte::FillRule::NonZero,
+        });
+        assert_eq!(px(&outline, 20, 20), [0, 0, 0, 0]);
+        assert_eq!(px(&outline, 5, 20), [255, 0, 0, 255]);
+        assert_eq!(px(&outline, 20, 34), [255, 0, 0, 255]);
+        assert_eq!(px(&outline, 1, 1), [0, 0, 0, 0]);
+
+        // Even-odd cuts the inner square out of the outer one.
+        let ring = render_path_asset(PathAsset {
+            svg_path_d: "M5,5 L35,5 L35,35 L5,35 Z M15,15 L25,15 L25,25 L15,25 Z".to_string(),
+            fill: Some([0, 0, 255, 255]),
+            stroke: None,
+            fill_rule: wavyte::FillRule::EvenOdd,
+        });
+        assert_eq!(px(&ring, 20, 20), [0, 0, 0, 0]);
+        assert_eq!(px(&ring, 10, 10), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn isolated_track_multiplies_only_within_its_stack() {
+        let solid = |color: [u8; 4], size: Option<f64>| {
+            Asset::SolidRect(wavyte::SolidRectAsset {
+                color,
+                width: size,
+                height: size,
+            })
+        };
+        let mut comp = two_layer_path_comp();
+        comp.assets
+            .insert("p0".to_string(), solid([200, 100, 50, 255], Some(32.0)));
+        comp.assets
+            .insert("p1".to_string(), solid([128, 255, 64, 255], None));
+        comp.assets
+            .insert("bg".to_string(), solid([0, 0, 255, 255], None));
+
+        let mut base = comp.tracks[0].clips[0].clone();
+        base.props.transform = Anim::constant(Transform2D::default());
+        let mut overlay = comp.tracks[1].clips[0].clone();
+        overlay.props.transform = Anim::constant(Transform2D::default());
+        overlay.props.blend = BlendMode::Multiply;
+        overlay.z_offset = 1;
+        let mut bg = base.clone();
+        bg.id = "bg".to_string();
+        bg.asset = "bg".to_string();
+
+        let mut bg_track = comp.tracks[0].clone();
+        bg_track.name = "bg".to_string();
+        bg_track.z_base = 0;
+        bg_track.clips = vec![bg];
+        let mut stack = comp.tracks[1].clone();
+        stack.z_base = 10;
+        stack.isolate = true;
+        stack.clips = vec![base, overlay];
+        comp.tracks = vec![bg_track, stack];
+
+        let render = |comp: &Composition| {
+            let assets = store_for(comp);
+            let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+            let frame = render_frame(comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+            let px = |x: usize, y: usize| -> [u8; 4] {
+                let i = (y * 64 + x) * 4;
+                frame.data[i..i + 4].try_into().unwrap()
+            };
+            (px(8, 8), px(48, 48))
+        };
+        let multiplied = [100u8, 100, 13, 255];
+        let near = |a: [u8; 4], b: [u8; 4]| {
+            a.iter()
+                .zip(b)
+                .all(|(&x, y)| (i32::from(x) - i32::from(y)).abs() <= 1)
+        };
+
+        // Isolated: the overlay multiplies the base, and lands unmultiplied where the stack is empty.
+        let (inside, outside) = render(&comp);
+        assert!(near(inside, multiplied), "{inside:?}");
+        assert_eq!(outside, [128, 255, 64, 255]);
+
+        // Not isolated: the overlay also multiplies the background track.
+        comp.tracks[1].isolate = false;
+        let (inside, outside) = render(&comp);
+        assert!(near(inside, multiplied), "{inside:?}");
+        assert_eq!(outside, [0, 0, 64, 255]);
+    }
+
+    #[test]
+    fn track_clip_rect_cuts_off_overflowing_children() {
+        let solid = |color: [u8; 4]| {
+            Asset::SolidRect(wavyte::SolidRectAsset {
+                color,
+                width: None,
+                height: None,
+            })
+        };
+        let mut comp = two_layer_path_comp();
+        comp.assets
+            .insert("p0".to_string(), solid([0, 0, 255, 255]));
+        comp.assets
+            .insert("p1".to_string(), solid([255, 0, 0, 255]));
+        comp.tracks[1].clip_to = Some(wavyte::TrackClip::Rect {
+            x: 8.0,
+            y: 8.0,
+            width: 24.0,
+            height: 24.0,
+        });
+
+        let assets = store_for(&comp);
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        let px = |x: usize, y: usize| -> [u8; 4] {
+            let i = (y * 64 + x) * 4;
+            frame.data[i..i + 4].try_into().unwrap()
+        };
+        assert_eq!(px(8, 8), [255, 0, 0, 255]);
+        assert_eq!(px(31, 31), [255, 0, 0, 255]);
+        assert_eq!(px(7, 20), [0, 0, 255, 255]);
+        assert_eq!(px(32, 20), [0, 0, 255, 255]);
+        assert_eq!(px(48, 48), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn track_clip_layout_box_is_inset_by_padding() {
+        let solid = |color: [u8; 4]| {
+            Asset::SolidRect(wavyte::SolidRectAsset {
+                color,
+                width: None,
+                height: None,
+            })
+        };
+        let mut comp = two_layer_path_comp();
+        comp.assets
+            .insert("p0".to_string(), solid([0, 0, 255, 255]));
+        comp.assets
+            .insert("p1".to_string(), solid([255, 0, 0, 255]));
+        comp.tracks[1].layout_padding = wavyte::Edges {
+            left: 4.0,
+            top: 6.0,
+            right: 10.0,
+            bottom: 12.0,
+        };
+        comp.tracks[1].clip_to = Some(wavyte::TrackClip::LayoutBox);
+
+        let assets = store_for(&comp);
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        let px = |x: usize, y: usize| -> [u8; 4] {
+            let i = (y * 64 + x) * 4;
+            frame.data[i..i + 4].try_into().unwrap()
+        };
+        // The box spans [4, 54) x [6, 52).
+        assert_eq!(px(4, 6), [255, 0, 0, 255]);
+        assert_eq!(px(53, 51), [255, 0, 0, 255]);
+        assert_eq!(px(3, 20), [0, 0, 255, 255]);
+        assert_eq!(px(20, 5), [0, 0, 255, 255]);
+        assert_eq!(px(54, 20), [0, 0, 255, 255]);
+        assert_eq!(px(20, 52), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn nested_composition_renders_its_solid_onto_the_parent() {
+        let solid = |color: [u8; 4], size: Option<f64>| {
+            Asset::SolidRect(wavyte::SolidRectAsset {
+                color,
+                width: size,
+                height: size,
+            })
+        };
+        let mut child = two_layer_path_comp();
+        child.canvas = Canvas {
+            width: 16,
+            height: 16,
+        };
+        child.tracks.truncate(1);
+        child
+            .assets
+            .insert("p0".to_string(), solid([255, 0, 0, 255], None));
+        child.assets.remove("p1");
+
+        let mut comp = two_layer_path_comp();
+        comp.assets
+            .insert("p0".to_string(), solid([0, 0, 255, 255], None));
+        comp.assets.insert(
+            "p1".to_string(),
+            Asset::Composition(wavyte::CompositionAsset {
+                composition: Box::new(child),
+            }),
+        );
+        comp.tracks[1].clips[0].props.transform = Anim::constant(Transform2D {
+            translate: wavyte::Vec2::new(20.0, 20.0),
+            ..Transform2D::default()
+        });
+
+        let assets = store_for(&comp);
+        assert!(assets.id_for_key("p1/p0").is_ok());
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        let px = |x: usize, y: usize| -> [u8; 4] {
+            let i = (y * 64 + x) * 4;
+            frame.data[i..i + 4].try_into().unwrap()
+        };
+        assert_eq!(px(20, 20), [255, 0, 0, 255]);
+        assert_eq!(px(35, 35), [255, 0, 0, 255]);
+        assert_eq!(px(19, 19), [0, 0, 255, 255]);
+        assert_eq!(px(36, 36), [0, 0, 255, 255]);
+    }
+
+    /// A 16x16 composition whose only clip shows `asset`.
+    fn nested_child(asset: Asset) -> Composition {
+        let mut child = two_layer_path_comp();
+        child.canvas = Canvas {
+            width: 16,
+            height: 16,
+        };
+        child.tracks.truncate(1);
+        child.assets.clear();
+        child.assets.insert("p0".to_string(), asset);
+        child
+    }
+
+    /// Blue background with `child` composited as the foreground clip, moved to (20, 20).
+    fn render_nested(child: Composition) -> impl Fn(usize, usize) -> [u8; 4] {
+        let mut comp = two_layer_path_comp();
+        comp.assets.insert(
+            "p0".to_string(),
+            Asset::SolidRect(wavyte::SolidRectAsset {
+                color: [0, 0, 255, 255],
+                width: None,
+                height: None,
+            }),
+        );
+        comp.assets.insert(
+            "p1".to_string(),
+            Asset::Composition(wavyte::CompositionAsset {
+                composition: Box::new(child),
+            }),
+        );
+        comp.tracks[1].clips[0].props.transform = Anim::constant(Transform2D {
+            translate: wavyte::Vec2::new(20.0, 20.0),
+            ..Transform2D::default()
+        });
+        let assets = store_for(&comp);
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        move |x, y| {
+            let i = (y * 64 + x) * 4;
+            frame.data[i..i + 4].try_into().unwrap()
+        }
+    }
+
+    #[test]
+    fn doubly_nested_composition_keeps_the_inner_clip_opacity() {
+        let red = Asset::SolidRect(wavyte::SolidRectAsset {
+            color: [255, 0, 0, 255],
+            width: None,
+            height: None,
+        });
+        let mut middle = nested_child(Asset::Composition(wavyte::CompositionAsset {
+            composition: Box::new(nested_child(red)),
+        }));
+        middle.tracks[0].clips[0].props.opacity = Anim::constant(0.5);
+
+        let px = render_nested(middle);
+        let inside = px(25, 25);
+        assert!(
+            inside[0].abs_diff(128) <= 2 && inside[2].abs_diff(127) <= 2,
+            "{inside:?}"
+        );
+        assert_eq!(px(19, 19), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn nested_composition_keeps_its_track_clip() {
+        let mut child = nested_child(Asset::SolidRect(wavyte::SolidRectAsset {
+            color: [255, 0, 0, 255],
+            width: None,
+            height: None,
+        }));
+        child.tracks[0].clip_to = Some(wavyte::TrackClip::Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 8.0,
+            height: 8.0,
+        });
+
+        let px = render_nested(child);
+        assert_eq!(px(20, 20), [255, 0, 0, 255]);
+        assert_eq!(px(27, 27), [255, 0, 0, 255]);
+        assert_eq!(px(28, 28), [0, 0, 255, 255]);
+        assert_eq!(px(35, 35), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn text_alignment_moves_rendered_glyphs() {
+        let render = |align: wavyte::TextAlign| {
+            let comp = CompositionBuilder::new(
+                wavyte::Fps::new(30, 1).unwrap(),
+                Canvas {
+                    width: 160,
+                    height: 48,
+                },
+                FrameIndex(1),
+            )
+            .asset(
+                "t0",
+                Asset::Text(wavyte::TextAsset {
+                    max_width_px: Some(160.0),
+                    align,
+                    ..wavyte::text_asset("wavyte", "tests/data/fonts/Inconsolata-Regular.ttf", 24.0)
+                }),
+            )
+            .unwrap()
+            .track(
+                TrackBuilder::new("main")
+                    .clip(
+                        ClipBuilder::new(
+                            "c0",
+                            "t0",
+                            FrameRange::new(FrameIndex(0), FrameIndex(1)).unwrap(),
+                        )
+                        .build()
+                        .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+            let assets = store_for(&comp);
+            let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+            let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+            let inked = frame
+                .data
+                .chunks_exact(4)
+                .enumerate()
+                .filter(|(_, px)| px[3] > 0)
+                .map(|(i, _)| i as u32 % frame.width)
+                .collect::<Vec<_>>();
+            assert!(!inked.is_empty(), "{align:?} text rendered no ink");
+            let (x0, x1) = (*inked.iter().min().unwrap(), *inked.iter().max().unwrap());
+            (x0, x1)
+        };
+
+        let (left0, left1) = render(wavyte::TextAlign::Left);
+        let (center0, center1) = render(wavyte::TextAlign::Center);
+        assert!(left0 < 8, "{left0}..{left1}");
+        assert!(center0 > left0 + 20, "{center0} vs {left0}");
+        assert_eq!(center1 - center0, left1 - left0);
+        assert!(
+            (i64::from(center0 + center1) - 160).abs() <= 4,
+            "{center0}..{center1}"
+        );
+    }
+
+    #[test]
+    fn translucent_path_fill_composites_once_premultiplied() {
+        let mut comp = simple_path_comp();
+        let Some(Asset::Path(path)) = comp.assets.get_mut("p0") else {
+            panic!("expected path asset");
+        };
+        path.fill = Some([255, 0, 0, 128]);
+        let assets = store_for(&comp);
+        let settings = RenderSettings {
+            clear_rgba: Some([18, 20, 28, 255]),
+            ..RenderSettings::default()
+        };
+        let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
+        let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+
+        let i = (32 * 64 + 32) * 4;
+        let px = &frame.data[i..i + 4];
+        // 50% red over (18, 20, 28): 128 + 18 * 127 / 255 = 137.
+        for (got, want) in px.iter().zip([137u8, 10, 14, 255]) {
+            assert!(got.abs_diff(want) <= 2, "{px:?}");
+        }
+    }
+
+    #[test]
+    fn asset_overrides_recolor_a_template_solid() {
+        let mut comp = two_layer_path_comp();
+        comp.tracks.truncate(1);
+        comp.assets.insert(
+            "p0".to_string(),
+            Asset::SolidRect(wavyte::SolidRectAsset {
+                color: [255, 0, 0, 255],
+                width: None,
+                height: None,
+            }),
+        );
+
+        let mut overrides = std::collections::BTreeMap::new();
+        overrides.insert(
+            "p0".to_string(),
+            Asset::SolidRect(wavyte::SolidRectAsset {
+                color: [0, 255, 0, 255],
+                width: None,
+                height: None,
+            }),
+        );
+        comp.override_assets(overrides.clone()).unwrap();
+
+        let assets = store_for(&comp);
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let frame = render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap();
+        assert_eq!(frame.data[..4], [0, 255, 0, 255]);
+
+        let missing = overrides.remove("p0").unwrap();
+        overrides.insert("nope".to_string(), missing);
+        assert!(comp.override_assets(overrides).is_err());
+    }
+
+    #[test]
+    fn plan_dump_is_stable_and_lists_effect_passes() {
+        let mut comp = two_layer_path_comp();
+        let assets = store_for(&comp);
+        let dump = wavyte::dump_plan(&comp, FrameIndex(0), &assets).unwrap();
+        assert_eq!(
+            dump,
+            wavyte::dump_plan(&comp, FrameIndex(0), &assets).unwrap()
+        );
+        assert!(
+            dump.starts_with("plan 64x64 final=s0 clear=none\n"),
+            "{dump}"
+        );
+        assert_eq!(dump.matches("  draw fill_path ").count(), 2, "{dump}");
+        assert!(!dump.contains(" offscreen "), "{dump}");
+
+        comp.tracks[1].clips[0]
+            .effects
+            .push(wavyte::EffectInstance {
+                kind: "blur".to_string(),
+                params: serde_json::json!({ "radius_px": 2 }),
+            });
+        let blurred = wavyte::dump_plan(&comp, FrameIndex(0), &assets).unwrap();
+        let pass_lines = |d: &str| d.lines().filter(|l| l.starts_with("pass ")).count();
+        assert_eq!(pass_lines(&blurred), pass_lines(&dump) + 1, "{blurred}");
+        assert!(
+            blurred.lines().any(|l| l.starts_with("pass ")
+                && l.contains(" offscreen ")
+                && l.ends_with(" blur radius_px=2 sigma=1 alpha_only=false")),
+            "{blurred}"
+        );
+    }
+
+    fn upscaled_ramp_row(sampler: Option<ImageSampling>) -> Vec<u8> {
+        let ramp = [0u8, 80, 160, 240];
+        let pixels = ramp
+            .iter()
+            .flat_map(|&v| [v, v, v, 255])
+            .collect::<Vec<u8>>();
+        let img = image::RgbaImage::from_raw(4, 1, pixels).unwrap();
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(img)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let mut mem = InMemoryAssetMap::new();
+        mem.insert("ramp.png", png).unwrap();
+
+        let clip = ClipBuilder::new(
+            "c0",
+            "ramp",
+            FrameRange::new(FrameIndex(0), FrameIndex(1)).unwrap(),
+        )
+        .transform(Anim::constant(Transform2D {
+            scale: wavyte::Vec2::new(2.0, 2.0),
+            ..Transform2D::default()
+        }))
+        .build()
+        .unwrap();
+        let comp = CompositionBuilder::new(
+            wavyte::Fps::new(30, 1).unwrap(),
+            Canvas {
+                width: 8,
+                height: 2,
+            },
+            FrameIndex(1),
+        )
+        .asset(
+            "ramp",
+            Asset::Image(ImageAsset {
+                source: "ramp.png".to_string(),
+                sampler,
+            }),
+        )
+        .unwrap()
+        .track(TrackBuilder::new("main").clip(clip).build().unwrap())
+        .build()
+        .unwrap();
+
+        let json = serde_json::to_string(&comp).unwrap();
+        let frame =
+            render_frame_to_vec(&json, &mem, FrameIndex(0), &RenderSettings::default()).unwrap();
+        frame.data[..8 * 4]
+            .chunks_exact(4)
+            .map(|px| px[0])
+            .collect()
+    }
+
+    fn fitted_square_in_wide_box(fit: wavyte::ContentFit) -> Vec<u8> {
+        let img = image::RgbaImage::from_raw(20, 20, [255u8, 0, 0, 255].repeat(400)).unwrap();
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(img)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let mut mem = InMemoryAssetMap::new();
+        mem.insert("square.png", png).unwrap();
+
+        // A 20x20 image in a 2:1 layout box, i.e. 20x10 at the canvas origin.
+        let clip = ClipBuilder::new(
+            "c0",
+            "square",
+            FrameRange::new(FrameIndex(0), FrameIndex(1)).unwrap(),
+        )
+        .aspect_ratio(2.0)
+        .fit(fit)
+        .build()
+        .unwrap();
+        let comp = CompositionBuilder::new(
+            wavyte::Fps::new(30, 1).unwrap(),
+            Canvas {
+                width: 20,
+                height: 20,
+            },
+            FrameIndex(1),
+        )
+        .asset(
+            "square",
+            Asset::Image(ImageAsset {
+                source: "square.png".to_string(),
+                sampler: None,
+            }),
+        )
+        .unwrap()
+        .track(TrackBuilder::new("main").clip(clip).build().unwrap())
+        .build()
+        .unwrap();
+
+        let json = serde_json::to_string(&comp).unwrap();
+        render_frame_to_vec(&json, &mem, FrameIndex(0), &RenderSettings::default())
+            .unwrap()
+            .data
+    }
+
+    #[test]
+    fn contain_letterboxes_and_cover_fills_the_layout_box() {
+        let px = |data: &[u8], x: usize, y: usize| {
+            let i = (y * 20 + x) * 4;
+            [data[i], data[i + 1], data[i + 2], data[i + 3]]
+        };
+
+        // Contain: a centered 10x10 square with transparent bars left and right.
+        let contain = fitted_square_in_wide_box(wavyte::ContentFit::Contain);
+        assert_eq!(px(&contain, 2, 5), [0, 0, 0, 0]);
+        assert_eq!(px(&contain, 10, 5), [255, 0, 0, 255]);
+        assert_eq!(px(&contain, 17, 5), [0, 0, 0, 0]);
+
+        // Cover: edge-to-edge across the box, with the overflow below it clipped.
+        let cover = fitted_square_in_wide_box(wavyte::ContentFit::Cover);
+        for x in [0, 10, 19] {
+            assert_eq!(px(&cover, x, 0), [255, 0, 0, 255]);
+            assert_eq!(px(&cover, x, 9), [255, 0, 0, 255]);
+            assert_eq!(px(&cover, x, 12), [0, 0, 0, 0]);
+        }
+
+        // Without a fit the image keeps its intrinsic size and overflows the box.
+        let none = fitted_square_in_wide_box(wavyte::ContentFit::None);
+        assert_eq!(px(&none, 10, 15), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn image_sequence_maps_clip_frames_to_sequence_indices() {
+        let mut mem = InMemoryAssetMap::new();
+        for (i, rgb) in [[255u8, 0, 0], [0, 255, 0], [0, 0, 255]].iter().enumerate() {
+            let px = [rgb[0], rgb[1], rgb[2], 255];
+            let img = image::RgbaImage::from_raw(4, 4, px.repeat(16)).unwrap();
+            let mut png = Vec::new();
+            image::DynamicImage::ImageRgba8(img)
+                .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+                .unwrap();
+            mem.insert(&format!("frames/img_{:04}.png", i + 1), png)
+                .unwrap();
+        }
+
+        // A 10 fps sequence in a 30 fps composition holds each image for three frames.
+        let clip = ClipBuilder::new(
+            "c0",
+            "seq",
+            FrameRange::new(FrameIndex(0), FrameIndex(12)).unwrap(),
+        )
+        .build()
+        .unwrap();
+        let comp = CompositionBuilder::new(
+            wavyte::Fps::new(30, 1).unwrap(),
+            Canvas {
+                width: 4,
+                height: 4,
+            },
+            FrameIndex(12),
+        )
+        .asset(
+            "seq",
+            Asset::ImageSequence(wavyte::ImageSequenceAsset {
+                pattern: "frames/img_{:04}.png".to_string(),
+                fps: wavyte::Fps::new(10, 1).unwrap(),
+                start_index: 1,
+                loop_mode: wavyte::VideoLoopMode::Loop,
+                sampler: None,
+            }),
+        )
+        .unwrap()
+        .track(TrackBuilder::new("main").clip(clip).build().unwrap())
+        .build()
+        .unwrap();
+
+        let json = serde_json::to_string(&comp).unwrap();
+        let pixel_at = |frame: u64| {
+            let data =
+                render_frame_to_vec(&json, &mem, FrameIndex(frame), &RenderSettings::default())
+                    .unwrap()
+                    .data;
+            [data[0], data[1], data[2], data[3]]
+        };
+        assert_eq!(pixel_at(0), [255, 0, 0, 255]);
+        assert_eq!(pixel_at(2), [255, 0, 0, 255]);
+        assert_eq!(pixel_at(3), [0, 255, 0, 255]);
+        assert_eq!(pixel_at(7), [0, 0, 255, 255]);
+        // Past the last image the loop starts over.
+        assert_eq!(pixel_at(9), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn image_sampler_controls_upscale_filtering() {
+        let nearest = upscaled_ramp_row(Some(ImageSampling::Nearest));
+        assert_eq!(nearest, [0, 0, 80, 80, 160, 160, 240, 240]);
+
+        let bilinear = upscaled_ramp_row(Some(ImageSampling::Bilinear));
+        assert!(
+            bilinear.iter().any(|v| ![0, 80, 160, 240].contains(v)),
+            "{bilinear:?}"
+        );
+        assert_eq!(upscaled_ramp_row(None), bilinear);
+    }
 }
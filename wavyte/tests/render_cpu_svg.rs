@@ -1,3 +1,5 @@
+#![cfg(feature = "render")]
+
 mod cpu_svg {
     use std::{collections::BTreeMap, fs};
 
@@ -51,6 +53,8 @@ mod cpu_svg {
                 layout_align_x: wavyte::LayoutAlignX::Start,
                 layout_align_y: wavyte::LayoutAlignY::Start,
                 layout_grid_columns: 2,
+                isolate: false,
+                clip_to: None,
                 clips: vec![Clip {
                     id: "c0".to_string(),
                     asset: "s0".to_string(),
@@ -59,14 +63,26 @@ mod cpu_svg {
                         transform: Anim::constant(Transform2D::default()),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: wavyte::ContentFit::None,
                     },
                     z_offset: 0,
                     effects: vec![],
                     transition_in: None,
                     transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 }],
             }],
             seed: 1,
+            background: None,
+            title: None,
+            description: None,
         }
     }
 
@@ -94,6 +110,10 @@ mod cpu_svg {
         let assets = PreparedAssetStore::prepare(&comp, &tmp).unwrap();
         let settings = RenderSettings {
             clear_rgba: Some([0, 0, 0, 0]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
         };
         let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
 
@@ -1,10 +1,14 @@
+#![cfg(feature = "render")]
+
 mod render_parallel_parity {
     use std::collections::BTreeMap;
 
     use wavyte::{
-        Anim, Asset, BackendKind, BlendMode, Canvas, Clip, ClipProps, Composition, FrameIndex,
-        FrameRange, Keyframe, Keyframes, PreparedAssetStore, RenderSettings, RenderThreading,
-        Track, Transform2D, Vec2, create_backend, render_frames_with_stats,
+        Anim, Asset, BackendKind, BlendMode, CallbackSink, Canvas, Clip, ClipProps, Composition,
+        FrameIndex, FrameRange, Keyframe, Keyframes, PreparedAssetStore, RenderSettings,
+        RenderThreading, Track, Transform2D, TransitionSpec, Vec2, create_backend,
+        frame_fingerprint, render_frame_if_changed, render_frames_with_stats,
+        render_to_sink_with_stats,
     };
 
     fn moving_comp() -> Composition {
@@ -13,6 +17,9 @@ mod render_parallel_parity {
             "p0".to_string(),
             Asset::Path(wavyte::PathAsset {
                 svg_path_d: "M0,0 L30,0 L30,30 L0,30 Z".to_string(),
+                fill: None,
+                stroke: None,
+                fill_rule: wavyte::FillRule::NonZero,
             }),
         );
 
@@ -57,6 +64,8 @@ mod render_parallel_parity {
                 layout_align_x: wavyte::LayoutAlignX::Start,
                 layout_align_y: wavyte::LayoutAlignY::Start,
                 layout_grid_columns: 2,
+                isolate: false,
+                clip_to: None,
                 clips: vec![Clip {
                     id: "c0".to_string(),
                     asset: "p0".to_string(),
@@ -65,14 +74,26 @@ mod render_parallel_parity {
                         transform,
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: wavyte::ContentFit::None,
                     },
                     z_offset: 0,
                     effects: vec![],
                     transition_in: None,
                     transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 }],
             }],
             seed: 7,
+            background: None,
+            title: None,
+            description: None,
         }
     }
 
@@ -94,6 +115,10 @@ mod render_parallel_parity {
         let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
         let settings = RenderSettings {
             clear_rgba: Some([0, 0, 0, 255]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
         };
 
         let mut seq_backend = create_backend(BackendKind::Cpu, &settings).unwrap();
@@ -113,6 +138,7 @@ mod render_parallel_parity {
                 chunk_size,
                 threads: Some(4),
                 static_frame_elision: false,
+                elision_cache_frames: 0,
             };
             let (par_frames, stats) =
                 render_frames_with_stats(&comp, range, par_backend.as_mut(), &assets, &opts)
@@ -129,6 +155,88 @@ mod render_parallel_parity {
         }
     }
 
+    #[test]
+    fn scaled_parallel_render_matches_scaled_sequential() {
+        let comp = moving_comp();
+        let range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let settings = RenderSettings {
+            clear_rgba: Some([0, 0, 0, 255]),
+            scale: 0.5,
+            ..RenderSettings::default()
+        };
+
+        let mut seq_backend = create_backend(BackendKind::Cpu, &settings).unwrap();
+        let (seq_frames, _) = render_frames_with_stats(
+            &comp,
+            range,
+            seq_backend.as_mut(),
+            &assets,
+            &RenderThreading::default(),
+        )
+        .unwrap();
+        let mut par_backend = create_backend(BackendKind::Cpu, &settings).unwrap();
+        let opts = RenderThreading {
+            parallel: true,
+            chunk_size: 4,
+            threads: Some(4),
+            static_frame_elision: false,
+            elision_cache_frames: 0,
+        };
+        let (par_frames, _) =
+            render_frames_with_stats(&comp, range, par_backend.as_mut(), &assets, &opts).unwrap();
+
+        for (a, b) in seq_frames.iter().zip(par_frames.iter()) {
+            assert_eq!(
+                (a.width, a.height),
+                (comp.canvas.width / 2, comp.canvas.height / 2)
+            );
+            assert_eq!((b.width, b.height), (a.width, a.height));
+            assert_eq!(a.data, b.data);
+        }
+    }
+
+    #[test]
+    fn op_profiling_reports_blur_time_when_enabled() {
+        let mut comp = moving_comp();
+        comp.tracks[0].clips[0].effects = vec![wavyte::EffectInstance {
+            kind: "blur".to_string(),
+            params: serde_json::json!({ "radius_px": 12, "sigma": 6.0 }),
+        }];
+        let range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+
+        for parallel in [false, true] {
+            let threading = RenderThreading {
+                parallel,
+                threads: Some(2),
+                ..RenderThreading::default()
+            };
+            let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+            let (_, stats) =
+                render_frames_with_stats(&comp, range, backend.as_mut(), &assets, &threading)
+                    .unwrap();
+            assert_eq!(stats.op_timings, wavyte::OpTimings::default());
+
+            let settings = RenderSettings {
+                profile_ops: true,
+                ..RenderSettings::default()
+            };
+            let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
+            let (_, stats) =
+                render_frames_with_stats(&comp, range, backend.as_mut(), &assets, &threading)
+                    .unwrap();
+            assert!(!stats.op_timings.get(wavyte::OpKind::Blur).is_zero());
+            assert!(!stats.op_timings.get(wavyte::OpKind::Draw).is_zero());
+            assert!(
+                stats
+                    .op_timings
+                    .iter()
+                    .all(|(kind, _)| kind != wavyte::OpKind::ColorMatrix)
+            );
+        }
+    }
+
     #[test]
     fn static_frame_elision_reports_expected_counts() {
         let comp = static_comp();
@@ -136,6 +244,10 @@ mod render_parallel_parity {
         let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
         let settings = RenderSettings {
             clear_rgba: Some([0, 0, 0, 255]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
         };
 
         let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
@@ -144,6 +256,7 @@ mod render_parallel_parity {
             chunk_size: range.len_frames() as usize,
             threads: Some(4),
             static_frame_elision: true,
+            elision_cache_frames: 0,
         };
         let (frames, stats) =
             render_frames_with_stats(&comp, range, backend.as_mut(), &assets, &opts).unwrap();
@@ -155,4 +268,229 @@ mod render_parallel_parity {
             assert_eq!(frame.data, frames[0].data);
         }
     }
+
+    #[test]
+    fn elision_cache_renders_alternating_poses_once_across_chunks() {
+        // Two static poses alternating every two frames, so every chunk repeats earlier poses.
+        let mut comp = moving_comp();
+        comp.duration = FrameIndex(12);
+        comp.tracks[0].clips[0].range = FrameRange::new(FrameIndex(0), FrameIndex(12)).unwrap();
+        comp.tracks[0].clips[0].props.transform = Anim::Keyframes(Keyframes {
+            keys: (0..6)
+                .map(|i| Keyframe {
+                    frame: FrameIndex(i * 2),
+                    value: Transform2D {
+                        translate: Vec2::new(if i % 2 == 0 { 4.0 } else { 24.0 }, 16.0),
+                        ..Transform2D::default()
+                    },
+                    ease: wavyte::Ease::Linear,
+                })
+                .collect(),
+            mode: wavyte::InterpMode::Hold,
+            default: None,
+        });
+        let range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+
+        let mut render = |threading: &RenderThreading| {
+            let mut frames = Vec::new();
+            let mut sink = CallbackSink::new(|_, frame: wavyte::FrameRGBA| {
+                frames.push(frame.data);
+                Ok(())
+            });
+            let stats = render_to_sink_with_stats(
+                &comp,
+                range,
+                &mut sink,
+                backend.as_mut(),
+                &assets,
+                threading,
+            )
+            .unwrap();
+            (frames, stats)
+        };
+
+        let (expected, _) = render(&RenderThreading::default());
+        let (frames, stats) = render(&RenderThreading {
+            parallel: true,
+            chunk_size: 3,
+            threads: Some(2),
+            static_frame_elision: true,
+            elision_cache_frames: 4,
+        });
+
+        assert_eq!(frames, expected);
+        assert_ne!(frames[0], frames[2]);
+        assert_eq!(stats.frames_total, 12);
+        assert_eq!(stats.frames_rendered, 2);
+        assert_eq!(stats.frames_elided, 10);
+    }
+
+    #[test]
+    fn render_if_changed_skips_identical_static_frames() {
+        let comp = static_comp();
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let settings = RenderSettings {
+            clear_rgba: Some([0, 0, 0, 255]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
+        };
+        let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
+
+        let (first, fp0) =
+            render_frame_if_changed(&comp, FrameIndex(0), None, backend.as_mut(), &assets).unwrap();
+        assert!(first.is_some());
+
+        let (second, fp1) =
+            render_frame_if_changed(&comp, FrameIndex(1), Some(fp0), backend.as_mut(), &assets)
+                .unwrap();
+        assert!(second.is_none());
+        assert_eq!(fp0, fp1);
+    }
+
+    #[test]
+    fn render_if_changed_renders_moving_frames() {
+        let comp = moving_comp();
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+
+        let (_, fp0) =
+            render_frame_if_changed(&comp, FrameIndex(0), None, backend.as_mut(), &assets).unwrap();
+        let (next, fp1) =
+            render_frame_if_changed(&comp, FrameIndex(1), Some(fp0), backend.as_mut(), &assets)
+                .unwrap();
+        assert!(next.is_some());
+        assert_ne!(fp0, fp1);
+    }
+
+    #[test]
+    fn frame_fingerprint_matches_static_frames_and_tracks_animation() {
+        let still = static_comp();
+        let assets = PreparedAssetStore::prepare(&still, ".").unwrap();
+        let fp =
+            |comp: &Composition, f: u64| frame_fingerprint(comp, FrameIndex(f), &assets).unwrap();
+        assert_eq!(fp(&still, 0), fp(&still, 5));
+
+        let moving = moving_comp();
+        assert_ne!(fp(&moving, 0), fp(&moving, 1));
+        assert_eq!(fp(&moving, 3), fp(&moving, 3));
+    }
+
+    fn crossfade_comp() -> Composition {
+        let mut comp = moving_comp();
+        comp.duration = FrameIndex(16);
+        let tr = TransitionSpec {
+            kind: "crossfade".to_string(),
+            duration_frames: 4,
+            ease: wavyte::Ease::Linear,
+            params: serde_json::Value::Null,
+        };
+
+        let mut a = comp.tracks[0].clips[0].clone();
+        a.range = FrameRange::new(FrameIndex(0), FrameIndex(12)).unwrap();
+        a.transition_out = Some(tr.clone());
+
+        let mut b = a.clone();
+        b.id = "c1".to_string();
+        b.range = FrameRange::new(FrameIndex(8), FrameIndex(16)).unwrap();
+        b.props.transform = Anim::constant(Transform2D {
+            translate: Vec2::new(30.0, 30.0),
+            ..Transform2D::default()
+        });
+        b.transition_out = None;
+        b.transition_in = Some(tr);
+
+        comp.tracks[0].clips = vec![a, b];
+        comp
+    }
+
+    #[test]
+    fn sub_range_render_matches_full_render_across_transition() {
+        let comp = crossfade_comp();
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let settings = RenderSettings {
+            clear_rgba: Some([0, 0, 0, 255]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
+        };
+        let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
+
+        let full_range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+        let (full, _) = render_frames_with_stats(
+            &comp,
+            full_range,
+            backend.as_mut(),
+            &assets,
+            &RenderThreading::default(),
+        )
+        .unwrap();
+
+        let sub_range = FrameRange::new(FrameIndex(9), FrameIndex(12)).unwrap();
+        let parallel = RenderThreading {
+            parallel: true,
+            chunk_size: 2,
+            threads: Some(2),
+            static_frame_elision: true,
+            elision_cache_frames: 0,
+        };
+        for threading in [RenderThreading::default(), parallel] {
+            let (sub, _) =
+                render_frames_with_stats(&comp, sub_range, backend.as_mut(), &assets, &threading)
+                    .unwrap();
+            assert_eq!(sub.len(), 3);
+            for (i, frame) in sub.iter().enumerate() {
+                assert_eq!(frame.data, full[9 + i].data, "frame {}", 9 + i);
+            }
+        }
+
+        // The crossfade is actually in progress inside the sub-range.
+        assert_ne!(full[9].data, full[11].data);
+    }
+
+    #[test]
+    fn peak_surface_bytes_grows_with_isolation_surfaces() {
+        let flat = static_comp();
+        let mut layered = static_comp();
+        let base = layered.tracks[0].clips[0].clone();
+        layered.tracks[0].clips = (0..6)
+            .map(|i| {
+                let mut clip = base.clone();
+                clip.id = format!("c{i}");
+                clip.effects = vec![wavyte::blur_effect(2, None)];
+                clip
+            })
+            .collect();
+
+        let run = |comp: &Composition, parallel: bool| {
+            let assets = PreparedAssetStore::prepare(comp, ".").unwrap();
+            let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+            let threading = RenderThreading {
+                parallel,
+                threads: Some(2),
+                ..RenderThreading::default()
+            };
+            let range = FrameRange::new(FrameIndex(0), FrameIndex(2)).unwrap();
+            render_frames_with_stats(comp, range, backend.as_mut(), &assets, &threading)
+                .unwrap()
+                .1
+        };
+
+        let flat_stats = run(&flat, false);
+        let layered_stats = run(&layered, false);
+        assert!(flat_stats.peak_surface_bytes >= 64 * 64 * 4);
+        assert!(
+            layered_stats.peak_surface_bytes > flat_stats.peak_surface_bytes,
+            "{layered_stats:?} vs {flat_stats:?}"
+        );
+        assert_eq!(layered_stats.bytes_decoded, 0);
+        assert_eq!(
+            run(&layered, true).peak_surface_bytes,
+            layered_stats.peak_surface_bytes
+        );
+    }
 }
@@ -0,0 +1,440 @@
+#![cfg(feature = "render")]
+
+mod render_sink {
+    use std::collections::BTreeMap;
+
+    use wavyte::{
+        AlphaTrimSink, Anim, Asset, BackendKind, BlendMode, CallbackSink, Canvas, Clip, ClipProps,
+        Composition, FrameIndex, FrameRange, GifSink, GifSinkOpts, PathAsset, PixelRect,
+        PngSequenceSink, PngSequenceSinkOpts, PreparedAssetStore, RenderSettings, RenderThreading,
+        Track, Transform2D, Vec2, WavyteError, create_backend, frame_metadata, render_to_sink,
+        render_to_sink_with_stats, write_frame_metadata_sidecar,
+    };
+
+    fn solid_comp(frames: u64) -> Composition {
+        let mut assets = BTreeMap::new();
+        assets.insert(
+            "bg".to_string(),
+            Asset::Path(PathAsset {
+                svg_path_d: "M0,0 L32,0 L32,24 L0,24 Z".to_string(),
+                fill: None,
+                stroke: None,
+                fill_rule: wavyte::FillRule::NonZero,
+            }),
+        );
+
+        Composition {
+            fps: wavyte::Fps::new(30, 1).unwrap(),
+            canvas: Canvas {
+                width: 32,
+                height: 24,
+            },
+            duration: FrameIndex(frames),
+            assets,
+            tracks: vec![Track {
+                name: "main".to_string(),
+                z_base: 0,
+                layout_mode: wavyte::LayoutMode::Absolute,
+                layout_gap_px: 0.0,
+                layout_padding: wavyte::Edges::default(),
+                layout_align_x: wavyte::LayoutAlignX::Start,
+                layout_align_y: wavyte::LayoutAlignY::Start,
+                layout_grid_columns: 2,
+                isolate: false,
+                clip_to: None,
+                clips: vec![Clip {
+                    id: "c0".to_string(),
+                    asset: "bg".to_string(),
+                    range: FrameRange::new(FrameIndex(0), FrameIndex(frames)).unwrap(),
+                    props: ClipProps {
+                        transform: Anim::constant(Transform2D::default()),
+                        opacity: Anim::constant(1.0),
+                        blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: wavyte::ContentFit::None,
+                    },
+                    z_offset: 0,
+                    effects: vec![],
+                    transition_in: None,
+                    transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
+                }],
+            }],
+            seed: 1,
+            background: None,
+            title: None,
+            description: None,
+        }
+    }
+
+    fn temp_out_dir(tag: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "wavyte_png_seq_{tag}_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ))
+    }
+
+    #[test]
+    fn png_sequence_writes_one_file_per_frame() {
+        let comp = solid_comp(3);
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let out_dir = temp_out_dir("three");
+
+        let mut sink = PngSequenceSink::new(PngSequenceSinkOpts {
+            out_dir: out_dir.clone(),
+            file_prefix: "frame_".to_string(),
+            zero_pad: 4,
+            overwrite: false,
+        })
+        .unwrap();
+        let range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+        render_to_sink(&comp, range, &mut sink, backend.as_mut(), &assets).unwrap();
+        assert_eq!(sink.frames_written(), 3);
+
+        for f in 0..3 {
+            let path = out_dir.join(format!("frame_{f:04}.png"));
+            assert!(path.exists(), "missing {}", path.display());
+            assert_eq!(image::image_dimensions(&path).unwrap(), (32, 24));
+        }
+
+        let conflict = PngSequenceSink::new(PngSequenceSinkOpts {
+            out_dir: out_dir.clone(),
+            file_prefix: "frame_".to_string(),
+            zero_pad: 4,
+            overwrite: false,
+        });
+        assert!(conflict.is_err());
+
+        let _ = std::fs::remove_dir_all(out_dir);
+    }
+
+    #[test]
+    fn callback_sink_receives_frames_in_order() {
+        let comp = solid_comp(10);
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+
+        let mut seen = Vec::<u64>::new();
+        let mut sink = CallbackSink::new(|idx: FrameIndex, frame: wavyte::FrameRGBA| {
+            assert_eq!((frame.width, frame.height), (32, 24));
+            seen.push(idx.0);
+            Ok(())
+        });
+        let threading = RenderThreading {
+            parallel: true,
+            chunk_size: 3,
+            threads: Some(2),
+            static_frame_elision: true,
+            elision_cache_frames: 0,
+        };
+        let range = FrameRange::new(FrameIndex(2), FrameIndex(9)).unwrap();
+        let stats = render_to_sink_with_stats(
+            &comp,
+            range,
+            &mut sink,
+            backend.as_mut(),
+            &assets,
+            &threading,
+        )
+        .unwrap();
+        let cfg = sink.config().expect("begin called before first frame");
+        assert_eq!((cfg.width, cfg.height), (32, 24));
+        assert_eq!(cfg.fps, comp.fps);
+        assert_eq!(stats.frames_total, 7);
+
+        assert_eq!(seen, (2..9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn callback_sink_error_aborts_render() {
+        let comp = solid_comp(5);
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+
+        let mut calls = 0u32;
+        let mut sink = CallbackSink::new(|idx: FrameIndex, _frame| {
+            calls += 1;
+            if idx.0 == 1 {
+                return Err(WavyteError::evaluation("encoder rejected frame"));
+            }
+            Ok(())
+        });
+        let range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+        let err = render_to_sink(&comp, range, &mut sink, backend.as_mut(), &assets).unwrap_err();
+        assert!(err.to_string().contains("encoder rejected frame"));
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn alpha_trim_crops_range_to_content_bounds() {
+        let mut comp = solid_comp(3);
+        comp.canvas = Canvas {
+            width: 200,
+            height: 100,
+        };
+        comp.assets.insert(
+            "bg".to_string(),
+            Asset::Path(PathAsset {
+                svg_path_d: "M50,20 L70,20 L70,50 L50,50 Z".to_string(),
+                fill: None,
+                stroke: None,
+                fill_rule: wavyte::FillRule::NonZero,
+            }),
+        );
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+
+        let mut sizes = Vec::new();
+        let mut sink = AlphaTrimSink::new(
+            CallbackSink::new(|_idx: FrameIndex, frame: wavyte::FrameRGBA| {
+                assert!(frame.data.chunks_exact(4).all(|px| px[3] == 255));
+                sizes.push((frame.width, frame.height));
+                Ok(())
+            }),
+            0,
+        );
+        let range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+        let stats = render_to_sink_with_stats(
+            &comp,
+            range,
+            &mut sink,
+            backend.as_mut(),
+            &assets,
+            &RenderThreading::default(),
+        )
+        .unwrap();
+        drop(sink);
+
+        assert_eq!(
+            stats.crop_rect,
+            Some(PixelRect {
+                x: 50,
+                y: 20,
+                width: 20,
+                height: 30,
+            })
+        );
+        assert_eq!(sizes, vec![(20, 30); 3]);
+    }
+
+    #[test]
+    fn alpha_trim_rounds_crop_to_even_and_cleans_its_spill() {
+        let mut comp = solid_comp(2);
+        comp.canvas = Canvas {
+            width: 200,
+            height: 100,
+        };
+        comp.assets.insert(
+            "bg".to_string(),
+            Asset::Path(PathAsset {
+                svg_path_d: "M181,20 L200,20 L200,51 L181,51 Z".to_string(),
+                fill: None,
+                stroke: None,
+                fill_rule: wavyte::FillRule::NonZero,
+            }),
+        );
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let spill_dir = temp_out_dir("alpha_trim_spill");
+        std::fs::create_dir_all(&spill_dir).unwrap();
+
+        let mut sizes = Vec::new();
+        let mut sink = AlphaTrimSink::new(
+            CallbackSink::new(|_idx: FrameIndex, frame: wavyte::FrameRGBA| {
+                sizes.push((frame.width, frame.height));
+                Ok(())
+            }),
+            0,
+        )
+        .with_spill_dir(&spill_dir);
+        let range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+        let stats = render_to_sink_with_stats(
+            &comp,
+            range,
+            &mut sink,
+            backend.as_mut(),
+            &assets,
+            &RenderThreading::default(),
+        )
+        .unwrap();
+        drop(sink);
+
+        // 19x31 content at the right edge grows leftward and downward to 20x32.
+        assert_eq!(
+            stats.crop_rect,
+            Some(PixelRect {
+                x: 180,
+                y: 20,
+                width: 20,
+                height: 32,
+            })
+        );
+        assert_eq!(sizes, vec![(20, 32); 2]);
+        assert_eq!(std::fs::read_dir(&spill_dir).unwrap().count(), 0);
+        std::fs::remove_dir_all(&spill_dir).unwrap();
+    }
+
+    #[test]
+    fn gif_sink_round_trips_frames_and_odd_dimensions() {
+        let mut comp = solid_comp(4);
+        comp.canvas = Canvas {
+            width: 33,
+            height: 25,
+        };
+        comp.assets.insert(
+            "bg".to_string(),
+            Asset::Path(PathAsset {
+                svg_path_d: "M0,0 L16,0 L16,25 L0,25 Z".to_string(),
+                fill: None,
+                stroke: None,
+                fill_rule: wavyte::FillRule::NonZero,
+            }),
+        );
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let mut backend = create_backend(BackendKind::Cpu, &RenderSettings::default()).unwrap();
+        let out_dir = temp_out_dir("gif");
+        let out_path = out_dir.join("preview.gif");
+
+        let mut sink = GifSink::new(GifSinkOpts {
+            out_path: out_path.clone(),
+            ..GifSinkOpts::default()
+        })
+        .unwrap();
+        let range = FrameRange::new(FrameIndex(0), comp.duration).unwrap();
+        render_to_sink(&comp, range, &mut sink, backend.as_mut(), &assets).unwrap();
+
+        let mut opts = gif::DecodeOptions::new();
+        opts.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = opts
+            .read_info(std::fs::File::open(&out_path).unwrap())
+            .unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (33, 25));
+        let mut delays = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height), (33, 25));
+            delays.push(frame.delay);
+            let transparent = frame.transparent.unwrap();
+            let row = &frame.buffer[..33];
+            assert!(row[..16].iter().all(|&i| i != transparent));
+            assert!(row[17..].iter().all(|&i| i == transparent));
+            assert!(row[..16].windows(2).all(|w| w[0] == w[1]));
+        }
+        // 30 fps in centiseconds: 0, 3, 7, 10, 13.
+        assert_eq!(delays, vec![3, 4, 3, 3]);
+
+        let _ = std::fs::remove_dir_all(out_dir);
+    }
+
+    #[test]
+    fn metadata_sidecar_lists_visible_nodes_and_bounds_per_frame() {
+        let mut comp = solid_comp(3);
+        let moving = Anim::Keyframes(wavyte::Keyframes {
+            keys: vec![
+                wavyte::Keyframe {
+                    frame: FrameIndex(0),
+                    value: Transform2D::default(),
+                    ease: wavyte::Ease::Linear,
+                },
+                wavyte::Keyframe {
+                    frame: FrameIndex(2),
+                    value: Transform2D {
+                        translate: Vec2::new(4.0, 2.0),
+                        scale: Vec2::new(0.5, 0.5),
+                        ..Transform2D::default()
+                    },
+                    ease: wavyte::Ease::Linear,
+                },
+            ],
+            mode: wavyte::InterpMode::Linear,
+            default: None,
+        });
+        let mut overlay = comp.tracks[0].clips[0].clone();
+        overlay.id = "c1".to_string();
+        overlay.range = FrameRange::new(FrameIndex(1), FrameIndex(3)).unwrap();
+        overlay.z_offset = 1;
+        overlay.props.transform = moving;
+        comp.tracks[0].clips.push(overlay);
+
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let out_dir = temp_out_dir("sidecar");
+        std::fs::create_dir_all(&out_dir).unwrap();
+        let path = out_dir.join("frames.json");
+        write_frame_metadata_sidecar(
+            &comp,
+            FrameRange::new(FrameIndex(0), FrameIndex(3)).unwrap(),
+            &assets,
+            &path,
+        )
+        .unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let frames = json.as_array().unwrap();
+        assert_eq!(frames.len(), 3);
+        let ids = |f: usize| {
+            frames[f]["nodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|n| n["clip_id"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        let bounds = |f: usize, n: usize| {
+            let b = &frames[f]["nodes"][n]["bounds"];
+            ["x", "y", "width", "height"].map(|k| b[k].as_f64().unwrap())
+        };
+        assert_eq!(ids(0), ["c0"]);
+        assert_eq!(ids(1), ["c0", "c1"]);
+        assert_eq!(ids(2), ["c0", "c1"]);
+        assert_eq!(bounds(0, 0), [0.0, 0.0, 32.0, 24.0]);
+        // The overlay's keyframes are clip-local, so frame 2 is halfway through its move.
+        assert_eq!(bounds(1, 1), [0.0, 0.0, 32.0, 24.0]);
+        assert_eq!(bounds(2, 1), [2.0, 1.0, 24.0, 18.0]);
+        assert_eq!(frames[2]["frame"], 2);
+        assert_ne!(frames[1]["fingerprint"], frames[2]["fingerprint"]);
+
+        std::fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[test]
+    fn frame_metadata_reports_world_transforms_of_visible_nodes() {
+        let mut comp = solid_comp(1);
+        let mut moved = comp.tracks[0].clips[0].clone();
+        moved.id = "c1".to_string();
+        moved.z_offset = 1;
+        moved.props.transform = Anim::constant(Transform2D {
+            translate: Vec2::new(8.0, 4.0),
+            scale: Vec2::new(0.5, 2.0),
+            ..Transform2D::default()
+        });
+        comp.tracks[0].clips.push(moved);
+
+        let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+        let meta = frame_metadata(&comp, FrameIndex(0), &assets).unwrap();
+        let nodes = meta
+            .nodes
+            .iter()
+            .map(|n| (n.clip_id.as_str(), n.asset.as_str(), n.transform))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            nodes,
+            [
+                ("c0", "bg", [1.0, 0.0, 0.0, 1.0, 0.0, 0.0]),
+                ("c1", "bg", [0.5, 0.0, 0.0, 2.0, 8.0, 4.0]),
+            ]
+        );
+        assert_eq!(meta.nodes[1].bounds.width, 16.0);
+        assert_eq!(meta.nodes[1].bounds.height, 48.0);
+    }
+}
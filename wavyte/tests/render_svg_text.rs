@@ -1,3 +1,5 @@
+#![cfg(feature = "render")]
+
 mod svg_text {
     use std::collections::BTreeMap;
 
@@ -46,6 +48,8 @@ mod svg_text {
                 layout_align_x: wavyte::LayoutAlignX::Start,
                 layout_align_y: wavyte::LayoutAlignY::Start,
                 layout_grid_columns: 2,
+                isolate: false,
+                clip_to: None,
                 clips: vec![Clip {
                     id: "c0".to_string(),
                     asset: "s0".to_string(),
@@ -58,14 +62,26 @@ mod svg_text {
                         }),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: wavyte::ContentFit::None,
                     },
                     z_offset: 0,
                     effects: vec![],
                     transition_in: None,
                     transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 }],
             }],
             seed: 1,
+            background: None,
+            title: None,
+            description: None,
         }
     }
 
@@ -95,6 +111,8 @@ mod svg_text {
                 layout_align_x: wavyte::LayoutAlignX::Start,
                 layout_align_y: wavyte::LayoutAlignY::Start,
                 layout_grid_columns: 2,
+                isolate: false,
+                clip_to: None,
                 clips: vec![Clip {
                     id: "c0".to_string(),
                     asset: "s0".to_string(),
@@ -107,14 +125,26 @@ mod svg_text {
                         }),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: wavyte::ContentFit::None,
                     },
                     z_offset: 0,
                     effects: vec![],
                     transition_in: None,
                     transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 }],
             }],
             seed: 1,
+            background: None,
+            title: None,
+            description: None,
         }
     }
 
@@ -141,6 +171,10 @@ mod svg_text {
         let comp = comp_with_svg_text();
         let settings = RenderSettings {
             clear_rgba: Some([0, 0, 0, 0]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
         };
         let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
         let assets = PreparedAssetStore::prepare(&comp, "tests/data").unwrap();
@@ -162,6 +196,10 @@ mod svg_text {
         let comp = comp_with_svg_text_missing_font_stack();
         let settings = RenderSettings {
             clear_rgba: Some([0, 0, 0, 0]),
+            text_snap: wavyte::TextSnap::None,
+            scale: 1.0,
+            profile_ops: false,
+            missing_glyphs: wavyte::MissingGlyphPolicy::Tofu,
         };
         let mut backend = create_backend(BackendKind::Cpu, &settings).unwrap();
         let assets = PreparedAssetStore::prepare(&comp, "tests/data").unwrap();
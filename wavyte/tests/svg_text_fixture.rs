@@ -1,3 +1,5 @@
+#![cfg(feature = "render")]
+
 #[test]
 fn svg_text_fixture_files_exist() {
     assert!(std::path::Path::new("tests/data/svg_with_text.svg").is_file());
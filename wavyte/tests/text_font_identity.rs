@@ -1,3 +1,5 @@
+#![cfg(feature = "render")]
+
 #[test]
 fn prepared_text_exposes_font_family_from_bytes() {
     let font_bytes = std::fs::read("tests/data/fonts/Inconsolata-Regular.ttf").unwrap();
@@ -21,3 +23,115 @@ fn prepared_text_exposes_font_family_from_bytes() {
     let family = engine.last_family_name().unwrap();
     assert!(!family.trim().is_empty());
 }
+
+#[derive(Clone, Default)]
+struct LogBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn warn_policy_reports_codepoints_missing_from_font() {
+    use wavyte::{
+        BackendKind, Canvas, ClipBuilder, CompositionBuilder, FrameIndex, FrameRange,
+        MissingGlyphPolicy, PreparedAsset, PreparedAssetStore, RenderSettings, TrackBuilder,
+    };
+
+    // Tuffy has no CJK coverage, and its .notdef glyph is a visible box.
+    let comp = CompositionBuilder::new(
+        wavyte::Fps::new(30, 1).unwrap(),
+        Canvas {
+            width: 128,
+            height: 64,
+        },
+        FrameIndex(1),
+    )
+    .text_asset("title", "ab \u{5B57}b", "tests/data/fonts/Tuffy.ttf", 24.0)
+    .unwrap()
+    .track(
+        TrackBuilder::new("main")
+            .clip(
+                ClipBuilder::new(
+                    "c0",
+                    "title",
+                    FrameRange::new(FrameIndex(0), FrameIndex(1)).unwrap(),
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap(),
+    )
+    .build()
+    .unwrap();
+    let assets = PreparedAssetStore::prepare(&comp, ".").unwrap();
+    let PreparedAsset::Text(text) = assets.get(assets.id_for_key("title").unwrap()).unwrap() else {
+        panic!("expected prepared text");
+    };
+    assert_eq!(text.missing_codepoints, vec!['\u{5B57}']);
+
+    let render = |policy: MissingGlyphPolicy| {
+        let logs = LogBuffer::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let settings = RenderSettings {
+            missing_glyphs: policy,
+            ..RenderSettings::default()
+        };
+        let frame = tracing::subscriber::with_default(subscriber, || {
+            let mut backend = wavyte::create_backend(BackendKind::Cpu, &settings).unwrap();
+            wavyte::render_frame(&comp, FrameIndex(0), backend.as_mut(), &assets).unwrap()
+        });
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        (frame, logs)
+    };
+
+    let (tofu, tofu_logs) = render(MissingGlyphPolicy::Tofu);
+    let (skipped, skip_logs) = render(MissingGlyphPolicy::Skip);
+    let (warned, warn_logs) = render(MissingGlyphPolicy::Warn);
+
+    assert!(!tofu_logs.contains("U+5B57"));
+    assert!(!skip_logs.contains("U+5B57"));
+    assert!(warn_logs.contains("U+5B57"), "{warn_logs}");
+    assert!(warn_logs.contains("title"), "{warn_logs}");
+    // All text lands on the canvas; dropping the tofu box removes ink, warning only adds the
+    // log line.
+    let (skip_ink, _) = ink(&skipped);
+    let (tofu_ink, tofu_bbox) = ink(&tofu);
+    assert!(skip_ink > 0, "skipped frame has no ink");
+    assert!(tofu_ink > skip_ink, "{tofu_ink} vs {skip_ink}");
+    let [x0, y0, x1, y1] = tofu_bbox;
+    assert!(x1 > x0 && y1 > y0 && x1 < 128 && y1 < 64, "{tofu_bbox:?}");
+    assert_eq!(skipped.data, warned.data);
+}
+
+/// Count of pixels with non-zero alpha, and their `[x0, y0, x1, y1]` bounding box.
+fn ink(frame: &wavyte::FrameRGBA) -> (usize, [u32; 4]) {
+    let mut count = 0;
+    let mut bbox = [u32::MAX, u32::MAX, 0, 0];
+    for (i, px) in frame.data.chunks_exact(4).enumerate() {
+        if px[3] == 0 {
+            continue;
+        }
+        let (x, y) = (i as u32 % frame.width, i as u32 / frame.width);
+        count += 1;
+        bbox = [
+            bbox[0].min(x),
+            bbox[1].min(y),
+            bbox[2].max(x),
+            bbox[3].max(y),
+        ];
+    }
+    (count, bbox)
+}
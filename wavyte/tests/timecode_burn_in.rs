@@ -0,0 +1,83 @@
+#![cfg(feature = "render")]
+
+use wavyte::{
+    Fps, FrameIndex, FrameRGBA, FrameSink, SinkConfig, TimecodeCorner, TimecodeSink, TimecodeStyle,
+    format_timecode,
+};
+
+struct Capture(Vec<FrameRGBA>);
+
+impl FrameSink for Capture {
+    fn begin(&mut self, _cfg: &SinkConfig) -> wavyte::WavyteResult<()> {
+        Ok(())
+    }
+
+    fn write_frame(&mut self, _frame: FrameIndex, rgba: &FrameRGBA) -> wavyte::WavyteResult<()> {
+        self.0.push(rgba.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> wavyte::WavyteResult<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn timecode_is_burned_into_chosen_corner_only() {
+    let fps = Fps::new(30, 1).unwrap();
+    assert_eq!(format_timecode(FrameIndex(30), fps, false), "00:00:01:00");
+
+    let font = std::fs::read(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("data")
+            .join("fonts")
+            .join("Inconsolata-Regular.ttf"),
+    )
+    .unwrap();
+    let mut style = TimecodeStyle::new(font);
+    style.size_px = 16.0;
+    style.corner = TimecodeCorner::TopRight;
+    style.margin_px = 4;
+
+    let (w, h) = (320u32, 180u32);
+    let blank = FrameRGBA {
+        width: w,
+        height: h,
+        data: vec![0u8; (w * h * 4) as usize],
+        premultiplied: true,
+    };
+    let mut sink = TimecodeSink::new(Capture(Vec::new()), style);
+    sink.begin(&SinkConfig {
+        width: w,
+        height: h,
+        fps,
+    })
+    .unwrap();
+    sink.write_frame(FrameIndex(30), &blank).unwrap();
+    let out = sink.into_inner().0.remove(0);
+
+    let mut touched = 0usize;
+    let mut glyph_px = 0usize;
+    let mut glyph_rows = std::collections::BTreeSet::new();
+    for y in 0..h {
+        for x in 0..w {
+            let i = ((y * w + x) * 4) as usize;
+            if out.data[i..i + 4] != [0, 0, 0, 0] {
+                touched += 1;
+                if out.data[i] > 128 {
+                    glyph_px += 1;
+                    glyph_rows.insert(y);
+                }
+                assert!(
+                    x >= w / 2 && y < h / 2,
+                    "pixel ({x},{y}) outside top-right corner"
+                );
+            }
+        }
+    }
+    assert!(touched > 0, "timecode box was not drawn");
+    assert!(glyph_px > 0, "timecode text was not drawn");
+    // Digits sit on the baseline inside the box rather than being clipped above it.
+    assert!(glyph_rows.len() >= 8, "{glyph_rows:?}");
+}
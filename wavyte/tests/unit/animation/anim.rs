@@ -78,3 +78,163 @@ fn expr_reverse_maps_frames() {
     assert_eq!(rev.sample(ctx(0)).unwrap(), 9.0);
     assert_eq!(rev.sample(ctx(9)).unwrap(), 0.0);
 }
+
+fn spring_anim(stiffness: f32, damping: f32) -> Anim<f64> {
+    Anim::Keyframes(Keyframes {
+        keys: vec![
+            Keyframe {
+                frame: FrameIndex(0),
+                value: 0.0,
+                ease: Ease::Linear,
+            },
+            Keyframe {
+                frame: FrameIndex(120),
+                value: 10.0,
+                ease: Ease::Linear,
+            },
+        ],
+        mode: InterpMode::Spring {
+            stiffness,
+            damping,
+            mass: 1.0,
+        },
+        default: None,
+    })
+}
+
+#[test]
+fn overdamped_spring_approaches_target_monotonically() {
+    let anim = spring_anim(100.0, 40.0);
+    anim.validate().unwrap();
+    let samples = (0..120)
+        .map(|f| anim.sample(ctx(f)).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(samples[0], 0.0);
+    assert!(samples.windows(2).all(|w| w[1] >= w[0]), "{samples:?}");
+    assert!(samples.iter().all(|&v| v <= 10.0));
+    assert!(samples[119] > 9.9, "{}", samples[119]);
+    // Closed form: the same frame always samples the same value, in any order.
+    assert_eq!(anim.sample(ctx(37)).unwrap(), samples[37]);
+}
+
+#[test]
+fn underdamped_spring_overshoots_target() {
+    let anim = spring_anim(100.0, 2.0);
+    let peak = (0..120)
+        .map(|f| anim.sample(ctx(f)).unwrap())
+        .fold(f64::MIN, f64::max);
+    assert!(peak > 10.5, "peak={peak}");
+    assert!((anim.sample(ctx(119)).unwrap() - 10.0).abs() < 0.5);
+    assert_eq!(anim.sample(ctx(120)).unwrap(), 10.0);
+}
+
+#[test]
+fn spring_rejects_non_positive_stiffness_or_mass() {
+    assert!(spring_anim(0.0, 1.0).validate().is_err());
+    assert!(spring_anim(10.0, -1.0).validate().is_err());
+}
+
+fn ramp_anim(mode: InterpMode) -> Anim<f64> {
+    Anim::Keyframes(Keyframes {
+        keys: vec![
+            Keyframe {
+                frame: FrameIndex(0),
+                value: 0.0,
+                ease: Ease::Linear,
+            },
+            Keyframe {
+                frame: FrameIndex(100),
+                value: 1.0,
+                ease: Ease::Linear,
+            },
+        ],
+        mode,
+        default: None,
+    })
+}
+
+#[test]
+fn steps_quantizes_ramp_into_count_levels() {
+    let mode: InterpMode = "steps(4)".parse().unwrap();
+    let anim = ramp_anim(mode);
+    anim.validate().unwrap();
+    let mut levels = (0..100)
+        .map(|f| anim.sample(ctx(f)).unwrap())
+        .collect::<Vec<_>>();
+    levels.dedup();
+    assert_eq!(levels, vec![0.0, 0.25, 0.5, 0.75]);
+
+    let anim = ramp_anim("steps(4, start)".parse().unwrap());
+    assert_eq!(anim.sample(ctx(0)).unwrap(), 0.25);
+    assert_eq!(anim.sample(ctx(99)).unwrap(), 1.0);
+}
+
+#[test]
+fn steps_parser_rejects_bad_input() {
+    assert!("steps(0)".parse::<InterpMode>().is_err());
+    assert!("steps(4, middle)".parse::<InterpMode>().is_err());
+    assert!("bounce".parse::<InterpMode>().is_err());
+    assert!(matches!("hold".parse::<InterpMode>(), Ok(InterpMode::Hold)));
+}
+
+#[test]
+fn interp_mode_deserializes_from_css_strings_and_round_trips() {
+    let lane: Keyframes<f64> = serde_json::from_value(serde_json::json!({
+        "keys": [
+            { "frame": 0, "value": 0.0, "ease": "Linear" },
+            { "frame": 8, "value": 1.0, "ease": "Linear" }
+        ],
+        "mode": "steps(4, start)",
+        "default": null
+    }))
+    .unwrap();
+    assert!(matches!(
+        lane.mode,
+        InterpMode::Steps {
+            count: 4,
+            jump_start: true
+        }
+    ));
+
+    let json = serde_json::to_string(&lane).unwrap();
+    let back: Keyframes<f64> = serde_json::from_str(&json).unwrap();
+    assert!(matches!(
+        back.mode,
+        InterpMode::Steps {
+            count: 4,
+            jump_start: true
+        }
+    ));
+    for (json, hold) in [("\"Hold\"", true), ("\"linear\"", false)] {
+        let mode: InterpMode = serde_json::from_str(json).unwrap();
+        assert_eq!(matches!(mode, InterpMode::Hold), hold);
+    }
+    let err = serde_json::from_str::<InterpMode>("\"steps(0)\"").unwrap_err();
+    assert!(
+        err.to_string().contains("count must be an integer"),
+        "{err}"
+    );
+}
+
+#[test]
+fn registered_custom_ease_shapes_keyframe_lane() {
+    crate::animation::ease::register_ease("my_snap", |t| if t < 0.75 { 0.0 } else { 1.0 }).unwrap();
+    let anim: Anim<f64> = serde_json::from_value(serde_json::json!({
+        "Keyframes": {
+            "keys": [
+                { "frame": 0, "value": 10.0, "ease": "my_snap" },
+                { "frame": 8, "value": 20.0, "ease": "Linear" }
+            ],
+            "mode": "Linear",
+            "default": null
+        }
+    }))
+    .unwrap();
+    let values = (0..=8)
+        .map(|f| anim.sample(ctx(f)).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        vec![10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 20.0, 20.0, 20.0]
+    );
+}
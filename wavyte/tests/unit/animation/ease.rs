@@ -34,3 +34,39 @@ fn monotonic_spot_check() {
         assert!(b < c);
     }
 }
+
+#[test]
+fn builtin_names_round_trip_and_cannot_be_registered() {
+    for name in ["Linear", "InOutQuad", "OutCubic"] {
+        let ease = Ease::from_name(name).unwrap();
+        assert_eq!(ease.name(), name);
+        let json = serde_json::to_string(&ease).unwrap();
+        assert_eq!(serde_json::from_str::<Ease>(&json).unwrap(), ease);
+    }
+    assert!(register_ease("Linear", |t| t).is_err());
+    assert!(register_ease(" ", |t| t).is_err());
+    assert!(serde_json::from_str::<Ease>("\"not_registered\"").is_err());
+}
+
+#[test]
+fn registered_ease_resolves_by_name() {
+    let handle = register_ease("unit_smoothstep", |t| t * t * (3.0 - 2.0 * t)).unwrap();
+    let ease: Ease = serde_json::from_str("\"unit_smoothstep\"").unwrap();
+    assert_eq!(ease, Ease::Custom(handle));
+    assert_eq!(ease.apply(0.5), 0.5);
+    assert_eq!(ease.apply(0.25), 0.15625);
+    assert_eq!(serde_json::to_string(&ease).unwrap(), "\"unit_smoothstep\"");
+}
+
+#[test]
+fn reregistering_keeps_already_parsed_curves() {
+    register_ease("unit_rereg", |t| t).unwrap();
+    let before: Ease = serde_json::from_str("\"unit_rereg\"").unwrap();
+    register_ease("unit_rereg", |t| t * t).unwrap();
+    let after: Ease = serde_json::from_str("\"unit_rereg\"").unwrap();
+
+    assert_eq!(before.apply(0.5), 0.5);
+    assert_eq!(after.apply(0.5), 0.25);
+    assert_eq!(before.name(), "unit_rereg");
+    assert_ne!(before, after);
+}
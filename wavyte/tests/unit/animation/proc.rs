@@ -36,6 +36,21 @@ fn noise_is_bounded_and_deterministic() {
     assert_eq!(v0, proc.sample(ctx(0, 7)).unwrap());
 }
 
+#[test]
+fn random_repeats_per_frame_and_varies_across_frames() {
+    let proc = Procedural::<f64>::new(ProceduralKind::Scalar(ProcScalar::Random {
+        amp: 10.0,
+        offset: 5.0,
+    }));
+    let values = (0..8)
+        .map(|f| proc.sample(ctx(f, 7)).unwrap())
+        .collect::<Vec<_>>();
+    assert!(values.iter().all(|v| (5.0..15.0).contains(v)));
+    assert!(values.windows(2).all(|w| w[0] != w[1]));
+    assert_eq!(values[3], proc.sample(ctx(3, 7)).unwrap());
+    assert_ne!(values[3], proc.sample(ctx(3, 8)).unwrap());
+}
+
 #[test]
 fn envelope_basic_boundaries() {
     let proc = Procedural::<f64>::new(ProceduralKind::Scalar(ProcScalar::Envelope {
@@ -51,3 +66,93 @@ fn envelope_basic_boundaries() {
     let released = proc.sample(ctx(30, 0)).unwrap();
     assert!((released - 0.0).abs() < 1e-9);
 }
+
+#[test]
+fn ken_burns_hits_from_and_to_poses() {
+    let proc = Procedural::<Transform2D>::new(ProceduralKind::KenBurns {
+        from_scale: 1.0,
+        to_scale: 1.5,
+        from_center: Vec2::new(50.0, 40.0),
+        to_center: Vec2::new(80.0, 20.0),
+        duration_frames: 11,
+    });
+    proc.validate().unwrap();
+
+    let first = proc.sample(ctx(0, 0)).unwrap();
+    assert_eq!(first.scale, Vec2::new(1.0, 1.0));
+    assert_eq!(first.anchor, Vec2::new(50.0, 40.0));
+    assert_eq!(first.translate, Vec2::ZERO);
+
+    let last = proc.sample(ctx(10, 0)).unwrap();
+    assert_eq!(last.scale, Vec2::new(1.5, 1.5));
+    assert_eq!(last.anchor, Vec2::new(80.0, 20.0));
+    // The focus point stays pinned where the pan started.
+    let focus = last.to_affine() * kurbo::Point::new(80.0, 20.0);
+    assert!((focus.x - 50.0).abs() < 1e-9 && (focus.y - 40.0).abs() < 1e-9);
+    assert_eq!(proc.sample(ctx(20, 0)).unwrap().scale, last.scale);
+
+    let mid = proc.sample(ctx(5, 0)).unwrap();
+    assert!((mid.scale.x - 1.25).abs() < 1e-9);
+}
+
+#[test]
+fn ken_burns_validates_duration_and_sample_type() {
+    let proc = Procedural::<Transform2D>::new(ProceduralKind::KenBurns {
+        from_scale: 1.0,
+        to_scale: 2.0,
+        from_center: Vec2::ZERO,
+        to_center: Vec2::ZERO,
+        duration_frames: 0,
+    });
+    assert!(proc.validate().is_err());
+
+    let as_scalar = Procedural::<f64>::new(proc.kind.clone());
+    assert!(as_scalar.sample(ctx(0, 0)).is_err());
+}
+
+#[test]
+fn triangle_peaks_a_quarter_cycle_after_phase() {
+    // 0.5 Hz at 30 fps: one cycle every 60 frames.
+    let tri = |phase: f64| {
+        Procedural::<f64>::new(ProceduralKind::Scalar(ProcScalar::Triangle {
+            amp: 2.0,
+            freq_hz: 0.5,
+            phase,
+            offset: 1.0,
+        }))
+    };
+    let p = tri(0.0);
+    let samples = (0..120)
+        .map(|f| p.sample(ctx(f, 0)).unwrap())
+        .collect::<Vec<_>>();
+    let peak = (0..60)
+        .max_by(|&a, &b| samples[a].total_cmp(&samples[b]))
+        .unwrap();
+    assert_eq!(peak, 15);
+    assert!((samples[15] - 3.0).abs() < 1e-9);
+    assert!((samples[30] - 1.0).abs() < 1e-9);
+    assert!((samples[45] + 1.0).abs() < 1e-9);
+    assert!((samples[75] - samples[15]).abs() < 1e-9, "periodic");
+
+    // A quarter-cycle phase shift moves the peak to frame 0.
+    assert!((tri(std::f64::consts::FRAC_PI_2).sample(ctx(0, 0)).unwrap() - 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn sawtooth_wraps_at_period_boundary() {
+    let p = Procedural::<f64>::new(ProceduralKind::Scalar(ProcScalar::Sawtooth {
+        amp: 1.0,
+        freq_hz: 1.0,
+        phase: 0.0,
+        offset: 0.0,
+    }));
+    let samples = (0..61)
+        .map(|f| p.sample(ctx(f, 0)).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(samples[0], -1.0);
+    assert!(samples[..30].windows(2).all(|w| w[1] > w[0]));
+    assert!(samples[29] > 0.9);
+    assert!((samples[30] + 1.0).abs() < 1e-9, "wraps to the minimum");
+    assert!((samples[60] + 1.0).abs() < 1e-9);
+    assert!((samples[45] - samples[15]).abs() < 1e-9);
+}
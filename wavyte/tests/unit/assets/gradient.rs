@@ -0,0 +1,59 @@
+use super::*;
+
+fn stops(colors: &[(f64, [u8; 4])]) -> Vec<GradientStop> {
+    colors
+        .iter()
+        .map(|&(offset, color)| GradientStop { offset, color })
+        .collect()
+}
+
+fn gradient(width: u32, height: u32) -> PreparedRadialGradient {
+    PreparedRadialGradient {
+        width,
+        height,
+        center: [0.5, 0.5],
+        radius: 0.5,
+        stops: stops(&[(0.0, [255, 255, 255, 255]), (1.0, [0, 0, 255, 255])]),
+    }
+}
+
+#[test]
+fn radial_gradient_clamps_to_end_outside_radius() {
+    let px = rasterize_radial_gradient_premul_rgba8(&gradient(8, 8)).unwrap();
+    assert_eq!(&px[0..4], &[0, 0, 255, 255]);
+}
+
+#[test]
+fn radial_gradient_premultiplies_interpolated_alpha() {
+    let mut g = gradient(1, 1);
+    g.stops = stops(&[(0.0, [255, 0, 0, 255]), (1.0, [255, 0, 0, 0])]);
+    g.radius = 1.0;
+    g.center = [0.0, 0.5];
+    // Single pixel center sits half a radius away.
+    let px = rasterize_radial_gradient_premul_rgba8(&g).unwrap();
+    assert_eq!(px, vec![128, 0, 0, 128]);
+}
+
+#[test]
+fn radial_gradient_hits_middle_stop_at_its_offset() {
+    let mut g = gradient(1, 1);
+    g.stops = stops(&[
+        (0.0, [255, 0, 0, 255]),
+        (0.5, [0, 255, 0, 255]),
+        (1.0, [0, 0, 255, 255]),
+    ]);
+    g.radius = 1.0;
+    g.center = [0.0, 0.5];
+    let px = rasterize_radial_gradient_premul_rgba8(&g).unwrap();
+    assert_eq!(px, vec![0, 255, 0, 255]);
+}
+
+#[test]
+fn stop_segment_clamps_outside_stop_range() {
+    let s = stops(&[(0.25, [10, 0, 0, 255]), (0.75, [20, 0, 0, 255])]);
+    assert_eq!(stop_segment(&s, 0.0).0, [10, 0, 0, 255]);
+    assert_eq!(stop_segment(&s, 1.0).0, [20, 0, 0, 255]);
+    let (a, b, f) = stop_segment(&s, 0.5);
+    assert_eq!((a, b), ([10, 0, 0, 255], [20, 0, 0, 255]));
+    assert!((f - 0.5).abs() < 1e-12);
+}
@@ -10,9 +10,87 @@ fn source_time_mapping_applies_trim_and_rate() {
         volume: 1.0,
         fade_in_sec: 0.0,
         fade_out_sec: 0.0,
+        fade_curve: crate::AudioFadeCurve::Linear,
         muted: false,
+        frame_blend: false,
+        sampler: None,
+        loop_mode: VideoLoopMode::None,
     };
 
-    let t = video_source_time_sec(&video, 15, crate::Fps::new(30, 1).unwrap());
+    let t = video_source_time_sec(&video, 15.0, crate::Fps::new(30, 1).unwrap());
     assert!((t - 2.0).abs() < 1e-9);
 }
+
+#[test]
+fn slow_playback_snaps_to_nearest_source_frame_index() {
+    let video = VideoAsset {
+        source: "a.mp4".to_string(),
+        trim_start_sec: 0.0,
+        trim_end_sec: None,
+        playback_rate: 0.5,
+        volume: 1.0,
+        fade_in_sec: 0.0,
+        fade_out_sec: 0.0,
+        fade_curve: crate::AudioFadeCurve::Linear,
+        muted: false,
+        frame_blend: false,
+        sampler: None,
+        loop_mode: VideoLoopMode::None,
+    };
+    let info = VideoSourceInfo {
+        source_path: PathBuf::from("a.mp4"),
+        width: 16,
+        height: 16,
+        fps_num: 30_000,
+        fps_den: 1001,
+        duration_sec: 10.0,
+        has_audio: false,
+    };
+    let fps = crate::Fps::new(30, 1).unwrap();
+
+    let indices = (0..6)
+        .map(|f| info.frame_index_at(video_source_time_sec(&video, f as f64, fps)))
+        .collect::<Vec<_>>();
+    assert_eq!(indices, vec![0, 0, 1, 1, 2, 2]);
+    // Adjacent output frames 1 and 2 straddle a source frame boundary.
+    assert_ne!(indices[1], indices[2]);
+    assert_eq!(info.frame_index_at(info.frame_time_sec(7)), 7);
+}
+
+#[test]
+fn looping_wraps_source_time_past_the_trimmed_end() {
+    let info = VideoSourceInfo {
+        source_path: PathBuf::from("a.mp4"),
+        width: 16,
+        height: 16,
+        fps_num: 30,
+        fps_den: 1,
+        duration_sec: 3.0,
+        has_audio: false,
+    };
+    let one_frame = info.frame_time_sec(1);
+    let past_end = 3.0 + one_frame;
+
+    let held = loop_video_source_time(VideoLoopMode::None, 0.0, None, &info, past_end);
+    assert_eq!(held, past_end);
+
+    let looped = loop_video_source_time(VideoLoopMode::Loop, 0.0, None, &info, past_end);
+    assert_eq!(info.frame_index_at(looped), 1);
+    assert_eq!(
+        info.frame_index_at(loop_video_source_time(
+            VideoLoopMode::Loop,
+            0.0,
+            None,
+            &info,
+            3.0
+        )),
+        0
+    );
+
+    // Ping-pong reflects at the end and never samples past the last frame.
+    let reflected = loop_video_source_time(VideoLoopMode::PingPong, 0.0, None, &info, past_end);
+    assert_eq!(info.frame_index_at(reflected), 89);
+    let trimmed =
+        loop_video_source_time(VideoLoopMode::Loop, 1.0, Some(2.0), &info, 2.0 + one_frame);
+    assert_eq!(info.frame_index_at(trimmed), 31);
+}
@@ -34,6 +34,9 @@ fn prepare_path_assets_without_external_io() {
         "p0".to_string(),
         model::Asset::Path(model::PathAsset {
             svg_path_d: "M0,0 L10,0 L10,10 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: crate::FillRule::NonZero,
         }),
     );
 
@@ -47,6 +50,9 @@ fn prepare_path_assets_without_external_io() {
         assets,
         tracks: vec![],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     };
 
     let store = PreparedAssetStore::prepare(&comp, ".").unwrap();
@@ -84,6 +90,122 @@ fn text_layout_smoke_with_local_font_if_present() {
     assert!(layout.lines().next().is_some());
 }
 
+#[test]
+fn centered_text_is_centered_within_max_width() {
+    let font_bytes = std::fs::read("tests/data/fonts/Inconsolata-Regular.ttf").unwrap();
+    let mut engine = TextLayoutEngine::new();
+    let layout = engine
+        .layout_aligned(
+            "wavyte",
+            font_bytes.as_slice(),
+            32.0,
+            TextBrushRgba8::default(),
+            TextParagraph {
+                max_width_px: Some(300.0),
+                align: model::TextAlign::Center,
+                ..TextParagraph::default()
+            },
+        )
+        .unwrap();
+
+    let (mut x0, mut x1) = (f32::INFINITY, f32::NEG_INFINITY);
+    for line in layout.lines() {
+        for item in line.items() {
+            let parley::layout::PositionedLayoutItem::GlyphRun(run) = item else {
+                continue;
+            };
+            for g in run.positioned_glyphs() {
+                x0 = x0.min(g.x);
+                x1 = x1.max(g.x + g.advance);
+            }
+        }
+    }
+    assert!(x0 > 0.0 && x1 < 300.0, "{x0}..{x1}");
+    assert!(((x0 + x1) * 0.5 - 150.0).abs() <= 1.0, "{x0}..{x1}");
+}
+
+#[test]
+fn text_spans_produce_runs_with_their_own_colors() {
+    let font_bytes = std::fs::read("tests/data/fonts/Inconsolata-Regular.ttf").unwrap();
+    let span = |text: &str, color: [u8; 4]| model::TextSpan {
+        text: text.to_string(),
+        color_rgba8: Some(color),
+        size_px: None,
+        weight: Some(700.0),
+        italic: false,
+    };
+    let mut engine = TextLayoutEngine::new();
+    let layout = engine
+        .layout_spans(
+            &[
+                span("Hello ", [255, 255, 255, 255]),
+                span("world", [255, 0, 0, 255]),
+            ],
+            font_bytes.as_slice(),
+            32.0,
+            TextBrushRgba8::default(),
+            TextParagraph::default(),
+        )
+        .unwrap();
+
+    let mut brushes = Vec::new();
+    for line in layout.lines() {
+        for item in line.items() {
+            if let parley::layout::PositionedLayoutItem::GlyphRun(run) = item {
+                let b = run.style().brush;
+                if !brushes.contains(&b) {
+                    brushes.push(b);
+                }
+            }
+        }
+    }
+    assert_eq!(
+        brushes,
+        [
+            TextBrushRgba8 {
+                r: 255,
+                g: 255,
+                b: 255,
+                a: 255
+            },
+            TextBrushRgba8 {
+                r: 255,
+                g: 0,
+                b: 0,
+                a: 255
+            },
+        ]
+    );
+}
+
+#[test]
+fn doubling_line_height_doubles_two_line_extent() {
+    let font_bytes = std::fs::read("tests/data/fonts/Inconsolata-Regular.ttf").unwrap();
+    let mut engine = TextLayoutEngine::new();
+    let mut extent = |line_height: f32| {
+        let layout = engine
+            .layout_aligned(
+                "first\nsecond",
+                font_bytes.as_slice(),
+                32.0,
+                TextBrushRgba8::default(),
+                TextParagraph {
+                    line_height: Some(line_height),
+                    ..TextParagraph::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(layout.lines().count(), 2);
+        layout
+            .lines()
+            .map(|l| f64::from(l.metrics().line_height))
+            .sum::<f64>()
+    };
+    let single = extent(1.0);
+    let double = extent(2.0);
+    assert!((double / single - 2.0).abs() < 0.05, "{single} -> {double}");
+}
+
 #[test]
 fn prepare_single_image_asset() {
     let tmp = std::env::temp_dir().join(format!(
@@ -109,6 +231,7 @@ fn prepare_single_image_asset() {
         "img".to_string(),
         model::Asset::Image(model::ImageAsset {
             source: "img.png".to_string(),
+            sampler: None,
         }),
     );
 
@@ -122,6 +245,9 @@ fn prepare_single_image_asset() {
         assets,
         tracks: vec![],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     };
 
     let store = PreparedAssetStore::prepare(&comp, &tmp).unwrap();
@@ -135,3 +261,51 @@ fn prepare_single_image_asset() {
 
     std::fs::remove_dir_all(&tmp).ok();
 }
+
+#[test]
+fn fallback_fonts_cover_glyphs_missing_from_the_primary() {
+    let primary = std::fs::read("tests/data/fonts/Inconsolata-Regular.ttf").unwrap();
+    let fallback = std::fs::read("tests/data/fonts/Tuffy.ttf").unwrap();
+    let mut engine = TextLayoutEngine::new();
+    // U+0250 (turned a) is in Tuffy but not in Inconsolata.
+    let text = "a\u{250}";
+    assert_eq!(
+        engine.missing_codepoints(text, primary.as_slice()).unwrap(),
+        vec!['\u{250}']
+    );
+
+    engine.set_fallback_fonts(&[fallback.as_slice()]).unwrap();
+    assert!(
+        engine
+            .missing_codepoints(text, primary.as_slice())
+            .unwrap()
+            .is_empty()
+    );
+    let layout = engine
+        .layout_plain(
+            text,
+            primary.as_slice(),
+            32.0,
+            TextBrushRgba8::default(),
+            None,
+        )
+        .unwrap();
+    let blob_ids = engine.last_font_blob_ids();
+    assert_eq!(blob_ids.len(), 2);
+
+    let mut used = Vec::new();
+    for line in layout.lines() {
+        for item in line.items() {
+            let parley::layout::PositionedLayoutItem::GlyphRun(run) = item else {
+                continue;
+            };
+            assert!(run.glyphs().all(|g| g.id != 0));
+            used.push(run.run().font().data.id());
+        }
+    }
+    used.sort_unstable();
+    used.dedup();
+    let mut expected = blob_ids.clone();
+    expected.sort_unstable();
+    assert_eq!(used, expected);
+}
@@ -18,9 +18,14 @@ fn mix_applies_overlap_and_fades() {
         volume: 1.0,
         fade_in_sec: 0.0,
         fade_out_sec: 0.0,
+        fade_curve: AudioFadeCurve::Linear,
         source_sample_rate: 4,
         source_channels: 2,
         source_interleaved_f32: Arc::new(vec![0.25, 0.25, 0.25, 0.25, 0.25, 0.25, 0.25, 0.25]),
+        pan: 0.0,
+        width: 1.0,
+        asset_key: "a".to_string(),
+        duck: None,
     };
     let seg_b = AudioSegment {
         timeline_start_sample: 2,
@@ -31,9 +36,14 @@ fn mix_applies_overlap_and_fades() {
         volume: 1.0,
         fade_in_sec: 0.5,
         fade_out_sec: 0.0,
+        fade_curve: AudioFadeCurve::Linear,
         source_sample_rate: 4,
         source_channels: 2,
         source_interleaved_f32: Arc::new(vec![1.0, 1.0, 1.0, 1.0]),
+        pan: 0.0,
+        width: 1.0,
+        asset_key: "b".to_string(),
+        duck: None,
     };
 
     let manifest = AudioManifest {
@@ -48,3 +58,301 @@ fn mix_applies_overlap_and_fades() {
     assert!(out[4] >= 0.25);
     assert!(out[6] > out[4]);
 }
+
+#[test]
+fn ducking_attenuates_music_under_loud_trigger_and_recovers() {
+    let sr = 1000;
+    let constant = |key: &str, start: u64, end: u64, lr: [f32; 2], duck| AudioSegment {
+        timeline_start_sample: start,
+        timeline_end_sample: end,
+        source_start_sec: 0.0,
+        source_end_sec: None,
+        playback_rate: 1.0,
+        volume: 1.0,
+        fade_in_sec: 0.0,
+        fade_out_sec: 0.0,
+        fade_curve: AudioFadeCurve::Linear,
+        source_sample_rate: sr,
+        source_channels: 2,
+        source_interleaved_f32: Arc::new(lr.repeat((end - start) as usize)),
+        pan: 0.0,
+        width: 1.0,
+        asset_key: key.to_string(),
+        duck,
+    };
+    // Music on both channels; the voice only on the left so the right channel is music alone.
+    let music = constant(
+        "music",
+        0,
+        3000,
+        [0.4, 0.4],
+        Some(AudioDuck {
+            trigger_asset: "voice".to_string(),
+            amount: 0.5,
+            attack_ms: 10.0,
+            release_ms: 50.0,
+            threshold: 0.05,
+        }),
+    );
+    let voice = constant("voice", 1000, 2000, [0.5, 0.0], None);
+    let manifest = AudioManifest {
+        sample_rate: sr,
+        channels: 2,
+        total_samples: 3000,
+        segments: vec![music, voice],
+    };
+    let out = mix_manifest(&manifest);
+    assert_eq!(out, mix_manifest(&manifest));
+
+    let rms_right = |from: usize, to: usize| {
+        let sum = (from..to).map(|i| out[i * 2 + 1].powi(2)).sum::<f32>();
+        (sum / (to - from) as f32).sqrt()
+    };
+    assert!((rms_right(500, 1000) - 0.4).abs() < 1e-4);
+    assert!((rms_right(1200, 2000) - 0.2).abs() < 1e-3);
+    assert!((rms_right(2500, 3000) - 0.4).abs() < 1e-3);
+}
+
+#[test]
+fn ducking_ramps_with_default_attack_and_release() {
+    let duck: AudioDuck =
+        serde_json::from_value(serde_json::json!({"trigger_asset": "voice", "amount": 1.0}))
+            .unwrap();
+    assert_eq!(
+        (duck.attack_ms, duck.release_ms, duck.threshold),
+        (10.0, 200.0, 0.05)
+    );
+
+    let sr = 1000;
+    let constant = |key: &str, start: u64, end: u64, lr: [f32; 2], duck| AudioSegment {
+        timeline_start_sample: start,
+        timeline_end_sample: end,
+        source_start_sec: 0.0,
+        source_end_sec: None,
+        playback_rate: 1.0,
+        volume: 1.0,
+        fade_in_sec: 0.0,
+        fade_out_sec: 0.0,
+        fade_curve: AudioFadeCurve::Linear,
+        source_sample_rate: sr,
+        source_channels: 2,
+        source_interleaved_f32: Arc::new(lr.repeat((end - start) as usize)),
+        pan: 0.0,
+        width: 1.0,
+        asset_key: key.to_string(),
+        duck,
+    };
+    let manifest = AudioManifest {
+        sample_rate: sr,
+        channels: 2,
+        total_samples: 2000,
+        segments: vec![
+            constant("music", 0, 2000, [0.5, 0.5], Some(duck)),
+            constant("voice", 500, 1000, [0.5, 0.0], None),
+        ],
+    };
+    let out = mix_manifest(&manifest);
+    // The voice sits on the left channel only, so the right channel is the ducked music.
+    let music = |i: usize| out[i * 2 + 1];
+    assert!(music(500) > 0.4, "attack must not switch instantly");
+    assert!(music(520) < 0.1);
+    assert!(music(1000) > 0.0 && music(1000) < 0.05);
+    assert!(music(1100) > 0.05 && music(1100) < 0.4, "release must ramp");
+    assert!(music(1999) > 0.45);
+}
+
+#[test]
+fn ducking_follows_an_ac_trigger_and_scales_with_threshold() {
+    let sr = 8000;
+    let segment = |key: &str, start: u64, samples: Vec<f32>, duck| AudioSegment {
+        timeline_start_sample: start,
+        timeline_end_sample: start + samples.len() as u64 / 2,
+        source_start_sec: 0.0,
+        source_end_sec: None,
+        playback_rate: 1.0,
+        volume: 1.0,
+        fade_in_sec: 0.0,
+        fade_out_sec: 0.0,
+        fade_curve: AudioFadeCurve::Linear,
+        source_sample_rate: sr,
+        source_channels: 2,
+        source_interleaved_f32: Arc::new(samples),
+        pan: 0.0,
+        width: 1.0,
+        asset_key: key.to_string(),
+        duck,
+    };
+    // A 200 Hz tone on the left only: its instantaneous level crosses zero 400 times a second.
+    let tone = (0..4000)
+        .flat_map(|i| {
+            let v = 0.5 * (std::f32::consts::TAU * 200.0 * i as f32 / sr as f32).sin();
+            [v, 0.0]
+        })
+        .collect::<Vec<_>>();
+    let ducked_rms = |threshold: f64| {
+        let duck = AudioDuck {
+            trigger_asset: "voice".to_string(),
+            amount: 0.5,
+            attack_ms: 10.0,
+            release_ms: 200.0,
+            threshold,
+        };
+        let manifest = AudioManifest {
+            sample_rate: sr,
+            channels: 2,
+            total_samples: 8000,
+            segments: vec![
+                segment("music", 0, [0.4, 0.4].repeat(8000), Some(duck)),
+                segment("voice", 2000, tone.clone(), None),
+            ],
+        };
+        let out = mix_manifest(&manifest);
+        let sum = (3000..6000).map(|i| out[i * 2 + 1].powi(2)).sum::<f32>();
+        (sum / 3000.0).sqrt()
+    };
+
+    // The follower holds through zero crossings, so the default threshold ducks fully.
+    assert!(
+        (ducked_rms(0.05) - 0.2).abs() < 2e-3,
+        "{}",
+        ducked_rms(0.05)
+    );
+    // A threshold above the tone's peak only ducks part of the way.
+    let partial = ducked_rms(1.0);
+    assert!(partial > 0.25 && partial < 0.4, "{partial}");
+}
+
+#[test]
+fn pan_and_width_shape_the_stereo_image() {
+    let mix = |pan: f32, width: f32| {
+        let seg = AudioSegment {
+            timeline_start_sample: 0,
+            timeline_end_sample: 2,
+            source_start_sec: 0.0,
+            source_end_sec: None,
+            playback_rate: 1.0,
+            volume: 1.0,
+            fade_in_sec: 0.0,
+            fade_out_sec: 0.0,
+            fade_curve: AudioFadeCurve::Linear,
+            source_sample_rate: 2,
+            source_channels: 2,
+            source_interleaved_f32: Arc::new(vec![0.4, 0.1, 0.4, 0.1]),
+            pan,
+            width,
+            asset_key: "a".to_string(),
+            duck: None,
+        };
+        mix_manifest(&AudioManifest {
+            sample_rate: 2,
+            channels: 2,
+            total_samples: 2,
+            segments: vec![seg],
+        })
+    };
+
+    assert_eq!(mix(0.0, 1.0), vec![0.4, 0.1, 0.4, 0.1]);
+
+    let left = mix(-1.0, 1.0);
+    assert!(left[0] > 0.4);
+    assert!(left[1].abs() < 1e-6);
+
+    let mono = mix(0.0, 0.0);
+    assert!((mono[0] - 0.25).abs() < 1e-6);
+    assert_eq!(mono[0], mono[1]);
+}
+
+#[test]
+fn equal_power_crossfade_holds_unity_power_at_midpoint() {
+    // One second fading out on the left channel overlapping one fading in on the right, so the
+    // two gains can be read separately.
+    let sr = 100;
+    let fade = |lr: [f32; 2], fade_in_sec: f64, fade_out_sec: f64, curve| AudioSegment {
+        timeline_start_sample: 0,
+        timeline_end_sample: sr,
+        source_start_sec: 0.0,
+        source_end_sec: None,
+        playback_rate: 1.0,
+        volume: 1.0,
+        fade_in_sec,
+        fade_out_sec,
+        fade_curve: curve,
+        source_sample_rate: sr as u32,
+        source_channels: 2,
+        source_interleaved_f32: Arc::new(lr.repeat(sr as usize)),
+        pan: 0.0,
+        width: 1.0,
+        asset_key: "a".to_string(),
+        duck: None,
+    };
+    let mid_power = |curve| {
+        let out = mix_manifest(&AudioManifest {
+            sample_rate: sr as u32,
+            channels: 2,
+            total_samples: sr,
+            segments: vec![
+                fade([1.0, 0.0], 0.0, 1.0, curve),
+                fade([0.0, 1.0], 1.0, 0.0, curve),
+            ],
+        });
+        let mid = (sr / 2) as usize * 2;
+        out[mid].powi(2) + out[mid + 1].powi(2)
+    };
+
+    assert!((mid_power(AudioFadeCurve::EqualPower) - 1.0).abs() < 1e-3);
+    // A linear crossfade dips to half power.
+    assert!((mid_power(AudioFadeCurve::Linear) - 0.5).abs() < 1e-3);
+}
+
+#[test]
+fn mix_audio_decodes_in_memory_wav_and_validates_first() {
+    // One second of a constant 0.5 mono tone at 24 kHz, resampled to the mix rate.
+    let mut wav = Vec::new();
+    let data_len = 24_000u32 * 2;
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    for field in [1u16, 1] {
+        wav.extend_from_slice(&field.to_le_bytes());
+    }
+    wav.extend_from_slice(&24_000u32.to_le_bytes());
+    wav.extend_from_slice(&48_000u32.to_le_bytes());
+    for field in [2u16, 16] {
+        wav.extend_from_slice(&field.to_le_bytes());
+    }
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for _ in 0..24_000 {
+        wav.extend_from_slice(&16_384i16.to_le_bytes());
+    }
+    let mut files = crate::InMemoryAssetMap::new();
+    files.insert("tone.wav", wav).unwrap();
+
+    let mut v: serde_json::Value =
+        serde_json::from_str(include_str!("../../data/simple_comp.json")).unwrap();
+    v["assets"]["t0"] = serde_json::json!({ "Audio": { "source": "tone.wav", "volume": 0.5 } });
+    let clip = &mut v["tracks"][0]["clips"][0];
+    clip.as_object_mut().unwrap().remove("transition_in");
+    clip.as_object_mut().unwrap().remove("transition_out");
+    let mut comp: Composition = serde_json::from_value(v).unwrap();
+    let assets = PreparedAssetStore::prepare_with_source(&comp, &files).unwrap();
+
+    let range = FrameRange::new(FrameIndex(0), FrameIndex(10)).unwrap();
+    let mixed = mix_audio(&comp, &assets, range).unwrap();
+    assert_eq!(
+        (mixed.sample_rate, mixed.channels),
+        (media::MIX_SAMPLE_RATE, 2)
+    );
+    assert_eq!(mixed.samples, 16_000);
+    assert!(
+        mixed
+            .interleaved_f32
+            .iter()
+            .all(|&s| (s - 0.25).abs() < 1e-6)
+    );
+
+    comp.tracks[0].clips[0].reverse = true;
+    let err = mix_audio(&comp, &assets, range).unwrap_err();
+    assert!(err.to_string().contains("cannot reverse audio"), "{err}");
+}
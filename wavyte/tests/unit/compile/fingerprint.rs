@@ -13,6 +13,9 @@ fn comp_with_opacity(opacity: f64) -> Composition {
             "p0".to_string(),
             crate::Asset::Path(crate::PathAsset {
                 svg_path_d: "M0,0 L10,0 L10,10 Z".to_string(),
+                fill: None,
+                stroke: None,
+                fill_rule: crate::FillRule::NonZero,
             }),
         )]),
         tracks: vec![crate::Track {
@@ -24,6 +27,8 @@ fn comp_with_opacity(opacity: f64) -> Composition {
             layout_align_x: crate::LayoutAlignX::Start,
             layout_align_y: crate::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![Clip {
                 id: "c0".to_string(),
                 asset: "p0".to_string(),
@@ -32,14 +37,26 @@ fn comp_with_opacity(opacity: f64) -> Composition {
                     transform: Anim::constant(crate::Transform2D::default()),
                     opacity: Anim::constant(opacity),
                     blend: BlendMode::Normal,
+                    opacity_at_composite: false,
+                    wiggle: None,
+                    box_anchor: None,
+                    aspect_ratio: None,
+                    fit: crate::ContentFit::None,
                 },
                 z_offset: 0,
                 effects: vec![],
                 transition_in: None,
                 transition_out: None,
+                seed: None,
+                loop_content: false,
+                time_remap: None,
+                reverse: false,
             }],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     }
 }
 
@@ -60,3 +77,25 @@ fn fingerprint_changes_when_scene_changes() {
     let b_eval = Evaluator::eval_frame(&b_comp, FrameIndex(0)).unwrap();
     assert_ne!(fingerprint_eval(&a_eval), fingerprint_eval(&b_eval));
 }
+
+#[test]
+fn fingerprint_changes_with_nested_composition_opacity() {
+    let nested = |opacity: f64| {
+        let mut comp = comp_with_opacity(1.0);
+        comp.assets.insert(
+            "child".to_string(),
+            crate::Asset::Composition(crate::CompositionAsset {
+                composition: Box::new(comp_with_opacity(1.0)),
+            }),
+        );
+        let clip = &mut comp.tracks[0].clips[0];
+        clip.asset = "child".to_string();
+        clip.props.opacity = Anim::constant(opacity);
+        comp
+    };
+    let a = Evaluator::eval_frame(&nested(1.0), FrameIndex(0)).unwrap();
+    let b = Evaluator::eval_frame(&nested(0.5), FrameIndex(0)).unwrap();
+    // The child nodes are identical; only the stack's composite differs.
+    assert_eq!(a.nodes[0].opacity, b.nodes[0].opacity);
+    assert_ne!(fingerprint_eval(&a), fingerprint_eval(&b));
+}
@@ -63,6 +63,9 @@ fn compile_path_emits_fillpath_without_asset_cache() {
         "p0".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L10,0 L10,10 L0,10 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: crate::FillRule::NonZero,
         }),
     );
 
@@ -83,6 +86,8 @@ fn compile_path_emits_fillpath_without_asset_cache() {
             layout_align_x: crate::LayoutAlignX::Start,
             layout_align_y: crate::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![Clip {
                 id: "c0".to_string(),
                 asset: "p0".to_string(),
@@ -91,6 +96,11 @@ fn compile_path_emits_fillpath_without_asset_cache() {
                     transform: Anim::constant(Transform2D::default()),
                     opacity: Anim::constant(1.0),
                     blend: BlendMode::Normal,
+                    opacity_at_composite: false,
+                    wiggle: None,
+                    box_anchor: None,
+                    aspect_ratio: None,
+                    fit: crate::ContentFit::None,
                 },
                 z_offset: 0,
                 effects: vec![],
@@ -101,9 +111,16 @@ fn compile_path_emits_fillpath_without_asset_cache() {
                     params: serde_json::Value::Null,
                 }),
                 transition_out: None,
+                seed: None,
+                loop_content: false,
+                time_remap: None,
+                reverse: false,
             }],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     };
 
     let eval = Evaluator::eval_frame(&comp, FrameIndex(1)).unwrap();
@@ -128,6 +145,9 @@ fn compile_applies_inline_effects_to_opacity_and_transform() {
         "p0".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L10,0 L10,10 L0,10 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: crate::FillRule::NonZero,
         }),
     );
 
@@ -148,6 +168,8 @@ fn compile_applies_inline_effects_to_opacity_and_transform() {
             layout_align_x: crate::LayoutAlignX::Start,
             layout_align_y: crate::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![Clip {
                 id: "c0".to_string(),
                 asset: "p0".to_string(),
@@ -156,6 +178,11 @@ fn compile_applies_inline_effects_to_opacity_and_transform() {
                     transform: Anim::constant(Transform2D::default()),
                     opacity: Anim::constant(1.0),
                     blend: BlendMode::Normal,
+                    opacity_at_composite: false,
+                    wiggle: None,
+                    box_anchor: None,
+                    aspect_ratio: None,
+                    fit: crate::ContentFit::None,
                 },
                 z_offset: 0,
                 effects: vec![
@@ -170,9 +197,16 @@ fn compile_applies_inline_effects_to_opacity_and_transform() {
                 ],
                 transition_in: None,
                 transition_out: None,
+                seed: None,
+                loop_content: false,
+                time_remap: None,
+                reverse: false,
             }],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     };
 
     let eval = Evaluator::eval_frame(&comp, FrameIndex(0)).unwrap();
@@ -201,6 +235,9 @@ fn compile_emits_offscreen_blur_pass_and_composites_blurred_surface() {
         "p0".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L10,0 L10,10 L0,10 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: crate::FillRule::NonZero,
         }),
     );
 
@@ -221,6 +258,8 @@ fn compile_emits_offscreen_blur_pass_and_composites_blurred_surface() {
             layout_align_x: crate::LayoutAlignX::Start,
             layout_align_y: crate::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![Clip {
                 id: "c0".to_string(),
                 asset: "p0".to_string(),
@@ -229,6 +268,11 @@ fn compile_emits_offscreen_blur_pass_and_composites_blurred_surface() {
                     transform: Anim::constant(Transform2D::default()),
                     opacity: Anim::constant(1.0),
                     blend: BlendMode::Normal,
+                    opacity_at_composite: false,
+                    wiggle: None,
+                    box_anchor: None,
+                    aspect_ratio: None,
+                    fit: crate::ContentFit::None,
                 },
                 z_offset: 0,
                 effects: vec![EffectInstance {
@@ -237,9 +281,16 @@ fn compile_emits_offscreen_blur_pass_and_composites_blurred_surface() {
                 }],
                 transition_in: None,
                 transition_out: None,
+                seed: None,
+                loop_content: false,
+                time_remap: None,
+                reverse: false,
             }],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     };
 
     let eval = Evaluator::eval_frame(&comp, FrameIndex(0)).unwrap();
@@ -262,7 +313,8 @@ fn compile_emits_offscreen_blur_pass_and_composites_blurred_surface() {
                 p.fx,
                 crate::effects::fx::PassFx::Blur {
                     radius_px: 3,
-                    sigma: 2.0
+                    sigma: 2.0,
+                    alpha_only: false,
                 }
             );
         }
@@ -292,6 +344,9 @@ fn compile_pairs_crossfade_into_single_composite_op() {
         "p0".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L10,0 L10,10 L0,10 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: crate::FillRule::NonZero,
         }),
     );
 
@@ -319,6 +374,8 @@ fn compile_pairs_crossfade_into_single_composite_op() {
             layout_align_x: crate::LayoutAlignX::Start,
             layout_align_y: crate::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![
                 Clip {
                     id: "a".to_string(),
@@ -328,11 +385,20 @@ fn compile_pairs_crossfade_into_single_composite_op() {
                         transform: Anim::constant(Transform2D::default()),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: crate::ContentFit::None,
                     },
                     z_offset: 0,
                     effects: vec![],
                     transition_in: None,
                     transition_out: Some(tr.clone()),
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 },
                 Clip {
                     id: "b".to_string(),
@@ -342,15 +408,27 @@ fn compile_pairs_crossfade_into_single_composite_op() {
                         transform: Anim::constant(Transform2D::default()),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: crate::ContentFit::None,
                     },
                     z_offset: 1,
                     effects: vec![],
                     transition_in: Some(tr),
                     transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 },
             ],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     };
 
     let eval = Evaluator::eval_frame(&comp, FrameIndex(8)).unwrap();
@@ -378,6 +456,9 @@ fn compile_pairs_wipe_into_single_composite_op() {
         "p0".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L10,0 L10,10 L0,10 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: crate::FillRule::NonZero,
         }),
     );
 
@@ -405,6 +486,8 @@ fn compile_pairs_wipe_into_single_composite_op() {
             layout_align_x: crate::LayoutAlignX::Start,
             layout_align_y: crate::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![
                 Clip {
                     id: "a".to_string(),
@@ -414,11 +497,20 @@ fn compile_pairs_wipe_into_single_composite_op() {
                         transform: Anim::constant(Transform2D::default()),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: crate::ContentFit::None,
                     },
                     z_offset: 0,
                     effects: vec![],
                     transition_in: None,
                     transition_out: Some(tr.clone()),
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 },
                 Clip {
                     id: "b".to_string(),
@@ -428,15 +520,27 @@ fn compile_pairs_wipe_into_single_composite_op() {
                         transform: Anim::constant(Transform2D::default()),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: crate::ContentFit::None,
                     },
                     z_offset: 1,
                     effects: vec![],
                     transition_in: Some(tr),
                     transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 },
             ],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     };
 
     let eval = Evaluator::eval_frame(&comp, FrameIndex(8)).unwrap();
@@ -454,12 +558,18 @@ fn compile_pairs_wipe_into_single_composite_op() {
             t,
             dir,
             soft_edge,
+            sweep,
         } => {
             assert_eq!(*a, SurfaceId(1));
             assert_eq!(*b, SurfaceId(2));
             assert!((*t - 0.5).abs() <= 1e-6);
             assert_eq!(*dir, WipeDir::TopToBottom);
             assert!((*soft_edge - 0.2).abs() <= 1e-6);
+            assert_eq!((sweep.x, sweep.y), (0, 0));
+            assert_eq!(
+                (sweep.width, sweep.height),
+                (plan.canvas.width, plan.canvas.height)
+            );
         }
         other => panic!("expected Wipe op, got {other:?}"),
     }
@@ -472,6 +582,9 @@ fn compile_does_not_pair_transitions_when_progress_is_not_aligned() {
         "p0".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L10,0 L10,10 L0,10 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: crate::FillRule::NonZero,
         }),
     );
 
@@ -505,6 +618,8 @@ fn compile_does_not_pair_transitions_when_progress_is_not_aligned() {
             layout_align_x: crate::LayoutAlignX::Start,
             layout_align_y: crate::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![
                 Clip {
                     id: "a".to_string(),
@@ -514,11 +629,20 @@ fn compile_does_not_pair_transitions_when_progress_is_not_aligned() {
                         transform: Anim::constant(Transform2D::default()),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: crate::ContentFit::None,
                     },
                     z_offset: 0,
                     effects: vec![],
                     transition_in: None,
                     transition_out: Some(out_tr),
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 },
                 Clip {
                     id: "b".to_string(),
@@ -528,15 +652,27 @@ fn compile_does_not_pair_transitions_when_progress_is_not_aligned() {
                         transform: Anim::constant(Transform2D::default()),
                         opacity: Anim::constant(1.0),
                         blend: BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: crate::ContentFit::None,
                     },
                     z_offset: 1,
                     effects: vec![],
                     transition_in: Some(in_tr),
                     transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 },
             ],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     };
 
     let eval = Evaluator::eval_frame(&comp, FrameIndex(8)).unwrap();
@@ -567,3 +703,159 @@ fn compile_does_not_pair_transitions_when_progress_is_not_aligned() {
     assert!((op0 - (1.0 / 3.0)).abs() <= 0.02);
     assert!((op1 - 0.5).abs() <= 1e-6);
 }
+
+#[test]
+fn scaled_canvas_rounds_to_even_and_keeps_unit_scale() {
+    let canvas = Canvas {
+        width: 101,
+        height: 63,
+    };
+    assert_eq!(scaled_canvas(canvas, 1.0), canvas);
+    assert_eq!(
+        scaled_canvas(canvas, 0.5),
+        Canvas {
+            width: 50,
+            height: 32
+        }
+    );
+    assert_eq!(
+        scaled_canvas(canvas, 0.001),
+        Canvas {
+            width: 2,
+            height: 2
+        }
+    );
+}
+
+#[test]
+fn compile_with_scale_shrinks_surfaces_transforms_and_pass_params() {
+    let mut assets = BTreeMap::new();
+    assets.insert(
+        "p0".to_string(),
+        Asset::Path(PathAsset {
+            svg_path_d: "M0,0 L10,0 L10,10 L0,10 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: crate::FillRule::NonZero,
+        }),
+    );
+    let comp = Composition {
+        fps: Fps::new(30, 1).unwrap(),
+        canvas: Canvas {
+            width: 64,
+            height: 64,
+        },
+        duration: FrameIndex(10),
+        assets,
+        tracks: vec![Track {
+            name: "t".to_string(),
+            z_base: 0,
+            layout_mode: crate::LayoutMode::Absolute,
+            layout_gap_px: 0.0,
+            layout_padding: crate::Edges::default(),
+            layout_align_x: crate::LayoutAlignX::Start,
+            layout_align_y: crate::LayoutAlignY::Start,
+            layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
+            clips: vec![Clip {
+                id: "c0".to_string(),
+                asset: "p0".to_string(),
+                range: FrameRange::new(FrameIndex(0), FrameIndex(10)).unwrap(),
+                props: ClipProps {
+                    transform: Anim::constant(Transform2D {
+                        translate: crate::foundation::core::Vec2::new(20.0, 8.0),
+                        ..Transform2D::default()
+                    }),
+                    opacity: Anim::constant(1.0),
+                    blend: BlendMode::Normal,
+                    opacity_at_composite: false,
+                    wiggle: None,
+                    box_anchor: None,
+                    aspect_ratio: None,
+                    fit: crate::ContentFit::None,
+                },
+                z_offset: 0,
+                effects: vec![EffectInstance {
+                    kind: "blur".to_string(),
+                    params: serde_json::json!({ "radius_px": 4, "sigma": 2.0 }),
+                }],
+                transition_in: None,
+                transition_out: None,
+                seed: None,
+                loop_content: false,
+                time_remap: None,
+                reverse: false,
+            }],
+        }],
+        seed: 1,
+        background: None,
+        title: None,
+        description: None,
+    };
+
+    let eval = Evaluator::eval_frame(&comp, FrameIndex(0)).unwrap();
+    let store = store_for(&comp);
+    let plan = compile_frame_with_cache(
+        &comp,
+        &eval,
+        &store,
+        &mut CompileCache::default(),
+        PlanView::scaled(0.5),
+    )
+    .unwrap();
+
+    assert_eq!(
+        plan.canvas,
+        Canvas {
+            width: 32,
+            height: 32
+        }
+    );
+    assert_eq!((plan.surfaces[0].width, plan.surfaces[0].height), (32, 32));
+    let Pass::Scene(scene) = &plan.passes[0] else {
+        panic!("expected Scene pass");
+    };
+    let DrawOp::FillPath { transform, .. } = &scene.ops[0] else {
+        panic!("expected FillPath op");
+    };
+    let origin = plan.surfaces[1].origin;
+    let corner = *transform * crate::foundation::core::Point::new(0.0, 0.0);
+    assert_eq!(
+        (
+            corner.x + f64::from(origin[0]),
+            corner.y + f64::from(origin[1])
+        ),
+        (10.0, 4.0)
+    );
+    let Pass::Offscreen(p) = &plan.passes[1] else {
+        panic!("expected Offscreen pass");
+    };
+    assert_eq!(
+        p.fx,
+        crate::effects::fx::PassFx::Blur {
+            radius_px: 2,
+            sigma: 1.0,
+            alpha_only: false,
+        }
+    );
+}
+
+#[test]
+fn pass_fx_dump_lists_parameters_by_name() {
+    assert_eq!(
+        dump_pass_fx(&PassFx::LinearLight(Box::new(PassFx::DropShadow {
+            offset: [3, -2],
+            blur_radius_px: 4,
+            sigma: 2.5,
+            color: [0, 0, 0, 128],
+        }))),
+        "linear_light drop_shadow offset=[3 -2] blur_radius_px=4 sigma=2.5 color=0,0,0,128"
+    );
+    assert_eq!(
+        dump_pass_fx(&PassFx::ClipRect {
+            rect: [0.0, 1.5, 32.0, 16.0],
+        }),
+        "clip_rect rect=[0 1.5 32 16]"
+    );
+}
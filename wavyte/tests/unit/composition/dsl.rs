@@ -1,6 +1,5 @@
 use super::*;
 use crate::{
-    animation::ease::Ease,
     composition::model::{Asset, TextAsset},
     foundation::core::{Fps, Vec2},
 };
@@ -48,6 +47,12 @@ fn builders_create_expected_structure() {
             size_px: 48.0,
             max_width_px: None,
             color_rgba8: [255, 255, 255, 255],
+            align: crate::TextAlign::Left,
+            valign: crate::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )
     .unwrap()
@@ -77,6 +82,12 @@ fn duplicate_asset_key_is_rejected() {
             size_px: 48.0,
             max_width_px: None,
             color_rgba8: [255, 255, 255, 255],
+            align: crate::TextAlign::Left,
+            valign: crate::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     )
     .unwrap();
@@ -90,8 +101,98 @@ fn duplicate_asset_key_is_rejected() {
                     size_px: 48.0,
                     max_width_px: None,
                     color_rgba8: [255, 255, 255, 255],
+                    align: crate::TextAlign::Left,
+                    valign: crate::TextVAlign::Top,
+                    spans: vec![],
+                    line_height: None,
+                    letter_spacing_px: None,
+                    fallback_fonts: vec![],
                 }),
             )
             .is_err()
     );
 }
+
+#[test]
+fn typed_helpers_match_equivalent_json() {
+    let range = FrameRange::new(FrameIndex(0), FrameIndex(20)).unwrap();
+    let clip = ClipBuilder::new("c0", "p0", range)
+        .blend(BlendMode::Normal)
+        .effect(blur_effect(4, Some(1.5)))
+        .effect(opacity_mul_effect(0.5))
+        .effect(transform_post_effect(Affine::translate((2.0, 3.0))))
+        .transition_in(crossfade_transition(5, Ease::Linear))
+        .transition_out(wipe_transition(
+            5,
+            Ease::InOutQuad,
+            WipeDir::TopToBottom,
+            0.25,
+        ))
+        .build()
+        .unwrap();
+    let built = CompositionBuilder::new(
+        Fps::new(30, 1).unwrap(),
+        Canvas {
+            width: 64,
+            height: 64,
+        },
+        FrameIndex(20),
+    )
+    .path_asset("p0", "M0,0 L10,0 L10,10 Z")
+    .unwrap()
+    .image_asset("i0", "assets/a.png")
+    .unwrap()
+    .track(TrackBuilder::new("main").clip(clip).build().unwrap())
+    .build()
+    .unwrap();
+
+    let json = r#"{
+        "fps": {"num": 30, "den": 1},
+        "canvas": {"width": 64, "height": 64},
+        "duration": 20,
+        "assets": {
+            "p0": {"Path": {"svg_path_d": "M0,0 L10,0 L10,10 Z"}},
+            "i0": {"Image": {"source": "assets/a.png"}}
+        },
+        "tracks": [{
+            "name": "main",
+            "z_base": 0,
+            "clips": [{
+                "id": "c0",
+                "asset": "p0",
+                "range": {"start": 0, "end": 20},
+                "props": {
+                    "transform": {"Keyframes": {"keys": [{"frame": 0, "value": {
+                        "translate": {"x": 0.0, "y": 0.0},
+                        "rotation_rad": 0.0,
+                        "scale": {"x": 1.0, "y": 1.0},
+                        "anchor": {"x": 0.0, "y": 0.0}
+                    }, "ease": "Linear"}], "mode": "Hold"}},
+                    "opacity": {"Keyframes": {"keys": [{"frame": 0, "value": 1.0, "ease": "Linear"}], "mode": "Hold"}},
+                    "blend": "Normal"
+                },
+                "z_offset": 0,
+                "effects": [
+                    {"kind": "blur", "params": {"radius_px": 4, "sigma": 1.5}},
+                    {"kind": "opacity_mul", "params": {"value": 0.5}},
+                    {"kind": "transform_post", "params": {"affine": [1.0, 0.0, 0.0, 1.0, 2.0, 3.0]}}
+                ],
+                "transition_in": {"kind": "crossfade", "duration_frames": 5, "ease": "Linear"},
+                "transition_out": {
+                    "kind": "wipe",
+                    "duration_frames": 5,
+                    "ease": "InOutQuad",
+                    "params": {"dir": "top_to_bottom", "soft_edge": 0.25}
+                }
+            }]
+        }],
+        "seed": 0
+    }"#;
+    let parsed: Composition = serde_json::from_str(json).unwrap();
+    parsed.validate().unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&built).unwrap(),
+        serde_json::to_value(&parsed).unwrap()
+    );
+}
@@ -11,6 +11,12 @@ fn basic_comp() -> Composition {
             size_px: 48.0,
             max_width_px: None,
             color_rgba8: [255, 255, 255, 255],
+            align: crate::TextAlign::Left,
+            valign: crate::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     );
     Composition {
@@ -30,6 +36,8 @@ fn basic_comp() -> Composition {
             layout_align_x: LayoutAlignX::Start,
             layout_align_y: LayoutAlignY::Start,
             layout_grid_columns: default_layout_grid_columns(),
+            isolate: false,
+            clip_to: None,
             clips: vec![Clip {
                 id: "c0".to_string(),
                 asset: "t0".to_string(),
@@ -41,6 +49,11 @@ fn basic_comp() -> Composition {
                     }),
                     opacity: Anim::constant(1.0),
                     blend: BlendMode::Normal,
+                    opacity_at_composite: false,
+                    wiggle: None,
+                    box_anchor: None,
+                    aspect_ratio: None,
+                    fit: crate::ContentFit::None,
                 },
                 z_offset: 0,
                 effects: vec![EffectInstance {
@@ -54,9 +67,16 @@ fn basic_comp() -> Composition {
                     params: serde_json::Value::Null,
                 }),
                 transition_out: None,
+                seed: None,
+                loop_content: false,
+                time_remap: None,
+                reverse: false,
             }],
         }],
         seed: 123,
+        background: None,
+        title: None,
+        description: None,
     }
 }
 
@@ -69,6 +89,54 @@ fn json_roundtrip() {
     assert_eq!(de.assets.len(), 1);
 }
 
+#[test]
+fn content_hash_ignores_json_formatting_and_tracks_changes() {
+    let comp = basic_comp();
+    let compact = serde_json::to_string(&comp).unwrap();
+    // Round-tripping through `Value` sorts object keys; pretty printing changes whitespace.
+    let value: serde_json::Value = serde_json::from_str(&compact).unwrap();
+    let reordered = serde_json::to_string_pretty(&value).unwrap();
+    assert_ne!(compact, reordered);
+
+    let a: Composition = serde_json::from_str(&compact).unwrap();
+    let b: Composition = serde_json::from_str(&reordered).unwrap();
+    assert_eq!(a.content_hash(), b.content_hash());
+    assert_eq!(a.content_hash(), comp.content_hash());
+
+    let mut changed = basic_comp();
+    changed.tracks[0].clips[0].props.opacity = Anim::constant(0.5);
+    assert_ne!(changed.content_hash(), comp.content_hash());
+}
+
+#[test]
+fn content_hash_tracks_custom_ease_curves_not_just_names() {
+    let mut value = serde_json::to_value(basic_comp()).unwrap();
+    value["tracks"][0]["clips"][0]["props"]["opacity"] = serde_json::json!({
+        "Keyframes": {
+            "keys": [
+                { "frame": 0, "value": 0.0, "ease": "unit_hash_curve" },
+                { "frame": 10, "value": 1.0, "ease": "Linear" }
+            ],
+            "mode": "Linear",
+            "default": null
+        }
+    });
+
+    crate::animation::ease::register_ease("unit_hash_curve", |t| t).unwrap();
+    let a: Composition = serde_json::from_value(value.clone()).unwrap();
+    let a_again: Composition = serde_json::from_value(value.clone()).unwrap();
+    crate::animation::ease::register_ease("unit_hash_curve", |t| t * t).unwrap();
+    let b: Composition = serde_json::from_value(value).unwrap();
+
+    assert_eq!(a.content_hash(), a_again.content_hash());
+    assert_ne!(a.content_hash(), b.content_hash());
+    // Plain serialization still spells the ease by name.
+    assert_eq!(
+        serde_json::to_value(&a).unwrap(),
+        serde_json::to_value(&b).unwrap()
+    );
+}
+
 #[test]
 fn validate_rejects_missing_asset() {
     let mut comp = basic_comp();
@@ -131,7 +199,11 @@ fn media_validation_rejects_non_positive_playback_rate() {
             volume: 1.0,
             fade_in_sec: 0.0,
             fade_out_sec: 0.0,
+            fade_curve: AudioFadeCurve::Linear,
             muted: false,
+            frame_blend: false,
+            sampler: None,
+            loop_mode: VideoLoopMode::None,
         }),
     );
     let comp = Composition {
@@ -144,6 +216,228 @@ fn media_validation_rejects_non_positive_playback_rate() {
         assets,
         tracks: vec![],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
+    };
+    assert!(comp.validate().is_err());
+}
+
+#[test]
+fn background_accepts_hex_and_array_and_rejects_bad_hex() {
+    assert_eq!(
+        parse_hex_color_rgba8("#112233"),
+        Some([0x11, 0x22, 0x33, 0xff])
+    );
+    assert_eq!(
+        parse_hex_color_rgba8("#11223380"),
+        Some([0x11, 0x22, 0x33, 0x80])
+    );
+    assert_eq!(parse_hex_color_rgba8("112233"), None);
+    assert_eq!(parse_hex_color_rgba8("#12345"), None);
+
+    let mut json = serde_json::to_value(basic_comp()).unwrap();
+    json["background"] = serde_json::json!([1, 2, 3, 4]);
+    json["title"] = serde_json::json!("Intro");
+    let comp: Composition = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(comp.background, Some([1, 2, 3, 4]));
+    assert_eq!(comp.title.as_deref(), Some("Intro"));
+
+    json["background"] = serde_json::json!("#zz0000");
+    assert!(serde_json::from_value::<Composition>(json).is_err());
+}
+
+#[test]
+fn radial_gradient_stops_are_validated_and_shorthand_expands() {
+    let mut json = serde_json::to_value(basic_comp()).unwrap();
+    json["assets"]["g0"] =
+        serde_json::json!({"RadialGradient": {"start": "#ff0000", "end": "#0000ff"}});
+    let comp: Composition = serde_json::from_value(json.clone()).unwrap();
+    comp.validate().unwrap();
+    let Asset::RadialGradient(g) = &comp.assets["g0"] else {
+        panic!("expected radial gradient");
     };
+    assert_eq!(
+        g.resolved_stops(),
+        vec![
+            GradientStop {
+                offset: 0.0,
+                color: [255, 0, 0, 255]
+            },
+            GradientStop {
+                offset: 1.0,
+                color: [0, 0, 255, 255]
+            },
+        ]
+    );
+
+    json["assets"]["g0"] = serde_json::json!({"RadialGradient": {"stops": [
+        {"offset": 0.0, "color": "#ff0000"},
+        {"offset": 0.5, "color": "#00ff00"},
+        {"offset": 1.0, "color": "#0000ff"}
+    ]}});
+    let comp: Composition = serde_json::from_value(json.clone()).unwrap();
+    comp.validate().unwrap();
+
+    json["assets"]["g0"] = serde_json::json!({"RadialGradient": {"stops": [
+        {"offset": 0.6, "color": "#ff0000"},
+        {"offset": 0.5, "color": "#00ff00"}
+    ]}});
+    let comp: Composition = serde_json::from_value(json.clone()).unwrap();
+    assert!(comp.validate().is_err());
+
+    json["assets"]["g0"] = serde_json::json!({"RadialGradient": {"start": "#ff0000"}});
+    let comp: Composition = serde_json::from_value(json).unwrap();
     assert!(comp.validate().is_err());
 }
+
+#[test]
+fn image_sequence_frame_path_substitutes_the_index() {
+    let seq = |pattern: &str| ImageSequenceAsset {
+        pattern: pattern.to_string(),
+        fps: Fps::new(24, 1).unwrap(),
+        start_index: 0,
+        loop_mode: VideoLoopMode::None,
+        sampler: None,
+    };
+    assert_eq!(
+        seq("frames/img_{:04}.png").frame_path(7).unwrap(),
+        "frames/img_0007.png"
+    );
+    assert_eq!(seq("f{}.png").frame_path(12).unwrap(), "f12.png");
+    for bad in ["frames/img.png", "f{:4}.png", "f{}{}.png", "f{x}.png"] {
+        assert!(seq(bad).frame_path(0).is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn nested_compositions_are_depth_limited() {
+    let mut comp = basic_comp();
+    for _ in 0..MAX_COMPOSITION_NESTING {
+        let mut parent = basic_comp();
+        parent.assets.insert(
+            "nested".to_string(),
+            Asset::Composition(CompositionAsset {
+                composition: Box::new(comp),
+            }),
+        );
+        comp = parent;
+    }
+    assert_eq!(comp.nesting_depth(), MAX_COMPOSITION_NESTING);
+    comp.validate().unwrap();
+
+    let mut too_deep = basic_comp();
+    too_deep.assets.insert(
+        "nested".to_string(),
+        Asset::Composition(CompositionAsset {
+            composition: Box::new(comp),
+        }),
+    );
+    let err = too_deep.validate().unwrap_err().to_string();
+    assert!(err.contains("nest more than"), "{err}");
+}
+
+#[test]
+fn nested_compositions_reject_audio_they_cannot_mix() {
+    let with_child = |child_asset: serde_json::Value| {
+        let child = serde_json::json!({
+            "fps": {"num": 30, "den": 1},
+            "canvas": {"width": 64, "height": 64},
+            "duration": 30,
+            "assets": {"m": child_asset},
+            "tracks": [],
+            "seed": 1
+        });
+        let mut comp = basic_comp();
+        comp.assets.insert(
+            "nested".to_string(),
+            Asset::Composition(CompositionAsset {
+                composition: Box::new(serde_json::from_value(child).unwrap()),
+            }),
+        );
+        comp.validate()
+    };
+
+    let err = with_child(serde_json::json!({"Audio": {"source": "a.wav"}})).unwrap_err();
+    assert!(err.to_string().contains("cannot play audio"), "{err}");
+    let err = with_child(serde_json::json!({"Video": {"source": "v.mp4"}})).unwrap_err();
+    assert!(err.to_string().contains("asset 'm'"), "{err}");
+    with_child(serde_json::json!({"Video": {"source": "v.mp4", "muted": true}})).unwrap();
+}
+
+#[test]
+fn time_remap_is_rejected_on_audible_clips() {
+    let mut comp = basic_comp();
+    comp.tracks[0].clips[0].time_remap = Some(Anim::constant(2.0));
+    comp.validate().unwrap();
+
+    let key = comp.tracks[0].clips[0].asset.clone();
+    let audio = |muted: bool| {
+        serde_json::from_value::<Asset>(serde_json::json!({
+            "Audio": {"source": "a.wav", "muted": muted}
+        }))
+        .unwrap()
+    };
+    comp.assets.insert(key.clone(), audio(false));
+    let err = comp.validate().unwrap_err();
+    assert!(err.to_string().contains("cannot time_remap audio"), "{err}");
+    comp.assets.insert(key, audio(true));
+    comp.validate().unwrap();
+}
+
+#[test]
+fn reverse_is_rejected_on_audible_clips() {
+    let mut comp = basic_comp();
+    comp.tracks[0].clips[0].reverse = true;
+    comp.validate().unwrap();
+
+    let key = comp.tracks[0].clips[0].asset.clone();
+    let video = |muted: bool| {
+        serde_json::from_value::<Asset>(serde_json::json!({
+            "Video": {"source": "v.mp4", "muted": muted}
+        }))
+        .unwrap()
+    };
+    comp.assets.insert(key.clone(), video(false));
+    let err = comp.validate().unwrap_err();
+    assert!(err.to_string().contains("cannot reverse audio"), "{err}");
+    comp.assets.insert(key, video(true));
+    comp.validate().unwrap();
+}
+
+#[test]
+fn asset_overrides_reject_overlapping_keys_and_invalid_results() {
+    let mut comp = basic_comp();
+    comp.assets.insert(
+        "c".to_string(),
+        Asset::Composition(CompositionAsset {
+            composition: Box::new(basic_comp()),
+        }),
+    );
+    let text = |text: &str| match basic_comp().assets.remove("t0") {
+        Some(Asset::Text(a)) => Asset::Text(TextAsset {
+            text: text.to_string(),
+            ..a
+        }),
+        _ => unreachable!(),
+    };
+    let before = comp.content_hash();
+
+    let overlapping = BTreeMap::from([
+        ("c".to_string(), text("flat")),
+        ("c/t0".to_string(), text("nested")),
+    ]);
+    let err = comp.override_assets(overlapping).unwrap_err();
+    assert!(err.to_string().contains("overlap"), "{err}");
+
+    let invalid = BTreeMap::from([("c/t0".to_string(), text("  "))]);
+    assert!(comp.override_assets(invalid).is_err());
+    assert_eq!(comp.content_hash(), before);
+
+    comp.override_assets(BTreeMap::from([("c/t0".to_string(), text("bye"))]))
+        .unwrap();
+    let Some(Asset::Composition(c)) = comp.assets.get("c") else {
+        unreachable!()
+    };
+    assert!(matches!(&c.composition.assets["t0"], Asset::Text(a) if a.text == "bye"));
+}
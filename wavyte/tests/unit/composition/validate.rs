@@ -0,0 +1,105 @@
+use super::*;
+
+fn fixture() -> serde_json::Value {
+    serde_json::from_str(include_str!("../../data/simple_comp.json")).unwrap()
+}
+
+#[test]
+fn valid_document_has_no_issues() {
+    assert_eq!(Composition::validate_value(&fixture()), Ok(()));
+}
+
+#[test]
+fn every_invalid_field_is_reported_with_its_path() {
+    let mut v = fixture();
+    v["fps"]["num"] = 0.into();
+    v["assets"]["t0"]["Text"]["size_px"] = (-1.0).into();
+
+    let issues = Composition::validate_value(&v).unwrap_err();
+    let paths = issues.iter().map(|i| i.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, ["/fps", "/assets/t0"]);
+    assert!(issues.iter().all(|i| i.code == IssueCode::Invalid));
+    assert_eq!(
+        issues[1].message,
+        "text asset size_px must be finite and > 0"
+    );
+}
+
+#[test]
+fn every_problem_with_one_entity_is_reported() {
+    let mut v = fixture();
+    v["assets"]["t0"]["Text"]["size_px"] = (-1.0).into();
+    v["assets"]["t0"]["Text"]["line_height"] = 0.0.into();
+
+    let issues = Composition::validate_value(&v).unwrap_err();
+    let messages = issues
+        .iter()
+        .map(|i| i.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        [
+            "text asset line_height must be finite and > 0",
+            "text asset size_px must be finite and > 0",
+        ]
+    );
+    assert!(issues.iter().all(|i| i.path == "/assets/t0"));
+}
+
+#[test]
+fn shape_errors_are_localized_to_the_failing_clip() {
+    let mut v = fixture();
+    v["tracks"][0]["clips"][0]["range"] = "soon".into();
+    v["canvas"]["width"] = "wide".into();
+
+    let issues = Composition::validate_value(&v).unwrap_err();
+    let paths = issues.iter().map(|i| i.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, ["/canvas", "/tracks/0/clips/0"]);
+    assert!(issues.iter().all(|i| i.code == IssueCode::Parse));
+}
+
+#[test]
+fn pointer_tokens_escape_slash_and_tilde() {
+    assert_eq!(pointer_token("a/b~c"), "a~1b~0c");
+}
+
+#[test]
+fn strict_parsing_reports_unknown_top_level_and_nested_fields() {
+    let mut v = fixture();
+    v["colour"] = "#ff0000".into();
+    v["tracks"][0]["clips"][0]["props"]["opacty"] = 0.5.into();
+    let json = serde_json::to_vec(&v).unwrap();
+
+    let err = Composition::from_reader_strict(json.as_slice()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "validation error: unknown fields: /colour, /tracks/0/clips/0/props/opacty"
+    );
+
+    // Lenient parsing is unchanged.
+    assert!(serde_json::from_slice::<Composition>(&json).is_ok());
+}
+
+#[test]
+fn strict_parsing_accepts_known_fields_at_their_defaults() {
+    let mut v = fixture();
+    v["tracks"][0]["clips"][0]["reverse"] = false.into();
+    v["tracks"][0]["clips"][0]["effects"] = serde_json::json!([]);
+    v["title"] = serde_json::Value::Null;
+    let json = serde_json::to_vec(&v).unwrap();
+
+    Composition::from_reader_strict(json.as_slice()).unwrap();
+}
+
+#[test]
+fn strict_parsing_reports_unknown_fields_inside_enum_variants() {
+    let mut v = fixture();
+    v["assets"]["t0"]["Text"]["font_sise"] = 12.into();
+    let json = serde_json::to_vec(&v).unwrap();
+
+    let err = Composition::from_reader_strict(json.as_slice()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "validation error: unknown fields: /assets/t0/Text/font_sise"
+    );
+}
@@ -1,5 +1,14 @@
 use super::*;
 
+fn full(width: u32, height: u32) -> PixelRect {
+    PixelRect {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    }
+}
+
 #[test]
 fn blur_radius_0_is_identity() {
     let src = vec![1u8, 2, 3, 4, 5, 6, 7, 8];
@@ -31,3 +40,135 @@ fn blur_spreads_energy_from_single_pixel() {
     let sum_a: u32 = out.chunks_exact(4).map(|px| u32::from(px[3])).sum();
     assert!((sum_a as i32 - 255).abs() <= 4);
 }
+
+#[test]
+fn sharpen_leaves_flat_image_unchanged() {
+    let (w, h) = (6u32, 4u32);
+    let px = [40u8, 80u8, 120u8, 200u8];
+    let src = px.repeat((w * h) as usize);
+    for amount in [0.0, 0.5, 1.0, 4.0, 25.0] {
+        let out = sharpen_rgba8_premul(&src, w, h, 2, 1.0, amount).unwrap();
+        assert_eq!(out, src, "amount {amount}");
+    }
+}
+
+#[test]
+fn sharpen_boosts_edge_contrast_and_stays_premultiplied() {
+    let (w, h) = (8u32, 1u32);
+    let mut src = Vec::new();
+    for x in 0..w {
+        let v = if x < 4 { 60u8 } else { 180u8 };
+        src.extend_from_slice(&[v, v, v, 255]);
+    }
+    let out = sharpen_rgba8_premul(&src, w, h, 2, 1.0, 1.0).unwrap();
+    assert!(out[3 * 4] < 60);
+    assert!(out[4 * 4] > 180);
+    for px in out.chunks_exact(4) {
+        assert!(px[0] <= px[3] && px[1] <= px[3] && px[2] <= px[3]);
+    }
+}
+
+#[test]
+fn radial_blur_strength_zero_is_identity() {
+    let src: Vec<u8> = (0..(5 * 3 * 4)).map(|v| (v * 7) as u8).collect();
+    let out = radial_blur_rgba8_premul(&src, 5, 3, full(5, 3), [0.5, 0.5], 0.0, 16).unwrap();
+    assert_eq!(out, src);
+}
+
+#[test]
+fn radial_blur_smears_toward_center_and_keeps_center() {
+    // Opaque white 9x9 with a single black pixel at the center.
+    let (w, h) = (9u32, 9u32);
+    let mut src = [255u8, 255, 255, 255].repeat((w * h) as usize);
+    let c = ((4 * w + 4) * 4) as usize;
+    src[c..c + 3].fill(0);
+
+    let out = radial_blur_rgba8_premul(&src, w, h, full(w, h), [0.5, 0.5], 1.0, 8).unwrap();
+    // The center pixel only samples itself.
+    assert_eq!(&out[c..c + 4], &[0, 0, 0, 255]);
+    // A neighbour on the way to the center picks up some of the dark pixel.
+    let n = ((4 * w + 5) * 4) as usize;
+    assert!(out[n] < 255, "{:?}", &out[n..n + 4]);
+    // A pixel off the center's row and column never samples it.
+    let off = ((5 * w + 8) * 4) as usize;
+    assert_eq!(&out[off..off + 4], &[255, 255, 255, 255]);
+    assert!(radial_blur_rgba8_premul(&src, w, h, full(w, h), [0.5, 0.5], 1.5, 8).is_err());
+}
+
+#[test]
+fn motion_blur_widens_vertical_line_only_horizontally() {
+    // Opaque black 9x5 with a white vertical line at x=4.
+    let (w, h) = (9u32, 5u32);
+    let mut src = [0u8, 0, 0, 255].repeat((w * h) as usize);
+    for y in 0..h {
+        let i = ((y * w + 4) * 4) as usize;
+        src[i..i + 3].fill(255);
+    }
+    let lit = |buf: &[u8]| {
+        (0..w)
+            .filter(|&x| buf[((2 * w + x) * 4) as usize] > 0)
+            .count()
+    };
+
+    let horizontal = motion_blur_rgba8_premul(&src, w, h, full(w, h), 0.0, 4.0, 5).unwrap();
+    assert!(lit(&horizontal) > 1, "{horizontal:?}");
+
+    let vertical = motion_blur_rgba8_premul(&src, w, h, full(w, h), 90.0, 4.0, 5).unwrap();
+    assert_eq!(vertical, src);
+
+    assert_eq!(
+        motion_blur_rgba8_premul(&src, w, h, full(w, h), 30.0, 0.0, 8).unwrap(),
+        src
+    );
+}
+
+#[test]
+fn alpha_only_blur_spreads_coverage_without_bleeding_color() {
+    // Opaque red 3x3 square centered in a transparent 9x9 surface.
+    let (w, h) = (9u32, 9u32);
+    let mut src = vec![0u8; (w * h * 4) as usize];
+    for y in 3..6 {
+        for x in 3..6 {
+            let i = ((y * w + x) * 4) as usize;
+            src[i..i + 4].copy_from_slice(&[255, 0, 0, 255]);
+        }
+    }
+
+    let out = blur_alpha_only_rgba8_premul(&src, w, h, 3, 1.5).unwrap();
+    // Newly covered pixels take the square's color rather than turning black.
+    let outside = ((4 * w + 1) * 4) as usize;
+    assert!(out[outside + 3] > 0, "{:?}", &out[outside..outside + 4]);
+    assert!(out[outside] > 0, "{:?}", &out[outside..outside + 4]);
+    assert_eq!(out[outside], out[outside + 3]);
+    assert_eq!(&out[outside + 1..outside + 3], &[0, 0]);
+    // Inside the square the color follows the softened coverage.
+    let edge = ((4 * w + 3) * 4) as usize;
+    assert_eq!(out[edge], out[edge + 3]);
+    assert_eq!(&out[edge + 1..edge + 3], &[0, 0]);
+
+    let full = blur_rgba8_premul(&src, w, h, 3, 1.5).unwrap();
+
+    let alpha = src.chunks_exact(4).map(|px| px[3]).collect::<Vec<_>>();
+    let blurred = blur_alpha8(&alpha, w, h, 3, 1.5).unwrap();
+    assert_eq!(
+        blurred,
+        full.chunks_exact(4).map(|px| px[3]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn alpha_only_blur_keeps_each_covered_color() {
+    // Opaque red and green halves meet in the middle; the blur must not mix them.
+    let (w, h) = (8u32, 1u32);
+    let mut src = Vec::new();
+    for x in 0..w {
+        src.extend_from_slice(if x < 4 {
+            &[255, 0, 0, 255]
+        } else {
+            &[0, 255, 0, 255]
+        });
+    }
+    let out = blur_alpha_only_rgba8_premul(&src, w, h, 2, 1.0).unwrap();
+    assert_eq!(&out[3 * 4..3 * 4 + 3], &[out[3 * 4 + 3], 0, 0]);
+    assert_eq!(&out[4 * 4..4 * 4 + 3], &[0, out[4 * 4 + 3], 0]);
+}
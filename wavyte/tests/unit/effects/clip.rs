@@ -0,0 +1,16 @@
+use super::*;
+
+#[test]
+fn clip_rect_clears_outside_and_blends_fractional_edges() {
+    let src: Vec<u8> = [200u8, 100, 50, 255].repeat(4);
+    let out = clip_rect_rgba8_premul(&src, 4, 1, [1.0, 0.0, 2.5, 1.0]).unwrap();
+    assert_eq!(&out[0..4], &[0, 0, 0, 0]);
+    assert_eq!(&out[4..8], &[200, 100, 50, 255]);
+    assert_eq!(&out[8..12], &[100, 50, 25, 128]);
+    assert_eq!(&out[12..16], &[0, 0, 0, 0]);
+}
+
+#[test]
+fn clip_rect_rejects_mismatched_buffer() {
+    assert!(clip_rect_rgba8_premul(&[0u8; 4], 2, 1, [0.0, 0.0, 1.0, 1.0]).is_err());
+}
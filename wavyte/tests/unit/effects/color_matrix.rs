@@ -0,0 +1,97 @@
+use super::*;
+
+#[test]
+fn identity_matrix_preserves_premultiplied_pixels() {
+    let src = vec![200u8, 100, 50, 255, 64, 32, 0, 128, 0, 0, 0, 0];
+    let out = color_matrix_rgba8_premul(&src, 3, 1, &IDENTITY).unwrap();
+    assert_eq!(out, src);
+}
+
+#[test]
+fn grayscale_collapses_red_to_rec709_luma() {
+    let out = color_matrix_rgba8_premul(&[255, 0, 0, 255], 1, 1, &grayscale_matrix()).unwrap();
+    assert_eq!(out, vec![54, 54, 54, 255]);
+}
+
+#[test]
+fn saturate_zero_matches_grayscale() {
+    let src = vec![255u8, 0, 0, 255, 10, 200, 90, 255, 60, 20, 10, 128];
+    let gray = color_matrix_rgba8_premul(&src, 3, 1, &grayscale_matrix()).unwrap();
+    let sat0 = color_matrix_rgba8_premul(&src, 3, 1, &saturate_matrix(0.0)).unwrap();
+    assert_eq!(gray, sat0);
+}
+
+#[test]
+fn brightness_scales_rgb_and_clamps() {
+    let out =
+        color_matrix_rgba8_premul(&[100, 200, 0, 255], 1, 1, &brightness_matrix(1.5)).unwrap();
+    assert_eq!(out, vec![150, 255, 0, 255]);
+}
+
+#[test]
+fn hue_rotate_zero_is_identity_and_full_turn_round_trips() {
+    assert_eq!(hue_rotate_matrix(0.0), IDENTITY);
+    assert_eq!(hue_rotate_matrix(-720.0), IDENTITY);
+
+    let src = vec![230u8, 40, 90, 255];
+    let full = color_matrix_rgba8_premul(&src, 1, 1, &hue_rotate_matrix(360.0)).unwrap();
+    assert_eq!(full, src);
+
+    // Rotations compose: 120° followed by 240° is (numerically) the identity matrix.
+    let (a, b) = (hue_rotate_matrix(120.0), hue_rotate_matrix(240.0));
+    for r in 0..3 {
+        for c in 0..3 {
+            let v: f32 = (0..3).map(|k| b[r * 5 + k] * a[k * 5 + c]).sum();
+            let want = if r == c { 1.0 } else { 0.0 };
+            assert!((v - want).abs() < 2e-3, "[{r}][{c}] = {v}");
+        }
+    }
+}
+
+#[test]
+fn hue_rotate_preserves_gray() {
+    let m = hue_rotate_matrix(123.0);
+    let out = color_matrix_rgba8_premul(&[128, 128, 128, 255], 1, 1, &m).unwrap();
+    for c in &out[..3] {
+        assert!((i32::from(*c) - 128).abs() <= 1, "{out:?}");
+    }
+}
+
+#[test]
+fn invert_maps_white_to_black_and_keeps_alpha() {
+    let src = vec![255u8, 255, 255, 255, 0, 0, 0, 128];
+    let out = color_matrix_rgba8_premul(&src, 2, 1, &invert_matrix()).unwrap();
+    assert_eq!(out, vec![0, 0, 0, 255, 128, 128, 128, 128]);
+}
+
+#[test]
+fn threshold_splits_on_luma_and_preserves_alpha() {
+    // Gray 153/255 = 0.6 luma.
+    let out = threshold_rgba8_premul(&[153, 153, 153, 255], 1, 1, 0.5).unwrap();
+    assert_eq!(out, vec![255, 255, 255, 255]);
+
+    let out = threshold_rgba8_premul(&[102, 102, 102, 255], 1, 1, 0.5).unwrap();
+    assert_eq!(out, vec![0, 0, 0, 255]);
+
+    // Half-transparent 0.6 gray stays half-transparent white.
+    let out = threshold_rgba8_premul(&[77, 77, 77, 128, 0, 0, 0, 0], 2, 1, 0.5).unwrap();
+    assert_eq!(out, vec![128, 128, 128, 128, 0, 0, 0, 0]);
+}
+
+#[test]
+fn channel_gain_zero_green_keeps_red_and_blue() {
+    let px = [200, 150, 100, 255, 40, 80, 120, 255];
+    let out = color_matrix_rgba8_premul(
+        &px,
+        2,
+        1,
+        &channel_gain_offset_matrix([1.0, 0.0, 1.0], [0.0; 3]),
+    )
+    .unwrap();
+    assert_eq!(out, vec![200, 0, 100, 255, 40, 0, 120, 255]);
+
+    let same =
+        color_matrix_rgba8_premul(&px, 2, 1, &channel_gain_offset_matrix([1.0; 3], [0.0; 3]))
+            .unwrap();
+    assert_eq!(same, px.to_vec());
+}
@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn linear_view_preserves_extremes_and_alpha() {
+    let src = vec![
+        0u8, 0, 0, 255, 255, 255, 255, 255, 64, 32, 0, 128, 0, 0, 0, 0,
+    ];
+    let mut seen = Vec::new();
+    let back = linear_light_rgba8_premul(&src, |l| {
+        seen = l.to_vec();
+        Ok(l.to_vec())
+    })
+    .unwrap();
+    assert_eq!(&seen[0..8], &[0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0]);
+    assert!((seen[11] - 128.0 / 255.0).abs() < 1e-6);
+    // Mid-gray decodes to a darker linear value.
+    assert!(seen[8] < 64.0 / 255.0);
+    assert_eq!(&seen[12..16], &[0.0; 4]);
+    assert_eq!(back, src);
+}
+
+#[test]
+fn identity_linear_pass_preserves_every_level() {
+    for a in [255u8, 128, 7] {
+        let src = (0..=255u8)
+            .flat_map(|v| {
+                let v = (u16::from(v) * u16::from(a) / 255) as u8;
+                [v, v, v, a]
+            })
+            .collect::<Vec<_>>();
+        let back = linear_light_rgba8_premul(&src, |l| Ok(l.to_vec())).unwrap();
+        assert_eq!(back, src, "alpha {a}");
+    }
+    // The linear view keeps the darkest levels distinct instead of rounding them to zero.
+    let dark = (0..=6u8).flat_map(|v| [v, v, v, 255]).collect::<Vec<_>>();
+    linear_light_rgba8_premul(&dark, |l| {
+        let levels = l.chunks_exact(4).map(|px| px[0]).collect::<Vec<_>>();
+        assert!(levels.windows(2).all(|w| w[0] < w[1]), "{levels:?}");
+        Ok(l.to_vec())
+    })
+    .unwrap();
+}
+
+#[test]
+fn linear_conversion_rejects_partial_pixels() {
+    assert!(linear_light_rgba8_premul(&[0, 0, 0], |l| Ok(l.to_vec())).is_err());
+}
@@ -56,6 +56,8 @@ fn wipe_ltr_endpoints_match_a_and_b() {
             t: 0.0,
             dir: WipeDir::LeftToRight,
             soft_edge: 0.0,
+            sweep_origin: [0, 0],
+            sweep_size: [w, h],
         },
     )
     .unwrap();
@@ -72,6 +74,8 @@ fn wipe_ltr_endpoints_match_a_and_b() {
             t: 1.0,
             dir: WipeDir::LeftToRight,
             soft_edge: 0.0,
+            sweep_origin: [0, 0],
+            sweep_size: [w, h],
         },
     )
     .unwrap();
@@ -98,6 +102,8 @@ fn wipe_ltr_midpoint_splits_image() {
             t: 0.5,
             dir: WipeDir::LeftToRight,
             soft_edge: 0.0,
+            sweep_origin: [0, 0],
+            sweep_size: [w, h],
         },
     )
     .unwrap();
@@ -125,6 +131,8 @@ fn wipe_soft_edge_blends_near_boundary() {
             t: 0.5,
             dir: WipeDir::LeftToRight,
             soft_edge: 0.25,
+            sweep_origin: [0, 0],
+            sweep_size: [w, h],
         },
     )
     .unwrap();
@@ -155,9 +163,103 @@ fn wipe_negative_soft_edge_is_treated_as_zero() {
             t: 0.5,
             dir: WipeDir::LeftToRight,
             soft_edge: -1.0,
+            sweep_origin: [0, 0],
+            sweep_size: [w, h],
         },
     )
     .unwrap();
     assert_eq!(&dst[0..8], &b[0..8]);
     assert_eq!(&dst[8..16], &a[8..16]);
 }
+
+#[test]
+fn over_at_places_source_at_origin() {
+    let mut dst = vec![0u8; 4 * 3 * 4];
+    let src = vec![255u8, 0, 0, 255, 0, 255, 0, 255];
+    over_at_in_place(&mut dst, 4, &src, 1, [2, 1], 1.0).unwrap();
+    let px = |x: usize, y: usize| &dst[(y * 4 + x) * 4..(y * 4 + x) * 4 + 4];
+    assert_eq!(px(2, 1), &[255, 0, 0, 255]);
+    assert_eq!(px(2, 2), &[0, 255, 0, 255]);
+    assert_eq!(px(1, 1), &[0, 0, 0, 0]);
+    assert_eq!(dst.iter().filter(|&&v| v != 0).count(), 4);
+
+    assert!(over_at_in_place(&mut dst, 4, &src, 1, [4, 0], 1.0).is_err());
+    assert!(over_at_in_place(&mut dst, 4, &src, 1, [0, 2], 1.0).is_err());
+}
+
+#[test]
+fn multiply_darkens_and_reduces_to_over_on_transparency() {
+    // White is the multiply identity.
+    assert_eq!(
+        multiply([100, 150, 200, 255], [255, 255, 255, 255], 1.0),
+        [100, 150, 200, 255]
+    );
+    // Black wins.
+    assert_eq!(
+        multiply([100, 150, 200, 255], [0, 0, 0, 255], 1.0),
+        [0, 0, 0, 255]
+    );
+    // Over a transparent destination this is plain alpha-over.
+    let src = [120, 60, 30, 200];
+    assert_eq!(
+        multiply([0, 0, 0, 0], src, 0.5),
+        over([0, 0, 0, 0], src, 0.5)
+    );
+    assert_eq!(
+        blend([10, 20, 30, 255], src, 1.0, BlendMode::Normal),
+        over([10, 20, 30, 255], src, 1.0)
+    );
+}
+
+fn lum8(px: PremulRgba8) -> f32 {
+    lum([0, 1, 2].map(|i| f32::from(px[i])))
+}
+
+#[test]
+fn color_blend_preserves_destination_luminance() {
+    let dst = [100, 150, 200, 255];
+    for src in [[200, 40, 60, 255], [10, 220, 30, 255], [255, 255, 0, 255]] {
+        let out = blend(dst, src, 1.0, BlendMode::Color);
+        assert_eq!(out[3], 255);
+        assert!(
+            (lum8(out) - lum8(dst)).abs() <= 1.0,
+            "src={src:?} out={out:?}"
+        );
+    }
+}
+
+#[test]
+fn luminosity_blend_preserves_destination_chroma() {
+    let dst = [120, 140, 100, 255];
+    let out = blend(dst, [90, 90, 90, 255], 1.0, BlendMode::Luminosity);
+    // Same channel differences (hue + saturation), shifted to the source luminance.
+    let diff = |p: PremulRgba8| {
+        [
+            i16::from(p[0]) - i16::from(p[1]),
+            i16::from(p[2]) - i16::from(p[1]),
+        ]
+    };
+    let (a, b) = (diff(out), diff(dst));
+    assert!(
+        (a[0] - b[0]).abs() <= 1 && (a[1] - b[1]).abs() <= 1,
+        "out={out:?}"
+    );
+    assert!((lum8(out) - 90.0).abs() <= 1.0, "out={out:?}");
+}
+
+#[test]
+fn hsl_blends_reduce_to_over_on_transparency() {
+    let src = [120, 60, 30, 200];
+    for mode in [
+        BlendMode::Hue,
+        BlendMode::Saturation,
+        BlendMode::Color,
+        BlendMode::Luminosity,
+    ] {
+        assert_eq!(
+            blend([0, 0, 0, 0], src, 0.5, mode),
+            over([0, 0, 0, 0], src, 0.5)
+        );
+        assert_eq!(blend([10, 20, 30, 255], src, 0.0, mode), [10, 20, 30, 255]);
+    }
+}
@@ -21,9 +21,217 @@ fn normalize_folds_opacity_and_drops_noop_blur() {
         Effect::Blur {
             radius_px: 0,
             sigma: 1.0,
+            alpha_only: false,
         },
     ];
     let p = normalize_effects(&fx);
     assert_eq!(p.inline.opacity_mul, 0.125);
     assert!(p.passes.is_empty());
 }
+
+#[test]
+fn parse_sharpen_defaults_radius_and_drops_zero_amount() {
+    let e = parse_effect(&inst("sharpen", serde_json::json!({ "amount": 0.75 }))).unwrap();
+    assert_eq!(
+        e,
+        Effect::Sharpen {
+            amount: 0.75,
+            radius_px: 2,
+            sigma: 1.0,
+        }
+    );
+    assert_eq!(
+        normalize_effects(std::slice::from_ref(&e)).passes,
+        vec![PassFx::Sharpen {
+            amount: 0.75,
+            radius_px: 2,
+            sigma: 1.0,
+        }]
+    );
+
+    let noop = parse_effect(&inst("sharpen", serde_json::json!({ "amount": 0.0 }))).unwrap();
+    assert!(normalize_effects(&[noop]).passes.is_empty());
+    assert!(parse_effect(&inst("sharpen", serde_json::json!({ "amount": -1.0 }))).is_err());
+}
+
+#[test]
+fn parse_pixelate_and_drop_identity_block() {
+    let e = parse_effect(&inst("pixelate", serde_json::json!({ "block_px": 8 }))).unwrap();
+    assert_eq!(e, Effect::Pixelate { block_px: 8 });
+    assert_eq!(
+        normalize_effects(&[e]).passes,
+        vec![PassFx::Pixelate { block_px: 8 }]
+    );
+    assert!(
+        normalize_effects(&[Effect::Pixelate { block_px: 1 }])
+            .passes
+            .is_empty()
+    );
+    assert!(parse_effect(&inst("pixelate", serde_json::json!({ "block_px": 0 }))).is_err());
+}
+
+#[test]
+fn parse_drop_shadow_with_hex_color_and_defaults() {
+    let e = parse_effect(&inst(
+        "drop_shadow",
+        serde_json::json!({ "offset": [3, -2], "color": "#ff000080" }),
+    ))
+    .unwrap();
+    assert_eq!(
+        e,
+        Effect::DropShadow {
+            offset: [3, -2],
+            blur_radius_px: 4,
+            sigma: 2.0,
+            color: [255, 0, 0, 128],
+        }
+    );
+    assert!(
+        parse_effect(&inst(
+            "drop_shadow",
+            serde_json::json!({ "offset": [1.5, 0] })
+        ))
+        .is_err()
+    );
+    assert!(parse_effect(&inst("drop_shadow", serde_json::json!({ "color": "red" }))).is_err());
+}
+
+#[test]
+fn color_convenience_effects_lower_to_color_matrix() {
+    let gray = parse_effect(&inst("grayscale", serde_json::json!({}))).unwrap();
+    let sat0 = parse_effect(&inst("saturate", serde_json::json!({ "amount": 0.0 }))).unwrap();
+    assert_eq!(gray, sat0);
+    assert!(matches!(
+        normalize_effects(&[gray]).passes.as_slice(),
+        [PassFx::ColorMatrix { .. }]
+    ));
+
+    let bright = parse_effect(&inst("brightness", serde_json::json!({ "value": 1.0 }))).unwrap();
+    assert!(normalize_effects(&[bright]).passes.is_empty());
+    assert!(
+        parse_effect(&inst(
+            "color_matrix",
+            serde_json::json!({ "matrix": [1.0, 0.0] })
+        ))
+        .is_err()
+    );
+}
+
+#[test]
+fn invert_and_threshold_parse_to_passes() {
+    let inv = parse_effect(&inst("invert", serde_json::json!({}))).unwrap();
+    assert_eq!(
+        inv,
+        Effect::ColorMatrix {
+            matrix: crate::effects::color_matrix::invert_matrix()
+        }
+    );
+
+    let th = parse_effect(&inst("threshold", serde_json::json!({ "cutoff": 0.5 }))).unwrap();
+    assert_eq!(th, Effect::Threshold { cutoff: 0.5 });
+    assert_eq!(
+        normalize_effects(&[inv, th]).passes.last(),
+        Some(&PassFx::Threshold { cutoff: 0.5 })
+    );
+    assert!(parse_effect(&inst("threshold", serde_json::json!({ "cutoff": 1.5 }))).is_err());
+    assert!(parse_effect(&inst("threshold", serde_json::json!({}))).is_err());
+}
+
+#[test]
+fn vignette_parses_defaults_and_rejects_bad_softness() {
+    let v = parse_effect(&inst("vignette", serde_json::json!({}))).unwrap();
+    assert_eq!(
+        v,
+        Effect::Vignette {
+            radius: 0.5,
+            softness: 0.5,
+            color: [0, 0, 0, 255],
+        }
+    );
+    let clear = parse_effect(&inst(
+        "vignette",
+        serde_json::json!({ "color": "#00000000" }),
+    ))
+    .unwrap();
+    assert!(normalize_effects(&[clear]).passes.is_empty());
+    assert!(parse_effect(&inst("vignette", serde_json::json!({ "softness": 0.0 }))).is_err());
+}
+
+#[test]
+fn radial_blur_parses_and_drops_zero_strength() {
+    let rb = parse_effect(&inst(
+        "radial_blur",
+        serde_json::json!({ "strength": 0.3, "center": [0.25, 0.75], "samples": 8 }),
+    ))
+    .unwrap();
+    assert_eq!(
+        rb,
+        Effect::RadialBlur {
+            center: [0.25, 0.75],
+            strength: 0.3,
+            samples: 8,
+        }
+    );
+    let zero = parse_effect(&inst("radial_blur", serde_json::json!({ "strength": 0.0 }))).unwrap();
+    assert!(normalize_effects(&[zero]).passes.is_empty());
+    assert!(parse_effect(&inst("radial_blur", serde_json::json!({ "strength": 2.0 }))).is_err());
+}
+
+#[test]
+fn motion_blur_reads_angle_distance_samples() {
+    let mb = parse_effect(&inst(
+        "motion_blur",
+        serde_json::json!({ "angle": 45.0, "distance": 12.0, "samples": 6 }),
+    ))
+    .unwrap();
+    assert_eq!(
+        mb,
+        Effect::MotionBlur {
+            angle_deg: 45.0,
+            distance_px: 12.0,
+            samples: 6,
+        }
+    );
+    let still = parse_effect(&inst("motion_blur", serde_json::json!({ "distance": 0.0 }))).unwrap();
+    assert!(normalize_effects(&[still]).passes.is_empty());
+    assert!(
+        parse_effect(&inst(
+            "motion_blur",
+            serde_json::json!({ "distance": -1.0 })
+        ))
+        .is_err()
+    );
+}
+
+#[test]
+fn channel_mixer_defaults_to_identity_and_validates() {
+    let id = parse_effect(&inst("channel_mixer", serde_json::json!({}))).unwrap();
+    assert_eq!(
+        id,
+        Effect::ColorMatrix {
+            matrix: crate::effects::color_matrix::IDENTITY
+        }
+    );
+    assert!(normalize_effects(&[id]).passes.is_empty());
+
+    let g0 = parse_effect(&inst("channel-mixer", serde_json::json!({ "g_gain": 0.0 }))).unwrap();
+    assert!(matches!(
+        normalize_effects(&[g0]).passes.as_slice(),
+        [PassFx::ColorMatrix { .. }]
+    ));
+    assert!(
+        parse_effect(&inst(
+            "channel_mixer",
+            serde_json::json!({ "r_gain": -1.0 })
+        ))
+        .is_err()
+    );
+    assert!(
+        parse_effect(&inst(
+            "channel_mixer",
+            serde_json::json!({ "b_offset": 2.0 })
+        ))
+        .is_err()
+    );
+    assert!(parse_effect(&inst("channel_mixer", serde_json::json!({ "g_gain": "x" }))).is_err());
+}
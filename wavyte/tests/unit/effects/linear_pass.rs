@@ -0,0 +1,52 @@
+use super::*;
+
+fn full(width: u32, height: u32) -> PixelRect {
+    PixelRect {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    }
+}
+use crate::effects::color_space::linear_light_rgba8_premul;
+
+fn run(fx: &PassFx, src: &[u8], w: u32, h: u32) -> Vec<u8> {
+    linear_light_rgba8_premul(src, |l| apply_pass_fx_f32(fx, l, w, h, full(w, h))).unwrap()
+}
+
+#[test]
+fn blurred_dark_levels_survive_the_linear_round_trip() {
+    let blur = PassFx::Blur {
+        radius_px: 3,
+        sigma: 1.5,
+        alpha_only: false,
+    };
+    for v in 1..=6u8 {
+        let src = [v, v, v, 255].repeat(8 * 8);
+        assert_eq!(run(&blur, &src, 8, 8), src, "level {v}");
+    }
+}
+
+#[test]
+fn pixelate_averages_in_linear_light() {
+    // Black/white checker: the linear mean is 0.5, which encodes to sRGB ~188 rather than 128.
+    let src = (0..4)
+        .flat_map(|i| {
+            let v = if i % 2 == 0 { 0 } else { 255 };
+            [v, v, v, 255]
+        })
+        .collect::<Vec<_>>();
+    let out = run(&PassFx::Pixelate { block_px: 2 }, &src, 2, 2);
+    assert!(
+        out.chunks_exact(4).all(|px| px == [188, 188, 188, 255]),
+        "{out:?}"
+    );
+}
+
+#[test]
+fn nested_linear_light_runs_the_inner_pass_once() {
+    let inner = PassFx::Pixelate { block_px: 2 };
+    let src = [10u8, 20, 30, 255, 200, 100, 50, 255].repeat(2);
+    let nested = PassFx::LinearLight(Box::new(inner.clone()));
+    assert_eq!(run(&nested, &src, 2, 2), run(&inner, &src, 2, 2));
+}
@@ -0,0 +1,55 @@
+use super::*;
+
+fn full(width: u32, height: u32) -> PixelRect {
+    PixelRect {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    }
+}
+
+#[test]
+fn pixelate_block_1_is_identity() {
+    let src: Vec<u8> = (0..(3 * 2 * 4)).map(|v| v as u8).collect();
+    let out = pixelate_rgba8_premul(&src, 3, 2, full(3, 2), 1).unwrap();
+    assert_eq!(out, src);
+}
+
+#[test]
+fn pixelate_checkerboard_block_4_averages_everything() {
+    let (w, h) = (4u32, 4u32);
+    let mut src = Vec::new();
+    for y in 0..h {
+        for x in 0..w {
+            let v = if (x + y) % 2 == 0 { 200u8 } else { 0u8 };
+            src.extend_from_slice(&[v, v, v, 200]);
+        }
+    }
+    let out = pixelate_rgba8_premul(&src, w, h, full(w, h), 4).unwrap();
+    for px in out.chunks_exact(4) {
+        assert_eq!(px, &[100, 100, 100, 200]);
+    }
+}
+
+#[test]
+fn pixelate_partial_edge_blocks_average_covered_pixels() {
+    // 3x1 row with block 2: [a b | c] -> [(a+b)/2, (a+b)/2 | c]
+    let src = vec![10u8, 0, 0, 255, 30, 0, 0, 255, 90, 0, 0, 255];
+    let out = pixelate_rgba8_premul(&src, 3, 1, full(3, 1), 2).unwrap();
+    assert_eq!(out, vec![20u8, 0, 0, 255, 20, 0, 0, 255, 90, 0, 0, 255]);
+}
+
+#[test]
+fn pixelate_aligns_tiles_to_the_frame_origin() {
+    // Surface starting at canvas x=1 with block 2: canvas tiles [0 1][2 3] -> [a][b c]
+    let src = vec![10u8, 0, 0, 255, 30, 0, 0, 255, 90, 0, 0, 255];
+    let frame = PixelRect {
+        x: 1,
+        y: 0,
+        width: 4,
+        height: 1,
+    };
+    let out = pixelate_rgba8_premul(&src, 3, 1, frame, 2).unwrap();
+    assert_eq!(out, vec![10u8, 0, 0, 255, 60, 0, 0, 255, 60, 0, 0, 255]);
+}
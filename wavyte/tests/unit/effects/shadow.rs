@@ -0,0 +1,40 @@
+use super::*;
+
+fn opaque_rect(w: u32, h: u32, x0: u32, y0: u32, x1: u32, y1: u32) -> Vec<u8> {
+    let mut src = vec![0u8; (w * h * 4) as usize];
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let i = ((y * w + x) * 4) as usize;
+            src[i..i + 4].copy_from_slice(&[255, 255, 255, 255]);
+        }
+    }
+    src
+}
+
+#[test]
+fn shadow_appears_down_and_right_of_opaque_rect() {
+    let (w, h) = (24u32, 24u32);
+    let src = opaque_rect(w, h, 4, 4, 12, 12);
+    let out = drop_shadow_rgba8_premul(&src, w, h, [4, 4], 2, 1.0, [0, 0, 0, 255]).unwrap();
+
+    let px = |x: u32, y: u32| {
+        let i = ((y * w + x) * 4) as usize;
+        [out[i], out[i + 1], out[i + 2], out[i + 3]]
+    };
+    // Source stays on top.
+    assert_eq!(px(6, 6), [255, 255, 255, 255]);
+    // Black shadow below/right of the rect, transparent above/left.
+    assert_eq!(px(12, 12), [0, 0, 0, 255]);
+    assert!(px(12, 15)[3] > 0);
+    assert_eq!(px(1, 1), [0, 0, 0, 0]);
+}
+
+#[test]
+fn shadow_output_stays_premultiplied() {
+    let (w, h) = (16u32, 16u32);
+    let src = opaque_rect(w, h, 2, 2, 8, 8);
+    let out = drop_shadow_rgba8_premul(&src, w, h, [3, 2], 3, 1.5, [200, 40, 40, 128]).unwrap();
+    for px in out.chunks_exact(4) {
+        assert!(px[0] <= px[3] && px[1] <= px[3] && px[2] <= px[3], "{px:?}");
+    }
+}
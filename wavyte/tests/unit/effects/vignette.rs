@@ -0,0 +1,39 @@
+use super::*;
+
+fn full(width: u32, height: u32) -> PixelRect {
+    PixelRect {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    }
+}
+
+#[test]
+fn vignette_keeps_center_and_darkens_corners() {
+    let (w, h) = (32u32, 32u32);
+    let src: Vec<u8> = [200u8, 180, 160, 255].repeat((w * h) as usize);
+    let out = vignette_rgba8_premul(&src, w, h, full(w, h), 0.4, 0.5, [0, 0, 0, 255]).unwrap();
+
+    let px = |x: u32, y: u32| {
+        let i = ((y * w + x) * 4) as usize;
+        [out[i], out[i + 1], out[i + 2], out[i + 3]]
+    };
+    assert_eq!(px(16, 16), [200, 180, 160, 255]);
+    let corner = px(0, 0);
+    assert!(
+        corner[0] < 100 && corner[1] < 90 && corner[2] < 80,
+        "{corner:?}"
+    );
+    assert_eq!(corner[3], 255);
+}
+
+#[test]
+fn vignette_preserves_alpha_and_transparent_pixels() {
+    let src = vec![0u8, 0, 0, 0, 100, 100, 100, 128];
+    let out =
+        vignette_rgba8_premul(&src, 2, 1, full(2, 1), 0.0, 0.1, [255, 255, 255, 255]).unwrap();
+    assert_eq!(&out[0..4], &[0, 0, 0, 0]);
+    assert_eq!(out[7], 128);
+    assert!(out[4] > 100 && out[4] <= 128, "{out:?}");
+}
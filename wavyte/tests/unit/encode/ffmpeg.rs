@@ -6,10 +6,14 @@ fn config_validation_catches_bad_values() {
         EncodeConfig {
             width: 0,
             height: 10,
-            fps: 30,
+            fps: Fps::new(30, 1).unwrap(),
             out_path: PathBuf::from("assets/out.mp4"),
             overwrite: true,
             audio: None,
+            metadata: BTreeMap::new(),
+            codec: VideoCodec::H264,
+            preserve_alpha: false,
+            first_frame_pts: FrameIndex(0),
         }
         .validate()
         .is_err()
@@ -19,10 +23,14 @@ fn config_validation_catches_bad_values() {
         EncodeConfig {
             width: 11,
             height: 10,
-            fps: 30,
+            fps: Fps::new(30, 1).unwrap(),
             out_path: PathBuf::from("assets/out.mp4"),
             overwrite: true,
             audio: None,
+            metadata: BTreeMap::new(),
+            codec: VideoCodec::H264,
+            preserve_alpha: false,
+            first_frame_pts: FrameIndex(0),
         }
         .validate()
         .is_err()
@@ -32,10 +40,14 @@ fn config_validation_catches_bad_values() {
         EncodeConfig {
             width: 10,
             height: 10,
-            fps: 0,
+            fps: Fps { num: 0, den: 1 },
             out_path: PathBuf::from("assets/out.mp4"),
             overwrite: true,
             audio: None,
+            metadata: BTreeMap::new(),
+            codec: VideoCodec::H264,
+            preserve_alpha: false,
+            first_frame_pts: FrameIndex(0),
         }
         .validate()
         .is_err()
@@ -59,3 +71,82 @@ fn flatten_straight_over_black_produces_expected_rgb() {
     flatten_to_opaque_rgba8(&mut dst, &src, false, [0, 0, 0, 255]).unwrap();
     assert_eq!(dst, vec![128u8, 0u8, 0u8, 255u8]);
 }
+
+#[test]
+fn ffmpeg_args_pass_rational_fps_exactly() {
+    let fps = Fps::new(24000, 1001).unwrap();
+    let cfg = default_mp4_config("out/ntsc.mp4", 64, 36, fps);
+    cfg.validate().unwrap();
+
+    let args = ffmpeg_args(&cfg);
+    let r = args.iter().position(|a| a == "-r").unwrap();
+    assert_eq!(args[r + 1], "24000/1001");
+    assert_eq!(
+        args.last().unwrap(),
+        &OsString::from(PathBuf::from("out/ntsc.mp4"))
+    );
+
+    // 24000 frames at 23.976 fps last exactly 1001 seconds.
+    assert_eq!(fps.frames_to_secs(24000), 1001.0);
+    assert_eq!(fps.secs_to_frames_floor(1001.0), 24000);
+    assert_eq!(crate::audio::mix::frame_to_sample(24, fps, 48_000), 48_048);
+}
+
+#[test]
+fn codec_must_match_container_extension() {
+    let fps = Fps::new(30, 1).unwrap();
+    let mut cfg = default_mp4_config("out/clip.webm", 64, 36, fps);
+    assert!(cfg.validate().is_err(), "h264 in webm");
+
+    cfg.codec = VideoCodec::Vp9;
+    cfg.validate().unwrap();
+    cfg.preserve_alpha = true;
+    cfg.validate().unwrap();
+
+    let args = ffmpeg_args(&cfg);
+    let cv = args.iter().position(|a| a == "-c:v").unwrap();
+    assert_eq!(args[cv + 1], "libvpx-vp9");
+    assert_eq!(args[cv + 3], "yuva420p");
+    assert!(!args.iter().any(|a| a == "-movflags"));
+
+    cfg.codec = VideoCodec::Av1;
+    assert!(cfg.validate().is_err(), "alpha requires vp9");
+    cfg.preserve_alpha = false;
+    cfg.validate().unwrap();
+    assert!(
+        default_mp4_config("out/clip.MP4", 64, 36, fps)
+            .validate()
+            .is_ok()
+    );
+}
+
+#[test]
+fn alpha_requires_alpha_capable_codec() {
+    let fps = Fps::new(30, 1).unwrap();
+    let mut cfg = default_mp4_config("out/clip.mov", 63, 35, fps);
+    cfg.preserve_alpha = true;
+    assert!(cfg.validate().is_err(), "h264 cannot carry alpha");
+
+    cfg.codec = VideoCodec::Qtrle;
+    cfg.validate().unwrap();
+    let args = ffmpeg_args(&cfg);
+    let cv = args.iter().position(|a| a == "-c:v").unwrap();
+    assert_eq!(args[cv + 1], "qtrle");
+    assert_eq!(args[cv + 3], "argb");
+
+    cfg.preserve_alpha = false;
+    let args = ffmpeg_args(&cfg);
+    assert!(args.iter().any(|a| a == "rgb24"));
+}
+
+#[test]
+fn first_frame_pts_offsets_output_timestamps() {
+    let mut cfg = default_mp4_config("out/chunk.mp4", 64, 36, Fps::new(30, 1).unwrap());
+    assert!(!ffmpeg_args(&cfg).iter().any(|a| a == "-output_ts_offset"));
+
+    cfg.first_frame_pts = FrameIndex(45);
+    let args = ffmpeg_args(&cfg);
+    let at = args.iter().position(|a| a == "-output_ts_offset").unwrap();
+    assert_eq!(args[at + 1], "1.500000");
+    assert!(at < args.len() - 1, "offset is an output option");
+}
@@ -0,0 +1,152 @@
+use super::*;
+
+#[test]
+fn unpremultiply_restores_straight_color() {
+    let mut px = vec![128u8, 0u8, 0u8, 128u8, 0u8, 0u8, 0u8, 0u8];
+    unpremultiply_rgba8_in_place(&mut px);
+    assert_eq!(px, vec![255u8, 0u8, 0u8, 128u8, 0u8, 0u8, 0u8, 0u8]);
+}
+
+#[test]
+fn path_for_zero_pads_frame_index() {
+    let dir = std::env::temp_dir().join(format!("wavyte_png_seq_unit_{}", std::process::id()));
+    let sink = PngSequenceSink::new(PngSequenceSinkOpts {
+        out_dir: dir.clone(),
+        file_prefix: "f_".to_string(),
+        zero_pad: 4,
+        overwrite: true,
+    })
+    .unwrap();
+    assert_eq!(sink.path_for(FrameIndex(7)), dir.join("f_0007.png"));
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn prefix_with_separator_is_rejected() {
+    assert!(
+        PngSequenceSink::new(PngSequenceSinkOpts {
+            file_prefix: "a/b".to_string(),
+            ..PngSequenceSinkOpts::default()
+        })
+        .is_err()
+    );
+}
+
+#[test]
+fn checkerboard_shows_through_transparent_pixels_only() {
+    // 4x1 frame: transparent, transparent, opaque red, half-transparent black (premultiplied).
+    let frame = FrameRGBA {
+        width: 4,
+        height: 1,
+        data: vec![0, 0, 0, 0, 0, 0, 0, 0, 255, 0, 0, 255, 0, 0, 0, 128],
+        premultiplied: true,
+    };
+    let opts = CheckerboardOpts {
+        cell_px: 1,
+        light: [200, 200, 200],
+        dark: [100, 100, 100],
+    };
+    let out = render_on_checkerboard(&frame, &opts);
+    assert_eq!(&out.data[0..4], &[200, 200, 200, 255]);
+    assert_eq!(&out.data[4..8], &[100, 100, 100, 255]);
+    assert_eq!(&out.data[8..12], &[255, 0, 0, 255]);
+    assert_eq!(&out.data[12..16], &[50, 50, 50, 255]);
+}
+
+#[test]
+fn checkerboard_sink_forwards_composited_frames() {
+    let mut got = Vec::new();
+    {
+        let mut sink = CheckerboardSink::new(
+            CallbackSink::new(|f: FrameIndex, rgba: FrameRGBA| {
+                got.push((f, rgba.data));
+                Ok(())
+            }),
+            CheckerboardOpts::default(),
+        );
+        let frame = FrameRGBA {
+            width: 1,
+            height: 1,
+            data: vec![0, 0, 0, 0],
+            premultiplied: true,
+        };
+        sink.write_frame(FrameIndex(3), &frame).unwrap();
+    }
+    assert_eq!(got, vec![(FrameIndex(3), vec![204, 204, 204, 255])]);
+}
+
+#[test]
+fn crop_frame_copies_rows_and_rejects_out_of_bounds() {
+    let frame = FrameRGBA {
+        width: 3,
+        height: 2,
+        data: (0u8..24).collect(),
+        premultiplied: true,
+    };
+    let rect = PixelRect {
+        x: 1,
+        y: 1,
+        width: 2,
+        height: 1,
+    };
+    let out = crop_frame(&frame, rect).unwrap();
+    assert_eq!((out.width, out.height), (2, 1));
+    assert_eq!(out.data, (16u8..24).collect::<Vec<_>>());
+    assert!(crop_frame(&frame, PixelRect { x: 2, ..rect }).is_err());
+}
+
+fn raw_frames(sink: &mut RawSink<Vec<u8>>, cfg: &SinkConfig, frames: u64) {
+    sink.begin(cfg).unwrap();
+    for f in 0..frames {
+        let frame = FrameRGBA {
+            width: cfg.width,
+            height: cfg.height,
+            data: vec![128; (cfg.width * cfg.height * 4) as usize],
+            premultiplied: true,
+        };
+        sink.write_frame(FrameIndex(f), &frame).unwrap();
+    }
+    sink.finish().unwrap();
+}
+
+#[test]
+fn raw_sink_writes_rgba_frames_back_to_back() {
+    let cfg = SinkConfig {
+        width: 5,
+        height: 3,
+        fps: Fps::new(30, 1).unwrap(),
+    };
+    let mut sink = RawSink::new(Vec::new(), RawFormat::Rgba);
+    raw_frames(&mut sink, &cfg, 4);
+    assert_eq!(sink.frames_written(), 4);
+    let bytes = sink.into_inner();
+    assert_eq!(bytes.len(), 4 * 5 * 3 * 4);
+    // Premultiplied input is written as straight alpha.
+    assert_eq!(&bytes[..4], &[255, 255, 255, 128]);
+}
+
+#[test]
+fn raw_sink_y4m_writes_header_and_420_frames() {
+    let cfg = SinkConfig {
+        width: 5,
+        height: 3,
+        fps: Fps::new(30000, 1001).unwrap(),
+    };
+    let mut sink = RawSink::new(Vec::new(), RawFormat::Y4m);
+    raw_frames(&mut sink, &cfg, 2);
+    let bytes = sink.into_inner();
+
+    let header = b"YUV4MPEG2 W5 H3 F30000:1001 Ip A1:1 C420jpeg XCOLORRANGE=LIMITED\n";
+    assert!(bytes.starts_with(header));
+    let frame_len = b"FRAME\n".len() + 5 * 3 + 2 * (3 * 2);
+    assert_eq!(bytes.len(), header.len() + 2 * frame_len);
+    assert_eq!(&bytes[header.len()..header.len() + 6], b"FRAME\n");
+}
+
+#[test]
+fn rgba_to_yuv420_maps_black_and_white_to_limited_range() {
+    let white = rgba_to_yuv420(&[255, 255, 255, 255], true, 1, 1);
+    assert_eq!(white, vec![235, 128, 128]);
+    let transparent = rgba_to_yuv420(&[255, 255, 255, 0], false, 1, 1);
+    assert_eq!(transparent, vec![16, 128, 128]);
+}
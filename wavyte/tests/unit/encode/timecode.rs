@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn non_drop_frame_counts_whole_seconds() {
+    let fps = Fps::new(30, 1).unwrap();
+    assert_eq!(format_timecode(FrameIndex(0), fps, false), "00:00:00:00");
+    assert_eq!(format_timecode(FrameIndex(29), fps, false), "00:00:00:29");
+    assert_eq!(format_timecode(FrameIndex(30), fps, false), "00:00:01:00");
+    assert_eq!(
+        format_timecode(FrameIndex(30 * 3661), fps, false),
+        "01:01:01:00"
+    );
+    // drop_frame is ignored for integer rates.
+    assert_eq!(format_timecode(FrameIndex(30), fps, true), "00:00:01:00");
+}
+
+#[test]
+fn drop_frame_skips_two_labels_each_minute_except_tenth() {
+    let fps = Fps::new(30_000, 1001).unwrap();
+    assert_eq!(format_timecode(FrameIndex(1799), fps, true), "00:00:59;29");
+    assert_eq!(format_timecode(FrameIndex(1800), fps, true), "00:01:00;02");
+    assert_eq!(
+        format_timecode(FrameIndex(17_982), fps, true),
+        "00:10:00;00"
+    );
+    assert_eq!(format_timecode(FrameIndex(1800), fps, false), "00:01:00:00");
+}
+
+#[test]
+fn corner_origin_anchors_box_with_margin() {
+    let frame = [100, 50];
+    let size = [20, 10];
+    assert_eq!(
+        corner_origin(TimecodeCorner::TopLeft, frame, size, 4),
+        Some([4, 4])
+    );
+    assert_eq!(
+        corner_origin(TimecodeCorner::BottomRight, frame, size, 4),
+        Some([76, 36])
+    );
+    assert_eq!(
+        corner_origin(TimecodeCorner::TopRight, frame, [98, 10], 4),
+        None
+    );
+}
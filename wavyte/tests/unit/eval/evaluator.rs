@@ -21,6 +21,12 @@ fn basic_comp(
             size_px: 48.0,
             max_width_px: None,
             color_rgba8: [255, 255, 255, 255],
+            align: crate::TextAlign::Left,
+            valign: crate::TextVAlign::Top,
+            spans: vec![],
+            line_height: None,
+            letter_spacing_px: None,
+            fallback_fonts: vec![],
         }),
     );
     Composition {
@@ -40,6 +46,8 @@ fn basic_comp(
             layout_align_x: crate::LayoutAlignX::Start,
             layout_align_y: crate::LayoutAlignY::Start,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![Clip {
                 id: "c0".to_string(),
                 asset: "t0".to_string(),
@@ -51,14 +59,26 @@ fn basic_comp(
                     }),
                     opacity,
                     blend: BlendMode::Normal,
+                    opacity_at_composite: false,
+                    wiggle: None,
+                    box_anchor: None,
+                    aspect_ratio: None,
+                    fit: crate::ContentFit::None,
                 },
                 z_offset: 0,
                 effects: vec![],
                 transition_in: tr_in,
                 transition_out: tr_out,
+                seed: None,
+                loop_content: false,
+                time_remap: None,
+                reverse: false,
             }],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     }
 }
 
@@ -141,3 +161,260 @@ fn transition_progress_boundaries() {
         1.0
     );
 }
+
+#[test]
+fn per_clip_seed_decorrelates_procedural_noise() {
+    let noise = Anim::Procedural(crate::animation::proc::Procedural::new(
+        crate::animation::proc::ProceduralKind::Scalar(
+            crate::animation::proc::ProcScalar::Noise1D {
+                amp: 0.4,
+                freq_hz: 3.0,
+                offset: 0.5,
+            },
+        ),
+    ));
+    let mut comp = basic_comp(noise, None, None);
+    let base = comp.tracks[0].clips[0].clone();
+    comp.tracks[0].clips = [("a", Some(1)), ("b", Some(2)), ("c", Some(1))]
+        .into_iter()
+        .map(|(id, seed)| Clip {
+            id: id.to_string(),
+            seed,
+            ..base.clone()
+        })
+        .collect();
+
+    let g = Evaluator::eval_frame(&comp, FrameIndex(9)).unwrap();
+    let opacity = |id: &str| g.nodes.iter().find(|n| n.clip_id == id).unwrap().opacity;
+    assert_ne!(opacity("a"), opacity("b"));
+    // The explicit seed, not the clip id, drives the noise.
+    assert_eq!(opacity("a"), opacity("c"));
+}
+
+#[test]
+fn loop_content_repeats_keyframe_cycle_over_range() {
+    // 10-frame opacity cycle (0 -> 1 closing on frame 10) on a 30-frame clip.
+    let cycle = Anim::Keyframes(crate::Keyframes {
+        keys: vec![
+            crate::Keyframe {
+                frame: FrameIndex(0),
+                value: 0.0,
+                ease: Ease::Linear,
+            },
+            crate::Keyframe {
+                frame: FrameIndex(10),
+                value: 1.0,
+                ease: Ease::Linear,
+            },
+        ],
+        mode: crate::InterpMode::Linear,
+        default: None,
+    });
+    let mut comp = basic_comp(cycle, None, None);
+    comp.duration = FrameIndex(30);
+    let clip = &mut comp.tracks[0].clips[0];
+    clip.range = FrameRange::new(FrameIndex(0), FrameIndex(30)).unwrap();
+
+    let opacity_at = |comp: &Composition, f: u64| {
+        Evaluator::eval_frame(comp, FrameIndex(f)).unwrap().nodes[0].opacity
+    };
+    // Without looping the last state holds.
+    assert_eq!(opacity_at(&comp, 25), 1.0);
+
+    comp.tracks[0].clips[0].loop_content = true;
+    let samples = (0..30).map(|f| opacity_at(&comp, f)).collect::<Vec<_>>();
+    assert_eq!(samples[..10], samples[10..20]);
+    assert_eq!(samples[..10], samples[20..]);
+    assert_eq!(samples[0], 0.0);
+    assert!((samples[25] - 0.5).abs() < 1e-9);
+    assert_eq!(samples.iter().filter(|&&v| v == 0.0).count(), 3);
+}
+
+#[test]
+fn wiggled_translate_stays_within_amp_and_is_deterministic() {
+    let mut comp = basic_comp(Anim::constant(1.0), None, None);
+    comp.tracks[0].clips[0].props.wiggle = Some(crate::Wiggle::new(4.0, 6.0, 3));
+    comp.validate().unwrap();
+
+    let translations = |comp: &Composition| {
+        (5..15)
+            .map(|f| {
+                let g = Evaluator::eval_frame(comp, FrameIndex(f)).unwrap();
+                let c = g.nodes[0].transform.as_coeffs();
+                (c[4], c[5])
+            })
+            .collect::<Vec<_>>()
+    };
+    let a = translations(&comp);
+    assert_eq!(a, translations(&comp));
+    for &(x, y) in &a {
+        assert!(
+            (x - 1.0).abs() <= 6.0 && (y - 2.0).abs() <= 6.0,
+            "({x}, {y})"
+        );
+    }
+    assert!(a.iter().any(|&(x, y)| x != 1.0 || y != 2.0));
+
+    comp.tracks[0].clips[0].props.wiggle = Some(crate::Wiggle::new(4.0, 6.0, 4));
+    assert_ne!(a, translations(&comp));
+}
+
+#[test]
+fn time_remap_slows_video_relative_to_playback_rate() {
+    let ramp = |end: f64| {
+        Anim::Keyframes(crate::Keyframes {
+            keys: vec![
+                crate::Keyframe {
+                    frame: FrameIndex(0),
+                    value: 0.0,
+                    ease: Ease::Linear,
+                },
+                crate::Keyframe {
+                    frame: FrameIndex(30),
+                    value: end,
+                    ease: Ease::Linear,
+                },
+            ],
+            mode: crate::InterpMode::Linear,
+            default: None,
+        })
+    };
+    let video = |playback_rate: f64| {
+        Asset::Video(crate::VideoAsset {
+            source: "a.mp4".to_string(),
+            trim_start_sec: 0.0,
+            trim_end_sec: None,
+            playback_rate,
+            volume: 1.0,
+            fade_in_sec: 0.0,
+            fade_out_sec: 0.0,
+            fade_curve: crate::AudioFadeCurve::Linear,
+            muted: true,
+            frame_blend: false,
+            sampler: None,
+            loop_mode: crate::VideoLoopMode::None,
+        })
+    };
+    let mut comp = basic_comp(Anim::constant(1.0), None, None);
+    comp.duration = FrameIndex(30);
+    comp.tracks[0].clips[0].range = FrameRange::new(FrameIndex(0), FrameIndex(30)).unwrap();
+    let source_times = |comp: &Composition| {
+        (0..30)
+            .map(|f| {
+                Evaluator::eval_frame(comp, FrameIndex(f)).unwrap().nodes[0]
+                    .source_time_s
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for rate in [1.0, 2.0] {
+        comp.assets.insert("t0".to_string(), video(rate));
+        comp.tracks[0].clips[0].time_remap = None;
+        let plain = source_times(&comp);
+
+        comp.tracks[0].clips[0].time_remap = Some(ramp(30.0));
+        assert_eq!(source_times(&comp), plain, "identity remap at rate {rate}");
+
+        comp.tracks[0].clips[0].time_remap = Some(ramp(15.0));
+        let slow = source_times(&comp);
+        for f in [2usize, 10, 20, 29] {
+            assert!(
+                (slow[f] - plain[f] * 0.5).abs() < 1e-9,
+                "frame {f} at rate {rate}: {} vs {}",
+                slow[f],
+                plain[f]
+            );
+        }
+    }
+}
+
+#[test]
+fn time_remap_steps_props_on_whole_content_frames() {
+    let ramp = |last: u64, value: f64| {
+        Anim::Keyframes(crate::Keyframes {
+            keys: vec![
+                crate::Keyframe {
+                    frame: FrameIndex(0),
+                    value: 0.0,
+                    ease: Ease::Linear,
+                },
+                crate::Keyframe {
+                    frame: FrameIndex(last),
+                    value,
+                    ease: Ease::Linear,
+                },
+            ],
+            mode: crate::InterpMode::Linear,
+            default: None,
+        })
+    };
+    let mut comp = basic_comp(ramp(9, 0.9), None, None);
+    comp.duration = FrameIndex(10);
+    comp.tracks[0].clips[0].range = FrameRange::new(FrameIndex(0), FrameIndex(10)).unwrap();
+    // Half speed: content frames 0, 0.5, 1, ..., 4.5.
+    comp.tracks[0].clips[0].time_remap = Some(ramp(10, 5.0));
+    let opacity = (0..10)
+        .map(|f| Evaluator::eval_frame(&comp, FrameIndex(f)).unwrap().nodes[0].opacity)
+        .collect::<Vec<_>>();
+    let expected = [0, 0, 1, 1, 2, 2, 3, 3, 4, 4].map(|i| f64::from(i) * 0.1);
+    for (got, want) in opacity.iter().zip(expected) {
+        assert!((got - want).abs() < 1e-9, "{opacity:?}");
+    }
+}
+
+#[test]
+fn reversed_clip_starts_on_the_last_forward_frame() {
+    let ramp = Anim::Keyframes(crate::Keyframes {
+        keys: vec![
+            crate::Keyframe {
+                frame: FrameIndex(0),
+                value: 0.0,
+                ease: Ease::Linear,
+            },
+            crate::Keyframe {
+                frame: FrameIndex(9),
+                value: 0.9,
+                ease: Ease::Linear,
+            },
+        ],
+        mode: crate::InterpMode::Linear,
+        default: None,
+    });
+    let mut comp = basic_comp(ramp, None, None);
+    comp.duration = FrameIndex(10);
+    comp.tracks[0].clips[0].range = FrameRange::new(FrameIndex(0), FrameIndex(10)).unwrap();
+    let opacity_at = |comp: &Composition, f: u64| {
+        Evaluator::eval_frame(comp, FrameIndex(f)).unwrap().nodes[0].opacity
+    };
+    let forward = (0..10).map(|f| opacity_at(&comp, f)).collect::<Vec<_>>();
+
+    comp.tracks[0].clips[0].reverse = true;
+    let backward = (0..10).map(|f| opacity_at(&comp, f)).collect::<Vec<_>>();
+    assert_eq!(backward[0], forward[9]);
+    assert_eq!(backward, forward.iter().rev().copied().collect::<Vec<_>>());
+
+    // Reverse applies before time_remap: the half-speed remap (0 -> 5 over 10 frames) is
+    // sampled at reversed clip-local frames 9, 8, ..., 0, giving content frames 4.5, 4, ..., 0.
+    // Props sample whole content frames, so each forward frame shows twice.
+    comp.tracks[0].clips[0].time_remap = Some(Anim::Keyframes(crate::Keyframes {
+        keys: vec![
+            crate::Keyframe {
+                frame: FrameIndex(0),
+                value: 0.0,
+                ease: Ease::Linear,
+            },
+            crate::Keyframe {
+                frame: FrameIndex(10),
+                value: 5.0,
+                ease: Ease::Linear,
+            },
+        ],
+        mode: crate::InterpMode::Linear,
+        default: None,
+    }));
+    assert_eq!(opacity_at(&comp, 1), forward[4]);
+    let remapped = (0..10).map(|f| opacity_at(&comp, f)).collect::<Vec<_>>();
+    let expected = [4, 4, 3, 3, 2, 2, 1, 1, 0, 0].map(|i| forward[i]);
+    assert_eq!(remapped, expected);
+}
@@ -30,3 +30,29 @@ fn other_preserves_source() {
     let err = WavyteError::Other(anyhow::Error::new(base));
     assert!(err.to_string().contains("boom"));
 }
+
+#[test]
+fn kind_maps_variants_and_wrapped_causes() {
+    assert_eq!(WavyteError::validation("x").kind(), ErrorKind::Schema);
+    assert_eq!(WavyteError::animation("x").kind(), ErrorKind::Evaluation);
+    assert_eq!(WavyteError::encode("x").kind(), ErrorKind::Encode);
+    assert_eq!(WavyteError::cancelled("x").kind(), ErrorKind::Cancelled);
+    assert_eq!(WavyteError::unsupported("x").kind(), ErrorKind::Unsupported);
+
+    let io = anyhow::Error::new(std::io::Error::other("boom")).context("read asset");
+    assert_eq!(WavyteError::Other(io).kind(), ErrorKind::Io);
+    let nested = anyhow::Error::new(WavyteError::decode("bad png")).context("prepare");
+    assert_eq!(WavyteError::Other(nested).kind(), ErrorKind::Decode);
+    assert_eq!(
+        WavyteError::Other(anyhow::anyhow!("opaque")).kind(),
+        ErrorKind::Evaluation
+    );
+}
+
+#[test]
+fn bad_json_maps_to_schema() {
+    let err: WavyteError = serde_json::from_str::<serde_json::Value>("{ not json")
+        .unwrap_err()
+        .into();
+    assert_eq!(err.kind(), ErrorKind::Schema);
+}
@@ -7,12 +7,18 @@ fn comp_for_layout(mode: LayoutMode) -> Composition {
         "a".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L10,0 L10,10 L0,10 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: crate::FillRule::NonZero,
         }),
     );
     assets.insert(
         "b".to_string(),
         Asset::Path(PathAsset {
             svg_path_d: "M0,0 L20,0 L20,10 L0,10 Z".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: crate::FillRule::NonZero,
         }),
     );
     Composition {
@@ -32,6 +38,8 @@ fn comp_for_layout(mode: LayoutMode) -> Composition {
             layout_align_x: crate::LayoutAlignX::Start,
             layout_align_y: crate::LayoutAlignY::Center,
             layout_grid_columns: 2,
+            isolate: false,
+            clip_to: None,
             clips: vec![
                 Clip {
                     id: "c0".to_string(),
@@ -41,11 +49,20 @@ fn comp_for_layout(mode: LayoutMode) -> Composition {
                         transform: Anim::constant(crate::Transform2D::default()),
                         opacity: Anim::constant(1.0),
                         blend: crate::BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: crate::ContentFit::None,
                     },
                     z_offset: 0,
                     effects: vec![],
                     transition_in: None,
                     transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 },
                 Clip {
                     id: "c1".to_string(),
@@ -55,15 +72,27 @@ fn comp_for_layout(mode: LayoutMode) -> Composition {
                         transform: Anim::constant(crate::Transform2D::default()),
                         opacity: Anim::constant(1.0),
                         blend: crate::BlendMode::Normal,
+                        opacity_at_composite: false,
+                        wiggle: None,
+                        box_anchor: None,
+                        aspect_ratio: None,
+                        fit: crate::ContentFit::None,
                     },
                     z_offset: 1,
                     effects: vec![],
                     transition_in: None,
                     transition_out: None,
+                    seed: None,
+                    loop_content: false,
+                    time_remap: None,
+                    reverse: false,
                 },
             ],
         }],
         seed: 1,
+        background: None,
+        title: None,
+        description: None,
     }
 }
 
@@ -84,3 +113,144 @@ fn center_mode_centers_each_clip() {
     assert_eq!(offsets.offset_for(0, 0), Vec2::new(45.0, 15.0));
     assert_eq!(offsets.offset_for(0, 1), Vec2::new(40.0, 15.0));
 }
+
+#[test]
+fn box_anchor_scales_about_layout_box_center() {
+    let mut comp = comp_for_layout(LayoutMode::HStack);
+    comp.tracks[0].clips[1].props.transform = Anim::constant(crate::Transform2D {
+        scale: Vec2::new(2.0, 2.0),
+        ..crate::Transform2D::default()
+    });
+    let store = PreparedAssetStore::prepare(&comp, ".").unwrap();
+    let offsets = resolve_layout_offsets(&comp, &store).unwrap();
+    assert_eq!(offsets.size_for(0, 1), Some(Vec2::new(20.0, 10.0)));
+
+    let c1_bounds = |comp: &Composition| {
+        let g = crate::Evaluator::eval_frame_with_layout(comp, FrameIndex(0), &offsets).unwrap();
+        let node = g.nodes.iter().find(|n| n.clip_id == "c1").unwrap();
+        node.transform
+            .transform_rect_bbox(kurbo::Rect::new(0.0, 0.0, 20.0, 10.0))
+    };
+    // Box is 20x10 at (15, 15); by default scaling grows from the box origin.
+    assert_eq!(c1_bounds(&comp), kurbo::Rect::new(15.0, 15.0, 55.0, 35.0));
+
+    comp.tracks[0].clips[1].props.box_anchor = Some(Vec2::new(0.5, 0.5));
+    assert_eq!(c1_bounds(&comp), kurbo::Rect::new(5.0, 10.0, 45.0, 30.0));
+}
+
+#[test]
+fn box_anchor_accounts_for_path_bounds_origin() {
+    let mut comp = comp_for_layout(LayoutMode::Absolute);
+    comp.assets.insert(
+        "b".to_string(),
+        Asset::Path(PathAsset {
+            svg_path_d: "M10 10 L30 30".to_string(),
+            fill: None,
+            stroke: None,
+            fill_rule: crate::FillRule::NonZero,
+        }),
+    );
+    comp.tracks[0].clips[1].props.transform = Anim::constant(crate::Transform2D {
+        scale: Vec2::new(2.0, 2.0),
+        ..crate::Transform2D::default()
+    });
+    comp.tracks[0].clips[1].props.box_anchor = Some(Vec2::new(0.5, 0.5));
+    let store = PreparedAssetStore::prepare(&comp, ".").unwrap();
+    let offsets = resolve_layout_offsets(&comp, &store).unwrap();
+    assert_eq!(offsets.origin_for(0, 1), Vec2::new(10.0, 10.0));
+
+    let g = crate::Evaluator::eval_frame_with_layout(&comp, FrameIndex(0), &offsets).unwrap();
+    let node = g.nodes.iter().find(|n| n.clip_id == "c1").unwrap();
+    // Scaling about the center of the path's own bounds keeps that center at (20, 20).
+    assert_eq!(
+        node.transform
+            .transform_rect_bbox(kurbo::Rect::new(10.0, 10.0, 30.0, 30.0)),
+        kurbo::Rect::new(0.0, 0.0, 40.0, 40.0)
+    );
+}
+
+#[test]
+fn bottom_aligned_text_stays_inside_its_vstack_slot() {
+    let mut comp = comp_for_layout(LayoutMode::VStack);
+    comp.assets.insert(
+        "a".to_string(),
+        Asset::Text(crate::TextAsset {
+            valign: crate::TextVAlign::Bottom,
+            ..crate::text_asset("Hi", "tests/data/fonts/Inconsolata-Regular.ttf", 12.0)
+        }),
+    );
+    let store = PreparedAssetStore::prepare(&comp, ".").unwrap();
+    let offsets = resolve_layout_offsets(&comp, &store).unwrap();
+    let size = offsets.size_for(0, 0).unwrap();
+    let origin = offsets.origin_for(0, 0);
+    assert!(origin.y < 0.0);
+
+    let g = crate::Evaluator::eval_frame_with_layout(&comp, FrameIndex(0), &offsets).unwrap();
+    let placed = |id: &str, local: kurbo::Rect| {
+        let node = g.nodes.iter().find(|n| n.clip_id == id).unwrap();
+        node.transform.transform_rect_bbox(local)
+    };
+    let text = placed(
+        "c0",
+        kurbo::Rect::new(origin.x, origin.y, origin.x + size.x, origin.y + size.y),
+    );
+    let below = placed("c1", kurbo::Rect::new(0.0, 0.0, 20.0, 10.0));
+    // The stack (text, 5px gap, 10px path) is centered in the 40px-tall canvas.
+    let top = (40.0 - (size.y + 5.0 + 10.0)) / 2.0;
+    assert!((text.y0 - top).abs() < 1e-9, "{text:?}");
+    assert!(
+        (text.y1 + 5.0 - below.y0).abs() < 1e-9,
+        "{text:?} {below:?}"
+    );
+}
+
+#[test]
+fn grid_places_children_at_cell_origins_and_keeps_their_transforms() {
+    let mut comp = comp_for_layout(LayoutMode::Grid);
+    comp.tracks[0].layout_align_y = crate::LayoutAlignY::Start;
+    let extra = comp.tracks[0].clips.clone();
+    comp.tracks[0]
+        .clips
+        .extend(extra.into_iter().enumerate().map(|(i, mut clip)| {
+            clip.id = format!("c{}", i + 2);
+            clip
+        }));
+    comp.tracks[0].clips[3].props.transform = Anim::constant(crate::Transform2D {
+        translate: Vec2::new(1.0, 2.0),
+        ..crate::Transform2D::default()
+    });
+    let store = PreparedAssetStore::prepare(&comp, ".").unwrap();
+    let offsets = resolve_layout_offsets(&comp, &store).unwrap();
+
+    // Cells are sized to the largest child (20x10) and separated by the 5px gap.
+    let g = crate::Evaluator::eval_frame_with_layout(&comp, FrameIndex(0), &offsets).unwrap();
+    let translate = |id: &str| {
+        let node = g.nodes.iter().find(|n| n.clip_id == id).unwrap();
+        let t = node.transform.translation();
+        Vec2::new(t.x, t.y)
+    };
+    assert_eq!(translate("c0"), Vec2::new(0.0, 0.0));
+    assert_eq!(translate("c1"), Vec2::new(25.0, 0.0));
+    assert_eq!(translate("c2"), Vec2::new(0.0, 15.0));
+    assert_eq!(translate("c3"), Vec2::new(26.0, 17.0));
+}
+
+#[test]
+fn aspect_ratio_sets_box_height_from_width() {
+    let mut comp = comp_for_layout(LayoutMode::VStack);
+    comp.tracks[0].layout_align_y = crate::LayoutAlignY::Start;
+    comp.tracks[0].clips[1].props.aspect_ratio = Some(16.0 / 9.0);
+    comp.tracks[0].clips.swap(0, 1);
+    comp.validate().unwrap();
+    let store = PreparedAssetStore::prepare(&comp, ".").unwrap();
+    let offsets = resolve_layout_offsets(&comp, &store).unwrap();
+
+    // The 20px-wide asset gets a 16:9 box, and the next clip stacks below that box.
+    let size = offsets.size_for(0, 0).unwrap();
+    assert_eq!(size.x, 20.0);
+    assert!((size.y - 11.25).abs() < 1e-9, "{size:?}");
+    assert_eq!(offsets.offset_for(0, 1), Vec2::new(0.0, 16.25));
+
+    comp.tracks[0].clips[0].props.aspect_ratio = Some(0.0);
+    assert!(comp.validate().is_err());
+}
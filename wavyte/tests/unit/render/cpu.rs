@@ -0,0 +1,28 @@
+use super::*;
+
+use vello_cpu::kurbo::Affine;
+
+#[test]
+fn text_snap_none_keeps_fractional_glyph_origins() {
+    let (x, y) = snap_glyph_origin(TextSnap::None, Affine::IDENTITY, 3.37, 11.62);
+    assert_eq!((x, y), (3.37, 11.62));
+}
+
+#[test]
+fn text_snap_pixel_rounds_device_origins_to_integers() {
+    let (x, y) = snap_glyph_origin(TextSnap::Pixel, Affine::IDENTITY, 3.37, 11.62);
+    assert_eq!((x, y), (3.0, 12.0));
+
+    // Snapping happens in device space, after the text transform.
+    let transform = Affine::translate((0.25, 0.5)) * Affine::scale(2.0);
+    let (x, y) = snap_glyph_origin(TextSnap::Pixel, transform, 3.37, 11.62);
+    let device = transform * vello_cpu::kurbo::Point::new(f64::from(x), f64::from(y));
+    assert!((device.x - device.x.round()).abs() < 1e-4, "{device:?}");
+    assert!((device.y - device.y.round()).abs() < 1e-4, "{device:?}");
+}
+
+#[test]
+fn text_snap_subpixel_uses_quarter_pixel_columns() {
+    let (x, y) = snap_glyph_origin(TextSnap::Subpixel, Affine::IDENTITY, 3.37, 11.62);
+    assert_eq!((x, y), (3.25, 12.0));
+}
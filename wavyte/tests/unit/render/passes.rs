@@ -2,7 +2,7 @@ use super::*;
 use crate::{
     assets::store::PreparedAssetStore,
     compile::plan::{CompositeOp, PixelFormat},
-    foundation::core::{Canvas, Rgba8Premul},
+    foundation::core::{Canvas, PixelRect, Rgba8Premul},
     foundation::error::WavyteResult,
 };
 
@@ -68,11 +68,13 @@ fn execute_plan_calls_in_expected_order() {
                 width: 4,
                 height: 3,
                 format: PixelFormat::Rgba8Premul,
+                origin: [0, 0],
             },
             SurfaceDesc {
                 width: 4,
                 height: 3,
                 format: PixelFormat::Rgba8Premul,
+                origin: [0, 0],
             },
         ],
         passes: vec![
@@ -87,6 +89,13 @@ fn execute_plan_calls_in_expected_order() {
                 fx: crate::effects::fx::PassFx::Blur {
                     radius_px: 0,
                     sigma: 1.0,
+                    alpha_only: false,
+                },
+                frame: PixelRect {
+                    x: 0,
+                    y: 0,
+                    width: 4,
+                    height: 3,
                 },
             }),
             Pass::Composite(CompositePass {
@@ -98,6 +107,7 @@ fn execute_plan_calls_in_expected_order() {
             }),
         ],
         final_surface: SurfaceId(0),
+        clear_rgba: None,
     };
 
     let mut backend = MockBackend::default();
@@ -111,6 +121,9 @@ fn execute_plan_calls_in_expected_order() {
         assets: std::collections::BTreeMap::new(),
         tracks: vec![],
         seed: 0,
+        background: None,
+        title: None,
+        description: None,
     };
     let store = PreparedAssetStore::prepare(&comp, ".").unwrap();
     let out = execute_plan(&mut backend, &plan, &store).unwrap();
@@ -141,6 +154,7 @@ fn execute_plan_returns_final_frame() {
             width: 2,
             height: 2,
             format: PixelFormat::Rgba8Premul,
+            origin: [0, 0],
         }],
         passes: vec![Pass::Scene(ScenePass {
             target: SurfaceId(0),
@@ -148,6 +162,8 @@ fn execute_plan_returns_final_frame() {
                 path: crate::foundation::core::BezPath::new(),
                 transform: crate::foundation::core::Affine::IDENTITY,
                 color: Rgba8Premul::from_straight_rgba(0, 0, 0, 0),
+                fill_rule: crate::FillRule::NonZero,
+                stroke: None,
                 opacity: 1.0,
                 blend: crate::composition::model::BlendMode::Normal,
                 z: 0,
@@ -155,6 +171,7 @@ fn execute_plan_returns_final_frame() {
             clear_to_transparent: true,
         })],
         final_surface: SurfaceId(0),
+        clear_rgba: None,
     };
 
     let mut backend = MockBackend::default();
@@ -168,6 +185,9 @@ fn execute_plan_returns_final_frame() {
         assets: std::collections::BTreeMap::new(),
         tracks: vec![],
         seed: 0,
+        background: None,
+        title: None,
+        description: None,
     };
     let store = PreparedAssetStore::prepare(&comp, ".").unwrap();
     let out = execute_plan(&mut backend, &plan, &store).unwrap();